@@ -18,7 +18,8 @@ use std::path::{Path, PathBuf};
 use anyhow::Error;
 use lazy_static::lazy_static;
 use regex::Regex;
-use reqwest::{Client, Url};
+use reqwest::Url;
+use wiki_client::WikiClient;
 
 use local::LocalTask;
 use remote::{RemoteTask, Response};
@@ -104,7 +105,7 @@ impl Task {
 pub struct TaskIndex {
     all_task_titles: HashSet<String>,
     local_tasks: Vec<LocalTask>,
-    client: Client,
+    client: WikiClient,
 }
 
 impl TaskIndex {
@@ -114,7 +115,7 @@ impl TaskIndex {
         let local_tasks = local::parse_tasks(workspace_root.as_ref().join("Cargo.toml")).unwrap();
         let local_task_titles = local_tasks.iter().map(|task| task.title.clone()).collect();
 
-        let client = Client::new();
+        let client = WikiClient::new();
         let all_task_titles = TaskIndex::all_task_titles(&client, &local_task_titles)?;
 
         Ok(TaskIndex {
@@ -136,10 +137,10 @@ impl TaskIndex {
     }
 
     fn all_task_titles(
-        client: &Client,
+        client: &WikiClient,
         local_task_titles: &HashSet<String>,
     ) -> Result<HashSet<String>> {
-        let mut all_task_titles = find_unimplemented_tasks::all_tasks()
+        let mut all_task_titles = find_unimplemented_tasks::all_tasks(client)?
             .into_iter()
             .map(|task| task.title)
             .collect::<HashSet<_>>();
@@ -158,15 +159,9 @@ impl TaskIndex {
             possible_draft_titles.sort();
 
             // FIXME: We might have to loop here if we get too many draft tasks.
-            let mut request = Url::parse("http://rosettacode.org/mw/api.php").unwrap();
-            request
-                .query_pairs_mut()
-                .append_pair("action", "query")
-                .append_pair("format", "json")
-                .append_pair("prop", "info")
-                .append_pair("titles", &possible_draft_titles.join("|"));
-
-            let response: Response = client.get(request.as_str()).send()?.json()?;
+            let titles = possible_draft_titles.join("|");
+            let response: Response =
+                serde_json::from_value(client.query(&[("prop", "info"), ("titles", &titles)])?)?;
 
             // In the response, any missing pages will have a negative ID.
             let bad_titles = response
@@ -193,7 +188,7 @@ impl TaskIndex {
 /// An iterator over tasks. Parses information from both the rust-rosetta repository and the
 /// Rosetta Code wiki.
 pub struct TaskIterator<'a> {
-    client: &'a Client,
+    client: &'a WikiClient,
     fetched_remote_tasks: VecDeque<RemoteTask>,
     requested_task_titles: VecDeque<String>,
     local_tasks: Vec<LocalTask>,
@@ -225,20 +220,14 @@ impl<'a> TaskIterator<'a> {
 
     /// Fetch the next set of remote tasks.
     fn fetch_task_batch(&mut self) -> Result<()> {
-        let end = cmp::min(self.requested_task_titles.len(), 50);
+        let end = cmp::min(
+            self.requested_task_titles.len(),
+            wiki_client::TITLES_PER_QUERY,
+        );
         let next_batch_titles = self.requested_task_titles.drain(..end).collect::<Vec<_>>();
-        let mut request = Url::parse("http://rosettacode.org/mw/api.php").unwrap();
-        request
-            .query_pairs_mut()
-            .append_pair("action", "query")
-            .append_pair("format", "json")
-            .append_pair("prop", "revisions")
-            .append_pair("rvprop", "content")
-            .append_pair("titles", &next_batch_titles.join("|"));
-
-        let response: Response = self.client.get(request.as_str()).send()?.json()?;
-
-        let mut remote_tasks = remote::parse_tasks(&response);
+        let contents = self.client.page_contents(&next_batch_titles)?;
+
+        let mut remote_tasks = remote::parse_tasks(&contents);
         remote_tasks.sort_by_key(|task| task.title());
         self.fetched_remote_tasks = remote_tasks.into_iter().collect();
 
@@ -33,6 +33,12 @@ pub struct LocalTask {
     ///
     /// The title is normalized, so it can be used to uniquely identify the task on the wiki.
     pub title: String,
+
+    /// Whether running the task, or one of its ignored tests, needs network access.
+    ///
+    /// Set with `requires-network = true` in the crate metadata, so that the smoke runner can
+    /// skip the task when offline.
+    pub requires_network: bool,
 }
 
 /// Check if the target of a package is of kind dylib or proc-macro.
@@ -42,7 +48,7 @@ fn is_dylib_or_proc_macro(target: &cargo_metadata::Target) -> bool {
 }
 
 /// Given a path to the root `Cargo.toml`, returns a list of tasks implemented in the rust-rosetta
-/// repository.
+/// repository: the workspace members with a `[package.metadata.rosettacode]` table.
 pub fn parse_tasks<P>(manifest_path: P) -> Result<Vec<LocalTask>, Error>
 where
     P: AsRef<Path>,
@@ -53,11 +59,6 @@ where
     let mut tasks = vec![];
 
     for member in &metadata.workspace_members {
-        // Skip if we encounter known non-task crates.
-        if member.name() == "rust-rosetta" || member.name() == "meta" {
-            continue;
-        }
-
         let package = packages.iter().find(|p| p.name == member.name()).unwrap();
 
         // If the package has a proc-macro or dylib target, it's probably just a dependency of
@@ -68,10 +69,24 @@ where
 
         let manifest_path = Path::new(&package.manifest_path);
 
-        let rosetta_url = parse_rosetta_url(manifest_path).context(format!(
+        let metadata = parse_rosetta_metadata(manifest_path).context(format!(
+            "could not parse rosetta code metadata from {}",
+            manifest_path.display()
+        ))?;
+        // Crates without Rosetta Code metadata, like `meta` itself and the data structures that
+        // have no task on the wiki, are not tasks.
+        let metadata = match metadata {
+            Some(metadata) => metadata,
+            None => continue,
+        };
+        let rosetta_url = parse_rosetta_url(&metadata).context(format!(
             "could not parse rosetta code URL from {}",
             manifest_path.display()
         ))?;
+        let requires_network = metadata
+            .get("requires-network")
+            .and_then(|r| r.as_bool())
+            .unwrap_or(false);
 
         let title = {
             let caps = TASK_URL_RE.captures(rosetta_url.as_str()).ok_or_else(|| {
@@ -89,24 +104,31 @@ where
             source: find_sources(manifest_path.parent().unwrap())?,
             url: rosetta_url,
             title,
+            requires_network,
         });
     }
 
     Ok(tasks)
 }
 
-/// Parses the Rosetta Code URL from the package metadata in a task's crate manifest.
-fn parse_rosetta_url<P>(manifest_path: P) -> Result<Url, Error>
+/// Parses the `[package.metadata.rosettacode]` table from a crate manifest, if it has one.
+fn parse_rosetta_metadata<P>(manifest_path: P) -> Result<Option<Value>, Error>
 where
     P: AsRef<Path>,
 {
     let manifest: Value = fs::read_to_string(manifest_path)?.parse()?;
 
-    let url = manifest
+    Ok(manifest
         .get("package")
         .and_then(|p| p.get("metadata"))
         .and_then(|m| m.get("rosettacode"))
-        .and_then(|m| m.get("url"))
+        .cloned())
+}
+
+/// Parses the Rosetta Code URL from the package metadata of a task.
+fn parse_rosetta_url(metadata: &Value) -> Result<Url, Error> {
+    let url = metadata
+        .get("url")
         .and_then(|u| u.as_str())
         .ok_or_else(|| anyhow!("unexpected metadata format"))?;
 
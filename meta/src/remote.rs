@@ -1,6 +1,6 @@
 //! Utilities for interacting with tasks implemented on the Rosetta Code wiki.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use lazy_static::lazy_static;
 use percent_encoding::{self, define_encode_set, QUERY_ENCODE_SET};
@@ -66,15 +66,6 @@ pub struct Page {
     pub id: i32,
 
     pub title: String,
-
-    #[serde(default)]
-    pub revisions: Vec<Revision>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct Revision {
-    #[serde(rename = "*")]
-    pub content: String,
 }
 
 /// Transforms a URL-encoded task title from the wiki to a human-readable task title.
@@ -91,18 +82,17 @@ pub fn encode_title(title: &str) -> String {
     encoded.collect()
 }
 
-pub fn parse_tasks(response: &Response) -> Vec<RemoteTask> {
-    response
-        .query
-        .pages
-        .values()
-        .map(|page| {
+/// The tasks with the given contents, by their titles.
+pub fn parse_tasks(contents: &BTreeMap<String, String>) -> Vec<RemoteTask> {
+    contents
+        .iter()
+        .map(|(title, content)| {
             let source = RUST_WIKI_SECTION_RE
-                .captures(&page.revisions[0].content)
+                .captures(content)
                 .map(|captures| captures.get(1).unwrap())
                 .map(|m| m.as_str().to_owned());
 
-            let title = page.title.clone();
+            let title = title.clone();
             let url = Url::parse(&format!(
                 "http://rosettacode.org/wiki/{}",
                 encode_title(&title)
@@ -56,6 +56,78 @@ macro_rules! test_sort {
     }
 }
 
+/// A sorting algorithm that sorts a slice in place.
+///
+/// Implementing it, usually for a unit struct, lets [`test_sorter!`] generate the test suite of
+/// [`test_sort!`] for the algorithm, along with a benchmark.
+///
+/// [`test_sorter!`]: ../macro.test_sorter.html
+/// [`test_sort!`]: ../macro.test_sort.html
+pub trait Sorter {
+    /// Sorts the elements in place, in ascending order.
+    fn sort<T>(elements: &mut [T])
+    where
+        T: Ord + Clone;
+}
+
+/// Generates the test suite of `test_sort!` and a benchmark for a [`Sorter`].
+///
+/// The benchmark needs the unstable `test` crate, so the crate using this macro must enable
+/// `#![feature(test)]` and declare `extern crate test;`.
+///
+/// [`Sorter`]: test_utils/trait.Sorter.html
+///
+/// # Example
+///
+/// ```ignore
+/// #![feature(test)]
+///
+/// extern crate test;
+///
+/// fn sort<E>(elements: &mut [E]) where E: Ord {
+///     elements.sort();
+/// }
+///
+/// #[cfg(test)]
+/// mod tests {
+///     use meta::test_sorter;
+///     use meta::test_utils::Sorter;
+///
+///     struct StdSort;
+///
+///     impl Sorter for StdSort {
+///         fn sort<T: Ord + Clone>(elements: &mut [T]) {
+///             super::sort(elements);
+///         }
+///     }
+///
+///     test_sorter!(StdSort);
+/// }
+/// ```
+#[macro_export]
+macro_rules! test_sorter {
+    ( $sorter:ty ) => {
+        fn sorter_sort<T: Ord + Clone>(elements: &mut [T]) {
+            <$sorter as $crate::test_utils::Sorter>::sort(elements);
+        }
+
+        $crate::test_sort!(sorter_sort);
+
+        #[bench]
+        fn bench_random_numbers(b: &mut ::test::Bencher) {
+            use $crate::rand::SeedableRng;
+
+            let mut rng = $crate::rand::rngs::StdRng::seed_from_u64(0);
+            let numbers: Vec<i32> = (0..1000).map(|_| rng.gen()).collect();
+            b.iter(|| {
+                let mut values = numbers.clone();
+                sorter_sort(&mut values);
+                values
+            });
+        }
+    };
+}
+
 /// Check if a slice is sorted properly.
 pub fn check_sorted<E>(candidate: &[E])
 where
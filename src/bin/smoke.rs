@@ -0,0 +1,73 @@
+use std::process::{self, Command};
+
+use structopt::StructOpt;
+
+use meta::local::{self, LocalTask};
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The package name of a task, such as "fizzbuzz"
+    #[structopt(name = "task")]
+    tasks: Vec<String>,
+
+    /// Also run the tasks that need network access
+    #[structopt(long = "online")]
+    online: bool,
+
+    /// Build the tests with optimizations
+    #[structopt(long = "release")]
+    release: bool,
+}
+
+/// Runs the tests of a task, ignored ones included. Returns whether they passed.
+fn run_tests(task: &LocalTask, release: bool) -> bool {
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.args(&["test", "--package", &task.package_name]);
+    if release {
+        cargo.arg("--release");
+    }
+    cargo.args(&["--", "--include-ignored"]);
+
+    match cargo.status() {
+        Ok(status) => status.success(),
+        Err(err) => {
+            eprintln!("could not run cargo: {}", err);
+            false
+        }
+    }
+}
+
+/// Runs the tests of every task in the repository, one task at a time, including the tests that
+/// are too slow for `cargo test` or that talk to other machines.
+///
+/// Tasks with `requires-network = true` in their metadata are skipped unless `--online` is
+/// given.
+///
+/// If no tasks are specified, runs all of them.
+fn main() {
+    let opt = Opt::from_args();
+
+    let manifest_path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+    let mut tasks = local::parse_tasks(manifest_path).unwrap();
+    if !opt.tasks.is_empty() {
+        tasks.retain(|task| opt.tasks.contains(&task.package_name));
+    }
+
+    let mut failed = vec![];
+    for task in &tasks {
+        if task.requires_network && !opt.online {
+            println!("Skipping {}: it needs network access", task.package_name);
+            continue;
+        }
+
+        println!("Testing {}", task.package_name);
+        if !run_tests(task, opt.release) {
+            failed.push(&task.package_name);
+        }
+    }
+
+    if !failed.is_empty() {
+        eprintln!("\nFailed: {:?}", failed);
+        process::exit(1);
+    }
+}
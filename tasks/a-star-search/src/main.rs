@@ -0,0 +1,311 @@
+//! Finds a cheapest path across a grid by A* search.
+//!
+//! A* is Dijkstra's algorithm with a guess added to the distance of each square: the queue is
+//! ordered by the cost of the path to the square so far plus an estimate of the cost from there
+//! to the goal, so that squares that lead towards the goal are tried first. As long as the
+//! estimate is never more than the true cost, the first path to reach the goal is a cheapest
+//! one.
+//!
+//! When the moves only go up, down, left and right, that estimate is the Manhattan
+//! distance, since every move costs at least one. The task also allows diagonal moves, which
+//! cover a step in both directions at once; the Manhattan distance would count them twice, so
+//! the estimate is the greater of the two distances instead, the Chebyshev distance.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+type Point = (i32, i32);
+type Cost = u32;
+
+struct Grid {
+    width: i32,
+    height: i32,
+    barriers: HashSet<Point>,
+    diagonal: bool,
+}
+
+impl Grid {
+    /// The cost of moving into a square with a barrier.
+    const BARRIER: Cost = 100;
+
+    /// The task's 8 by 8 grid with a wall that only leaves a way around.
+    fn task() -> Grid {
+        let barriers = [
+            (2, 4),
+            (2, 5),
+            (2, 6),
+            (3, 6),
+            (4, 6),
+            (5, 6),
+            (5, 5),
+            (5, 4),
+            (5, 3),
+            (5, 2),
+            (4, 2),
+            (3, 2),
+        ];
+        Grid {
+            width: 8,
+            height: 8,
+            barriers: barriers.iter().copied().collect(),
+            diagonal: true,
+        }
+    }
+
+    fn contains(&self, (x, y): Point) -> bool {
+        0 <= x && x < self.width && 0 <= y && y < self.height
+    }
+
+    fn cost(&self, to: Point) -> Cost {
+        if self.barriers.contains(&to) {
+            Self::BARRIER
+        } else {
+            1
+        }
+    }
+
+    /// The squares that can be moved to from `p`, with the cost of moving there.
+    fn neighbours(&self, (x, y): Point) -> Vec<(Point, Cost)> {
+        let mut moves = vec![(1, 0), (0, 1), (-1, 0), (0, -1)];
+        if self.diagonal {
+            moves.extend(&[(1, 1), (-1, 1), (-1, -1), (1, -1)]);
+        }
+        moves
+            .into_iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|&p| self.contains(p))
+            .map(|p| (p, self.cost(p)))
+            .collect()
+    }
+
+    /// An estimate of the cost from `from` to `to` that is never too high: the Manhattan
+    /// distance, or the Chebyshev distance if diagonal moves are allowed.
+    fn heuristic(&self, from: Point, to: Point) -> Cost {
+        let (dx, dy) = ((from.0 - to.0).abs(), (from.1 - to.1).abs());
+        if self.diagonal {
+            dx.max(dy) as Cost
+        } else {
+            (dx + dy) as Cost
+        }
+    }
+
+    fn show(&self, path: &[Point], start: Point, goal: Point) -> String {
+        let mut lines = vec![];
+        for y in 0..self.height {
+            let line: String = (0..self.width)
+                .map(|x| match (x, y) {
+                    p if p == start => 'S',
+                    p if p == goal => 'G',
+                    p if path.contains(&p) => 'x',
+                    p if self.barriers.contains(&p) => '#',
+                    _ => '.',
+                })
+                .collect();
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+/// The cost of a cheapest path from `start` to `goal`, and its squares, or `None` if there is
+/// none. `estimate` guesses the cost from a square to the goal.
+///
+/// Among squares with the same estimated total, the one that is nearest the goal by the
+/// estimate is tried first, which on an open grid goes straight for the goal.
+fn a_star<H>(grid: &Grid, start: Point, goal: Point, estimate: H) -> Option<(Cost, Vec<Point>)>
+where
+    H: Fn(Point) -> Cost,
+{
+    let mut cost = HashMap::new();
+    let mut prev = HashMap::new();
+    let mut done = HashSet::new();
+    let mut queue = BinaryHeap::new();
+
+    cost.insert(start, 0);
+    queue.push(Reverse((estimate(start), estimate(start), start)));
+    while let Some(Reverse((_, _, p))) = queue.pop() {
+        if p == goal {
+            let mut path = vec![goal];
+            while let Some(&before) = prev.get(path.last().unwrap()) {
+                path.push(before);
+            }
+            path.reverse();
+            return Some((cost[&goal], path));
+        }
+        // Squares come out again for each cheaper path found to them while they waited.
+        if !done.insert(p) {
+            continue;
+        }
+        for (next, step) in grid.neighbours(p) {
+            let alt = cost[&p] + step;
+            if cost.get(&next).is_none_or(|&c| alt < c) {
+                cost.insert(next, alt);
+                prev.insert(next, p);
+                let h = estimate(next);
+                queue.push(Reverse((alt + h, h, next)));
+            }
+        }
+    }
+    None
+}
+
+fn main() {
+    let (start, goal) = ((0, 0), (7, 7));
+    for &diagonal in &[true, false] {
+        let grid = Grid {
+            diagonal,
+            ..Grid::task()
+        };
+        if diagonal {
+            println!("With diagonal moves, by the Chebyshev distance:");
+        } else {
+            println!("Without diagonal moves, by the Manhattan distance:");
+        }
+        match a_star(&grid, start, goal, |p| grid.heuristic(p, goal)) {
+            Some((cost, path)) => {
+                println!("{}", grid.show(&path, start, goal));
+                println!("Path: {:?}", path);
+                println!("Cost: {}", cost);
+            }
+            None => println!("No path"),
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dijkstras_algorithm::Graph;
+    use rand::prelude::*;
+
+    /// The cost of the cheapest path to every square, by Dijkstra's algorithm on the grid as
+    /// a graph, from `start`, or to `start` if `reversed`.
+    fn dijkstra(grid: &Grid, start: Point, reversed: bool) -> HashMap<Point, Cost> {
+        let squares: Vec<Point> = (0..grid.height)
+            .flat_map(|y| (0..grid.width).map(move |x| (x, y)))
+            .collect();
+        let names: Vec<String> = squares.iter().map(|p| format!("{:?}", p)).collect();
+        let name = |p: Point| names[(p.1 * grid.width + p.0) as usize].as_str();
+        let mut graph = Graph::new();
+        for &p in &squares {
+            graph.get_or_insert_vertex(name(p));
+        }
+        for &p in &squares {
+            for (next, step) in grid.neighbours(p) {
+                let (from, to) = if reversed { (next, p) } else { (p, next) };
+                graph.add_edge(name(from), name(to), step as usize);
+            }
+        }
+        let paths = graph.dijkstra(graph.vertex_index(name(start)).unwrap());
+        squares
+            .iter()
+            .filter_map(|&p| {
+                let node = graph.vertex_index(name(p)).unwrap();
+                paths.dist[node].map(|d| (p, d as Cost))
+            })
+            .collect()
+    }
+
+    fn random_grid(rng: &mut StdRng, diagonal: bool) -> Grid {
+        let (width, height) = (rng.gen_range(1, 12), rng.gen_range(1, 12));
+        let barriers = (0..rng.gen_range(0, width * height))
+            .map(|_| (rng.gen_range(0, width), rng.gen_range(0, height)))
+            .collect();
+        Grid {
+            width,
+            height,
+            barriers,
+            diagonal,
+        }
+    }
+
+    fn assert_path(grid: &Grid, path: &[Point], cost: Cost, start: Point, goal: Point) {
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        let total: Cost = path
+            .windows(2)
+            .map(|step| {
+                let moves = grid.neighbours(step[0]);
+                moves.iter().find(|(p, _)| *p == step[1]).unwrap().1
+            })
+            .sum();
+        assert_eq!(total, cost);
+    }
+
+    #[test]
+    fn task_example() {
+        let (start, goal) = ((0, 0), (7, 7));
+        for &(diagonal, expected) in &[(true, 11), (false, 14)] {
+            let grid = Grid {
+                diagonal,
+                ..Grid::task()
+            };
+            let (cost, path) = a_star(&grid, start, goal, |p| grid.heuristic(p, goal)).unwrap();
+            assert_eq!(cost, expected);
+            assert_eq!(path.len() as Cost, cost + 1);
+            assert!(path.iter().all(|p| !grid.barriers.contains(p)));
+            assert_path(&grid, &path, cost, start, goal);
+            assert_eq!(dijkstra(&grid, start, false)[&goal], cost);
+        }
+    }
+
+    #[test]
+    fn heuristics_are_admissible() {
+        // No estimate is more than the cost to the goal, from anywhere on any grid.
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            for &diagonal in &[false, true] {
+                let grid = random_grid(&mut rng, diagonal);
+                let goal = (rng.gen_range(0, grid.width), rng.gen_range(0, grid.height));
+                for (p, cost) in dijkstra(&grid, goal, true) {
+                    assert!(grid.heuristic(p, goal) <= cost);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn as_cheap_as_dijkstra() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..300 {
+            for &diagonal in &[false, true] {
+                let grid = random_grid(&mut rng, diagonal);
+                let start = (rng.gen_range(0, grid.width), rng.gen_range(0, grid.height));
+                let goal = (rng.gen_range(0, grid.width), rng.gen_range(0, grid.height));
+                let expected = dijkstra(&grid, start, false)[&goal];
+                // The heuristic, no estimate at all, which leaves every square tied, and an
+                // estimate that is too low by varying amounts, which breaks ties differently.
+                let estimates: [Box<dyn Fn(Point) -> Cost>; 3] = [
+                    Box::new(|p| grid.heuristic(p, goal)),
+                    Box::new(|_| 0),
+                    Box::new(|p| grid.heuristic(p, goal) / 2),
+                ];
+                for estimate in &estimates {
+                    let (cost, path) = a_star(&grid, start, goal, estimate).unwrap();
+                    assert_eq!(cost, expected);
+                    assert_path(&grid, &path, cost, start, goal);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overestimates_can_miss() {
+        // Counting diagonal moves twice can lead the search past the cheapest path, which is
+        // why the estimate must not be too high.
+        let mut rng = StdRng::seed_from_u64(2);
+        let missed = (0..300).any(|_| {
+            let grid = random_grid(&mut rng, true);
+            let start = (rng.gen_range(0, grid.width), rng.gen_range(0, grid.height));
+            let goal = (rng.gen_range(0, grid.width), rng.gen_range(0, grid.height));
+            let manhattan = |(x, y): Point| ((x - goal.0).abs() + (y - goal.1).abs()) as Cost;
+            let (optimal, _) = a_star(&grid, start, goal, |p| grid.heuristic(p, goal)).unwrap();
+            let (cost, _) = a_star(&grid, start, goal, manhattan).unwrap();
+            assert!(cost >= optimal);
+            cost > optimal
+        });
+        assert!(missed);
+    }
+}
@@ -6,63 +6,73 @@ Further,$allow$for$each$word$in$a$column$to$be$either$left$
 justified,$right$justified,$or$center$justified$within$its$column.
 ";
 
+/// How the words are placed in their column.
+#[derive(Clone, Copy, Debug)]
+enum Alignment {
+    Left,
+    Right,
+    Center,
+}
+
 fn main() {
     let (chunks, max_lengths) = align_columns(TEST_STR);
     print_aligned_columns(&chunks, &max_lengths);
 }
 
+/// Splits the text into rows of fields, and finds the width of every column.
+///
+/// A `$` at the end of a line does not start another field, and rows may have fewer fields
+/// than others. Widths are counted in characters, so that accented words line up too.
 fn align_columns(text: &str) -> (Vec<Vec<String>>, Vec<usize>) {
-    let lines: Vec<String> = text.split('\n').map(|s| s.to_string()).collect();
     let mut max_lengths: Vec<usize> = Vec::new();
     let mut chunks: Vec<Vec<String>> = Vec::new();
 
-    for line in &lines {
+    for line in text.lines() {
+        let line = line.strip_suffix('$').unwrap_or(line);
         let split_line: Vec<String> = line.split('$').map(|s| s.to_string()).collect();
-        chunks.push(split_line.clone());
-        let v: Vec<usize> = split_line.iter().map(|chunk| chunk.len()).collect();
 
-        for i in 0..v.len() {
-            if i < max_lengths.len() {
-                max_lengths[i] = std::cmp::max(max_lengths[i], v[i]);
-            } else {
-                max_lengths.push(v[i]);
+        for (i, chunk) in split_line.iter().enumerate() {
+            let length = chunk.chars().count();
+            match max_lengths.get_mut(i) {
+                Some(max) => *max = (*max).max(length),
+                None => max_lengths.push(length),
             }
         }
+        chunks.push(split_line);
     }
 
     (chunks, max_lengths)
 }
 
-fn print_aligned_columns(chunks: &[Vec<String>], max_lengths: &[usize]) {
-    // left aligned
-    for chunk in chunks {
-        for (i, split) in chunk.iter().enumerate() {
-            print!("{0:<1$}", split, 1 + max_lengths[i]);
-        }
-        println!();
-    }
-    println!();
-    // right aligned
+/// Lays out the rows in columns separated by a space, without trailing spaces.
+fn format_columns(chunks: &[Vec<String>], max_lengths: &[usize], alignment: Alignment) -> String {
+    let mut output = String::new();
     for chunk in chunks {
+        let mut line = String::new();
         for (i, split) in chunk.iter().enumerate() {
-            print!("{0:>1$}", split, 1 + max_lengths[i]);
-        }
-        println!();
-    }
-    println!();
-    // center aligned
-    for chunk in chunks {
-        for (i, split) in chunk.iter().enumerate() {
-            let spaces: usize = 1 + max_lengths[i] - split.len();
-            for _ in 0..spaces >> 1 {
-                print!(" ");
-            }
-            print!("{}", split);
-            for _ in 0..(spaces - (spaces >> 1)) {
-                print!(" ");
+            let spaces = max_lengths[i] - split.chars().count();
+            let before = match alignment {
+                Alignment::Left => 0,
+                Alignment::Right => spaces,
+                Alignment::Center => spaces / 2,
+            };
+            if i > 0 {
+                line.push(' ');
             }
+            line.extend(std::iter::repeat_n(' ', before));
+            line.push_str(split);
+            line.extend(std::iter::repeat_n(' ', spaces - before));
         }
-        println!();
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+fn print_aligned_columns(chunks: &[Vec<String>], max_lengths: &[usize]) {
+    for &alignment in &[Alignment::Left, Alignment::Right, Alignment::Center] {
+        println!("{:?} aligned:", alignment);
+        println!("{}", format_columns(chunks, max_lengths, alignment));
     }
 }
 
@@ -79,3 +89,47 @@ fn test_result() {
     }
     print_aligned_columns(&chunks, &max_lengths);
 }
+
+#[test]
+fn test_golden_outputs() {
+    let (chunks, max_lengths) = align_columns(TEST_STR);
+    let golden = [
+        (Alignment::Left, include_str!("../resources/left.txt")),
+        (Alignment::Right, include_str!("../resources/right.txt")),
+        (Alignment::Center, include_str!("../resources/center.txt")),
+    ];
+    for &(alignment, expected) in &golden {
+        assert_eq!(
+            format_columns(&chunks, &max_lengths, alignment),
+            expected,
+            "{:?}",
+            alignment
+        );
+    }
+}
+
+#[test]
+fn test_trailing_delimiters_and_ragged_rows() {
+    let (chunks, max_lengths) = align_columns("a$bb$\nccc$d$e$ffff\n\ng\n");
+    assert_eq!(max_lengths, [3, 2, 1, 4]);
+    assert_eq!(chunks[0], ["a", "bb"]);
+    assert_eq!(chunks[2], [""]);
+    assert_eq!(
+        format_columns(&chunks, &max_lengths, Alignment::Right),
+        "  a bb\nccc  d e ffff\n\n  g\n"
+    );
+    assert_eq!(
+        format_columns(&chunks, &max_lengths, Alignment::Center),
+        " a  bb\nccc d  e ffff\n\n g\n"
+    );
+}
+
+#[test]
+fn test_unicode_widths() {
+    let (chunks, max_lengths) = align_columns("é$x\nabc$y\n");
+    assert_eq!(max_lengths, [3, 1]);
+    assert_eq!(
+        format_columns(&chunks, &max_lengths, Alignment::Left),
+        "é   x\nabc y\n"
+    );
+}
@@ -1,3 +1,5 @@
+use prime_decomposition::factor;
+
 #[derive(Debug, PartialEq)]
 enum AliquotType {
     Terminating,
@@ -9,18 +11,40 @@ enum AliquotType {
     NonTerminating,
 }
 
-#[cfg_attr(feature = "clippy", allow(needless_range_loop))]
-fn classify_aliquot(num: i64) -> (AliquotType, Vec<i64>) {
-    let limit = 1i64 << 47; // 140737488355328
-    let mut terms = Some(num).into_iter().collect::<Vec<_>>();
-    for i in 0..16 {
-        let n = terms[i];
-        let divsum = (1..(n + 1) / 2 + 1)
-            .filter(|&x| n % x == 0 && n != x)
-            .fold(0, |sum, x| sum + x);
+/// Sequences with more than this many terms are considered non-terminating.
+const MAX_TERMS: usize = 16;
+
+/// Sequences with a term larger than this are considered non-terminating.
+const MAX_TERM: u64 = 1 << 47; // 140737488355328
+
+/// Returns the sum of the proper divisors of `n`.
+///
+/// This is computed from the prime factorization of `n`: the sum of all divisors of
+/// `p1^k1 * p2^k2 * ...` is the product of `1 + p + ... + p^k` over all prime powers.
+fn proper_divisor_sum(n: u64) -> u64 {
+    let factors = factor(n);
+    let mut divisor_sum = 1;
+    let mut i = 0;
+    while i < factors.len() {
+        let p = factors[i];
+        let (mut power, mut power_sum) = (1, 1);
+        while i < factors.len() && factors[i] == p {
+            power *= p;
+            power_sum += power;
+            i += 1;
+        }
+        divisor_sum *= power_sum;
+    }
+    divisor_sum - n
+}
+
+fn classify_aliquot(num: u64) -> (AliquotType, Vec<u64>) {
+    let mut terms = vec![num];
+    while terms.len() < MAX_TERMS {
+        let divsum = proper_divisor_sum(*terms.last().unwrap());
         let classification = if divsum == 0 {
             Some(AliquotType::Terminating)
-        } else if divsum > limit {
+        } else if divsum > MAX_TERM {
             Some(AliquotType::NonTerminating)
         } else if let Some(prev_idx) = terms.iter().position(|&x| x == divsum) {
             let cycle_len = terms.len() - prev_idx;
@@ -48,8 +72,30 @@ fn classify_aliquot(num: i64) -> (AliquotType, Vec<i64>) {
 
 fn main() {
     let nums = [
-        1i64, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 28, 496, 220, 1184, 12_496, 1_264_460, 790, 909,
-        562, 1064, 1488, /* , 15355717786080 */
+        1,
+        2,
+        3,
+        4,
+        5,
+        6,
+        7,
+        8,
+        9,
+        10,
+        11,
+        12,
+        28,
+        496,
+        220,
+        1184,
+        12_496,
+        1_264_460,
+        790,
+        909,
+        562,
+        1064,
+        1488,
+        15_355_717_786_080,
     ];
     for num in &nums {
         println!("{} {:?}", num, classify_aliquot(*num));
@@ -58,7 +104,41 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::{classify_aliquot, AliquotType};
+    use super::{classify_aliquot, proper_divisor_sum, AliquotType};
+
+    #[test]
+    fn divisor_sums() {
+        let sums = [0, 1, 1, 3, 1, 6, 1, 7, 4, 8, 1, 16];
+        for (n, &sum) in (1..).zip(&sums) {
+            assert_eq!(proper_divisor_sum(n), sum);
+        }
+        assert_eq!(proper_divisor_sum(220), 284);
+        assert_eq!(proper_divisor_sum(284), 220);
+    }
+
+    #[test]
+    fn one_to_ten() {
+        let expected: [&[u64]; 10] = [
+            &[1, 0],
+            &[2, 1, 0],
+            &[3, 1, 0],
+            &[4, 3, 1, 0],
+            &[5, 1, 0],
+            &[6, 6],
+            &[7, 1, 0],
+            &[8, 7, 1, 0],
+            &[9, 4, 3, 1, 0],
+            &[10, 8, 7, 1, 0],
+        ];
+        for (n, &terms) in (1..).zip(&expected) {
+            let expected_type = if n == 6 {
+                AliquotType::Perfect
+            } else {
+                AliquotType::Terminating
+            };
+            assert_eq!(classify_aliquot(n), (expected_type, terms.to_vec()));
+        }
+    }
 
     #[test]
     fn terminating() {
@@ -135,7 +215,6 @@ mod tests {
         );
     }
 
-    #[ignore]
     #[test]
     fn non_terminating() {
         assert_eq!(
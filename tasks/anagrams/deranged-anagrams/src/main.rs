@@ -0,0 +1,87 @@
+use anagrams::anagrams;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+
+/// Whether no letter is at the same place in both words.
+fn is_deranged(a: &str, b: &str) -> bool {
+    a.chars().zip(b.chars()).all(|(x, y)| x != y)
+}
+
+/// The longest pair of anagrams that are derangements of each other. Among pairs of the same
+/// length, the one that sorts first is chosen.
+fn longest_deranged(groups: &HashMap<String, HashSet<String>>) -> Option<(String, String)> {
+    let mut best: Option<(String, String)> = None;
+    for group in groups.values().filter(|group| group.len() > 1) {
+        let mut words: Vec<&String> = group.iter().collect();
+        words.sort();
+        for (i, a) in words.iter().enumerate() {
+            for b in &words[i + 1..] {
+                if !is_deranged(a, b) {
+                    continue;
+                }
+                let candidate = ((*a).clone(), (*b).clone());
+                let better = match &best {
+                    None => true,
+                    Some(best) => {
+                        let (len, best_len) = (a.chars().count(), best.0.chars().count());
+                        len > best_len || (len == best_len && candidate < *best)
+                    }
+                };
+                if better {
+                    best = Some(candidate);
+                }
+            }
+        }
+    }
+    best
+}
+
+fn main() {
+    let reader = BufReader::new(File::open("../resources/unixdict.txt").unwrap());
+    let groups = anagrams(reader.lines().map(|l| l.unwrap()));
+    match longest_deranged(&groups) {
+        Some((a, b)) => println!("{} {}", a, b),
+        None => println!("no deranged anagrams"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups(words: &[&str]) -> HashMap<String, HashSet<String>> {
+        anagrams(words.iter().map(|w| w.to_string()))
+    }
+
+    #[test]
+    fn derangements() {
+        assert!(is_deranged("abc", "bca"));
+        assert!(!is_deranged("abc", "acb"));
+        assert!(is_deranged("", ""));
+    }
+
+    #[test]
+    fn longest() {
+        let words = [
+            "tops", "stop", "spot", "post", "opts", "ab", "ba", "abc", "acb",
+        ];
+        // "ab" and "ba" are deranged too, but shorter.
+        assert_eq!(
+            longest_deranged(&groups(&words)),
+            Some(("opts".to_string(), "post".to_string()))
+        );
+        assert_eq!(longest_deranged(&groups(&["abc", "acb", "ab"])), None);
+    }
+
+    #[test]
+    fn unixdict() {
+        let reader = BufReader::new(File::open("../resources/unixdict.txt").unwrap());
+        let groups = anagrams(reader.lines().map(|l| l.unwrap()));
+        assert_eq!(
+            longest_deranged(&groups),
+            Some(("excitation".to_string(), "intoxicate".to_string()))
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Swings a pendulum in the terminal. Its motion follows `θ'' = -(g / L) sin θ`, without the
+//! small angle approximation, integrated with the fourth-order Runge-Kutta method.
+//!
+//! cargo run -p animate-a-pendulum -- [degrees] [seconds]
+
+use bresenhams_line_algorithm::line_points;
+use runge_kutta_method::rk4_step;
+use std::io::stdout;
+use std::ops::{Add, Mul};
+use std::thread;
+use std::time::Duration;
+use term_grid::{TermGrid, CLEAR};
+
+const GRAVITY: f64 = 9.81;
+/// The length of the rod, in metres.
+const LENGTH: f64 = 1.0;
+
+/// The time step of the integration.
+const DT: f64 = 0.01;
+/// The steps between two frames, which are shown as long apart as they are in the simulation.
+const STEPS_PER_FRAME: usize = 4;
+
+/// The length of the rod, in lines of the terminal.
+const REACH: usize = 15;
+/// Characters are about twice as tall as they are wide, so the rod spans twice as many columns
+/// as lines for the same length.
+const ASPECT: f64 = 2.0;
+
+/// The angle of the rod from the vertical, in radians, counterclockwise, and how fast it
+/// changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct State {
+    angle: f64,
+    velocity: f64,
+}
+
+impl Add for State {
+    type Output = State;
+
+    fn add(self, other: State) -> State {
+        State {
+            angle: self.angle + other.angle,
+            velocity: self.velocity + other.velocity,
+        }
+    }
+}
+
+impl Mul<f64> for State {
+    type Output = State;
+
+    fn mul(self, k: f64) -> State {
+        State {
+            angle: self.angle * k,
+            velocity: self.velocity * k,
+        }
+    }
+}
+
+/// How the state changes: the angle at its velocity, and the velocity with the part of gravity
+/// across the rod.
+fn derivative(_: f64, state: State) -> State {
+    State {
+        angle: state.velocity,
+        velocity: -GRAVITY / LENGTH * state.angle.sin(),
+    }
+}
+
+/// The kinetic and potential energy of the bob, per unit of mass, with the lowest point as the
+/// zero of the potential energy.
+fn energy(state: State) -> f64 {
+    let speed = LENGTH * state.velocity;
+    speed * speed / 2.0 + GRAVITY * LENGTH * (1.0 - state.angle.cos())
+}
+
+/// The pendulum at rest at `angle`, at time 0, followed by its state `DT` after the one before.
+fn swing(angle: f64) -> impl Iterator<Item = (f64, State)> {
+    let start = State {
+        angle,
+        velocity: 0.0,
+    };
+    (0..).scan(start, |state, i| {
+        let t = i as f64 * DT;
+        let current = *state;
+        *state = rk4_step(&derivative, t, current, DT);
+        Some((t, current))
+    })
+}
+
+/// The rod hanging from the middle of the top line, with the bob at its end. The bob goes out
+/// of the picture when it swings above the pivot.
+fn picture(state: State) -> TermGrid {
+    let width = 2 * (ASPECT * REACH as f64) as usize + 3;
+    let mut grid = TermGrid::new(width, REACH + 2);
+    let pivot = ((width / 2) as isize, 0);
+    let bob = (
+        pivot.0 + (ASPECT * REACH as f64 * state.angle.sin()).round() as isize,
+        pivot.1 + (REACH as f64 * state.angle.cos()).round() as isize,
+    );
+    let mut put = |(x, y): (isize, isize), c| {
+        if x >= 0 && y >= 0 {
+            grid.set(x as usize, y as usize, c);
+        }
+    };
+    for point in line_points(pivot, bob) {
+        put(point, '.');
+    }
+    put(pivot, '+');
+    put(bob, 'O');
+    grid
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let degrees: f64 = args.next().map_or(60.0, |d| d.parse().expect("an angle"));
+    let seconds: f64 = args.next().map_or(20.0, |s| s.parse().expect("a duration"));
+    let start = energy(State {
+        angle: degrees.to_radians(),
+        velocity: 0.0,
+    });
+
+    print!("{}", CLEAR);
+    let frames = swing(degrees.to_radians())
+        .step_by(STEPS_PER_FRAME)
+        .take_while(|&(t, _)| t <= seconds);
+    for (t, state) in frames {
+        let mut grid = picture(state);
+        let status = format!(
+            "t = {:5.2} s, angle = {:6.1}°, energy drift = {:.1e}",
+            t,
+            state.angle.to_degrees(),
+            energy(state) - start
+        );
+        grid.put_str(0, REACH + 1, &status);
+        grid.show(stdout().lock()).unwrap();
+        thread::sleep(Duration::from_secs_f64(DT * STEPS_PER_FRAME as f64));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    /// The times at which the pendulum passes through the bottom going the same way as it first
+    /// does, interpolated between the steps, up to `seconds`.
+    fn crossings(angle: f64, seconds: f64) -> Vec<f64> {
+        let states: Vec<(f64, State)> = swing(angle).take_while(|&(t, _)| t <= seconds).collect();
+        states
+            .windows(2)
+            .filter(|w| w[0].1.angle > 0.0 && w[1].1.angle <= 0.0)
+            .map(|w| {
+                let (a, b) = (w[0].1.angle, w[1].1.angle);
+                w[0].0 + DT * a / (a - b)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn energy_is_conserved() {
+        for &degrees in &[10.0, 60.0, 120.0, 179.0] {
+            let start = energy(State {
+                angle: f64::to_radians(degrees),
+                velocity: 0.0,
+            });
+            // Ten minutes, or 60000 steps.
+            let drift = swing(f64::to_radians(degrees))
+                .take_while(|&(t, _)| t <= 600.0)
+                .map(|(_, state)| (energy(state) - start).abs())
+                .fold(0.0, f64::max);
+            assert!(drift / start < 1e-5, "{}°: {}", degrees, drift / start);
+        }
+    }
+
+    #[test]
+    fn small_swings() {
+        // With the small angle approximation, the period is 2π sqrt(L / g).
+        let period = 2.0 * PI * (LENGTH / GRAVITY).sqrt();
+        let times = crossings(f64::to_radians(1.0), 20.0);
+        for pair in times.windows(2) {
+            assert!((pair[1] - pair[0] - period).abs() < 1e-4 * period);
+        }
+        assert_eq!(times.len(), 10);
+    }
+
+    #[test]
+    fn large_swings_take_longer() {
+        // The period grows with the amplitude; for 60°, it is 2π sqrt(L / g) · 1.0732.
+        let period = 2.0 * PI * (LENGTH / GRAVITY).sqrt() * 1.073_182;
+        let times = crossings(f64::to_radians(60.0), 20.0);
+        let average = (times[times.len() - 1] - times[0]) / (times.len() - 1) as f64;
+        assert!((average - period).abs() < 1e-5 * period, "{}", average);
+        // Halfway through a swing, it is as far out on the other side.
+        let half = swing(f64::to_radians(60.0))
+            .take_while(|&(t, _)| t <= period / 2.0)
+            .last()
+            .unwrap();
+        assert!((half.1.angle + f64::to_radians(60.0)).abs() < 0.05);
+    }
+
+    #[test]
+    fn pictures() {
+        let (width, middle) = (63, 31);
+        let rest = picture(State {
+            angle: 0.0,
+            velocity: 0.0,
+        });
+        assert_eq!(rest.width(), width);
+        assert_eq!(rest.get(middle, 0), Some('+'));
+        assert!((1..REACH).all(|y| rest.get(middle, y) == Some('.')));
+        assert_eq!(rest.get(middle, REACH), Some('O'));
+
+        let level = picture(State {
+            angle: PI / 2.0,
+            velocity: 0.0,
+        });
+        assert_eq!(level.get(middle + 30, 0), Some('O'));
+        assert_eq!(level.get(middle + 1, 0), Some('.'));
+        assert!(level
+            .to_string()
+            .lines()
+            .skip(1)
+            .all(|l| l.trim().is_empty()));
+    }
+}
@@ -0,0 +1,91 @@
+//! Computes π with the Gauss–Legendre algorithm, which converges quadratically: every
+//! iteration of the arithmetic-geometric mean roughly doubles the number of correct digits.
+//!
+//! Accepts the number of digits as an optional command line argument.
+
+use num::{BigInt, One};
+
+/// Fixed-point arithmetic on top of `BigInt`.
+///
+/// A real number `x` is represented by the integer `x * 10^digits`, truncated towards zero.
+struct Fixed {
+    one: BigInt,
+}
+
+impl Fixed {
+    fn new(digits: usize) -> Self {
+        Fixed {
+            one: num::pow(BigInt::from(10), digits),
+        }
+    }
+
+    fn int(&self, n: i64) -> BigInt {
+        &self.one * n
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a * b / &self.one
+    }
+
+    fn div(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a * &self.one / b
+    }
+
+    fn sqrt(&self, a: &BigInt) -> BigInt {
+        (a * &self.one).sqrt()
+    }
+}
+
+/// Returns π with `digits` decimal places, as a string.
+fn pi(digits: usize) -> String {
+    // A few guard digits absorb the rounding errors of the intermediate operations.
+    let fixed = Fixed::new(digits + 10);
+
+    let mut a = fixed.int(1);
+    let mut g = fixed.div(&a, &fixed.sqrt(&fixed.int(2)));
+    let mut z = fixed.div(&a, &fixed.int(4));
+    let mut n = BigInt::one();
+
+    // Stop once the two means agree to within the rounding error.
+    while (&a - &g) > BigInt::from(1000) {
+        let a1 = (&a + &g) / 2;
+        g = fixed.sqrt(&fixed.mul(&a, &g));
+        let d = &a1 - &a;
+        z -= fixed.mul(&d, &d) * &n;
+        n *= 2;
+        a = a1;
+    }
+
+    let pi = fixed.div(&fixed.mul(&a, &a), &z).to_string();
+    format!("{}.{}", &pi[..1], &pi[1..=digits])
+}
+
+fn main() {
+    let digits = std::env::args().nth(1).map_or(1000, |arg| {
+        arg.parse().expect("expected a number of digits")
+    });
+    println!("{}", pi(digits));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_thousand_digits() {
+        let reference = include_str!("../resources/pi.txt").trim();
+        assert_eq!(pi(1000), reference);
+    }
+
+    #[test]
+    fn few_digits() {
+        assert_eq!(pi(1), "3.1");
+        assert_eq!(pi(10), "3.1415926535");
+    }
+
+    #[test]
+    fn more_digits_extend_fewer() {
+        let long = pi(3000);
+        assert!(long.starts_with(&pi(1500)));
+    }
+}
@@ -1,13 +1,15 @@
-//! Accepts two command line arguments
-//! cargo run --name agm arg1 arg2
+//! Accepts two optional command line arguments, defaulting to `1` and `1/√2`.
+//! cargo run -p arithmetic-geometric-mean -- arg1 arg2
 
 use std::f32;
 
 fn main() {
-    let mut args = std::env::args();
+    let mut args = std::env::args().skip(1);
 
-    let x = args.next().unwrap().to_owned().parse::<f32>().unwrap();
-    let y = args.next().unwrap().to_owned().parse::<f32>().unwrap();
+    let x = args.next().map_or(1f32, |arg| arg.parse::<f32>().unwrap());
+    let y = args.next().map_or(f32::consts::FRAC_1_SQRT_2, |arg| {
+        arg.parse::<f32>().unwrap()
+    });
 
     let result = agm(x, y);
     println!("The arithmetic-geometric mean is {}", result);
@@ -40,3 +42,10 @@ fn test_agm_12_6() {
 
     assert!((agm(12f32, 6f32) - 8.740746f32).abs() < f32::EPSILON);
 }
+
+#[test]
+fn test_agm_1_inverse_sqrt_2() {
+    use std::f32;
+
+    assert!((agm(1f32, f32::consts::FRAC_1_SQRT_2) - 0.847_213_1f32).abs() < 1e-6);
+}
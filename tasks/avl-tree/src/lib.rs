@@ -431,9 +431,7 @@ impl<K: Ord + Copy + Debug + Display, V: Debug + Copy + Display> AVLTree<K, V> {
         // slug: (pointer to parent of deleted node, side of deleted node)
         let slug = self.delete(k);
         let (pdel, side) = slug;
-        if pdel.is_none() {
-            return None;
-        };
+        pdel?;
         let ndel = self.get_node(pdel);
 
         let mut p = pdel;
@@ -591,9 +589,9 @@ impl<K: Ord + Copy + Debug + Display, V: Debug + Copy + Display> AVLTree<K, V> {
                 value = Blue.bold().paint(format!("{:.*}", 2, node.value)),
                 width = 4
             );
-            let _ = write!(
+            let _ = writeln!(
                 f,
-                "{bal:<-width$}\n",
+                "{bal:<-width$}",
                 bal = Red.bold().paint(node.balance),
                 width = 2
             );
@@ -780,8 +778,10 @@ fn get_deletion_constants(is_left: bool) -> &'static BalanceConstants {
 pub fn random_bal_tree(n: u32) -> AVLTree<i32, f32> {
     let mut tree: AVLTree<i32, f32> = AVLTree::new();
     let mut rng = rand::thread_rng();
-    // `Uniform` rather than `gen_range`'s `Uniform::sample_single` for speed
-    let key_range = Uniform::new(-(n as i32) / 2, (n as i32) / 2);
+    // `Uniform` rather than `gen_range`'s `Uniform::sample_single` for speed. Inclusive, so
+    // that the range is not empty for an empty tree.
+    let half = (n / 2) as i32;
+    let key_range = Uniform::new_inclusive(-half, half);
     let value_range = Uniform::new(-1.0, 1.0);
     tree.insert_bal(0, rng.sample(value_range));
     for _ in 0..n {
@@ -792,6 +792,8 @@ pub fn random_bal_tree(n: u32) -> AVLTree<i32, f32> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use rand::distributions::Uniform;
     use rand::prelude::*;
 
@@ -1075,11 +1077,45 @@ mod tests {
         res = tree.gather_balances();
         let (_, bals) = res;
 
-        if bals.len() > 0 {
+        if !bals.is_empty() {
             assert!(*bals.iter().max().unwrap() < 2);
             assert!(*bals.iter().min().unwrap() > -2);
         }
+    }
+
+    #[test]
+    /// Random inserts and deletes, checked against a `BTreeMap` after every operation.
+    fn test_random_operations() {
+        let mut tree: AVLTree<i32, f32> = AVLTree::new();
+        let mut model = BTreeMap::new();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..5000 {
+            let key = rng.gen_range(-200, 200);
+            if rng.gen_bool(0.6) {
+                let value = rng.gen_range(-1.0, 1.0);
+                // An existing key keeps its value.
+                tree.insert_bal(key, value);
+                model.entry(key).or_insert(value);
+            } else {
+                tree.delete_bal(key);
+                model.remove(&key);
+            }
+
+            // The balances are gathered from the right, so the keys come out in descending
+            // order.
+            let (keys, bals) = tree.gather_balances();
+            assert!(keys.iter().rev().eq(model.keys()));
+            assert!(bals.iter().all(|b| (-1..=1).contains(b)));
+            assert_eq!(tree.store.len(), model.len());
+        }
 
-        return;
+        // The balances kept up to date by the rotations are the true ones.
+        let (_, bals) = tree.gather_balances();
+        let root = tree.root;
+        tree.compute_balances(root);
+        assert_eq!(tree.gather_balances().1, bals);
+        for (&k, &v) in &model {
+            assert_eq!(tree.lookup(k), Some(v));
+        }
     }
 }
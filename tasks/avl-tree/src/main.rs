@@ -22,20 +22,26 @@ fn main() {
 
     let mut tree = avl_tree::random_bal_tree(opt.nodes as u32);
     let mut rng = rand::thread_rng();
-    // `Uniform` rather than `gen_range`'s `Uniform::sample_single`
-    let key_range = Uniform::new(-(opt.operations as i32) / 2, (opt.operations as i32) / 2);
+    // `Uniform` rather than `gen_range`'s `Uniform::sample_single`. Inclusive, so that the
+    // range is not empty without any operations.
+    let half = (opt.operations / 2) as i32;
+    let key_range = Uniform::new_inclusive(-half, half);
     let value_range = Uniform::new(-1.0, 1.0);
     tree.insert_bal(0, rng.sample(value_range));
     for _ in 0..opt.operations {
-        tree.insert_bal(rng.sample(key_range), rng.sample(value_range));
+        if rng.gen() {
+            tree.insert_bal(rng.sample(key_range), rng.sample(value_range));
+        } else {
+            tree.delete_bal(rng.sample(key_range));
+        }
     }
     let (_, bals) = tree.gather_balances();
-    assert!(*bals.iter().max().unwrap() < 2);
-    assert!(*bals.iter().min().unwrap() > -2);
+    // The deletes may have emptied the tree, which is balanced too.
+    assert!(bals.iter().all(|&b| -2 < b && b < 2));
 
     println!(
-        "AVL tree after ~{} random inserts and ~{} random deletes, starting with {} nodes:",
-        opt.operations, opt.operations, opt.nodes
+        "AVL tree after {} random inserts and deletes, starting with {} nodes:",
+        opt.operations, opt.nodes
     );
     println!("{}", tree);
 }
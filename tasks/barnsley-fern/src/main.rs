@@ -0,0 +1,213 @@
+//! Draws Barnsley's fern with an iterated function system: a point jumps around under one of
+//! four affine maps at a time, picked at random with fixed probabilities, and the places it
+//! visits make up the fern.
+//!
+//! cargo run -p barnsley-fern -- [points] [seed] [height]
+
+use bitmap::{Color, Image};
+use rand::prelude::*;
+use write_a_ppm_file::{Format, PPMWritable};
+
+/// The map from `(x, y)` to `(a x + b y + e, c x + d y + f)`, picked with probability `p`.
+struct Transform {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+    p: f64,
+}
+
+/// The stem, the ever smaller copies of the whole fern going up, and the largest leaves on the
+/// left and the right.
+const TRANSFORMS: [Transform; 4] = [
+    Transform {
+        a: 0.0,
+        b: 0.0,
+        c: 0.0,
+        d: 0.16,
+        e: 0.0,
+        f: 0.0,
+        p: 0.01,
+    },
+    Transform {
+        a: 0.85,
+        b: 0.04,
+        c: -0.04,
+        d: 0.85,
+        e: 0.0,
+        f: 1.6,
+        p: 0.85,
+    },
+    Transform {
+        a: 0.2,
+        b: -0.26,
+        c: 0.23,
+        d: 0.22,
+        e: 0.0,
+        f: 1.6,
+        p: 0.07,
+    },
+    Transform {
+        a: -0.15,
+        b: 0.28,
+        c: 0.26,
+        d: 0.24,
+        e: 0.0,
+        f: 0.44,
+        p: 0.07,
+    },
+];
+
+/// The smallest rectangle around the fern, as `((x_min, y_min), (x_max, y_max))`.
+const BOUNDS: ((f64, f64), (f64, f64)) = ((-2.1820, 0.0), (2.6558, 9.9983));
+
+/// The points of the fern, one after the other, from the origin.
+struct Fern<R> {
+    rng: R,
+    point: (f64, f64),
+}
+
+impl<R: Rng> Iterator for Fern<R> {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<(f64, f64)> {
+        let mut r: f64 = self.rng.gen();
+        // The last one takes whatever rounding left over.
+        let t = TRANSFORMS
+            .iter()
+            .find(|t| {
+                r -= t.p;
+                r < 0.0
+            })
+            .unwrap_or(&TRANSFORMS[3]);
+        let (x, y) = self.point;
+        self.point = (t.a * x + t.b * y + t.e, t.c * x + t.d * y + t.f);
+        Some(self.point)
+    }
+}
+
+fn fern<R: Rng>(rng: R) -> Fern<R> {
+    Fern {
+        rng,
+        point: (0.0, 0.0),
+    }
+}
+
+/// The first `points` points of the fern in green on black, `height` pixels tall, and as wide
+/// as the fern takes.
+fn draw<I: Iterator<Item = (f64, f64)>>(points: I, height: usize) -> Image {
+    let ((x_min, y_min), (x_max, y_max)) = BOUNDS;
+    let scale = (height - 1) as f64 / (y_max - y_min);
+    let width = ((x_max - x_min) * scale).round() as usize + 1;
+    let mut image = Image::new(width, height);
+    let green = Color {
+        red: 34,
+        green: 139,
+        blue: 34,
+    };
+    for (x, y) in points {
+        let column = ((x - x_min) * scale).round() as usize;
+        // Up the picture, from the bottom row.
+        let row = height - 1 - ((y - y_min) * scale).round() as usize;
+        image[(column, row)] = green;
+    }
+    image
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let points = args
+        .next()
+        .map_or(200_000, |n| n.parse().expect("a number of points"));
+    let seed = args.next().map_or(0, |s| s.parse().expect("a seed"));
+    let height = args.next().map_or(600, |h| h.parse().expect("a height"));
+
+    let points = fern(StdRng::seed_from_u64(seed)).take(points);
+    draw(points, height)
+        .write_ppm("barnsley-fern.ppm", Format::Raw)
+        .unwrap();
+    println!("Wrote barnsley-fern.ppm");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POINTS: usize = 200_000;
+
+    fn points() -> Vec<(f64, f64)> {
+        fern(StdRng::seed_from_u64(0)).take(POINTS).collect()
+    }
+
+    #[test]
+    fn probabilities_add_up() {
+        let total: f64 = TRANSFORMS.iter().map(|t| t.p).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = points();
+        let ((x_min, y_min), (x_max, y_max)) = BOUNDS;
+        let fold = |f: fn(f64, f64) -> f64, g: fn(&(f64, f64)) -> f64, start| {
+            points.iter().map(g).fold(start, f)
+        };
+        let left = fold(f64::min, |p| p.0, 0.0);
+        let right = fold(f64::max, |p| p.0, 0.0);
+        let bottom = fold(f64::min, |p| p.1, 0.0);
+        let top = fold(f64::max, |p| p.1, 0.0);
+        // Inside the box, and reaching nearly all the way to its sides.
+        assert!(x_min <= left && left < x_min + 0.01, "{}", left);
+        assert!(x_max - 0.01 < right && right <= x_max, "{}", right);
+        assert!(y_min <= bottom && bottom < y_min + 0.01, "{}", bottom);
+        assert!(y_max - 0.01 < top && top <= y_max, "{}", top);
+    }
+
+    #[test]
+    fn density() {
+        let points = points();
+        let share = |keep: &dyn Fn(&(f64, f64)) -> bool| {
+            points.iter().filter(|p| keep(p)).count() as f64 / POINTS as f64
+        };
+
+        // Only the stem is on the y axis, with the 1% of the points that went there last.
+        let stem = share(&|p| p.0 == 0.0);
+        assert!((0.009..0.011).contains(&stem), "{}", stem);
+
+        // The fern is thin at the bottom, where there is only the stem and the lowest leaves,
+        // and about as dense all the way up from there.
+        let band = |low: f64| share(&|p| low <= p.1 && p.1 < low + 1.0);
+        assert!(band(0.0) < 0.02 && band(1.0) < 0.05);
+        for low in 3..9 {
+            let share = band(f64::from(low));
+            assert!((0.10..0.14).contains(&share), "{}: {}", low, share);
+        }
+
+        // The mean of the points that the maps keep in place on average: m = sum p (A m + b),
+        // a pair of linear equations.
+        let sum = |f: fn(&Transform) -> f64| TRANSFORMS.iter().map(|t| t.p * f(t)).sum::<f64>();
+        let (a, b) = (1.0 - sum(|t| t.a), -sum(|t| t.b));
+        let (c, d) = (-sum(|t| t.c), 1.0 - sum(|t| t.d));
+        let (e, f) = (sum(|t| t.e), sum(|t| t.f));
+        let det = a * d - b * c;
+        let mean = ((e * d - b * f) / det, (a * f - c * e) / det);
+        let x = points.iter().map(|p| p.0).sum::<f64>() / POINTS as f64;
+        let y = points.iter().map(|p| p.1).sum::<f64>() / POINTS as f64;
+        // The points follow each other closely, so they vary less than random ones would.
+        assert!((x - mean.0).abs() < 0.05, "{} {}", x, mean.0);
+        assert!((y - mean.1).abs() < 0.1, "{} {}", y, mean.1);
+    }
+
+    #[test]
+    fn picture() {
+        let image = draw(points().into_iter(), 100);
+        assert_eq!((image.width, image.height), (49, 100));
+        // The tip, where the fern reaches furthest up and to the right, but no other corner.
+        assert_ne!(image[(48, 0)], Color::default());
+        for &corner in &[(0, 0), (0, 99), (48, 99)] {
+            assert_eq!(image[corner], Color::default());
+        }
+    }
+}
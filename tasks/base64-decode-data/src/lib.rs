@@ -0,0 +1,131 @@
+//! Base64 decoding as described in RFC 4648.
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    /// A character outside of the alphabet, at the given byte offset.
+    InvalidCharacter(char, usize),
+    /// The number of characters, ignoring whitespace, is not a multiple of four.
+    InvalidLength,
+    /// Padding in the middle of the data, or unused bits that are not zero.
+    InvalidPadding,
+}
+
+/// The value of a character of the standard alphabet.
+fn value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes base64 data, skipping any whitespace (such as the line breaks of MIME).
+///
+/// Every group of four characters turns into three bytes, or fewer if the group ends in `=`.
+/// Unused bits before the padding must be zero, so that there is only one way to encode any
+/// data.
+pub fn decode(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    let chars: Vec<(usize, u8)> = encoded
+        .bytes()
+        .enumerate()
+        .filter(|&(_, c)| !c.is_ascii_whitespace())
+        .collect();
+    if !chars.len().is_multiple_of(4) {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut decoded = Vec::with_capacity(chars.len() / 4 * 3);
+    let groups = chars.len() / 4;
+    for (n, group) in chars.chunks(4).enumerate() {
+        let padding = group.iter().rev().take_while(|&&(_, c)| c == b'=').count();
+        // Only the last group may be padded, and by no more than two characters.
+        if padding > 2 || (padding > 0 && n + 1 != groups) {
+            return Err(DecodeError::InvalidPadding);
+        }
+
+        let mut bits = 0u32;
+        for &(offset, c) in &group[..4 - padding] {
+            let v = value(c).ok_or_else(|| {
+                // Report the whole character, even if it is not ASCII.
+                let c = encoded[offset..].chars().next().unwrap();
+                if c == '=' {
+                    DecodeError::InvalidPadding
+                } else {
+                    DecodeError::InvalidCharacter(c, offset)
+                }
+            })?;
+            bits = bits << 6 | u32::from(v);
+        }
+        bits <<= 6 * padding;
+
+        let bytes = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+        let len = 3 - padding;
+        if bytes[len..].iter().any(|&b| b != 0) {
+            return Err(DecodeError::InvalidPadding);
+        }
+        decoded.extend_from_slice(&bytes[..len]);
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use base64_encode_data::encode;
+    use rand::prelude::*;
+
+    #[test]
+    fn rfc_4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("Zg==", "f"),
+            ("Zm8=", "fo"),
+            ("Zm9v", "foo"),
+            ("Zm9vYg==", "foob"),
+            ("Zm9vYmE=", "fooba"),
+            ("Zm9vYmFy", "foobar"),
+        ];
+        for &(encoded, expected) in &vectors {
+            assert_eq!(decode(encoded).unwrap(), expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(decode(" Zm9v\r\nYmFy\n").unwrap(), b"foobar");
+        assert_eq!(decode("Zm 9v Yg = =").unwrap(), b"foob");
+        assert_eq!(decode("\t\n").unwrap(), b"");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(decode("Zm9"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            decode("Zm9v!mFy"),
+            Err(DecodeError::InvalidCharacter('!', 4))
+        );
+        assert_eq!(
+            decode("Zm9vY\u{e9}="),
+            Err(DecodeError::InvalidCharacter('\u{e9}', 5))
+        );
+        assert_eq!(decode("Zg==Zm8="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode("Z==="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode("Zm=v"), Err(DecodeError::InvalidPadding));
+        // "Zh==" has bits set that do not fit in the single decoded byte.
+        assert_eq!(decode("Zh=="), Err(DecodeError::InvalidPadding));
+    }
+
+    #[test]
+    fn random_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let len = rng.gen_range(0, 100);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(decode(&encode(&data)).unwrap(), data);
+        }
+    }
+}
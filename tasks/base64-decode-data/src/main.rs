@@ -0,0 +1,8 @@
+use base64_decode_data::decode;
+
+const ENCODED: &str = "VG8gZXJyIGlzIGh1bWFuLCBidXQgdG8gcmVhbGx5IGZvdWwgdGhpbmdzIHVwIHlvdSBuZWVkIGEgY29tcHV0ZXIuCiAgICAtLSBQYXVsIFIuIEVocmxpY2g=";
+
+fn main() {
+    let decoded = decode(ENCODED).expect("the task's data is valid base64");
+    println!("{}", String::from_utf8_lossy(&decoded));
+}
@@ -0,0 +1,24 @@
+//! Encodes the file named on the command line, or the standard input if there is none.
+
+use std::fs;
+use std::io::{self, Read};
+
+use base64_encode_data::encode;
+
+fn main() -> io::Result<()> {
+    let data = match std::env::args().nth(1) {
+        Some(path) => fs::read(path)?,
+        None => {
+            let mut data = vec![];
+            io::stdin().read_to_end(&mut data)?;
+            data
+        }
+    };
+
+    // Wrap the output at 76 characters, like MIME does.
+    let encoded = encode(&data);
+    for line in encoded.as_bytes().chunks(76) {
+        println!("{}", String::from_utf8_lossy(line));
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use bitmap::{Color, Image};
+
+/// Where the center of pixel `i` out of `to` falls among `from` pixels, kept between the
+/// centers of the first and last pixel.
+fn source_coordinate(i: usize, from: usize, to: usize) -> f64 {
+    let x = (i as f64 + 0.5) * from as f64 / to as f64 - 0.5;
+    x.max(0.0).min((from - 1) as f64)
+}
+
+/// Mixes two values, `t` being the weight of the second one.
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Resizes an image. Each new pixel is interpolated between the four source pixels around
+/// where it falls, first along the rows and then between the rows.
+pub fn scale(image: &Image, width: usize, height: usize) -> Image {
+    let mut scaled = Image::new(width, height);
+    if image.width == 0 || image.height == 0 {
+        return scaled;
+    }
+
+    for y in 0..height {
+        let sy = source_coordinate(y, image.height, height);
+        let y0 = sy.floor() as usize;
+        let y1 = (y0 + 1).min(image.height - 1);
+        let ty = sy - y0 as f64;
+
+        for x in 0..width {
+            let sx = source_coordinate(x, image.width, width);
+            let x0 = sx.floor() as usize;
+            let x1 = (x0 + 1).min(image.width - 1);
+            let tx = sx - x0 as f64;
+
+            let (c00, c10) = (image[(x0, y0)], image[(x1, y0)]);
+            let (c01, c11) = (image[(x0, y1)], image[(x1, y1)]);
+            let channel = |get: fn(Color) -> u8| {
+                let top = lerp(f64::from(get(c00)), f64::from(get(c10)), tx);
+                let bottom = lerp(f64::from(get(c01)), f64::from(get(c11)), tx);
+                lerp(top, bottom, ty).round() as u8
+            };
+            scaled[(x, y)] = Color {
+                red: channel(|c| c.red),
+                green: channel(|c| c.green),
+                blue: channel(|c| c.blue),
+            };
+        }
+    }
+    scaled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Red grows by 4 per column and green by 3 per row, so that interpolating between any
+    /// pixels is exact.
+    fn gradient(width: usize, height: usize) -> Image {
+        let mut image = Image::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                image[(x, y)] = Color {
+                    red: (4 * x) as u8,
+                    green: (3 * y) as u8,
+                    blue: 100,
+                };
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn same_size() {
+        let image = gradient(7, 5);
+        assert_eq!(scale(&image, 7, 5).data, image.data);
+    }
+
+    #[test]
+    fn gradients_stay_linear() {
+        let image = gradient(40, 30);
+        for &(width, height) in &[(64, 48), (17, 13), (100, 7), (1, 1)] {
+            let scaled = scale(&image, width, height);
+            for y in 0..height {
+                let sy = source_coordinate(y, 30, height);
+                for x in 0..width {
+                    let sx = source_coordinate(x, 40, width);
+                    let pixel = scaled[(x, y)];
+                    assert!((f64::from(pixel.red) - 4.0 * sx).abs() <= 0.5);
+                    assert!((f64::from(pixel.green) - 3.0 * sy).abs() <= 0.5);
+                    assert_eq!(pixel.blue, 100);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn doubling_a_checkerboard() {
+        let mut image = Image::new(2, 2);
+        image[(0, 0)].red = 200;
+        image[(1, 1)].red = 200;
+        let scaled = scale(&image, 4, 4);
+        let reds: Vec<u8> = scaled.data.iter().map(|c| c.red).collect();
+        // The outer pixels copy their corner, the inner ones mix in a quarter of the others.
+        assert_eq!(
+            reds,
+            [
+                200, 150, 50, 0, //
+                150, 125, 75, 50, //
+                50, 75, 125, 150, //
+                0, 50, 150, 200,
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert!(scale(&Image::new(0, 0), 3, 3)
+            .data
+            .iter()
+            .all(|&c| c == Color::default()));
+        assert!(scale(&gradient(3, 3), 0, 5).data.is_empty());
+    }
+}
@@ -0,0 +1,24 @@
+//! Scales a PPM file by a factor of 1.6.
+//!
+//! cargo run -p bilinear-interpolation -- input.ppm output.ppm
+
+use bilinear_interpolation::scale;
+use read_a_ppm_file::{read_ppm_file, Error};
+use std::env;
+use write_a_ppm_file::{Format, PPMWritable};
+
+const FACTOR: f64 = 1.6;
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: bilinear-interpolation input.ppm output.ppm");
+        std::process::exit(2);
+    }
+
+    let image = read_ppm_file(&args[0])?;
+    let width = (image.width as f64 * FACTOR).round() as usize;
+    let height = (image.height as f64 * FACTOR).round() as usize;
+    scale(&image, width, height).write_ppm(&args[1], Format::Raw)?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use bitmap::{Color, Image};
+
+/// The pixels of the line from `from` to `to`, both included, in order.
+///
+/// The error term tracks how far the midpoint between the two candidate pixels is from the
+/// ideal line, scaled by two so that only integers are involved. Where the line passes exactly
+/// through that midpoint the choice depends on the direction, so lines are always traced from
+/// their leftmost (then topmost) end, and the same pixels are drawn both ways.
+pub fn line_points(from: (isize, isize), to: (isize, isize)) -> Vec<(isize, isize)> {
+    let (reversed, (x0, y0), (x1, y1)) = if from <= to {
+        (false, from, to)
+    } else {
+        (true, to, from)
+    };
+
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+
+    let mut points = Vec::with_capacity(dx.max(-dy) as usize + 1);
+    let (mut x, mut y) = (x0, y0);
+    let mut error = dx + dy;
+    loop {
+        points.push((x, y));
+        if (x, y) == (x1, y1) {
+            break;
+        }
+        let e2 = 2 * error;
+        if e2 >= dy {
+            error += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            error += dx;
+            y += sy;
+        }
+    }
+
+    if reversed {
+        points.reverse();
+    }
+    points
+}
+
+/// Draws a line, leaving out the pixels that fall outside of the image.
+pub fn draw_line(image: &mut Image, from: (isize, isize), to: (isize, isize), color: Color) {
+    for (x, y) in line_points(from, to) {
+        if x >= 0 && y >= 0 && (x as usize) < image.width && (y as usize) < image.height {
+            image[(x as usize, y as usize)] = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn horizontal_vertical_and_diagonal() {
+        assert_eq!(
+            line_points((0, 0), (3, 0)),
+            [(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+        assert_eq!(line_points((2, 3), (2, 1)), [(2, 3), (2, 2), (2, 1)]);
+        assert_eq!(line_points((0, 0), (-2, 2)), [(0, 0), (-1, 1), (-2, 2)]);
+        assert_eq!(line_points((5, 5), (5, 5)), [(5, 5)]);
+    }
+
+    #[test]
+    fn shallow_and_steep() {
+        assert_eq!(
+            line_points((0, 0), (5, 2)),
+            [(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]
+        );
+        assert_eq!(
+            line_points((0, 0), (2, 5)),
+            [(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (2, 5)]
+        );
+        assert_eq!(
+            line_points((0, 0), (4, -1)),
+            [(0, 0), (1, 0), (2, -1), (3, -1), (4, -1)]
+        );
+    }
+
+    #[test]
+    fn symmetric() {
+        for x0 in -4..=4 {
+            for y0 in -4..=4 {
+                for &(x1, y1) in &[(0, 0), (7, 2), (-3, 6), (5, -5), (1, -7), (-6, -1)] {
+                    let forward = line_points((x0, y0), (x1, y1));
+                    let mut backward = line_points((x1, y1), (x0, y0));
+                    backward.reverse();
+                    assert_eq!(forward, backward);
+
+                    // Every step moves to one of the eight neighbours.
+                    for pair in forward.windows(2) {
+                        let (a, b) = (pair[0], pair[1]);
+                        assert!((a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1);
+                        assert_ne!(a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clipped() {
+        let white = Color {
+            red: 255,
+            green: 255,
+            blue: 255,
+        };
+        let mut image = Image::new(3, 3);
+        draw_line(&mut image, (-2, -2), (4, 4), white);
+        for x in 0..3 {
+            for y in 0..3 {
+                let expected = if x == y { white } else { Color::default() };
+                assert_eq!(image[(x, y)], expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use bitmap::{Color, Image};
+use bresenhams_line_algorithm::draw_line;
+
+fn main() {
+    let mut image = Image::new(20, 12);
+    let white = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+    draw_line(&mut image, (1, 1), (18, 4), white);
+    draw_line(&mut image, (18, 4), (10, 10), white);
+    draw_line(&mut image, (10, 10), (1, 1), white);
+
+    for y in 0..image.height {
+        for x in 0..image.width {
+            print!("{}", if image[(x, y)] == white { '#' } else { '.' });
+        }
+        println!();
+    }
+}
@@ -0,0 +1,141 @@
+use bitmap::{Color, Image};
+
+/// Replaces the color of the region around `start`, made of the pixels of the same color that
+/// are connected horizontally or vertically, and returns the number of pixels filled.
+///
+/// The seeds still to be filled are kept on a stack of our own rather than on the call stack,
+/// since a large region would need far too many recursive calls. Each seed is extended into a
+/// whole horizontal span, and one seed is pushed for each run of matching pixels just above
+/// and below that span.
+pub fn flood_fill(image: &mut Image, start: (usize, usize), color: Color) -> usize {
+    let target = image[start];
+    if target == color {
+        return 0;
+    }
+
+    let mut filled = 0;
+    let mut seeds = vec![start];
+    while let Some((x, y)) = seeds.pop() {
+        if image[(x, y)] != target {
+            continue;
+        }
+
+        let mut left = x;
+        while left > 0 && image[(left - 1, y)] == target {
+            left -= 1;
+        }
+        let mut right = x;
+        while right + 1 < image.width && image[(right + 1, y)] == target {
+            right += 1;
+        }
+        for x in left..=right {
+            image[(x, y)] = color;
+        }
+        filled += right - left + 1;
+
+        let rows = [y.checked_sub(1), Some(y + 1).filter(|&y| y < image.height)];
+        for row in rows.iter().flatten() {
+            let mut in_run = false;
+            for x in left..=right {
+                let matches = image[(x, *row)] == target;
+                if matches && !in_run {
+                    seeds.push((x, *row));
+                }
+                in_run = matches;
+            }
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+    const RED: Color = Color {
+        red: 255,
+        green: 0,
+        blue: 0,
+    };
+
+    /// A white ring between the radii 10 and 15, centered in a black 40x40 image.
+    fn ring() -> Image {
+        let mut image = Image::new(40, 40);
+        for y in 0..40 {
+            for x in 0..40 {
+                let d2 = (x as isize - 20).pow(2) + (y as isize - 20).pow(2);
+                if (100..=225).contains(&d2) {
+                    image[(x, y)] = WHITE;
+                }
+            }
+        }
+        image
+    }
+
+    fn count(image: &Image, color: Color) -> usize {
+        image.data.iter().filter(|&&c| c == color).count()
+    }
+
+    #[test]
+    fn inside_of_a_ring() {
+        let mut image = ring();
+        let black = count(&image, Color::default());
+        let white = count(&image, WHITE);
+        // The pixels closer than 10 to the center, which the ring closes off.
+        let disk = (-9isize..=9)
+            .flat_map(|x| (-9isize..=9).map(move |y| x * x + y * y))
+            .filter(|&d2| d2 < 100)
+            .count();
+
+        assert_eq!(flood_fill(&mut image, (20, 20), RED), disk);
+        assert_eq!(count(&image, RED), disk);
+        assert_eq!(count(&image, WHITE), white);
+        assert_eq!(count(&image, Color::default()), black - disk);
+    }
+
+    #[test]
+    fn outside_of_a_ring() {
+        let mut image = ring();
+        let black = count(&image, Color::default());
+        let outside = (0..40isize)
+            .flat_map(|x| (0..40isize).map(move |y| (x - 20).pow(2) + (y - 20).pow(2)))
+            .filter(|&d2| d2 > 225)
+            .count();
+        let filled = flood_fill(&mut image, (0, 0), RED);
+        assert_eq!(filled, count(&image, RED));
+        assert_eq!(count(&image, Color::default()) + filled, black);
+        assert_eq!(image[(20, 20)], Color::default());
+        assert_eq!(image[(39, 39)], RED);
+        assert_eq!(filled, outside);
+    }
+
+    #[test]
+    fn the_ring_itself() {
+        let mut image = ring();
+        let white = count(&image, WHITE);
+        assert_eq!(flood_fill(&mut image, (20, 5), RED), white);
+        assert_eq!(flood_fill(&mut image, (20, 5), RED), 0);
+    }
+
+    #[test]
+    fn large_region() {
+        // Would overflow the stack if every pixel were a recursive call.
+        let mut image = Image::new(2000, 2000);
+        assert_eq!(flood_fill(&mut image, (1000, 1000), RED), 2000 * 2000);
+    }
+
+    #[test]
+    fn diagonal_gaps_are_walls() {
+        let mut image = Image::new(3, 3);
+        for i in 0..3 {
+            image[(i, 2 - i)] = WHITE;
+        }
+        assert_eq!(flood_fill(&mut image, (0, 0), RED), 3);
+        assert_eq!(image[(2, 2)], Color::default());
+    }
+}
@@ -0,0 +1,47 @@
+use bitmap::{Color, Image};
+use flood_fill::flood_fill;
+
+fn main() {
+    let white = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+    let red = Color {
+        red: 255,
+        green: 0,
+        blue: 0,
+    };
+
+    // Two nested squares, the space between which gets filled.
+    let mut image = Image::new(16, 12);
+    for &(lo, hi_x, hi_y) in &[(1, 14, 10), (5, 10, 7)] {
+        for x in lo..=hi_x {
+            image[(x, lo)] = white;
+            image[(x, hi_y)] = white;
+        }
+        for y in lo..=hi_y {
+            image[(lo, y)] = white;
+            image[(hi_x, y)] = white;
+        }
+    }
+    let filled = flood_fill(&mut image, (3, 3), red);
+
+    for y in 0..image.height {
+        for x in 0..image.width {
+            let c = image[(x, y)];
+            print!(
+                "{}",
+                if c == white {
+                    '#'
+                } else if c == red {
+                    '+'
+                } else {
+                    '.'
+                }
+            );
+        }
+        println!();
+    }
+    println!("{} pixels filled", filled);
+}
@@ -0,0 +1,113 @@
+use bitmap::{Color, Image};
+use std::collections::BTreeSet;
+
+/// The pixels of the circle around `center`, each of them once.
+///
+/// Only the octant from the rightmost point up to the diagonal is traced; the seven others are
+/// its reflections. Going one row further at each step, the error term tells whether the
+/// midpoint between the two candidate columns is inside the circle, in which case the column
+/// stays the same.
+pub fn circle_points(center: (isize, isize), radius: isize) -> BTreeSet<(isize, isize)> {
+    let (cx, cy) = center;
+    let mut points = BTreeSet::new();
+    let (mut x, mut y) = (radius, 0);
+    let mut error = 1 - radius;
+    while x >= y {
+        for &(dx, dy) in &[(x, y), (y, x)] {
+            for &(sx, sy) in &[(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+                points.insert((cx + sx * dx, cy + sy * dy));
+            }
+        }
+        y += 1;
+        if error < 0 {
+            error += 2 * y + 1;
+        } else {
+            x -= 1;
+            error += 2 * (y - x) + 1;
+        }
+    }
+    points
+}
+
+/// Draws a circle, leaving out the pixels that fall outside of the image.
+pub fn draw_circle(image: &mut Image, center: (isize, isize), radius: isize, color: Color) {
+    for (x, y) in circle_points(center, radius) {
+        if x >= 0 && y >= 0 && (x as usize) < image.width && (y as usize) < image.height {
+            image[(x as usize, y as usize)] = color;
+        }
+    }
+}
+
+/// Draws the image as text, with `#` for the pixels of the given color.
+pub fn to_ascii(image: &Image, color: Color) -> String {
+    let mut text = String::new();
+    for y in 0..image.height {
+        for x in 0..image.width {
+            text.push(if image[(x, y)] == color { '#' } else { '.' });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+
+    #[test]
+    fn close_to_the_radius() {
+        for radius in 1..100 {
+            for (x, y) in circle_points((3, -4), radius) {
+                let distance = (((x - 3).pow(2) + (y + 4).pow(2)) as f64).sqrt();
+                assert!(
+                    (distance - radius as f64).abs() <= 0.5,
+                    "({}, {}) is at {} from the center, for a radius of {}",
+                    x,
+                    y,
+                    distance,
+                    radius
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn eight_way_symmetry() {
+        for radius in 0..30 {
+            let points = circle_points((0, 0), radius);
+            for &(x, y) in &points {
+                for &p in &[(-x, y), (x, -y), (y, x), (-y, -x)] {
+                    assert!(points.contains(&p));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_circles() {
+        assert_eq!(
+            circle_points((0, 0), 0).into_iter().collect::<Vec<_>>(),
+            [(0, 0)]
+        );
+        assert_eq!(
+            circle_points((0, 0), 1).into_iter().collect::<Vec<_>>(),
+            [(-1, 0), (0, -1), (0, 1), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn fixture() {
+        let mut image = Image::new(17, 17);
+        draw_circle(&mut image, (8, 8), 7, WHITE);
+        assert_eq!(
+            to_ascii(&image, WHITE),
+            include_str!("../resources/circle.txt")
+        );
+    }
+}
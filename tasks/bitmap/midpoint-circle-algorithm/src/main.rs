@@ -0,0 +1,13 @@
+use bitmap::{Color, Image};
+use midpoint_circle_algorithm::{draw_circle, to_ascii};
+
+fn main() {
+    let white = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+    let mut image = Image::new(17, 17);
+    draw_circle(&mut image, (8, 8), 7, white);
+    print!("{}", to_ascii(&image, white));
+}
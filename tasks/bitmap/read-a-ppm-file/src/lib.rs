@@ -0,0 +1,252 @@
+use bitmap::{Color, Image};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The file is not a well-formed plain (`P3`) or raw (`P6`) PPM file.
+    Format(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Format(message) => write!(f, "invalid PPM file: {}", message),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Reads the whitespace separated fields of a PPM file, skipping the comments that run from a
+/// `#` to the end of the line.
+struct Fields<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(&c) = self.data.get(self.pos) {
+            if c == b'#' {
+                while self.data.get(self.pos).is_some_and(|&c| c != b'\n') {
+                    self.pos += 1;
+                }
+            } else if c.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn token(&mut self) -> Option<&'a [u8]> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|c| !c.is_ascii_whitespace() && *c != b'#')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            None
+        } else {
+            Some(&self.data[start..self.pos])
+        }
+    }
+
+    fn number(&mut self, what: &'static str) -> Result<usize, Error> {
+        self.token()
+            .and_then(|t| std::str::from_utf8(t).ok())
+            .and_then(|t| t.parse().ok())
+            .ok_or(Error::Format(what))
+    }
+}
+
+/// Reads a PPM image. Channels with a maximum value other than 255 are scaled to 0-255.
+pub fn read_ppm<R: Read>(mut reader: R) -> Result<Image, Error> {
+    let mut data = vec![];
+    reader.read_to_end(&mut data)?;
+    let mut fields = Fields {
+        data: &data,
+        pos: 0,
+    };
+
+    let raw = match fields.token() {
+        Some(b"P3") => false,
+        Some(b"P6") => true,
+        _ => return Err(Error::Format("unknown magic number")),
+    };
+    let width = fields.number("bad width")?;
+    let height = fields.number("bad height")?;
+    let max = fields.number("bad maximum value")?;
+    if max == 0 || max > 65535 {
+        return Err(Error::Format("bad maximum value"));
+    }
+    let samples = width
+        .checked_mul(height)
+        .and_then(|n| n.checked_mul(3))
+        .ok_or(Error::Format("image too large"))?;
+
+    let values: Vec<usize> = if raw {
+        // A single whitespace character separates the header from the pixels.
+        if !data.get(fields.pos).is_some_and(u8::is_ascii_whitespace) {
+            return Err(Error::Format("no whitespace after the header"));
+        }
+        let pixels = &data[fields.pos + 1..];
+        // Samples take two bytes, most significant first, when they do not fit in one.
+        let size = if max < 256 { 1 } else { 2 };
+        let bytes = samples
+            .checked_mul(size)
+            .ok_or(Error::Format("image too large"))?;
+        if pixels.len() < bytes {
+            return Err(Error::Format("not enough pixels"));
+        }
+        pixels[..bytes]
+            .chunks(size)
+            .map(|c| c.iter().fold(0, |v, &b| v << 8 | usize::from(b)))
+            .collect()
+    } else {
+        (0..samples)
+            .map(|_| fields.number("bad or missing sample"))
+            .collect::<Result<_, _>>()?
+    };
+    if values.iter().any(|&v| v > max) {
+        return Err(Error::Format("sample above the maximum value"));
+    }
+
+    let scale = |v: usize| ((v * 255 + max / 2) / max) as u8;
+    let mut image = Image::new(width, height);
+    for (pixel, rgb) in image.data.iter_mut().zip(values.chunks(3)) {
+        *pixel = Color {
+            red: scale(rgb[0]),
+            green: scale(rgb[1]),
+            blue: scale(rgb[2]),
+        };
+    }
+    Ok(image)
+}
+
+pub fn read_ppm_file(filename: &str) -> Result<Image, Error> {
+    read_ppm(BufReader::new(File::open(filename)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+    use std::env;
+    use std::fs;
+    use write_a_ppm_file::{Format, PPMWritable};
+
+    fn random_image(rng: &mut StdRng) -> Image {
+        let mut image = Image::new(rng.gen_range(1, 20), rng.gen_range(1, 20));
+        for pixel in &mut image.data {
+            *pixel = Color {
+                red: rng.gen(),
+                green: rng.gen(),
+                blue: rng.gen(),
+            };
+        }
+        image
+    }
+
+    #[test]
+    fn round_trip_through_files() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for &format in &[Format::Plain, Format::Raw] {
+            let image = random_image(&mut rng);
+            let fname = format!(
+                "{}/test-{}.ppm",
+                env::temp_dir().to_str().unwrap(),
+                rng.gen::<u32>(),
+            );
+            image.write_ppm(&fname, format).unwrap();
+            let read = read_ppm_file(&fname).unwrap();
+            fs::remove_file(&fname).unwrap();
+
+            assert_eq!((read.width, read.height), (image.width, image.height));
+            assert_eq!(read.data, image.data);
+        }
+    }
+
+    #[test]
+    fn comments_and_whitespace() {
+        let file = b"P3\n# made by hand\n2 1 # width and height\n255\n  1 2 3\n\n4 5\t6\n";
+        let image = read_ppm(&file[..]).unwrap();
+        assert_eq!(
+            image[(1, 0)],
+            Color {
+                red: 4,
+                green: 5,
+                blue: 6
+            }
+        );
+
+        // In a raw file the pixels may start with bytes that look like whitespace or comments.
+        let file = b"P6 1 1 255\n# \n";
+        let image = read_ppm(&file[..]).unwrap();
+        assert_eq!(
+            image[(0, 0)],
+            Color {
+                red: b'#',
+                green: b' ',
+                blue: b'\n'
+            }
+        );
+    }
+
+    #[test]
+    fn other_maximum_values() {
+        let image = read_ppm(&b"P3 1 1 15 15 0 5"[..]).unwrap();
+        assert_eq!(
+            image[(0, 0)],
+            Color {
+                red: 255,
+                green: 0,
+                blue: 85
+            }
+        );
+
+        let image = read_ppm(&b"P6 1 1 65535\n\xff\xff\x00\x00\x80\x00"[..]).unwrap();
+        assert_eq!(
+            image[(0, 0)],
+            Color {
+                red: 255,
+                green: 0,
+                blue: 128
+            }
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let error = |file: &[u8]| match read_ppm(file) {
+            Err(Error::Format(message)) => message,
+            r => panic!("unexpected result: {:?}", r.map(|_| ())),
+        };
+        assert_eq!(error(b"P5 1 1 255\n\0"), "unknown magic number");
+        assert_eq!(error(b"P3 x 1 255"), "bad width");
+        assert_eq!(error(b"P3 1 1 0"), "bad maximum value");
+        assert_eq!(error(b"P3 1 1 255 1 2"), "bad or missing sample");
+        assert_eq!(error(b"P3 1 1 10 1 2 11"), "sample above the maximum value");
+        assert_eq!(error(b"P6 2 1 255\n\0\0\0"), "not enough pixels");
+        assert_eq!(error(b"P6 1 1 255"), "no whitespace after the header");
+
+        // Sizes that overflow, in samples, or in bytes when each sample takes two.
+        let file = format!("P6 {} 2 255\n", usize::MAX);
+        assert_eq!(error(file.as_bytes()), "image too large");
+        let file = format!("P6 {} 1 65535\n\0\0", usize::MAX / 3);
+        assert_eq!(error(file.as_bytes()), "image too large");
+    }
+}
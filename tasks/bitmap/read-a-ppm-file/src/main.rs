@@ -0,0 +1,40 @@
+//! Reads the PPM file given on the command line, or else writes a gradient to a temporary
+//! file and reads it back.
+
+use bitmap::{Color, Image};
+use read_a_ppm_file::{read_ppm_file, Error};
+use std::env;
+use write_a_ppm_file::{Format, PPMWritable};
+
+fn main() -> Result<(), Error> {
+    let filename = match env::args().nth(1) {
+        Some(filename) => filename,
+        None => {
+            let mut image = Image::new(256, 16);
+            for y in 0..16 {
+                for x in 0..256 {
+                    image[(x, y)] = Color {
+                        red: x as u8,
+                        green: 0,
+                        blue: 255 - x as u8,
+                    };
+                }
+            }
+            let filename = env::temp_dir().join("gradient.ppm");
+            let filename = filename.to_str().unwrap().to_string();
+            image.write_ppm(&filename, Format::Raw)?;
+            filename
+        }
+    };
+
+    let image = read_ppm_file(&filename)?;
+    println!("{}: {}x{}", filename, image.width, image.height);
+    if !image.data.is_empty() {
+        println!("top left: {:?}", image[(0, 0)]);
+        println!(
+            "bottom right: {:?}",
+            image[(image.width - 1, image.height - 1)]
+        );
+    }
+    Ok(())
+}
@@ -1,9 +1,7 @@
 use std::default::Default;
-use std::fs::File;
-use std::io::{BufWriter, Error, Write};
 use std::ops::{Index, IndexMut};
 
-#[derive(Copy, Clone, Default, PartialEq, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct Color {
     pub red: u8,
     pub green: u8,
@@ -30,19 +28,6 @@ impl Image {
             *elem = color;
         }
     }
-
-    pub fn write_ppm(&self, filename: &str) -> Result<(), Error> {
-        let file = File::create(filename)?;
-        let mut writer = BufWriter::new(file);
-        writeln!(&mut writer, "P6")?;
-        writeln!(&mut writer, "{} {} 255", self.width, self.height)?;
-        for color in &self.data {
-            for &channel in &[color.red, color.green, color.blue] {
-                write!(&mut writer, "{}", channel as u8)?;
-            }
-        }
-        Ok(())
-    }
 }
 
 impl Index<(usize, usize)> for Image {
@@ -95,7 +80,7 @@ mod tests {
     #[should_panic]
     fn out_of_bounds() {
         let image = Image::new(10, 10);
-        image[(10, 11)];
+        let _ = image[(10, 11)];
     }
 
     #[test]
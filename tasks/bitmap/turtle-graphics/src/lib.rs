@@ -0,0 +1,217 @@
+//! A turtle that draws lines as it walks around the plane, and the drawing of those lines on an
+//! image.
+//!
+//! The turtle keeps its own coordinates, with y going up and angles in degrees going
+//! counterclockwise from the x axis. Drawing scales the lines to fit the image, so the turtle
+//! never has to know how big the picture will be.
+
+use bitmap::{Color, Image};
+use bresenhams_line_algorithm::draw_line;
+
+pub type Point = (f64, f64);
+pub type Line = (Point, Point);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct State {
+    x: f64,
+    y: f64,
+    heading: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Turtle {
+    state: State,
+    pen_down: bool,
+    /// The states saved by `push`, which `pop` goes back to.
+    saved: Vec<State>,
+    lines: Vec<Line>,
+}
+
+impl Turtle {
+    /// A turtle at the origin, facing along the x axis, with its pen down.
+    pub fn new() -> Self {
+        Turtle {
+            state: State {
+                x: 0.0,
+                y: 0.0,
+                heading: 0.0,
+            },
+            pen_down: true,
+            saved: vec![],
+            lines: vec![],
+        }
+    }
+
+    pub fn position(&self) -> Point {
+        (self.state.x, self.state.y)
+    }
+
+    pub fn heading(&self) -> f64 {
+        self.state.heading
+    }
+
+    /// Walks `distance` ahead, drawing a line if the pen is down.
+    pub fn forward(&mut self, distance: f64) {
+        let from = self.position();
+        let radians = self.state.heading.to_radians();
+        self.state.x += distance * radians.cos();
+        self.state.y += distance * radians.sin();
+        if self.pen_down {
+            self.lines.push((from, self.position()));
+        }
+    }
+
+    pub fn back(&mut self, distance: f64) {
+        self.forward(-distance);
+    }
+
+    pub fn left(&mut self, degrees: f64) {
+        self.state.heading = (self.state.heading + degrees).rem_euclid(360.0);
+    }
+
+    pub fn right(&mut self, degrees: f64) {
+        self.left(-degrees);
+    }
+
+    pub fn pen_up(&mut self) {
+        self.pen_down = false;
+    }
+
+    pub fn pen_down(&mut self) {
+        self.pen_down = true;
+    }
+
+    /// Saves the position and heading, for `pop`.
+    pub fn push(&mut self) {
+        self.saved.push(self.state);
+    }
+
+    /// Goes back to the position and heading saved last, without drawing.
+    ///
+    /// # Panics
+    ///
+    /// If nothing was saved.
+    pub fn pop(&mut self) {
+        self.state = self.saved.pop().expect("nothing to pop");
+    }
+
+    /// The lines drawn so far, as pairs of ends.
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// Draws the lines on an image of `width` by `height`, scaled to fit inside a border of
+    /// `margin` pixels, and centred.
+    pub fn render(&self, width: usize, height: usize, margin: usize, color: Color) -> Image {
+        let mut image = Image::new(width, height);
+        if self.lines.is_empty() {
+            return image;
+        }
+        let points = self.lines.iter().flat_map(|&(a, b)| vec![a, b]);
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for (x, y) in points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        let inner = |size: usize| size.saturating_sub(2 * margin + 1) as f64;
+        let (span_x, span_y) = (max.0 - min.0, max.1 - min.1);
+        let scale = (inner(width) / span_x).min(inner(height) / span_y);
+        // Lines along one axis only have the other to fit, and a single point has none.
+        let scale = if scale.is_finite() { scale } else { 0.0 };
+        let offset = (
+            (width as f64 - 1.0 - span_x * scale) / 2.0,
+            (height as f64 - 1.0 - span_y * scale) / 2.0,
+        );
+        let pixel = |(x, y): Point| {
+            let px = offset.0 + (x - min.0) * scale;
+            // Images count rows from the top.
+            let py = height as f64 - 1.0 - offset.1 - (y - min.1) * scale;
+            (px.round() as isize, py.round() as isize)
+        };
+        for &(a, b) in &self.lines {
+            draw_line(&mut image, pixel(a), pixel(b), color);
+        }
+        image
+    }
+}
+
+impl Default for Turtle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+
+    fn close(a: Point, b: Point) -> bool {
+        (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9
+    }
+
+    #[test]
+    fn walks_and_turns() {
+        let mut turtle = Turtle::new();
+        turtle.forward(2.0);
+        turtle.left(90.0);
+        turtle.forward(1.0);
+        assert!(close(turtle.position(), (2.0, 1.0)));
+        turtle.right(180.0);
+        assert_eq!(turtle.heading(), 270.0);
+        turtle.pen_up();
+        turtle.back(3.0);
+        assert!(close(turtle.position(), (2.0, 4.0)));
+        assert_eq!(turtle.lines().len(), 2);
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut turtle = Turtle::new();
+        turtle.push();
+        turtle.left(45.0);
+        turtle.forward(5.0);
+        turtle.pop();
+        assert_eq!(turtle.position(), (0.0, 0.0));
+        assert_eq!(turtle.heading(), 0.0);
+        assert_eq!(turtle.lines().len(), 1);
+    }
+
+    #[test]
+    fn render_fits_the_image() {
+        // A unit square, drawn on a 10x10 image with a 1 pixel border.
+        let mut turtle = Turtle::new();
+        for _ in 0..4 {
+            turtle.forward(1.0);
+            turtle.left(90.0);
+        }
+        let image = turtle.render(10, 10, 1, WHITE);
+        for y in 0..10 {
+            for x in 0..10 {
+                let on_square = (1..=8).contains(&x)
+                    && (1..=8).contains(&y)
+                    && (x == 1 || x == 8 || y == 1 || y == 8);
+                assert_eq!(image[(x, y)] == WHITE, on_square, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn render_flat_lines() {
+        let mut turtle = Turtle::new();
+        turtle.forward(3.0);
+        let image = turtle.render(5, 3, 0, WHITE);
+        let row: Vec<bool> = (0..5).map(|x| image[(x, 1)] == WHITE).collect();
+        assert_eq!(row, [true; 5]);
+        assert!(Turtle::new()
+            .render(4, 4, 0, WHITE)
+            .data
+            .iter()
+            .all(|&c| c != WHITE));
+    }
+}
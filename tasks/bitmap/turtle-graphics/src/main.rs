@@ -0,0 +1,62 @@
+use bitmap::Color;
+use turtle_graphics::Turtle;
+use write_a_ppm_file::{Format, PPMWritable};
+
+/// A square with a roof, standing on the turtle's position.
+fn house(turtle: &mut Turtle, size: f64) {
+    for _ in 0..4 {
+        turtle.forward(size);
+        turtle.left(90.0);
+    }
+    // Up the left wall to the roof, which is an equilateral triangle.
+    turtle.left(90.0);
+    turtle.pen_up();
+    turtle.forward(size);
+    turtle.pen_down();
+    turtle.right(30.0);
+    turtle.forward(size);
+    turtle.right(120.0);
+    turtle.forward(size);
+}
+
+/// A bar for each number, side by side, as tall as the number.
+fn bar_chart(turtle: &mut Turtle, numbers: &[f64], width: f64) {
+    turtle.left(90.0);
+    for &number in numbers {
+        turtle.forward(number);
+        turtle.right(90.0);
+        turtle.forward(width);
+        turtle.right(90.0);
+        turtle.forward(number);
+        turtle.left(180.0);
+    }
+}
+
+fn main() {
+    let white = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+    let mut turtle = Turtle::new();
+    house(&mut turtle, 100.0);
+
+    // The chart has a turtle of its own, which starts off to the right of where the house was
+    // finished.
+    let (x, _) = turtle.position();
+    let mut chart = Turtle::new();
+    chart.pen_up();
+    chart.forward(x + 150.0);
+    chart.pen_down();
+    bar_chart(&mut chart, &[50.0, 33.0, 200.0, 130.0, 50.0], 20.0);
+
+    turtle
+        .render(300, 300, 10, white)
+        .write_ppm("house.ppm", Format::Raw)
+        .unwrap();
+    chart
+        .render(300, 300, 10, white)
+        .write_ppm("bar-chart.ppm", Format::Raw)
+        .unwrap();
+    println!("Wrote house.ppm and bar-chart.ppm");
+}
@@ -0,0 +1,113 @@
+use bitmap::Image;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// The two encodings of the pixels of a PPM file.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    /// `P3`: the channels are written out as decimal numbers.
+    Plain,
+    /// `P6`: each channel is a single byte.
+    Raw,
+}
+
+pub trait PPMWritable {
+    fn write_ppm_to<W: Write>(&self, writer: W, format: Format) -> io::Result<()>;
+
+    fn write_ppm(&self, filename: &str, format: Format) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(filename)?);
+        self.write_ppm_to(writer, format)
+    }
+}
+
+impl PPMWritable for Image {
+    fn write_ppm_to<W: Write>(&self, mut writer: W, format: Format) -> io::Result<()> {
+        let magic = match format {
+            Format::Plain => "P3",
+            Format::Raw => "P6",
+        };
+        writeln!(writer, "{}", magic)?;
+        writeln!(writer, "{} {} 255", self.width, self.height)?;
+        for row in self.data.chunks(self.width.max(1)) {
+            for color in row {
+                let channels = [color.red, color.green, color.blue];
+                match format {
+                    Format::Plain => {
+                        writeln!(writer, "{} {} {}", channels[0], channels[1], channels[2])?
+                    }
+                    Format::Raw => writer.write_all(&channels)?,
+                }
+            }
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bitmap::Color;
+    use std::env;
+    use std::fs;
+    use std::io::prelude::*;
+    use std::io::BufReader;
+
+    fn image() -> Image {
+        let mut image = Image::new(2, 1);
+        image[(0, 0)] = Color {
+            red: 1,
+            green: 2,
+            blue: 3,
+        };
+        image[(1, 0)] = Color {
+            red: 4,
+            green: 5,
+            blue: 60,
+        };
+        image
+    }
+
+    fn temp_file() -> String {
+        format!(
+            "{}/test-{}.ppm",
+            env::temp_dir().to_str().unwrap(),
+            rand::random::<u32>(),
+        )
+    }
+
+    #[test]
+    fn write_ppm() {
+        let fname = temp_file();
+        image().write_ppm(&fname, Format::Raw).unwrap();
+
+        let file = File::open(&fname).unwrap();
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "P6\n");
+        line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "2 1 255\n");
+        let mut bytes = reader.bytes();
+        assert_eq!(bytes.next().unwrap().unwrap(), 1);
+        assert_eq!(bytes.next().unwrap().unwrap(), 2);
+        assert_eq!(bytes.next().unwrap().unwrap(), 3);
+        assert_eq!(bytes.next().unwrap().unwrap(), 4);
+        assert_eq!(bytes.next().unwrap().unwrap(), 5);
+        assert_eq!(bytes.next().unwrap().unwrap(), 60);
+        assert!(bytes.next().is_none());
+        fs::remove_file(&fname).unwrap();
+    }
+
+    #[test]
+    fn write_plain_ppm() {
+        let fname = temp_file();
+        image().write_ppm(&fname, Format::Plain).unwrap();
+        assert_eq!(
+            fs::read_to_string(&fname).unwrap(),
+            "P3\n2 1 255\n1 2 3\n4 5 60\n"
+        );
+        fs::remove_file(&fname).unwrap();
+    }
+}
@@ -0,0 +1,209 @@
+//! A Bloom filter: a set that answers whether it contains an item in a fixed number of bits,
+//! however large the items are, at the price of sometimes answering yes for an item that was
+//! never inserted. It never answers no for one that was.
+//!
+//! Each item sets `k` bits of the filter, and the filter contains an item if all its bits are
+//! set. The `k` positions come from two hashes of the item by double hashing: the `i`-th is
+//! `h1 + i h2`, which is as good as `k` independent hashes for a Bloom filter (Kirsch and
+//! Mitzenmacher, [Less Hashing, Same Performance][paper]).
+//!
+//! [paper]: https://www.eecs.harvard.edu/~michaelm/postscripts/rsa2008.pdf
+
+use std::collections::hash_map::DefaultHasher;
+use std::f64::consts::LN_2;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use rand::prelude::*;
+
+pub struct BloomFilter<T: ?Sized> {
+    bits: Vec<u64>,
+    /// The number of bits, which need not be a multiple of 64.
+    len: usize,
+    hashes: usize,
+    /// How many items were inserted, counting duplicates.
+    items: usize,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Hash + ?Sized> BloomFilter<T> {
+    /// A filter of `bits` bits, with `hashes` bits set for each item.
+    ///
+    /// # Panics
+    ///
+    /// If either is 0.
+    pub fn new(bits: usize, hashes: usize) -> Self {
+        assert!(bits > 0 && hashes > 0, "a filter needs bits and hashes");
+        BloomFilter {
+            bits: vec![0; bits.div_ceil(64)],
+            len: bits,
+            hashes,
+            items: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// The smallest filter whose false positive rate is at most `rate` once it holds `items`
+    /// items: it has `-items ln(rate) / ln(2)^2` bits, and `ln(2)` times as many hashes as it
+    /// has bits per item.
+    pub fn with_rate(items: usize, rate: f64) -> Self {
+        assert!(0.0 < rate && rate < 1.0, "the rate is a probability");
+        let bits = (-(items.max(1) as f64) * rate.ln() / (LN_2 * LN_2)).ceil();
+        let hashes = (bits / items.max(1) as f64 * LN_2).round().max(1.0);
+        Self::new(bits as usize, hashes as usize)
+    }
+
+    /// The bit positions of `item`.
+    fn positions(&self, item: &T) -> impl Iterator<Item = usize> {
+        let hash = |salt: u8| {
+            let mut hasher = DefaultHasher::new();
+            salt.hash(&mut hasher);
+            item.hash(&mut hasher);
+            hasher.finish()
+        };
+        let h1 = hash(0);
+        // An odd step, so that the positions do not all fall on a few bits when `len` is even.
+        let h2 = hash(1) | 1;
+        let len = self.len as u64;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize)
+    }
+
+    pub fn insert(&mut self, item: &T) {
+        for position in self.positions(item) {
+            self.bits[position / 64] |= 1 << (position % 64);
+        }
+        self.items += 1;
+    }
+
+    /// Whether the filter may contain `item`. It does if it was inserted, and may anyway with the
+    /// false positive rate.
+    pub fn contains(&self, item: &T) -> bool {
+        self.positions(item)
+            .all(|position| self.bits[position / 64] & (1 << (position % 64)) != 0)
+    }
+
+    /// The probability that `contains` is true for an item that was not inserted: with `n` items
+    /// inserted, each bit is still clear with probability `(1 - 1/m)^(kn)`, about `e^(-kn/m)`,
+    /// and an item is a false positive if its `k` bits are all set.
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        let (m, k, n) = (self.len as f64, self.hashes as f64, self.items as f64);
+        (1.0 - (-k * n / m).exp()).powf(k)
+    }
+
+    /// The proportion of bits that are set.
+    pub fn fill_ratio(&self) -> f64 {
+        let set: u32 = self.bits.iter().map(|word| word.count_ones()).sum();
+        f64::from(set) / self.len as f64
+    }
+}
+
+/// The proportion of `trials` random numbers that `filter` contains. They are very unlikely to
+/// have been inserted, among the 2^64 possible numbers, so they count as false positives.
+fn measured_false_positive_rate<R: Rng>(
+    filter: &BloomFilter<u64>,
+    trials: usize,
+    rng: &mut R,
+) -> f64 {
+    let positives = (0..trials).filter(|_| filter.contains(&rng.gen())).count();
+    positives as f64 / trials as f64
+}
+
+fn main() {
+    let mut words = BloomFilter::with_rate(3, 0.01);
+    for word in &["apple", "banana", "cherry"] {
+        words.insert(*word);
+    }
+    for word in &["apple", "cherry", "durian"] {
+        println!("contains {}: {}", word, words.contains(*word));
+    }
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for &rate in &[0.1, 0.01, 0.001] {
+        let inserted: Vec<u64> = (0..10_000).map(|_| rng.gen()).collect();
+        let mut filter = BloomFilter::with_rate(inserted.len(), rate);
+        for n in &inserted {
+            filter.insert(n);
+        }
+        println!(
+            "{} bits, {} hashes: {:.4} estimated, {:.4} measured false positive rate",
+            filter.len,
+            filter.hashes,
+            filter.estimated_false_positive_rate(),
+            measured_false_positive_rate(&filter, 100_000, &mut rng)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_false_negatives() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut filter = BloomFilter::new(1000, 3);
+        let inserted: Vec<u64> = (0..300).map(|_| rng.gen()).collect();
+        for n in &inserted {
+            filter.insert(n);
+        }
+        assert!(inserted.iter().all(|n| filter.contains(n)));
+    }
+
+    #[test]
+    fn empty() {
+        let filter: BloomFilter<str> = BloomFilter::new(100, 4);
+        assert!(!filter.contains("anything"));
+        assert_eq!(filter.estimated_false_positive_rate(), 0.0);
+        assert_eq!(filter.fill_ratio(), 0.0);
+    }
+
+    #[test]
+    fn sizing() {
+        // The usual figures: 9.6 bits per item and 7 hashes for a rate of 1%.
+        let filter: BloomFilter<u64> = BloomFilter::with_rate(1000, 0.01);
+        assert_eq!(filter.len, 9586);
+        assert_eq!(filter.hashes, 7);
+    }
+
+    #[test]
+    fn fill_ratio_near_prediction() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut filter = BloomFilter::new(10_000, 5);
+        for _ in 0..1000 {
+            filter.insert(&rng.gen::<u64>());
+        }
+        let predicted = 1.0 - (-5.0_f64 * 1000.0 / 10_000.0).exp();
+        assert!((filter.fill_ratio() - predicted).abs() < 0.01);
+    }
+
+    #[test]
+    fn false_positive_rate_near_prediction() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for &(bits, hashes, items) in &[
+            (10_000, 3, 1000),
+            (10_000, 7, 1000),
+            (20_000, 5, 4000),
+            (4096, 2, 1000),
+            (50_000, 4, 10_000),
+        ] {
+            let mut filter = BloomFilter::new(bits, hashes);
+            let inserted: Vec<u64> = (0..items).map(|_| rng.gen()).collect();
+            for n in &inserted {
+                filter.insert(n);
+            }
+            let estimated = filter.estimated_false_positive_rate();
+            let measured = measured_false_positive_rate(&filter, 50_000, &mut rng);
+            // Within a tenth of the prediction, or the sampling error for the smaller rates.
+            let tolerance = (0.1 * estimated).max(4.0 * (estimated / 50_000.0).sqrt());
+            assert!(
+                (measured - estimated).abs() < tolerance,
+                "{} bits, {} hashes, {} items: {} estimated, {} measured",
+                bits,
+                hashes,
+                items,
+                estimated,
+                measured
+            );
+        }
+    }
+}
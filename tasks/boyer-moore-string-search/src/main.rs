@@ -0,0 +1,159 @@
+/// For every byte, one more than the position of its last occurrence in the pattern, or 0 if
+/// it does not occur at all.
+fn bad_character_table(pattern: &[u8]) -> [usize; 256] {
+    let mut table = [0; 256];
+    for (i, &c) in pattern.iter().enumerate() {
+        table[c as usize] = i + 1;
+    }
+    table
+}
+
+/// `shift[j]` is how far the pattern may safely move when the comparison fails at `j - 1`,
+/// that is after the last `m - j` characters matched. `shift[0]` is used after a full match,
+/// and is the period of the pattern.
+fn good_suffix_table(pattern: &[u8]) -> Vec<usize> {
+    let m = pattern.len();
+    let mut shift = vec![0; m + 1];
+    // `border[i]` is where the widest border of the suffix starting at `i` begins.
+    let mut border = vec![0; m + 1];
+
+    // The matched suffix occurs again further left in the pattern.
+    let (mut i, mut j) = (m, m + 1);
+    border[i] = j;
+    while i > 0 {
+        while j <= m && pattern[i - 1] != pattern[j - 1] {
+            if shift[j] == 0 {
+                shift[j] = j - i;
+            }
+            j = border[j];
+        }
+        i -= 1;
+        j -= 1;
+        border[i] = j;
+    }
+
+    // Only a part of the matched suffix occurs again, as a prefix of the pattern.
+    let mut j = border[0];
+    for (i, s) in shift.iter_mut().enumerate() {
+        if *s == 0 {
+            *s = j;
+        }
+        if i == j {
+            j = border[j];
+        }
+    }
+    shift
+}
+
+/// The positions of all the occurrences of `needle` in `haystack`, including overlapping
+/// ones. The pattern is compared from its end, and moves on by the larger of the shifts
+/// allowed by the bad-character and the good-suffix rules.
+fn search(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    let m = needle.len();
+    if m == 0 {
+        return (0..=haystack.len()).collect();
+    }
+
+    let bad_character = bad_character_table(needle);
+    let good_suffix = good_suffix_table(needle);
+    let mut positions = vec![];
+    let mut s = 0;
+    while s + m <= haystack.len() {
+        let mut j = m;
+        while j > 0 && needle[j - 1] == haystack[s + j - 1] {
+            j -= 1;
+        }
+        if j == 0 {
+            positions.push(s);
+            s += good_suffix[0];
+        } else {
+            let c = haystack[s + j - 1];
+            s += good_suffix[j].max(j.saturating_sub(bad_character[c as usize]));
+        }
+    }
+    positions
+}
+
+fn main() {
+    let examples = [
+        ("GCTAGCTCTACGAGTCTA", "TCTA"),
+        ("GGCTATAATGCGTA", "TAATAAA"),
+        ("there would have been a time for such a word", "word"),
+        ("needle need noodle needle", "needle"),
+        (
+            "Nearby farms grew an acre of alfalfa on the dairy's behalf, with bales of all that \
+             alfalfa exchanged for milk.",
+            "alfalfa",
+        ),
+    ];
+    for &(text, pattern) in &examples {
+        println!(
+            "Found '{}' in '{}' at positions {:?}",
+            pattern,
+            text,
+            search(text.as_bytes(), pattern.as_bytes())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    #[test]
+    fn tables() {
+        let bad_character = bad_character_table(b"ANPANMAN");
+        assert_eq!(bad_character[b'A' as usize], 7);
+        assert_eq!(bad_character[b'N' as usize], 8);
+        assert_eq!(bad_character[b'P' as usize], 3);
+        assert_eq!(bad_character[b'Z' as usize], 0);
+
+        assert_eq!(good_suffix_table(b"ABBABAB"), [5, 5, 5, 5, 2, 5, 4, 1]);
+        assert_eq!(good_suffix_table(b"aaaa"), [1, 1, 2, 3, 4]);
+        assert_eq!(good_suffix_table(b"abcd"), [4, 4, 4, 4, 1]);
+    }
+
+    #[test]
+    fn task_examples() {
+        let text = "Nearby farms grew an acre of alfalfa on the dairy's behalf, with bales of all \
+                    that alfalfa exchanged for milk.";
+        assert_eq!(search(text.as_bytes(), b"alfalfa"), [29, 83]);
+        assert_eq!(search(b"GCTAGCTCTACGAGTCTA", b"TCTA"), [6, 14]);
+        assert_eq!(search(b"GGCTATAATGCGTA", b"TAATAAA"), [] as [usize; 0]);
+        assert_eq!(search(b"needle need noodle needle", b"needle"), [0, 19]);
+    }
+
+    #[test]
+    fn overlapping_and_edge_cases() {
+        assert_eq!(search(b"aaaaa", b"aa"), [0, 1, 2, 3]);
+        assert_eq!(search(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(search(b"abc", b"abcd"), [] as [usize; 0]);
+        assert_eq!(search(b"", b""), [0]);
+        assert_eq!(search(b"ab", b""), [0, 1, 2]);
+    }
+
+    #[test]
+    fn same_as_knuth_morris_pratt() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..2000 {
+            // A small alphabet, so that both heuristics get exercised.
+            let alphabet = if rng.gen() { &b"ab"[..] } else { &b"abcd"[..] };
+            let random = |rng: &mut StdRng, len: usize| -> Vec<u8> {
+                (0..len).map(|_| *alphabet.choose(rng).unwrap()).collect()
+            };
+            let len = rng.gen_range(0, 80);
+            let haystack = random(&mut rng, len);
+            let len = rng.gen_range(1, 8);
+            let needle = random(&mut rng, len);
+            assert_eq!(
+                search(&haystack, &needle),
+                knuth_morris_pratt_string_search::search(&haystack, &needle),
+                "{:?} in {:?}",
+                needle,
+                haystack
+            );
+        }
+    }
+}
@@ -1,131 +1,325 @@
-extern crate image;
-extern crate rand;
+//! Grows a Brownian tree by diffusion-limited aggregation: particles wander at random until
+//! they touch the tree, and stick to it where they touched it.
+//!
+//! cargo run -p brownian-tree -- [particles] [seed] [threads]
+//!
+//! The particles wander in batches, all of them around the tree as it was before the batch,
+//! which the threads share without changing, and remember where they went near it. Afterwards,
+//! they stick one at a time, in order, each at the first place on its way that touches the tree
+//! as it is by then, which may have grown since the batch started. So they stick where they
+//! would have if they had wandered one after the other. Each particle has its own random
+//! numbers, from the seed and its number, so the tree is the same whichever thread each
+//! particle wandered in, and however many there were.
 
-use std::cmp::min;
-use std::env;
-use std::path::Path;
-use std::process;
+use std::thread;
 
-use image::ColorType;
-use rand::distributions::Uniform;
-use rand::{thread_rng, Rng};
+use bitmap::{Color, Image};
+use rand::rngs::SmallRng;
+use rand::{random, Rng, SeedableRng};
+use write_a_ppm_file::{Format, PPMWritable};
 
-fn help() {
-    println!("Usage: brownian_tree <output_path> <mote_count> <edge_length>");
+/// How many particles wander around the same tree.
+const BATCH: u64 = 64;
+
+type Point = (u16, u16);
+
+/// The cells from `min` to `max`, on both axes.
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: Point,
+    max: Point,
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let mut output_path = Path::new("out.png");
-    let mut mote_count: u32 = 10_000;
-    let mut width: usize = 512;
-    let mut height: usize = 512;
-
-    match args.len() {
-        1 => {}
-        4 => {
-            output_path = Path::new(&args[1]);
-            mote_count = args[2].parse::<u32>().unwrap();
-            width = args[3].parse::<usize>().unwrap();
-            height = width;
-        }
-        _ => {
-            help();
-            process::exit(0);
-        }
+impl Bounds {
+    fn contains(&self, (x, y): (usize, usize)) -> bool {
+        let (min, max) = (self.min, self.max);
+        (usize::from(min.0)..=usize::from(max.0)).contains(&x)
+            && (usize::from(min.1)..=usize::from(max.1)).contains(&y)
     }
+}
 
-    assert!(width >= 2);
-
-    // Base 1d array
-    let mut field_raw = vec![0u8; width * height];
-    populate_tree(&mut field_raw, width, height, mote_count);
+struct Tree {
+    width: usize,
+    height: usize,
+    /// When each cell joined the tree, counting from the seed at the centre, if it did.
+    cells: Vec<Option<u32>>,
+    particles: u32,
+    /// The smallest rectangle around the tree.
+    bounds: Bounds,
+}
 
-    // Balance image for 8-bit grayscale
-    let our_max = field_raw.iter().max().unwrap();
-    let fudge = u8::max_value() / our_max;
-    let balanced: Vec<u8> = field_raw.iter().map(|e| e * fudge).collect();
+impl Tree {
+    /// The seed of a tree at the centre of a grid of `width` by `height` cells.
+    fn new(width: usize, height: usize) -> Self {
+        assert!(width >= 3 && height >= 3);
+        assert!(width <= usize::from(u16::MAX) && height <= usize::from(u16::MAX));
+        let centre = ((width / 2) as u16, (height / 2) as u16);
+        let mut tree = Tree {
+            width,
+            height,
+            cells: vec![None; width * height],
+            particles: 0,
+            bounds: Bounds {
+                min: centre,
+                max: centre,
+            },
+        };
+        tree.stick(width / 2, height / 2);
+        tree
+    }
 
-    match image::save_buffer(
-        output_path,
-        &balanced,
-        width as u32,
-        height as u32,
-        ColorType::Gray(8),
-    ) {
-        Err(e) => println!("Error writing output image:\n{}", e),
-        Ok(_) => println!("Output written to:\n{}", output_path.to_str().unwrap()),
+    fn stick(&mut self, x: usize, y: usize) -> bool {
+        let cell = &mut self.cells[y * self.width + x];
+        if cell.is_some() {
+            return false;
+        }
+        *cell = Some(self.particles);
+        self.particles += 1;
+        let (x, y) = (x as u16, y as u16);
+        let bounds = &mut self.bounds;
+        bounds.min = (bounds.min.0.min(x), bounds.min.1.min(y));
+        bounds.max = (bounds.max.0.max(x), bounds.max.1.max(y));
+        true
     }
-}
 
-fn populate_tree(raw: &mut Vec<u8>, width: usize, height: usize, mc: u32) {
-    // Vector of 'width' elements slices
-    let mut field_base: Vec<_> = raw.chunks_mut(width).collect();
-    // Addressable 2d vector
-    let field = field_base.as_mut_slice();
+    fn is_free(&self, x: usize, y: usize) -> bool {
+        self.cells[y * self.width + x].is_none()
+    }
 
-    // Seed mote
-    field[width / 2][height / 2] = 1;
+    /// Whether a particle at a free cell touches the tree, along a side or at a corner.
+    fn touches(&self, x: usize, y: usize) -> bool {
+        (y - 1..=y + 1).any(|y| (x - 1..=x + 1).any(|x| !self.is_free(x, y)))
+    }
 
-    let x_spawn_range = Uniform::new(1, width - 1);
-    let y_spawn_range = Uniform::new(1, height - 1);
-    let mut rng = thread_rng();
+    /// A cell away from the border, where the eight neighbours of a particle are all on the
+    /// grid.
+    fn spawn<R: Rng>(&self, rng: &mut R) -> (usize, usize) {
+        let x = rng.gen_range(1, self.width - 1);
+        let y = rng.gen_range(1, self.height - 1);
+        (x, y)
+    }
 
-    for i in 0..mc {
-        if i % 100 == 0 {
-            println!("{}", i)
+    /// The way of a particle that starts somewhere at random and wanders until it touches the
+    /// tree, where it stops. A particle that wanders off starts over, and one that starts on
+    /// the tree is lost. Only the places within `near` are kept, which must include the cells
+    /// next to the tree.
+    fn wander<R: Rng>(&self, rng: &mut R, near: Bounds) -> Vec<Point> {
+        let mut way = vec![];
+        let (mut x, mut y) = self.spawn(rng);
+        loop {
+            if near.contains((x, y)) {
+                way.push((x as u16, y as u16));
+            }
+            // The neighbours of a particle that does not touch the tree are all free, so it
+            // only ever gets into the tree by starting there.
+            if !self.is_free(x, y) || self.touches(x, y) {
+                return way;
+            }
+            let nx = (x as isize + rng.gen_range(-1, 2)) as usize;
+            let ny = (y as isize + rng.gen_range(-1, 2)) as usize;
+            if nx < 1 || ny < 1 || nx >= self.width - 1 || ny >= self.height - 1 {
+                let start = self.spawn(rng);
+                x = start.0;
+                y = start.1;
+            } else {
+                x = nx;
+                y = ny;
+            }
         }
+    }
 
-        // Spawn mote
-        let mut x = rng.sample(x_spawn_range);
-        let mut y = rng.sample(y_spawn_range);
-
-        // Increment field value when motes spawn on top of the structure
-        if field[x][y] > 0 {
-            field[x][y] = min(field[x][y] + 1, u8::max_value()) as u8;
-            continue;
+    /// Sticks a particle that took `way` to the tree as it is now, where it would have
+    /// stopped: the first place that touches the tree, unless it got into the tree before.
+    /// Returns whether it stuck.
+    fn stick_on_way(&mut self, way: Vec<Point>) -> bool {
+        for (x, y) in way {
+            let (x, y) = (usize::from(x), usize::from(y));
+            if !self.is_free(x, y) {
+                return false;
+            }
+            if self.touches(x, y) {
+                return self.stick(x, y);
+            }
         }
+        false
+    }
 
-        loop {
-            let contacts = field[x - 1][y - 1]
-                + field[x][y - 1]
-                + field[x + 1][y - 1]
-                + field[x - 1][y]
-                + field[x + 1][y]
-                + field[x - 1][y + 1]
-                + field[x][y + 1]
-                + field[x + 1][y + 1];
-
-            if contacts > 0 {
-                field[x][y] = min(field[x][y] + 1, u8::max_value()) as u8;
-                break;
+    /// Grows the tree until `particles` more have stuck to it, with the random walks of each
+    /// `batch` of particles spread over `threads` threads.
+    fn grow(&mut self, particles: u32, seed: u64, threads: usize, batch: u64) {
+        let target = self.particles + particles;
+        assert!(
+            target as usize <= (self.width - 2) * (self.height - 2),
+            "the tree does not fit"
+        );
+        let particle_rng = |particle: u64| {
+            SmallRng::seed_from_u64(seed ^ particle.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        };
+
+        let mut next = 0;
+        while self.particles < target {
+            let particles: Vec<u64> = (next..next + batch).collect();
+            next += batch;
+            // The tree grows by at most a cell a particle, and a particle that touches it is
+            // next to it, so the rest of each way cannot touch what the batch adds.
+            let margin = batch as u16 + 1;
+            let near = Bounds {
+                min: (
+                    self.bounds.min.0.saturating_sub(margin),
+                    self.bounds.min.1.saturating_sub(margin),
+                ),
+                max: (
+                    self.bounds.max.0.saturating_add(margin),
+                    self.bounds.max.1.saturating_add(margin),
+                ),
+            };
+            let tree = &*self;
+            let wander = |particle: u64| tree.wander(&mut particle_rng(particle), near);
+            let ways: Vec<Vec<Point>> = if threads <= 1 {
+                particles.iter().map(|&particle| wander(particle)).collect()
             } else {
-                let range = Uniform::new(-1, 2);
-                let xw = rng.sample(range) + x as i32;
-                let yw = rng.sample(range) + y as i32;
-                if xw < 1 || xw >= (width as i32 - 1) || yw < 1 || yw >= (height as i32 - 1) {
-                    // println!("wandered off");
+                let per_thread = particles.len().div_ceil(threads);
+                thread::scope(|scope| {
+                    let handles: Vec<_> = particles
+                        .chunks(per_thread)
+                        .map(|chunk| {
+                            scope
+                                .spawn(move || chunk.iter().map(|&p| wander(p)).collect::<Vec<_>>())
+                        })
+                        .collect();
+                    handles
+                        .into_iter()
+                        .flat_map(|handle| handle.join().unwrap())
+                        .collect()
+                })
+            };
+
+            for way in ways {
+                if self.particles == target {
                     break;
                 }
-                x = xw as usize;
-                y = yw as usize;
+                self.stick_on_way(way);
             }
         }
     }
+
+    /// The tree on black, from blue at the seed to white at the tips.
+    fn image(&self) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        for (pixel, cell) in image.data.iter_mut().zip(&self.cells) {
+            if let Some(order) = cell {
+                let shade = (u64::from(*order) * 255 / u64::from(self.particles)) as u8;
+                *pixel = Color {
+                    red: shade,
+                    green: shade,
+                    blue: 255,
+                };
+            }
+        }
+        image
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let particles = args
+        .next()
+        .map_or(5000, |n| n.parse().expect("a number of particles"));
+    let seed = args
+        .next()
+        .map_or_else(random, |seed| seed.parse().expect("a seed"));
+    let threads = args.next().map_or_else(
+        || thread::available_parallelism().map_or(1, |n| n.get()),
+        |n| n.parse().expect("a number of threads"),
+    );
+
+    let mut tree = Tree::new(256, 256);
+    tree.grow(particles, seed, threads, BATCH);
+    tree.image()
+        .write_ppm("brownian-tree.ppm", Format::Raw)
+        .unwrap();
+    println!(
+        "Wrote brownian-tree.ppm, with {} particles from seed {}",
+        tree.particles, seed
+    );
 }
 
 #[cfg(test)]
 mod tests {
-    use super::populate_tree;
+    use super::*;
+
+    fn grown(seed: u64, threads: usize) -> Tree {
+        let mut tree = Tree::new(48, 48);
+        tree.grow(150, seed, threads, BATCH);
+        tree
+    }
+
+    #[test]
+    fn same_seed_same_tree() {
+        let tree = grown(7, 1);
+        assert_eq!(tree.particles, 151);
+        assert_eq!(tree.cells, grown(7, 1).cells);
+        assert_ne!(tree.cells, grown(8, 1).cells);
+    }
+
+    #[test]
+    fn threads_do_not_change_the_tree() {
+        let tree = grown(7, 1);
+        for &threads in &[2, 3, 8] {
+            assert_eq!(grown(7, threads).cells, tree.cells, "{} threads", threads);
+        }
+    }
+
+    #[test]
+    fn batches_match_one_at_a_time() {
+        // A batch of one wanders around the tree as it is. A particle's random numbers only
+        // depend on its number, so the particles take the same ways in batches, and stick at
+        // the same places.
+        let mut one_at_a_time = Tree::new(48, 48);
+        one_at_a_time.grow(150, 7, 1, 1);
+        assert_eq!(one_at_a_time.cells, grown(7, 1).cells);
+    }
+
+    #[test]
+    fn particles_stick_to_the_tree() {
+        let tree = grown(3, 4);
+        let order = |x: usize, y: usize| tree.cells[y * tree.width + x];
+        assert_eq!(order(24, 24), Some(0));
+        // Each particle stuck next to one that was there before it.
+        for y in 0..tree.height {
+            for x in 0..tree.width {
+                if let Some(n) = order(x, y).filter(|&n| n > 0) {
+                    assert!(x > 0 && y > 0 && x < tree.width - 1 && y < tree.height - 1);
+                    let earlier = (y - 1..=y + 1)
+                        .flat_map(|y| (x - 1..=x + 1).map(move |x| (x, y)))
+                        .any(|(x, y)| order(x, y).is_some_and(|m| m < n));
+                    assert!(earlier, "{} at ({}, {})", n, x, y);
+                }
+            }
+        }
+    }
 
     #[test]
-    fn test_brownian_tree() {
-        let width = 128;
-        let height = 128;
-        let mote_count = 1000;
-        let mut field_raw = vec![0u8; width * height];
-        populate_tree(&mut field_raw, width, height, mote_count);
-        let our_max = field_raw.iter().max().unwrap();
-        assert!(*our_max >= 1);
+    fn grows_further() {
+        let mut tree = grown(5, 2);
+        tree.grow(10, 6, 2, BATCH);
+        assert_eq!(tree.particles, 161);
+        let count = tree.cells.iter().filter(|cell| cell.is_some()).count();
+        assert_eq!(count, 161);
+    }
+
+    #[test]
+    fn image() {
+        let tree = Tree::new(3, 3);
+        let image = tree.image();
+        assert_eq!(
+            image[(1, 1)],
+            Color {
+                red: 0,
+                green: 0,
+                blue: 255
+            }
+        );
+        assert_eq!(image[(0, 0)], Color::default());
     }
 }
@@ -0,0 +1,156 @@
+//! Prints the calendar of a year in the layout of `cal`, with as many months side by side as
+//! fit in the width of the page.
+//!
+//! cargo run -p calendar -- [width] [year]
+//!
+//! The task asks for 1969 on a 132 column line printer; 80 columns suit a terminal, and 20 fit
+//! a single month.
+
+use chrono::{Datelike, NaiveDate};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The width of a month: seven days of two digits, with a space between them.
+const MONTH_WIDTH: usize = 20;
+/// The space between two months side by side.
+const GAP: usize = 2;
+
+/// The lines of a month: its name, the days of the week, and the weeks it spans, all
+/// `MONTH_WIDTH` wide.
+fn month_lines(year: i32, month: u32) -> Vec<String> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).expect("a valid month");
+    let next = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1),
+    };
+    let days = next.expect("a valid month").pred_opt().unwrap().day() as usize;
+    let offset = first.weekday().num_days_from_sunday() as usize;
+
+    let mut lines = vec![
+        format!(
+            "{:^width$}",
+            MONTHS[month as usize - 1],
+            width = MONTH_WIDTH
+        ),
+        "Su Mo Tu We Th Fr Sa".to_string(),
+    ];
+    let weeks = (offset + days).div_ceil(7);
+    for week in 0..weeks {
+        let line: Vec<String> = (0..7)
+            .map(|weekday| {
+                let day = (7 * week + weekday + 1).wrapping_sub(offset);
+                if (1..=days).contains(&day) {
+                    format!("{:>2}", day)
+                } else {
+                    "  ".to_string()
+                }
+            })
+            .collect();
+        lines.push(line.join(" "));
+    }
+    lines
+}
+
+/// The calendar of `year`, with as many months side by side as fit in `width` columns, but at
+/// least one. Each row of months is as tall as its longest month, and lines end without
+/// trailing spaces.
+fn calendar(year: i32, width: usize) -> String {
+    let per_row = ((width + GAP) / (MONTH_WIDTH + GAP)).clamp(1, 12);
+    let used = per_row * MONTH_WIDTH + (per_row - 1) * GAP;
+
+    let mut output = String::new();
+    let mut push = |line: &str| {
+        output += line.trim_end();
+        output.push('\n');
+    };
+    push(&format!("{:^width$}", "[Snoopy]", width = used));
+    push(&format!("{:^width$}", year, width = used));
+
+    let months: Vec<Vec<String>> = (1..=12).map(|month| month_lines(year, month)).collect();
+    for row in months.chunks(per_row) {
+        push("");
+        let blank = " ".repeat(MONTH_WIDTH);
+        let height = row.iter().map(Vec::len).max().unwrap();
+        for i in 0..height {
+            let line: Vec<&str> = row
+                .iter()
+                .map(|lines| lines.get(i).unwrap_or(&blank).as_str())
+                .collect();
+            push(&line.join(&" ".repeat(GAP)));
+        }
+    }
+    output
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let width = args.next().map_or(80, |w| w.parse().expect("a width"));
+    let year = args.next().map_or(1969, |y| y.parse().expect("a year"));
+    print!("{}", calendar(year, width));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    fn golden(width: usize) {
+        let expected = fs::read_to_string(format!("resources/{}.txt", width)).unwrap();
+        assert_eq!(calendar(1969, width), expected, "{} columns", width);
+    }
+
+    #[test]
+    fn line_printer() {
+        golden(132);
+    }
+
+    #[test]
+    fn terminal() {
+        golden(80);
+    }
+
+    #[test]
+    fn narrow() {
+        golden(20);
+    }
+
+    #[test]
+    fn months() {
+        // 1969 started on a Wednesday, and was not a leap year.
+        let january = month_lines(1969, 1);
+        assert_eq!(january[2], "          1  2  3  4");
+        let february = month_lines(1969, 2);
+        assert_eq!(february[6].trim_end(), "23 24 25 26 27 28");
+        // Leap years have a 29th of February, and a month starting on a Saturday spans six
+        // weeks.
+        assert_eq!(month_lines(2000, 2)[6].trim_end(), "27 28 29");
+        assert_eq!(month_lines(2020, 8)[7].trim_end(), "30 31");
+        // February 2015 started on a Sunday, and spans only four.
+        assert_eq!(month_lines(2015, 2).len(), 2 + 4);
+        assert_eq!(month_lines(1969, 1).len(), 2 + 5);
+    }
+
+    #[test]
+    fn lines_fit() {
+        for &width in &[20, 42, 80, 132] {
+            let text = calendar(1969, width);
+            assert!(text.lines().all(|line| line.len() <= width), "{}", width);
+        }
+        // Too narrow for a month, which still gets printed.
+        assert_eq!(calendar(1969, 10), calendar(1969, 20));
+    }
+}
@@ -3,83 +3,115 @@ use std::io;
 use std::io::prelude::*;
 use std::io::BufReader;
 use std::net::{TcpListener, TcpStream};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex};
 use std::thread;
 
 type Username = String;
 
-/// Sends a message to all clients except the sending client.
-fn broadcast_message(
-    user: &str,
-    clients: &mut HashMap<String, TcpStream>,
-    message: &str,
-) -> io::Result<()> {
-    for (client, stream) in clients.iter_mut() {
-        if client != user {
-            writeln!(stream, "{}", message)?;
+/// Something that lines of chat can be sent to.
+///
+/// This is a `TcpStream` in the server, but the hub does not care, so it can be tested without
+/// any sockets.
+trait Connection {
+    fn send(&mut self, line: &str) -> io::Result<()>;
+}
+
+impl Connection for TcpStream {
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self, "{}", line)
+    }
+}
+
+/// Routes messages between the clients in the chat room.
+struct Hub<C> {
+    clients: HashMap<Username, C>,
+}
+
+impl<C: Connection> Hub<C> {
+    fn new() -> Self {
+        Hub {
+            clients: HashMap::new(),
         }
     }
 
-    Ok(())
+    /// Adds a client to the chat room, unless the name is already taken, in which case the
+    /// connection is given back.
+    fn join(&mut self, name: &str, mut connection: C) -> Result<(), C> {
+        if self.clients.contains_key(name) {
+            return Err(connection);
+        }
+
+        // The greeting always comes before any message from the others. Like for broadcasts,
+        // a failure is noticed by the client's own session.
+        let _ = connection.send(&format!("Welcome, {}!", name));
+        self.broadcast(name, &format!("{} has joined the chat room.", name));
+        self.clients.insert(name.to_owned(), connection);
+        Ok(())
+    }
+
+    fn message(&mut self, name: &str, message: &str) {
+        self.broadcast(name, &format!("{}: {}", name, message));
+    }
+
+    fn leave(&mut self, name: &str) {
+        if self.clients.remove(name).is_some() {
+            self.broadcast(name, &format!("{} has left the chat room.", name));
+        }
+    }
+
+    /// Sends a message to all clients except the sending client.
+    ///
+    /// A client that cannot be reached is not the sender's problem: it is removed from the room
+    /// when its own session ends.
+    fn broadcast(&mut self, sender: &str, line: &str) {
+        for (client, connection) in self.clients.iter_mut() {
+            if client != sender {
+                let _ = connection.send(line);
+            }
+        }
+    }
+}
+
+fn chat_session(stream: TcpStream, hub: &Mutex<Hub<TcpStream>>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let mut name = String::new();
+    loop {
+        write!(writer, "Please enter a username: ")?;
+        if reader.read_line(&mut name)? == 0 {
+            // The client left before choosing a name.
+            return Ok(());
+        }
+        name = name.trim().to_owned();
+
+        match hub.lock().unwrap().join(&name, writer) {
+            Ok(()) => break,
+            Err(connection) => writer = connection,
+        }
+
+        writeln!(writer, "That username is taken.")?;
+        name.clear();
+    }
+
+    let result = reader
+        .lines()
+        .try_for_each(|line| line.map(|line| hub.lock().unwrap().message(&name, &line)));
+
+    hub.lock().unwrap().leave(&name);
+    result
 }
 
 fn chat_loop(listener: &TcpListener) -> io::Result<()> {
-    let local_clients: Arc<RwLock<HashMap<Username, TcpStream>>> =
-        Arc::new(RwLock::new(HashMap::new()));
+    let hub = Arc::new(Mutex::new(Hub::new()));
 
     println!("Accepting connections on {}", listener.local_addr()?.port());
 
     for stream in listener.incoming() {
         match stream {
             Ok(stream) => {
-                let client_clients = Arc::clone(&local_clients);
-                thread::spawn(move || -> io::Result<()> {
-                    let mut reader = BufReader::new(stream.try_clone()?);
-                    let mut writer = stream;
-
-                    let mut name = String::new();
-                    loop {
-                        write!(writer, "Please enter a username: ")?;
-                        reader.read_line(&mut name)?;
-                        name = name.trim().to_owned();
-
-                        let clients = client_clients.read().unwrap();
-                        if !clients.contains_key(&name) {
-                            writeln!(writer, "Welcome, {}!", &name)?;
-                            break;
-                        }
-
-                        writeln!(writer, "That username is taken.")?;
-                        name.clear();
-                    }
-
-                    {
-                        let mut clients = client_clients.write().unwrap();
-                        clients.insert(name.clone(), writer);
-                        broadcast_message(
-                            &name,
-                            &mut *clients,
-                            &format!("{} has joined the chat room.", &name),
-                        )?;
-                    }
-
-                    for line in reader.lines() {
-                        let mut clients = client_clients.write().unwrap();
-                        broadcast_message(&name, &mut *clients, &format!("{}: {}", &name, line?))?;
-                    }
-
-                    {
-                        let mut clients = client_clients.write().unwrap();
-                        clients.remove(&name);
-                        broadcast_message(
-                            &name,
-                            &mut *clients,
-                            &format!("{} has left the chat room.", &name),
-                        )?;
-                    }
-
-                    Ok(())
-                });
+                let hub = Arc::clone(&hub);
+                thread::spawn(move || chat_session(stream, &hub));
             }
             Err(e) => {
                 println!("Connection failed: {}", e);
@@ -97,11 +129,118 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::io;
     use std::io::prelude::*;
     use std::io::{BufReader, BufWriter};
     use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+    use std::rc::Rc;
     use std::thread;
 
+    use super::{Connection, Hub};
+
+    /// A connection that records the lines sent to it.
+    #[derive(Clone, Default)]
+    struct Recorder(Rc<RefCell<Vec<String>>>);
+
+    impl Recorder {
+        fn take(&self) -> Vec<String> {
+            self.0.borrow_mut().drain(..).collect()
+        }
+    }
+
+    impl Connection for Recorder {
+        fn send(&mut self, line: &str) -> io::Result<()> {
+            self.0.borrow_mut().push(line.to_owned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn hub_routing() {
+        let mut hub = Hub::new();
+        let (alice, bob, carol) = (
+            Recorder::default(),
+            Recorder::default(),
+            Recorder::default(),
+        );
+
+        assert!(hub.join("alice", alice.clone()).is_ok());
+        assert!(hub.join("bob", bob.clone()).is_ok());
+        assert!(hub.join("alice", carol.clone()).is_err());
+        assert!(hub.join("carol", carol.clone()).is_ok());
+        assert_eq!(
+            alice.take(),
+            vec![
+                "Welcome, alice!",
+                "bob has joined the chat room.",
+                "carol has joined the chat room.",
+            ]
+        );
+        assert_eq!(
+            bob.take(),
+            vec!["Welcome, bob!", "carol has joined the chat room."]
+        );
+        assert_eq!(carol.take(), vec!["Welcome, carol!"]);
+
+        hub.message("bob", "hi");
+        assert_eq!(alice.take(), vec!["bob: hi"]);
+        assert!(bob.take().is_empty());
+        assert_eq!(carol.take(), vec!["bob: hi"]);
+
+        hub.leave("alice");
+        hub.message("carol", "bye");
+        assert!(alice.take().is_empty());
+        assert_eq!(
+            bob.take(),
+            vec!["alice has left the chat room.", "carol: bye"]
+        );
+
+        // The name is free again.
+        assert!(hub.join("alice", alice.clone()).is_ok());
+    }
+
+    /// A client that cannot be written to does not stop the others from chatting.
+    #[test]
+    fn hub_broken_connection() {
+        struct Broken;
+        impl Connection for Broken {
+            fn send(&mut self, _: &str) -> io::Result<()> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+            }
+        }
+
+        enum Client {
+            Broken(Broken),
+            Working(Recorder),
+        }
+        impl Connection for Client {
+            fn send(&mut self, line: &str) -> io::Result<()> {
+                match self {
+                    Client::Broken(c) => c.send(line),
+                    Client::Working(c) => c.send(line),
+                }
+            }
+        }
+
+        let mut hub = Hub::new();
+        let working = Recorder::default();
+        assert!(hub
+            .join("working", Client::Working(working.clone()))
+            .is_ok());
+        assert!(hub.join("broken", Client::Broken(Broken)).is_ok());
+        hub.message("working", "anyone there?");
+        hub.message("broken", "still here");
+        assert_eq!(
+            working.take(),
+            vec![
+                "Welcome, working!",
+                "broken has joined the chat room.",
+                "broken: still here",
+            ]
+        );
+    }
+
     fn create_client<A>(addr: A) -> (BufReader<TcpStream>, BufWriter<TcpStream>)
     where
         A: ToSocketAddrs,
@@ -1,54 +1,70 @@
+use modular_inverse::egcd;
+
 fn main() {
     let l = [(2, 3), (3, 5), (2, 7)];
     println!("{:?}", chinese_remainder(&l));
 }
 
-fn chinese_remainder(l: &[(i32, i32)]) -> Option<i32> {
-    let product = l.iter().fold(1, |prod, &(_, n)| prod * n);
+/// Finds the smallest non-negative `x` such that `x ≡ a (mod n)` for every pair `(a, n)`.
+///
+/// The moduli do not need to be pairwise coprime. Returns `None` if the congruences
+/// contradict each other.
+fn chinese_remainder(l: &[(i64, i64)]) -> Option<i64> {
+    // Merge the congruences one by one into a single congruence `x ≡ a (mod n)`.
+    let (a, _) = l.iter().try_fold((0, 1), |(a1, n1), &(a2, n2)| {
+        // With p * n1 + q * n2 = g, the merged solution is a1 + n1 * p * (a2 - a1) / g, which
+        // only exists if g divides the difference of the residues.
+        let (g, p, _) = egcd(n1, n2);
+        let difference = a2 - a1;
+        if difference % g != 0 {
+            return None;
+        }
 
-    let mut sum = 0;
-    for &(a, n) in l {
-        let mut term = product / n;
+        let n = n1 / g * n2;
+        let k = i128::from(difference / g) * i128::from(p) % i128::from(n2 / g);
+        let a = (i128::from(a1) + i128::from(n1) * k).rem_euclid(i128::from(n));
+        Some((a as i64, n))
+    })?;
+    Some(a)
+}
 
-        let inv = match mul_inv(term, n) {
-            Some(inv) => inv,
-            None => return None,
-        };
+#[cfg(test)]
+mod tests {
+    use super::chinese_remainder;
+    use rand::prelude::*;
 
-        term *= inv;
-        term *= a;
-        sum += term;
-    }
-    Some(sum % product)
-}
+    #[test]
+    fn test() {
+        let l = [(2, 3), (3, 5), (2, 7)];
+        assert_eq!(chinese_remainder(&l), Some(23));
 
-fn mul_inv(a: i32, b: i32) -> Option<i32> {
-    let (gcd, mut x, _) = egcd(a, b);
-    if gcd != 1 {
-        // No multiplicative inverse exists
-        return None;
-    }
-    if x < 0 {
-        x += b;
+        let l = [(10, 11), (4, 22), (9, 19)];
+        assert_eq!(chinese_remainder(&l), None);
     }
-    Some(x % b)
-}
 
-#[cfg_attr(feature = "cargo-clippy", allow(many_single_char_names))]
-fn egcd(a: i32, b: i32) -> (i32, i32, i32) {
-    if a == 0 {
-        return (b, 0, 1);
+    #[test]
+    fn non_coprime_moduli() {
+        assert_eq!(chinese_remainder(&[(2, 4), (4, 6)]), Some(10));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
     }
 
-    let (g, y, x) = egcd(b % a, a);
-    (g, x - (b / a) * y, y)
-}
+    #[test]
+    fn random_congruences() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let x = rng.gen_range(0, 1_000_000_000);
+            let l: Vec<(i64, i64)> = (0..rng.gen_range(1, 5))
+                .map(|_| {
+                    let n = rng.gen_range(2, 1000);
+                    (x % n, n)
+                })
+                .collect();
 
-#[test]
-fn test() {
-    let l = [(2, 3), (3, 5), (2, 7)];
-    assert_eq!(chinese_remainder(&l), Some(23));
-
-    let l = [(10, 11), (4, 22), (9, 19)];
-    assert_eq!(chinese_remainder(&l), None);
+            let solution = chinese_remainder(&l).unwrap();
+            assert!(solution <= x);
+            for &(a, n) in &l {
+                assert_eq!(solution % n, a);
+            }
+        }
+    }
 }
@@ -0,0 +1,136 @@
+use nalgebra::DMatrix;
+
+#[derive(Debug, PartialEq)]
+enum CholeskyError {
+    /// Only square matrices can be decomposed.
+    NonSquare,
+    /// The matrix is not symmetric.
+    Asymmetric,
+    /// A non-positive value was found on the diagonal during the decomposition, so the matrix
+    /// is not positive-definite.
+    NotPositiveDefinite,
+}
+
+/// Computes the lower triangular matrix `L` such that `L * Lᵀ = a`, using the
+/// Cholesky–Banachiewicz algorithm (row by row).
+fn cholesky(a: &DMatrix<f64>) -> Result<DMatrix<f64>, CholeskyError> {
+    if !a.is_square() {
+        return Err(CholeskyError::NonSquare);
+    }
+    if *a != a.transpose() {
+        return Err(CholeskyError::Asymmetric);
+    }
+
+    let n = a.nrows();
+    let mut l = DMatrix::zeros(n, n);
+
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum();
+
+            if i == j {
+                let diagonal = a[(i, i)] - sum;
+                if diagonal <= 0.0 {
+                    return Err(CholeskyError::NotPositiveDefinite);
+                }
+                l[(i, j)] = diagonal.sqrt();
+            } else {
+                l[(i, j)] = (a[(i, j)] - sum) / l[(j, j)];
+            }
+        }
+    }
+
+    Ok(l)
+}
+
+fn print_matrix(m: &DMatrix<f64>) {
+    for i in 0..m.nrows() {
+        for j in 0..m.ncols() {
+            print!("{:10.5}", m[(i, j)]);
+        }
+        println!();
+    }
+}
+
+fn main() {
+    let examples = [
+        DMatrix::from_row_slice(3, 3, &[25., 15., -5., 15., 18., 0., -5., 0., 11.]),
+        DMatrix::from_row_slice(
+            4,
+            4,
+            &[
+                18., 22., 54., 42., 22., 70., 86., 62., 54., 86., 174., 134., 42., 62., 134., 106.,
+            ],
+        ),
+    ];
+
+    for a in &examples {
+        match cholesky(a) {
+            Ok(l) => print_matrix(&l),
+            Err(e) => println!("Could not decompose matrix: {:?}", e),
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matrix_eq(a: &DMatrix<f64>, b: &DMatrix<f64>, epsilon: f64) {
+        assert_eq!(a.shape(), b.shape());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < epsilon, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn first_example() {
+        let a = DMatrix::from_row_slice(3, 3, &[25., 15., -5., 15., 18., 0., -5., 0., 11.]);
+        let l = cholesky(&a).unwrap();
+
+        let expected = DMatrix::from_row_slice(3, 3, &[5., 0., 0., 3., 3., 0., -1., 1., 3.]);
+        assert_matrix_eq(&l, &expected, 1e-12);
+        assert_matrix_eq(&(&l * l.transpose()), &a, 1e-12);
+    }
+
+    #[test]
+    fn second_example() {
+        let a = DMatrix::from_row_slice(
+            4,
+            4,
+            &[
+                18., 22., 54., 42., 22., 70., 86., 62., 54., 86., 174., 134., 42., 62., 134., 106.,
+            ],
+        );
+        let l = cholesky(&a).unwrap();
+
+        #[rustfmt::skip]
+        let expected = DMatrix::from_row_slice(4, 4, &[
+            4.24264, 0.0, 0.0, 0.0,
+            5.18545, 6.56591, 0.0, 0.0,
+            12.72792, 3.04604, 1.64974, 0.0,
+            9.89949, 1.62455, 1.84971, 1.39262,
+        ]);
+        assert_matrix_eq(&l, &expected, 1e-5);
+        assert_matrix_eq(&(&l * l.transpose()), &a, 1e-10);
+    }
+
+    #[test]
+    fn rejects_invalid_matrices() {
+        let not_square = DMatrix::from_row_slice(2, 3, &[1., 2., 3., 4., 5., 6.]);
+        assert_eq!(cholesky(&not_square), Err(CholeskyError::NonSquare));
+
+        let asymmetric = DMatrix::from_row_slice(2, 2, &[4., 1., 2., 3.]);
+        assert_eq!(cholesky(&asymmetric), Err(CholeskyError::Asymmetric));
+
+        let indefinite = DMatrix::from_row_slice(2, 2, &[1., 2., 2., 1.]);
+        assert_eq!(
+            cholesky(&indefinite),
+            Err(CholeskyError::NotPositiveDefinite)
+        );
+
+        let negative = DMatrix::from_row_slice(1, 1, &[-4.]);
+        assert_eq!(cholesky(&negative), Err(CholeskyError::NotPositiveDefinite));
+    }
+}
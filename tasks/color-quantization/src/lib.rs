@@ -0,0 +1,219 @@
+use bitmap::{Color, Image};
+use std::collections::HashMap;
+
+fn channels(c: Color) -> [u8; 3] {
+    [c.red, c.green, c.blue]
+}
+
+/// The difference between the smallest and the largest value of a channel in a box.
+fn range(colors: &[Color], channel: usize) -> u8 {
+    let values = colors.iter().map(|&c| channels(c)[channel]);
+    values.clone().max().unwrap_or(0) - values.min().unwrap_or(0)
+}
+
+/// The channel with the largest range, and that range.
+fn widest_channel(colors: &[Color]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| (channel, range(colors, channel)))
+        .max_by_key(|&(_, r)| r)
+        .unwrap()
+}
+
+fn mean(colors: &[Color]) -> Color {
+    let mut sums = [0u64; 3];
+    for &c in colors {
+        for (sum, value) in sums.iter_mut().zip(&channels(c)) {
+            *sum += u64::from(*value);
+        }
+    }
+    let n = colors.len() as u64;
+    let [red, green, blue] = sums.map(|sum| ((sum + n / 2) / n) as u8);
+    Color { red, green, blue }
+}
+
+/// Chooses a palette of at most `size` colors with the median cut: all the pixels start in one
+/// box, and the box that spans the widest range of any channel is split in two halves with the
+/// same number of pixels, until there are enough boxes. Each box then gives the mean of its
+/// pixels.
+pub fn median_cut(image: &Image, size: usize) -> Vec<Color> {
+    if image.data.is_empty() || size == 0 {
+        return vec![];
+    }
+
+    let mut boxes = vec![image.data.clone()];
+    while boxes.len() < size {
+        let (index, (channel, width)) = boxes
+            .iter()
+            .map(|b| widest_channel(b))
+            .enumerate()
+            .max_by_key(|&(_, (_, width))| width)
+            .unwrap();
+        // Every box is a single color.
+        if width == 0 {
+            break;
+        }
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_by_key(|&c| channels(c)[channel]);
+        // The half below the median, moved on to a value boundary so that a color does not end
+        // up in both boxes.
+        let median = channels(colors[colors.len() / 2])[channel];
+        let mut split = colors.partition_point(|&c| channels(c)[channel] < median);
+        if split == 0 {
+            split = colors.partition_point(|&c| channels(c)[channel] <= median);
+        }
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+    boxes.iter().map(|b| mean(b)).collect()
+}
+
+fn distance(a: Color, b: Color) -> u32 {
+    channels(a)
+        .iter()
+        .zip(&channels(b))
+        .map(|(&x, &y)| (i32::from(x) - i32::from(y)).pow(2) as u32)
+        .sum()
+}
+
+/// Replaces every pixel with the closest color of the palette.
+pub fn quantize(image: &Image, palette: &[Color]) -> Image {
+    let mut quantized = Image::new(image.width, image.height);
+    let mut closest = HashMap::new();
+    for (pixel, &color) in quantized.data.iter_mut().zip(&image.data) {
+        *pixel = *closest.entry(color).or_insert_with(|| {
+            *palette
+                .iter()
+                .min_by_key(|&&p| distance(p, color))
+                .expect("an empty palette")
+        });
+    }
+    quantized
+}
+
+/// The mean squared difference per channel between two images of the same size.
+pub fn mean_squared_error(a: &Image, b: &Image) -> f64 {
+    let total: u64 = a
+        .data
+        .iter()
+        .zip(&b.data)
+        .map(|(&x, &y)| u64::from(distance(x, y)))
+        .sum();
+    total as f64 / (3 * a.data.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    /// A gradient from red to blue with green rising along y, crossed by a gray band, with a
+    /// yellow disk in the middle.
+    fn fixture() -> Image {
+        let mut image = Image::new(96, 64);
+        for y in 0..64 {
+            for x in 0..96 {
+                let d2 = (x as i32 - 48).pow(2) + (y as i32 - 32).pow(2);
+                image[(x, y)] = if d2 < 144 {
+                    Color {
+                        red: 240,
+                        green: 220,
+                        blue: (d2 / 2) as u8,
+                    }
+                } else if (28..36).contains(&y) {
+                    let v = (x * 2) as u8;
+                    Color {
+                        red: v,
+                        green: v,
+                        blue: v,
+                    }
+                } else {
+                    Color {
+                        red: (255 - x * 255 / 95) as u8,
+                        green: (y * 4) as u8,
+                        blue: (x * 255 / 95) as u8,
+                    }
+                };
+            }
+        }
+        image
+    }
+
+    fn distinct(image: &Image) -> usize {
+        image
+            .data
+            .iter()
+            .map(|&c| channels(c))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    #[test]
+    fn sixteen_colors() {
+        let image = fixture();
+        assert!(distinct(&image) > 1000);
+
+        let palette = median_cut(&image, 16);
+        assert_eq!(palette.len(), 16);
+        let quantized = quantize(&image, &palette);
+        assert!(distinct(&quantized) <= 16);
+
+        let error = mean_squared_error(&image, &quantized);
+        // An RMS difference of about 20 per channel.
+        assert!(error < 450.0, "error {}", error);
+
+        // Two levels of each channel, and twice as many of red: a fixed palette of 16 colors
+        // does much worse.
+        let mut fixed = vec![];
+        for &red in &[0, 85, 170, 255] {
+            for &green in &[64, 192] {
+                for &blue in &[64, 192] {
+                    fixed.push(Color { red, green, blue });
+                }
+            }
+        }
+        let fixed_error = mean_squared_error(&image, &quantize(&image, &fixed));
+        assert!(
+            error * 2.0 < fixed_error,
+            "{} against {}",
+            error,
+            fixed_error
+        );
+    }
+
+    #[test]
+    fn more_colors_less_error() {
+        let image = fixture();
+        let errors: Vec<f64> = [2, 4, 8, 16, 32, 64]
+            .iter()
+            .map(|&size| mean_squared_error(&image, &quantize(&image, &median_cut(&image, size))))
+            .collect();
+        for pair in errors.windows(2) {
+            assert!(pair[1] < pair[0], "{:?}", errors);
+        }
+    }
+
+    #[test]
+    fn few_colors_are_kept() {
+        let mut image = Image::new(10, 10);
+        for (i, pixel) in image.data.iter_mut().enumerate() {
+            let v = (i % 5) as u8 * 50;
+            *pixel = Color {
+                red: v,
+                green: 255 - v,
+                blue: 7,
+            };
+        }
+        let palette = median_cut(&image, 16);
+        assert_eq!(palette.len(), 5);
+        assert_eq!(quantize(&image, &palette).data, image.data);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(median_cut(&Image::new(0, 0), 16).is_empty());
+        assert!(median_cut(&fixture(), 0).is_empty());
+    }
+}
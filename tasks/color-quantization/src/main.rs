@@ -0,0 +1,29 @@
+//! Reduces a PPM image to 16 colors.
+//!
+//! cargo run -p color-quantization -- input.ppm output.ppm
+
+use color_quantization::{mean_squared_error, median_cut, quantize};
+use read_a_ppm_file::{read_ppm_file, Error};
+use write_a_ppm_file::{Format, PPMWritable};
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: color-quantization input.ppm output.ppm");
+        std::process::exit(2);
+    }
+
+    let image = read_ppm_file(&args[0])?;
+    let palette = median_cut(&image, 16);
+    let quantized = quantize(&image, &palette);
+    quantized.write_ppm(&args[1], Format::Raw)?;
+
+    for color in &palette {
+        println!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue);
+    }
+    println!(
+        "mean squared error: {:.1}",
+        mean_squared_error(&image, &quantized)
+    );
+    Ok(())
+}
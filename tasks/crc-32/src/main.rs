@@ -1,23 +1,50 @@
+/// The CRC-32 polynomial, with the bits reversed since the bytes are processed from their least
+/// significant bit.
+const POLYNOMIAL: u32 = 0xedb8_8320;
+
 fn main() {
     let s = "The quick brown fox jumps over the lazy dog";
     println!("{:X}", crc(s.as_bytes()));
+    println!("{:X}", crc_bitwise(s.as_bytes()));
 }
 
-fn crc(bytes: &[u8]) -> u32 {
-    // Store the CRC of all possible 256 one byte values in table
+/// Divides the remainder by the polynomial one bit at a time.
+fn crc_bitwise(bytes: &[u8]) -> u32 {
+    let mut crc: u32 = 0xffff_ffff;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            if crc & 1 == 1 {
+                crc = (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+    crc ^ 0xffff_ffff
+}
+
+/// The CRC of all possible 256 one byte values, which is what the inner loop of `crc_bitwise`
+/// amounts to for each byte.
+fn table() -> [u32; 256] {
     let mut table: [u32; 256] = [0; 256];
     for (i, value) in table.iter_mut().enumerate() {
         let mut word = i as u32;
         for _ in 0..8 {
             if word & 1 == 1 {
-                word = (word >> 1) ^ 0xedb8_8320
+                word = (word >> 1) ^ POLYNOMIAL
             } else {
                 word >>= 1;
             }
         }
         *value = word;
     }
+    table
+}
 
+/// Processes a whole byte at a time with a lookup table.
+fn crc(bytes: &[u8]) -> u32 {
+    let table = table();
     let mut crc: u32 = 0xffff_ffff;
     for byte in bytes {
         crc = table[(crc as u8 ^ *byte) as usize] ^ (crc >> 8);
@@ -25,8 +52,34 @@ fn crc(bytes: &[u8]) -> u32 {
     crc ^ 0xffff_ffff
 }
 
-#[test]
-fn test() {
-    let s = "The quick brown fox jumps over the lazy dog";
-    assert_eq!(crc(s.as_bytes()), 0x414F_A339);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    #[test]
+    fn test() {
+        let s = "The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc(s.as_bytes()), 0x414F_A339);
+        assert_eq!(crc_bitwise(s.as_bytes()), 0x414F_A339);
+    }
+
+    #[test]
+    fn known_values() {
+        assert_eq!(crc(b""), 0);
+        assert_eq!(crc(b"123456789"), 0xCBF4_3926);
+        assert_eq!(table()[1], 0x7707_3096);
+        assert_eq!(table()[255], 0x2D02_EF8D);
+    }
+
+    #[test]
+    fn implementations_agree() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let len = rng.gen_range(0, 200);
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(crc(&bytes), crc_bitwise(&bytes));
+        }
+    }
 }
@@ -0,0 +1,187 @@
+//! Levenshtein distance with transpositions of adjacent characters counted as one edit.
+//!
+//! The restricted variant (also called the optimal string alignment distance) does not edit a
+//! substring more than once, so "CA" is 3 edits away from "ABC" instead of 2 ("CA" -> "AC"
+//! -> "ABC"). It does not satisfy the triangle inequality, while the unrestricted variant is a
+//! metric.
+
+use std::collections::HashMap;
+
+use levenshtein_distance::levenshtein_distance;
+
+/// The optimal string alignment distance: insertions, deletions, substitutions, and
+/// transpositions of adjacent characters that are not edited again.
+fn restricted_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // `d[i][j]` is the distance between the first `i` characters of `a` and the first `j`
+    // characters of `b`.
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// The Damerau-Levenshtein distance, with the algorithm of Lowrance and Wagner: characters
+/// may be inserted between two transposed ones, or deleted from between them.
+fn unrestricted_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max = a.len() + b.len();
+
+    // Indices are shifted by one compared to `restricted_distance`, to make room for a border
+    // of distances that are too large to be chosen.
+    let mut d = vec![vec![0; b.len() + 2]; a.len() + 2];
+    d[0][0] = max;
+    for i in 0..=a.len() {
+        d[i + 1][0] = max;
+        d[i + 1][1] = i;
+    }
+    for j in 0..=b.len() {
+        d[0][j + 1] = max;
+        d[1][j + 1] = j;
+    }
+
+    // The last row of `a` where each character was seen.
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+    for i in 1..=a.len() {
+        // The last column of `b`, in this row, that matched `a[i - 1]`.
+        let mut last_column = 0;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_column;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_column = j;
+                0
+            } else {
+                1
+            };
+            d[i + 1][j + 1] = (d[i][j] + cost)
+                .min(d[i + 1][j] + 1)
+                .min(d[i][j + 1] + 1)
+                .min(d[k][l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(a[i - 1], i);
+    }
+    d[a.len() + 1][b.len() + 1]
+}
+
+fn main() {
+    let pairs = [
+        ("kitten", "sitting"),
+        ("ca", "abc"),
+        ("a tc", "a cat"),
+        ("rosettacode", "raisethysword"),
+        ("Smith", "Smtih"),
+    ];
+    println!(
+        "{:<13} {:<13} {:>11} {:>10} {:>12}",
+        "a", "b", "Levenshtein", "restricted", "unrestricted"
+    );
+    for &(a, b) in &pairs {
+        println!(
+            "{:<13} {:<13} {:>11} {:>10} {:>12}",
+            a,
+            b,
+            levenshtein_distance(a, b),
+            restricted_distance(a, b),
+            unrestricted_distance(a, b)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    fn random_word(rng: &mut StdRng) -> String {
+        // Few letters, so that there are many transpositions to find.
+        let len = rng.gen_range(0, 8);
+        (0..len)
+            .map(|_| rng.gen_range(b'a', b'd') as char)
+            .collect()
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(restricted_distance("ca", "abc"), 3);
+        assert_eq!(unrestricted_distance("ca", "abc"), 2);
+        assert_eq!(restricted_distance("Smith", "Smtih"), 1);
+        assert_eq!(unrestricted_distance("Smith", "Smtih"), 1);
+        assert_eq!(levenshtein_distance("Smith", "Smtih"), 2);
+        assert_eq!(unrestricted_distance("a tc", "a cat"), 2);
+        assert_eq!(restricted_distance("a tc", "a cat"), 3);
+        assert_eq!(unrestricted_distance("kitten", "sitting"), 3);
+        assert_eq!(unrestricted_distance("", "abc"), 3);
+        assert_eq!(restricted_distance("abc", ""), 3);
+        assert_eq!(unrestricted_distance("", ""), 0);
+        assert_eq!(unrestricted_distance("éa", "aé"), 1);
+    }
+
+    #[test]
+    fn bounded_by_levenshtein() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..2000 {
+            let (a, b) = (random_word(&mut rng), random_word(&mut rng));
+            let (restricted, unrestricted) =
+                (restricted_distance(&a, &b), unrestricted_distance(&a, &b));
+            assert!(unrestricted <= restricted, "{} {}", a, b);
+            assert!(restricted <= levenshtein_distance(&a, &b), "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn symmetric() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..2000 {
+            let (a, b) = (random_word(&mut rng), random_word(&mut rng));
+            assert_eq!(restricted_distance(&a, &b), restricted_distance(&b, &a));
+            assert_eq!(unrestricted_distance(&a, &b), unrestricted_distance(&b, &a));
+            assert_eq!(unrestricted_distance(&a, &b) == 0, a == b);
+        }
+    }
+
+    #[test]
+    fn triangle_inequality() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..2000 {
+            let (a, b, c) = (
+                random_word(&mut rng),
+                random_word(&mut rng),
+                random_word(&mut rng),
+            );
+            assert!(
+                unrestricted_distance(&a, &c)
+                    <= unrestricted_distance(&a, &b) + unrestricted_distance(&b, &c),
+                "{} {} {}",
+                a,
+                b,
+                c
+            );
+        }
+        // Which the restricted variant does not satisfy.
+        assert!(
+            restricted_distance("ca", "abc")
+                > restricted_distance("ca", "ac") + restricted_distance("ac", "abc")
+        );
+    }
+}
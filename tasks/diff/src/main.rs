@@ -0,0 +1,307 @@
+//! Compares two text files line by line, and prints their differences in the unified format
+//! of `diff -u`.
+//!
+//! cargo run -p diff -- old.txt new.txt
+//!
+//! The lines that both files keep are a longest common subsequence of their lines. It is found
+//! with the [algorithm of Myers], which takes O((n + m) d) time for `d` differences, so that
+//! similar files are compared quickly.
+//!
+//! [algorithm of Myers]: http://www.xmailserver.org/diff2.pdf
+
+use std::fs;
+
+/// One step of the edit script that turns the old lines into the new ones, with the indices
+/// of the lines involved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Edit {
+    Keep(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// The shortest edit script from `a` to `b`. Deletions come before the insertions they are
+/// next to, as `diff` prints them.
+fn diff<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    // `v[k]` is how far along `a` the furthest path on diagonal `k = x - y` reaches; the
+    // diagonals are offset so that they can be negative.
+    let offset = max + 1;
+    let mut v = vec![0; 2 * offset as usize + 1];
+    let index = |k: isize| (k + offset) as usize;
+    // The `v` of each round, to find the path back.
+    let mut trace = vec![];
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            // Either down from diagonal `k + 1` (an insertion), or right from `k - 1` (a
+            // deletion), whichever got further.
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = v[index(previous_k)];
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Keep(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == previous_x {
+                edits.push(Edit::Insert(previous_y as usize));
+            } else {
+                edits.push(Edit::Delete(previous_x as usize));
+            }
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+    edits.reverse();
+    edits
+}
+
+/// The range of a hunk header: where the lines start, counting from 1, and how many there
+/// are. Like `diff`, the count is left out when it is 1, and an empty range starts at the
+/// line before it.
+fn range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+/// The differences from `old` to `new` in the unified format, with `context` unchanged lines
+/// around each change. Hunks whose contexts would overlap are merged.
+fn unified_diff(old_name: &str, new_name: &str, old: &str, new: &str, context: usize) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let edits = diff(&a, &b);
+
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|&i| !matches!(edits[i], Edit::Keep(..)))
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // The ranges of edits that make up each hunk.
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &i in &changes {
+        let start = i.saturating_sub(context);
+        let end = (i + 1 + context).min(edits.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    // How many lines of each file come before each edit.
+    let positions: Vec<(usize, usize)> = edits
+        .iter()
+        .scan((0, 0), |(x, y), edit| {
+            let before = (*x, *y);
+            match edit {
+                Edit::Keep(..) => {
+                    *x += 1;
+                    *y += 1;
+                }
+                Edit::Delete(_) => *x += 1,
+                Edit::Insert(_) => *y += 1,
+            }
+            Some(before)
+        })
+        .collect();
+
+    let mut output = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunks {
+        let hunk = &edits[start..end];
+        let (old_start, new_start) = positions[start];
+        let old_len = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Insert(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Delete(_)))
+            .count();
+
+        output += &format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_len),
+            range(new_start, new_len)
+        );
+        for edit in hunk {
+            match *edit {
+                Edit::Keep(i, _) => output += &format!(" {}\n", a[i]),
+                Edit::Delete(i) => output += &format!("-{}\n", a[i]),
+                Edit::Insert(j) => output += &format!("+{}\n", b[j]),
+            }
+        }
+    }
+    output
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (old_name, new_name) = match &args[..] {
+        [old, new] => (old.as_str(), new.as_str()),
+        _ => ("resources/change.old", "resources/change.new"),
+    };
+    let old = fs::read_to_string(old_name).expect("could not read the old file");
+    let new = fs::read_to_string(new_name).expect("could not read the new file");
+    print!("{}", unified_diff(old_name, new_name, &old, &new, 3));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden(name: &str) {
+        let read = |extension: &str| {
+            fs::read_to_string(format!("resources/{}.{}", name, extension)).unwrap()
+        };
+        assert_eq!(
+            unified_diff("old", "new", &read("old"), &read("new"), 3),
+            read("diff"),
+            "{}",
+            name
+        );
+    }
+
+    /// The length of a longest common subsequence, by dynamic programming.
+    fn lcs_len(a: &[u8], b: &[u8]) -> usize {
+        let mut row = vec![0; b.len() + 1];
+        for &x in a {
+            let mut diagonal = 0;
+            for (j, &y) in b.iter().enumerate() {
+                let next = if x == y {
+                    diagonal + 1
+                } else {
+                    row[j].max(row[j + 1])
+                };
+                diagonal = row[j + 1];
+                row[j + 1] = next;
+            }
+        }
+        row[b.len()]
+    }
+
+    #[test]
+    fn insertion() {
+        golden("insertion");
+    }
+
+    #[test]
+    fn deletion() {
+        golden("deletion");
+    }
+
+    #[test]
+    fn change() {
+        golden("change");
+    }
+
+    #[test]
+    fn identical_files() {
+        assert_eq!(
+            unified_diff("a", "b", "same\ntext\n", "same\ntext\n", 3),
+            ""
+        );
+        assert_eq!(unified_diff("a", "b", "", "", 3), "");
+    }
+
+    #[test]
+    fn from_and_to_nothing() {
+        assert_eq!(
+            unified_diff("a", "b", "", "one\ntwo\n", 3),
+            "--- a\n+++ b\n@@ -0,0 +1,2 @@\n+one\n+two\n"
+        );
+        assert_eq!(
+            unified_diff("a", "b", "one\n", "", 3),
+            "--- a\n+++ b\n@@ -1 +0,0 @@\n-one\n"
+        );
+    }
+
+    #[test]
+    fn edit_scripts() {
+        let edits = diff(b"ABCABBA", b"CBABAC");
+        // The example of the paper, which needs 5 edits.
+        assert_eq!(
+            edits
+                .iter()
+                .filter(|e| !matches!(e, Edit::Keep(..)))
+                .count(),
+            5
+        );
+        assert_eq!(diff(b"", b""), []);
+        assert_eq!(diff(b"a", b"b"), [Edit::Delete(0), Edit::Insert(0)]);
+    }
+
+    #[test]
+    fn keeps_a_longest_common_subsequence() {
+        // Every pair of words over a small alphabet, up to a few letters long.
+        let words: Vec<Vec<u8>> = (0..1 << 10)
+            .map(|n: u32| {
+                let len = n % 6;
+                (0..len)
+                    .map(|i| b'a' + ((n >> (i + 3)) & 1) as u8)
+                    .collect()
+            })
+            .collect();
+        for a in words.iter().step_by(7) {
+            for b in words.iter().step_by(11) {
+                let edits = diff(a, b);
+                let mut kept = vec![];
+                let (mut i, mut j) = (0, 0);
+                for edit in &edits {
+                    match *edit {
+                        Edit::Keep(x, y) => {
+                            assert_eq!((x, y), (i, j));
+                            assert_eq!(a[x], b[y]);
+                            kept.push(a[x]);
+                            i += 1;
+                            j += 1;
+                        }
+                        Edit::Delete(x) => {
+                            assert_eq!(x, i);
+                            i += 1;
+                        }
+                        Edit::Insert(y) => {
+                            assert_eq!(y, j);
+                            j += 1;
+                        }
+                    }
+                }
+                assert_eq!((i, j), (a.len(), b.len()));
+                assert_eq!(kept.len(), lcs_len(a, b), "{:?} {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! A directed graph with named vertices and weighted edges, and the shortest paths in it by
+//! Dijkstra's algorithm.
+//!
+//! The algorithm settles the vertices in order of their distance from the source: it takes the
+//! nearest vertex that is not settled yet out of a priority queue, and offers each of its
+//! neighbours the path through it. That order is only right if no edge has a negative cost,
+//! which `Cost` being unsigned guarantees.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub type Node = usize;
+pub type Cost = usize;
+
+/// Graph structure, represented as an adjacency list.
+#[derive(Clone, Debug, Default)]
+pub struct Graph<'a> {
+    vertices: Vec<&'a str>,
+    adj_list: Vec<Vec<(Node, Cost)>>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new() -> Graph<'a> {
+        Graph {
+            vertices: Vec::new(),
+            adj_list: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn name(&self, node: Node) -> &'a str {
+        self.vertices[node]
+    }
+
+    /// Returns the index of the vertex, or `None` if vertex not found.
+    pub fn vertex_index(&self, vertex: &str) -> Option<Node> {
+        self.vertices.iter().position(|&v| v == vertex)
+    }
+
+    /// Returns the index of the vertex. If vertex is not found, inserts the vertex.
+    pub fn get_or_insert_vertex(&mut self, vertex: &'a str) -> Node {
+        self.vertex_index(vertex).unwrap_or_else(|| {
+            self.adj_list.push(Vec::new());
+            self.vertices.push(vertex);
+            self.vertices.len() - 1
+        })
+    }
+
+    /// Adds the given edge to the graph, or changes its cost if it is there already.
+    pub fn add_edge(&mut self, from: &'a str, to: &'a str, cost: Cost) {
+        let from_idx = self.get_or_insert_vertex(from);
+        let to_idx = self.get_or_insert_vertex(to);
+
+        let edges = &mut self.adj_list[from_idx];
+        match edges.iter_mut().find(|(v, _)| *v == to_idx) {
+            Some(edge) => edge.1 = cost,
+            None => edges.push((to_idx, cost)),
+        }
+    }
+
+    /// The vertices that the edges from `node` lead to, with their costs.
+    pub fn edges(&self, node: Node) -> &[(Node, Cost)] {
+        &self.adj_list[node]
+    }
+
+    /// Implements Dijkstra's algorithm, finding the shortest paths from `source` to every
+    /// vertex that can be reached from it.
+    ///
+    /// The queue may hold a vertex several times, once for each time a shorter path to it was
+    /// found; all but the first time it comes out are stale, and skipped.
+    pub fn dijkstra(&self, source: Node) -> ShortestPaths {
+        let mut dist = vec![None; self.len()];
+        let mut prev = vec![None; self.len()];
+        let mut settled = vec![false; self.len()];
+        let mut queue = BinaryHeap::new();
+
+        dist[source] = Some(0);
+        queue.push(Reverse((0, source)));
+        while let Some(Reverse((dist_u, u))) = queue.pop() {
+            if settled[u] {
+                continue;
+            }
+            settled[u] = true;
+            for &(v, cost) in self.edges(u) {
+                let alt = dist_u + cost;
+                if dist[v].is_none_or(|dist_v| alt < dist_v) {
+                    dist[v] = Some(alt);
+                    prev[v] = Some(u);
+                    queue.push(Reverse((alt, v)));
+                }
+            }
+        }
+        ShortestPaths { dist, prev }
+    }
+
+    /// The length of a shortest path from `source` to `target`, and the names of its vertices,
+    /// or `None` if there's no path, or if the source or target is not in the graph.
+    pub fn shortest_path(&self, source: &str, target: &str) -> Option<(Cost, Vec<&'a str>)> {
+        let source_idx = self.vertex_index(source)?;
+        let target_idx = self.vertex_index(target)?;
+        let paths = self.dijkstra(source_idx);
+        let path = paths.path_to(target_idx)?;
+        let names = path.into_iter().map(|node| self.name(node)).collect();
+        Some((paths.dist[target_idx].unwrap(), names))
+    }
+}
+
+/// The shortest paths from one vertex, as a tree.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths {
+    /// The length of the shortest path to each vertex, or `None` if it cannot be reached.
+    pub dist: Vec<Option<Cost>>,
+    /// The vertex before each one on its shortest path, or `None` for the source and the
+    /// vertices that cannot be reached.
+    pub prev: Vec<Option<Node>>,
+}
+
+impl ShortestPaths {
+    /// The vertices of the shortest path to `target`, from the source, if there is one.
+    pub fn path_to(&self, target: Node) -> Option<Vec<Node>> {
+        self.dist[target]?;
+        let mut path = vec![target];
+        while let Some(parent) = self.prev[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    /// The distances by the Bellman–Ford algorithm, which relaxes every edge as many times as
+    /// a path can have edges, in no particular order.
+    fn bellman_ford(graph: &Graph, source: Node) -> Vec<Option<Cost>> {
+        let mut dist = vec![None; graph.len()];
+        dist[source] = Some(0);
+        for _ in 1..graph.len() {
+            for u in 0..graph.len() {
+                if let Some(dist_u) = dist[u] {
+                    for &(v, cost) in graph.edges(u) {
+                        let alt = dist_u + cost;
+                        if dist[v].is_none_or(|dist_v: Cost| alt < dist_v) {
+                            dist[v] = Some(alt);
+                        }
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    fn example() -> Graph<'static> {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b", 7);
+        graph.add_edge("a", "c", 9);
+        graph.add_edge("a", "f", 14);
+        graph.add_edge("b", "c", 10);
+        graph.add_edge("b", "d", 15);
+        graph.add_edge("c", "d", 11);
+        graph.add_edge("c", "f", 2);
+        graph.add_edge("d", "e", 6);
+        graph.add_edge("e", "f", 9);
+        graph
+    }
+
+    #[test]
+    fn test_dijkstras() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b", 7);
+        graph.add_edge("b", "c", 10);
+        graph.add_edge("c", "d", 5);
+        graph.add_edge("a", "d", 30);
+        graph.add_edge("y", "z", 10); //Disconnected from the rest
+
+        assert_eq!(
+            graph.shortest_path("a", "d"),
+            Some((22, vec!["a", "b", "c", "d"]))
+        );
+        assert_eq!(graph.shortest_path("a", "a"), Some((0, vec!["a"])));
+        assert!(graph.shortest_path("a", "y").is_none());
+        assert!(graph.shortest_path("e", "y").is_none());
+        assert!(graph.shortest_path("a", "e").is_none());
+
+        // A cheaper edge replaces the old one.
+        graph.add_edge("a", "d", 3);
+        assert_eq!(graph.shortest_path("a", "d"), Some((3, vec!["a", "d"])));
+
+        assert_eq!(
+            example().shortest_path("a", "e"),
+            Some((26, vec!["a", "c", "d", "e"]))
+        );
+        assert_eq!(
+            example().shortest_path("a", "f"),
+            Some((11, vec!["a", "c", "f"]))
+        );
+    }
+
+    #[test]
+    fn matches_bellman_ford() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let names: Vec<String> = (0..30).map(|i| i.to_string()).collect();
+        for _ in 0..200 {
+            let n = rng.gen_range(1, names.len());
+            let mut graph = Graph::new();
+            for name in &names[..n] {
+                graph.get_or_insert_vertex(name);
+            }
+            for _ in 0..rng.gen_range(0, 4 * n) {
+                let (u, v) = (rng.gen_range(0, n), rng.gen_range(0, n));
+                graph.add_edge(&names[u], &names[v], rng.gen_range(0, 20));
+            }
+
+            let source = rng.gen_range(0, n);
+            let paths = graph.dijkstra(source);
+            assert_eq!(paths.dist, bellman_ford(&graph, source));
+            // Each path is as long as its distance says.
+            for target in 0..n {
+                if let Some(path) = paths.path_to(target) {
+                    assert_eq!(path[0], source);
+                    let length = path
+                        .windows(2)
+                        .map(|step| {
+                            let edges = graph.edges(step[0]);
+                            edges.iter().find(|(v, _)| *v == step[1]).unwrap().1
+                        })
+                        .sum();
+                    assert_eq!(paths.dist[target], Some(length));
+                }
+            }
+        }
+    }
+}
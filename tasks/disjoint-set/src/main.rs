@@ -0,0 +1,236 @@
+//! A disjoint-set forest, which keeps track of a partition of the numbers `0..n` into sets as
+//! they are merged, and tells quickly whether two numbers are in the same set.
+//!
+//! Each set is a tree whose root stands for the set. Two optimizations keep the trees flat, so
+//! that the operations take amortized time that is nearly constant:
+//!
+//! - union by rank hangs the shallower tree below the root of the deeper one, and
+//! - path compression hangs every node that `find` goes through directly below the root.
+//!
+//! The demonstration is [site percolation][task]: a grid whose cells are open with probability
+//! `p` percolates if a path of open cells joins its top and bottom rows.
+//!
+//! [task]: http://rosettacode.org/wiki/Percolation/Site_percolation
+
+use rand::prelude::*;
+
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    /// An upper bound on the height of the tree below each root.
+    rank: Vec<u8>,
+    sets: usize,
+}
+
+impl DisjointSet {
+    /// `n` sets of one number each.
+    pub fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            sets: n,
+        }
+    }
+
+    /// The root of the tree of `x`, which stands for its set.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Path compression: go up again, hanging each node below the root.
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merges the sets of `a` and `b`, and returns whether they were different.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        // Union by rank: the tree only gets taller when both were as tall.
+        let (low, high) = if self.rank[a] < self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[low] = high;
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// How many sets there are.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+/// Whether a path of open cells joins the top row of a grid to its bottom row, moving up,
+/// down, left and right.
+fn percolates(open: &[Vec<bool>]) -> bool {
+    let (height, width) = (open.len(), open[0].len());
+    // The cells, and two more sets for everything connected to the top and to the bottom.
+    let (top, bottom) = (height * width, height * width + 1);
+    let mut sets = DisjointSet::new(height * width + 2);
+    let cell = |row: usize, column: usize| row * width + column;
+
+    for row in 0..height {
+        for column in 0..width {
+            if !open[row][column] {
+                continue;
+            }
+            if row == 0 {
+                sets.union(cell(row, column), top);
+            }
+            if row == height - 1 {
+                sets.union(cell(row, column), bottom);
+            }
+            // The neighbours above and to the left; those below and to the right join later.
+            if row > 0 && open[row - 1][column] {
+                sets.union(cell(row, column), cell(row - 1, column));
+            }
+            if column > 0 && open[row][column - 1] {
+                sets.union(cell(row, column), cell(row, column - 1));
+            }
+        }
+    }
+    sets.connected(top, bottom)
+}
+
+fn random_grid<R: Rng>(height: usize, width: usize, p: f64, rng: &mut R) -> Vec<Vec<bool>> {
+    (0..height)
+        .map(|_| (0..width).map(|_| rng.gen_bool(p)).collect())
+        .collect()
+}
+
+fn main() {
+    let (height, width, trials) = (15, 15, 1000);
+    let mut rng = StdRng::seed_from_u64(0);
+    println!(
+        "Probability that a {}x{} grid percolates, over {} trials:",
+        height, width, trials
+    );
+    for tenths in 0..=10 {
+        let p = f64::from(tenths) / 10.0;
+        let count = (0..trials)
+            .filter(|_| percolates(&random_grid(height, width, p, &mut rng)))
+            .count();
+        println!("p = {:.1}: {:.3}", p, count as f64 / f64::from(trials));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+
+    /// The number of connected components of a graph, by breadth-first search.
+    fn components(n: usize, edges: &[(usize, usize)]) -> usize {
+        let mut neighbours = vec![vec![]; n];
+        for &(a, b) in edges {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+        let mut seen = vec![false; n];
+        let mut count = 0;
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            count += 1;
+            seen[start] = true;
+            let mut queue = VecDeque::from(vec![start]);
+            while let Some(v) = queue.pop_front() {
+                for &w in &neighbours[v] {
+                    if !seen[w] {
+                        seen[w] = true;
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn union_and_find() {
+        let mut sets = DisjointSet::new(6);
+        assert_eq!(sets.sets(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.sets(), 3);
+    }
+
+    #[test]
+    fn trees_stay_flat() {
+        // Union by rank alone bounds the height of the trees by log2(n).
+        let mut sets = DisjointSet::new(1024);
+        for step in 0..10 {
+            for i in (0..1024).step_by(2 << step) {
+                sets.union(i, i + (1 << step));
+            }
+        }
+        assert_eq!(sets.sets(), 1);
+        assert!(sets.rank.iter().all(|&rank| rank <= 10));
+        // After a find, the path from each number goes straight to the root.
+        let root = sets.find(1023);
+        assert_eq!(sets.parent[1023], root);
+    }
+
+    #[test]
+    fn matches_breadth_first_search() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let n = rng.gen_range(1, 60);
+            let edge_count = rng.gen_range(0, 2 * n);
+            let edges: Vec<(usize, usize)> = (0..edge_count)
+                .map(|_| (rng.gen_range(0, n), rng.gen_range(0, n)))
+                .collect();
+            let mut sets = DisjointSet::new(n);
+            for &(a, b) in &edges {
+                sets.union(a, b);
+            }
+            assert_eq!(sets.sets(), components(n, &edges));
+
+            // Two numbers are connected exactly when a search from one reaches the other.
+            let (a, b) = (rng.gen_range(0, n), rng.gen_range(0, n));
+            let mut with_edge = edges.clone();
+            with_edge.push((a, b));
+            let same = components(n, &with_edge) == components(n, &edges);
+            assert_eq!(sets.connected(a, b), same);
+        }
+    }
+
+    #[test]
+    fn percolation() {
+        assert!(percolates(&[vec![true, false], vec![true, false]]));
+        assert!(!percolates(&[vec![true, false], vec![false, true]]));
+        // A winding path.
+        let grid: Vec<Vec<bool>> = ["#..", "###", "..#", "###", "#.."]
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        assert!(percolates(&grid));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(!percolates(&random_grid(15, 15, 0.0, &mut rng)));
+        assert!(percolates(&random_grid(15, 15, 1.0, &mut rng)));
+    }
+}
@@ -1,8 +1,20 @@
+//! Looks up the addresses of a host twice: once with the resolver of the standard library, and
+//! once by sending the DNS queries ourselves.
+//!
+//! Accepts the host name as an optional command line argument.
+
+mod wire;
+
+use std::fs;
 use std::io;
-use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Used when no name server can be found in `/etc/resolv.conf`.
+const FALLBACK_NAME_SERVER: &str = "8.8.8.8:53";
 
 fn get_ips(host: &str) -> io::Result<impl Iterator<Item = IpAddr>> {
-    let hosts = host.to_socket_addrs().unwrap();
+    let hosts = host.to_socket_addrs()?;
     let ips = hosts.map(|h| match h {
         SocketAddr::V4(s_v4) => IpAddr::V4(*s_v4.ip()),
         SocketAddr::V6(s_v6) => IpAddr::V6(*s_v6.ip()),
@@ -10,14 +22,68 @@ fn get_ips(host: &str) -> io::Result<impl Iterator<Item = IpAddr>> {
     Ok(ips)
 }
 
+/// Returns the first name server configured on the system.
+fn name_server() -> SocketAddr {
+    fs::read_to_string("/etc/resolv.conf")
+        .ok()
+        .and_then(|conf| {
+            conf.lines()
+                .filter_map(|line| line.strip_prefix("nameserver"))
+                .find_map(|server| server.trim().parse::<IpAddr>().ok())
+        })
+        .map_or_else(
+            || FALLBACK_NAME_SERVER.parse().unwrap(),
+            |ip| SocketAddr::new(ip, 53),
+        )
+}
+
+/// Asks `server` for the records of type `record_type` of `host`, and returns the addresses
+/// in the answer.
+fn query(server: SocketAddr, host: &str, record_type: u16) -> io::Result<Vec<IpAddr>> {
+    let local: SocketAddr = if server.is_ipv4() {
+        "0.0.0.0:0".parse().unwrap()
+    } else {
+        "[::]:0".parse().unwrap()
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.set_read_timeout(Some(Duration::from_secs(5)))?;
+    socket.connect(server)?;
+
+    // The ID only has to tell our answer apart from stray packets.
+    let id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |t| t.subsec_nanos() as u16);
+    socket.send(&wire::build_query(id, host, record_type))?;
+
+    // Responses over UDP are limited to 512 bytes.
+    let mut buffer = [0; 512];
+    let len = socket.recv(&mut buffer)?;
+    let records = wire::parse_response(&buffer[..len], id)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(wire::addresses(&records).collect())
+}
+
 fn main() -> io::Result<()> {
-    for ip in get_ips("www.kame.net:80")? {
+    let host = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "www.kame.net".to_string());
+
+    println!("Using the system resolver:");
+    for ip in get_ips(&format!("{}:80", host))? {
         match ip {
             IpAddr::V4(ip) => println!("ip v4: {}", ip),
             IpAddr::V6(ip) => println!("ip v6: {}", ip),
         }
     }
 
+    let server = name_server();
+    println!("Asking {} directly:", server);
+    for &(record_type, label) in &[(wire::TYPE_A, "ip v4"), (wire::TYPE_AAAA, "ip v6")] {
+        for ip in query(server, &host, record_type)? {
+            println!("{}: {}", label, ip);
+        }
+    }
+
     Ok(())
 }
 
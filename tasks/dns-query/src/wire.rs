@@ -0,0 +1,334 @@
+//! Just enough of the DNS wire format (RFC 1035) to ask for the addresses of a host.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_CNAME: u16 = 5;
+pub const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+/// Set in the flags of a response.
+const FLAG_RESPONSE: u16 = 0x8000;
+/// Asks the server to resolve the name recursively.
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// The packet ended in the middle of a field.
+    Truncated,
+    /// A compressed name points outside the packet, or loops.
+    BadPointer,
+    /// A label in a name is longer than 63 bytes, or a name is too long.
+    BadName,
+    /// The data of a record does not match its declared length.
+    BadLength,
+    /// The packet is not the answer to our query.
+    UnexpectedPacket,
+    /// The server reported an error, such as `NXDOMAIN` (3).
+    ResponseCode(u8),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Truncated => write!(f, "truncated packet"),
+            ParseError::BadPointer => write!(f, "invalid compression pointer"),
+            ParseError::BadName => write!(f, "invalid name"),
+            ParseError::BadLength => write!(f, "record data does not match its length"),
+            ParseError::UnexpectedPacket => write!(f, "not a response to the query"),
+            ParseError::ResponseCode(code) => write!(f, "server returned error code {}", code),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The fixed-size header at the start of every message.
+#[derive(Debug, PartialEq)]
+pub struct Header {
+    pub id: u16,
+    pub flags: u16,
+    pub questions: u16,
+    pub answers: u16,
+    pub authorities: u16,
+    pub additionals: u16,
+}
+
+/// A resource record from the answer section.
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub record_type: u16,
+    pub ttl: u32,
+    pub data: RecordData,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RecordData {
+    Address(IpAddr),
+    Alias(String),
+    Other(Vec<u8>),
+}
+
+/// Builds a query for the records of type `record_type` of `name`.
+pub fn build_query(id: u16, name: &str, record_type: u16) -> Vec<u8> {
+    let mut packet = vec![];
+    for field in &[id, FLAG_RECURSION_DESIRED, 1, 0, 0, 0] {
+        packet.extend_from_slice(&field.to_be_bytes());
+    }
+    for label in name.trim_end_matches('.').split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&record_type.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+    packet
+}
+
+/// A cursor over a packet. Names may point backwards into the packet, so the whole packet is
+/// kept around.
+struct Reader<'a> {
+    packet: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], ParseError> {
+        let bytes = self
+            .packet
+            .get(self.position..self.position + n)
+            .ok_or(ParseError::Truncated)?;
+        self.position += n;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ParseError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ParseError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn header(&mut self) -> Result<Header, ParseError> {
+        Ok(Header {
+            id: self.u16()?,
+            flags: self.u16()?,
+            questions: self.u16()?,
+            answers: self.u16()?,
+            authorities: self.u16()?,
+            additionals: self.u16()?,
+        })
+    }
+
+    /// Reads a name, following compression pointers: a length byte with the two top bits set
+    /// is followed by one more byte, and together (minus those bits) they give the offset
+    /// where the rest of the name is.
+    fn name(&mut self) -> Result<String, ParseError> {
+        let mut labels = vec![];
+        let mut length = 0;
+        // Where to continue after the name, once the first pointer has been followed.
+        let mut end = None;
+        // Every pointer must point strictly backwards, so following them terminates.
+        let mut limit = self.position;
+
+        loop {
+            let len = self.u8()?;
+            match len {
+                0 => break,
+                1..=63 => {
+                    let label = self.bytes(usize::from(len))?;
+                    length += label.len() + 1;
+                    if length > 255 {
+                        return Err(ParseError::BadName);
+                    }
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                }
+                0xc0..=0xff => {
+                    let offset = usize::from(u16::from_be_bytes([len & 0x3f, self.u8()?]));
+                    if offset >= limit {
+                        return Err(ParseError::BadPointer);
+                    }
+                    end.get_or_insert(self.position);
+                    limit = offset;
+                    self.position = offset;
+                }
+                _ => return Err(ParseError::BadName),
+            }
+        }
+
+        if let Some(end) = end {
+            self.position = end;
+        }
+        Ok(labels.join("."))
+    }
+
+    fn record(&mut self) -> Result<Record, ParseError> {
+        let name = self.name()?;
+        let record_type = self.u16()?;
+        let _class = self.u16()?;
+        let ttl = self.u32()?;
+        let length = usize::from(self.u16()?);
+
+        let start = self.position;
+        let data = match (record_type, length) {
+            (TYPE_A, 4) => {
+                let b = self.bytes(4)?;
+                RecordData::Address(Ipv4Addr::new(b[0], b[1], b[2], b[3]).into())
+            }
+            (TYPE_AAAA, 16) => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(self.bytes(16)?);
+                RecordData::Address(Ipv6Addr::from(octets).into())
+            }
+            (TYPE_CNAME, _) => RecordData::Alias(self.name()?),
+            _ => RecordData::Other(self.bytes(length)?.to_vec()),
+        };
+        if self.position != start + length {
+            return Err(ParseError::BadLength);
+        }
+
+        Ok(Record {
+            name,
+            record_type,
+            ttl,
+            data,
+        })
+    }
+}
+
+/// Parses the response to the query with the given `id`, returning its answer section.
+pub fn parse_response(packet: &[u8], id: u16) -> Result<Vec<Record>, ParseError> {
+    let mut reader = Reader {
+        packet,
+        position: 0,
+    };
+
+    let header = reader.header()?;
+    if header.id != id || header.flags & FLAG_RESPONSE == 0 {
+        return Err(ParseError::UnexpectedPacket);
+    }
+    let code = (header.flags & 0xf) as u8;
+    if code != 0 {
+        return Err(ParseError::ResponseCode(code));
+    }
+
+    for _ in 0..header.questions {
+        reader.name()?;
+        // The type and class of the question.
+        reader.bytes(4)?;
+    }
+    (0..header.answers).map(|_| reader.record()).collect()
+}
+
+/// Picks the addresses out of an answer section.
+pub fn addresses(records: &[Record]) -> impl Iterator<Item = IpAddr> + '_ {
+    records.iter().filter_map(|record| match record.data {
+        RecordData::Address(ip) => Some(ip),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The answer to an `A` query for `www.kame.net`, with a `CNAME` in front of the address.
+    /// Both the alias and the name of the address record are compressed.
+    const A_RESPONSE: &[u8] = &[
+        0x12, 0x34, 0x81, 0x80, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x03, 0x77, 0x77,
+        0x77, 0x04, 0x6b, 0x61, 0x6d, 0x65, 0x03, 0x6e, 0x65, 0x74, 0x00, 0x00, 0x01, 0x00, 0x01,
+        0xc0, 0x0c, 0x00, 0x05, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2c, 0x00, 0x08, 0x05, 0x6d, 0x61,
+        0x6e, 0x67, 0x6f, 0xc0, 0x10, 0xc0, 0x2a, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2c,
+        0x00, 0x04, 0xcb, 0xb2, 0x8d, 0xc2,
+    ];
+
+    /// The answer to an `AAAA` query for `www.kame.net`.
+    const AAAA_RESPONSE: &[u8] = &[
+        0xbe, 0xef, 0x81, 0x80, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x03, 0x77, 0x77,
+        0x77, 0x04, 0x6b, 0x61, 0x6d, 0x65, 0x03, 0x6e, 0x65, 0x74, 0x00, 0x00, 0x1c, 0x00, 0x01,
+        0xc0, 0x0c, 0x00, 0x1c, 0x00, 0x01, 0x00, 0x00, 0x01, 0x2c, 0x00, 0x10, 0x20, 0x01, 0x02,
+        0x00, 0x0d, 0xff, 0xff, 0xf1, 0x02, 0x16, 0x3e, 0xff, 0xfe, 0xb1, 0x44, 0xd7,
+    ];
+
+    #[test]
+    fn query() {
+        let query = build_query(0xbeef, "www.kame.net", TYPE_AAAA);
+        // One question, with recursion desired.
+        assert_eq!(
+            query[..12],
+            [0xbe, 0xef, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+        );
+        // The question section of the response repeats the query.
+        assert_eq!(query[12..], AAAA_RESPONSE[12..30]);
+        assert_eq!(build_query(0xbeef, "www.kame.net.", TYPE_AAAA), query);
+    }
+
+    #[test]
+    fn a_records() {
+        let records = parse_response(A_RESPONSE, 0x1234).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                Record {
+                    name: "www.kame.net".to_string(),
+                    record_type: TYPE_CNAME,
+                    ttl: 300,
+                    data: RecordData::Alias("mango.kame.net".to_string()),
+                },
+                Record {
+                    name: "mango.kame.net".to_string(),
+                    record_type: TYPE_A,
+                    ttl: 300,
+                    data: RecordData::Address("203.178.141.194".parse().unwrap()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn aaaa_records() {
+        let records = parse_response(AAAA_RESPONSE, 0xbeef).unwrap();
+        assert_eq!(
+            addresses(&records).collect::<Vec<_>>(),
+            vec!["2001:200:dff:fff1:216:3eff:feb1:44d7"
+                .parse::<IpAddr>()
+                .unwrap()]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_response(A_RESPONSE, 0x4321),
+            Err(ParseError::UnexpectedPacket)
+        );
+        assert_eq!(
+            parse_response(&A_RESPONSE[..A_RESPONSE.len() - 1], 0x1234),
+            Err(ParseError::Truncated)
+        );
+
+        let mut nxdomain = AAAA_RESPONSE.to_vec();
+        nxdomain[3] |= 3;
+        assert_eq!(
+            parse_response(&nxdomain, 0xbeef),
+            Err(ParseError::ResponseCode(3))
+        );
+
+        // A name that points at itself.
+        let mut looping = AAAA_RESPONSE.to_vec();
+        looping[31] = 30;
+        assert_eq!(
+            parse_response(&looping, 0xbeef),
+            Err(ParseError::BadPointer)
+        );
+    }
+}
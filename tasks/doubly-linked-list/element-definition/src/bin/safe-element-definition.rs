@@ -0,0 +1,86 @@
+//! A doubly linked list without `unsafe`. Each node is shared by the nodes on both sides of it,
+//! so it is kept in an `Rc`, and its contents in a `RefCell`, so that they can change while it is
+//! shared.
+//!
+//! If the links backward were `Rc`s as well, every pair of neighbours would keep each other
+//! alive, and the list would never be freed. They are `Weak` instead, which do not keep a node
+//! alive: the list owns its first node, and every node owns the next one.
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    next: Link<T>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+    element: T,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    /// Unlinks the nodes one by one; dropping the first one would otherwise drop the next one
+    /// from inside its own drop, and so on, which overflows the stack on long lists.
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            link = node.borrow_mut().next.take();
+        }
+    }
+}
+
+fn main() {
+    let list = LinkedList::<i32>::new();
+    drop(list);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_every_node() {
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..3 {
+            let node = Rc::new(RefCell::new(Node {
+                next: None,
+                prev: list.tail.as_ref().map(Rc::downgrade),
+                element: Rc::clone(&counter),
+            }));
+            match list.tail.take() {
+                None => list.head = Some(Rc::clone(&node)),
+                Some(tail) => tail.borrow_mut().next = Some(Rc::clone(&node)),
+            }
+            list.tail = Some(node);
+            list.len += 1;
+        }
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
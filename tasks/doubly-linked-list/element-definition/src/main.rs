@@ -4,28 +4,47 @@
 //!
 //! In order to circumvent the multiple mutable references, raw C-like pointers are used. Note that
 //! these cannot be dereferenced with guaranteed safety and thus dereferencing is relegated to
-//! `unsafe {}` blocks.
+//! `unsafe {}` blocks. The list owns its nodes through these pointers, so it frees them itself
+//! when it is dropped.
+//!
+//! `bin/safe-element-definition.rs` defines the same list with reference counting instead, and
+//! no `unsafe` at all.
 //!
 //! [doc]: https://doc.rust-lang.org/std/collections/struct.LinkedList.html
-//! [src]: https://github.com/rust-lang/rust/blob/master/src/libcollections/linked_list.rs
+//! [src]: https://github.com/rust-lang/rust/blob/master/library/alloc/src/collections/linked_list.rs
 
 #![allow(dead_code)]
 
-use std::ptr;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 
 /// User-facing implementation
 pub struct LinkedList<T> {
-    length: usize,
-    list_head: Link<T>,
-    list_tail: Rawlink<Node<T>>,
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    /// Tells the compiler that the list owns values of type `T`, which it cannot tell from the
+    /// pointers alone
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+/// Type definition. `NonNull` is a raw mutable pointer which is never null, so that `None` can
+/// stand for the null pointer.
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    next: Link<T>,
+    prev: Link<T>,
+    element: T,
 }
 
 impl<T> LinkedList<T> {
     pub fn new() -> LinkedList<T> {
         LinkedList {
-            length: 0,
-            list_head: None,
-            list_tail: Rawlink { p: ptr::null_mut() },
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
         }
     }
 }
@@ -36,20 +55,15 @@ impl<T> Default for LinkedList<T> {
     }
 }
 
-/// Type definition
-type Link<T> = Option<Box<Node<T>>>;
-
-/// Pointer is wrapped in struct so that Option-like methods can be added to it later (wrappers
-/// around NULL checks)
-struct Rawlink<T> {
-    /// Raw mutable pointer
-    p: *mut T,
-}
-
-struct Node<T> {
-    next: Link<T>,
-    prev: Rawlink<Node<T>>,
-    value: T,
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let mut link = self.head;
+        while let Some(node) = link {
+            // Every node was allocated as a `Box`, and only the list points to it.
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            link = node.next;
+        }
+    }
 }
 
 fn main() {
@@ -65,3 +79,34 @@ fn main() {
     drop(list1);
     drop(list2);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[test]
+    fn drops_every_node() {
+        // The nodes are linked by hand, as the definition has no way to insert them.
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..3 {
+            let node = Box::new(Node {
+                next: None,
+                prev: list.tail,
+                element: Rc::clone(&counter),
+            });
+            let node = NonNull::from(Box::leak(node));
+            match list.tail {
+                None => list.head = Some(node),
+                Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            }
+            list.tail = Some(node);
+            list.len += 1;
+        }
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
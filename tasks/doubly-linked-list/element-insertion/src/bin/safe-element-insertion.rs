@@ -0,0 +1,214 @@
+//! Insertion into the reference counted list of the element definition, which needs no `unsafe`:
+//! the `RefCell`s check at run time that no node is changed while it is borrowed.
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    next: Link<T>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+    element: T,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    /// Links a new node between `prev` and `next`, which are neighbours, or the ends of the list
+    /// where they are `None`.
+    fn link_between(&mut self, prev: Link<T>, next: Link<T>, element: T) {
+        let node = Rc::new(RefCell::new(Node {
+            next: next.clone(),
+            prev: prev.as_ref().map(Rc::downgrade),
+            element,
+        }));
+        match prev {
+            None => self.head = Some(Rc::clone(&node)),
+            Some(prev) => prev.borrow_mut().next = Some(Rc::clone(&node)),
+        }
+        match next {
+            None => self.tail = Some(node),
+            Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&node)),
+        }
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, elt: T) {
+        let head = self.head.clone();
+        self.link_between(None, head, elt);
+    }
+
+    pub fn push_back(&mut self, elt: T) {
+        let tail = self.tail.clone();
+        self.link_between(tail, None, elt);
+    }
+
+    /// Inserts `elt` at position `index`, after the elements before it, walking from the end of
+    /// the list that is closer.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length of the list.
+    pub fn insert(&mut self, index: usize, elt: T) {
+        assert!(index <= self.len, "index out of bounds");
+        let (prev, next) = if index <= self.len / 2 {
+            let mut next = self.head.clone();
+            for _ in 0..index {
+                next = next.and_then(|n| n.borrow().next.clone());
+            }
+            let prev = match &next {
+                None => self.tail.clone(),
+                Some(n) => n.borrow().prev.as_ref().and_then(Weak::upgrade),
+            };
+            (prev, next)
+        } else {
+            let mut prev = self.tail.clone();
+            for _ in index..self.len {
+                prev = prev.and_then(|p| p.borrow().prev.as_ref().and_then(Weak::upgrade));
+            }
+            let next = prev.as_ref().and_then(|p| p.borrow().next.clone());
+            (prev, next)
+        };
+        self.link_between(prev, next, elt);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    /// Unlinks the nodes one by one, so that dropping a long list does not recurse through it.
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            link = node.borrow_mut().next.take();
+        }
+    }
+}
+
+fn main() {
+    // The task: insert C between A and B.
+    let mut list = LinkedList::new();
+    list.push_back('A');
+    list.push_back('B');
+    list.insert(1, 'C');
+    assert_eq!(list.len(), 3);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl<T: Clone + PartialEq + std::fmt::Debug> LinkedList<T> {
+        /// Checks the elements, read from the front and from the back.
+        fn check(&self, expected: &[T]) {
+            let mut forward = vec![];
+            let mut link = self.head.clone();
+            while let Some(node) = link {
+                forward.push(node.borrow().element.clone());
+                link = node.borrow().next.clone();
+            }
+            let mut backward = vec![];
+            let mut link = self.tail.clone();
+            while let Some(node) = link {
+                backward.push(node.borrow().element.clone());
+                link = node.borrow().prev.as_ref().and_then(Weak::upgrade);
+            }
+            backward.reverse();
+            assert_eq!(forward, expected);
+            assert_eq!(backward, expected);
+            assert_eq!(self.len, expected.len());
+        }
+    }
+
+    #[test]
+    fn insert_between() {
+        let mut list = LinkedList::new();
+        list.push_back('A');
+        list.push_back('B');
+        list.insert(1, 'C');
+        list.check(&['A', 'C', 'B']);
+    }
+
+    #[test]
+    fn matches_vec() {
+        let mut list = LinkedList::new();
+        let mut vec = vec![];
+        for i in 0..40 {
+            match i % 4 {
+                0 => {
+                    list.push_front(i);
+                    vec.insert(0, i);
+                }
+                1 => {
+                    list.push_back(i);
+                    vec.push(i);
+                }
+                _ => {
+                    let index = (i * 7) % (vec.len() + 1);
+                    list.insert(index, i);
+                    vec.insert(index, i);
+                }
+            }
+            list.check(&vec);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.insert(2, 2);
+    }
+
+    #[test]
+    fn drops_every_node() {
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for i in 0..5 {
+            list.insert(i / 2, Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 6);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_list() {
+        // Long enough that a recursive drop would overflow the stack.
+        let mut list = LinkedList::new();
+        for i in 0..200_000 {
+            list.push_back(i);
+        }
+        drop(list);
+    }
+}
@@ -1,92 +1,106 @@
 //! This expands upon the implementation defined on [Rosetta Code][element definition] and consists
-//! of the relevant lines from the `LinkedList` implementation in the Rust standard library.
+//! of the relevant lines from the `LinkedList` implementation in the Rust standard library, with
+//! an insertion in the middle of the list added, as the task asks for.
+//!
+//! `bin/safe-element-insertion.rs` does the same with the reference counted list.
 //!
 //! [element definition]: http://rosettacode.org/wiki/Doubly-linked_list/Element_definition
 
 #![allow(dead_code)]
 
-use std::mem;
-use std::ptr;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 
 pub struct LinkedList<T> {
-    length: usize,
-    list_head: Link<T>,
-    list_tail: Rawlink<Node<T>>,
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
 }
 
-type Link<T> = Option<Box<Node<T>>>;
-
-struct Rawlink<T> {
-    p: *mut T,
-}
+type Link<T> = Option<NonNull<Node<T>>>;
 
 struct Node<T> {
     next: Link<T>,
-    prev: Rawlink<Node<T>>,
-    value: T,
+    prev: Link<T>,
+    element: T,
 }
 
-impl<T> Node<T> {
-    fn new(v: T) -> Node<T> {
-        Node {
-            value: v,
-            next: None,
-            prev: Rawlink::none(),
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
         }
     }
-}
 
-impl<T> Rawlink<T> {
-    fn none() -> Self {
-        Rawlink { p: ptr::null_mut() }
+    /// Links a new node between `prev` and `next`, which are neighbours, or the ends of the list
+    /// where they are `None`.
+    fn link_between(&mut self, prev: Link<T>, next: Link<T>, element: T) {
+        let node = Box::new(Node {
+            next,
+            prev,
+            element,
+        });
+        // The list owns the node from now on, and frees it when it is dropped.
+        let node = Some(NonNull::from(Box::leak(node)));
+        unsafe {
+            match prev {
+                None => self.head = node,
+                Some(prev) => (*prev.as_ptr()).next = node,
+            }
+            match next {
+                None => self.tail = node,
+                Some(next) => (*next.as_ptr()).prev = node,
+            }
+        }
+        self.len += 1;
     }
 
-    fn some(n: &mut T) -> Rawlink<T> {
-        Rawlink { p: n }
+    pub fn push_front(&mut self, elt: T) {
+        let head = self.head;
+        self.link_between(None, head, elt);
     }
-}
 
-impl<'a, T> From<&'a mut Link<T>> for Rawlink<Node<T>> {
-    fn from(node: &'a mut Link<T>) -> Self {
-        match node.as_mut() {
-            None => Rawlink::none(),
-            Some(ptr) => Rawlink::some(ptr),
-        }
+    pub fn push_back(&mut self, elt: T) {
+        let tail = self.tail;
+        self.link_between(tail, None, elt);
     }
-}
-
-fn link_no_prev<T>(mut next: Box<Node<T>>) -> Link<T> {
-    next.prev = Rawlink::none();
-    Some(next)
-}
 
-impl<T> LinkedList<T> {
-    pub fn new() -> LinkedList<T> {
-        LinkedList {
-            length: 0,
-            list_head: None,
-            list_tail: Rawlink { p: ptr::null_mut() },
-        }
+    /// Inserts `elt` at position `index`, after the elements before it, walking from the end of
+    /// the list that is closer.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length of the list.
+    pub fn insert(&mut self, index: usize, elt: T) {
+        assert!(index <= self.len, "index out of bounds");
+        let (prev, next) = unsafe {
+            if index <= self.len / 2 {
+                let mut next = self.head;
+                for _ in 0..index {
+                    next = (*next.unwrap().as_ptr()).next;
+                }
+                (next.map_or(self.tail, |n| (*n.as_ptr()).prev), next)
+            } else {
+                let mut prev = self.tail;
+                for _ in index..self.len {
+                    prev = (*prev.unwrap().as_ptr()).prev;
+                }
+                (prev, prev.and_then(|p| (*p.as_ptr()).next))
+            }
+        };
+        self.link_between(prev, next, elt);
     }
 
-    #[inline]
-    fn push_front_node(&mut self, mut new_head: Box<Node<T>>) {
-        match self.list_head {
-            None => {
-                self.list_head = link_no_prev(new_head);
-                self.list_tail = Rawlink::from(&mut self.list_head);
-            }
-            Some(ref mut head) => {
-                new_head.prev = Rawlink::none();
-                head.prev = Rawlink::some(&mut *new_head);
-                mem::swap(head, &mut new_head);
-                head.next = Some(new_head);
-            }
-        }
-        self.length += 1;
+    pub fn len(&self) -> usize {
+        self.len
     }
-    pub fn push_front(&mut self, elt: T) {
-        self.push_front_node(Box::new(Node::new(elt)));
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 }
 
@@ -96,6 +110,17 @@ impl<T> Default for LinkedList<T> {
     }
 }
 
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let mut link = self.head;
+        while let Some(node) = link {
+            // Every node was allocated as a `Box`, and only the list points to it.
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            link = node.next;
+        }
+    }
+}
+
 fn main() {
     use std::collections;
     let mut list1 = collections::LinkedList::new();
@@ -103,4 +128,106 @@ fn main() {
 
     let mut list2 = LinkedList::new();
     list2.push_front(8);
+
+    // The task: insert C between A and B.
+    let mut list = LinkedList::new();
+    list.push_back('A');
+    list.push_back('B');
+    list.insert(1, 'C');
+    assert_eq!(list.len(), 3);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    impl<T: Clone + PartialEq + std::fmt::Debug> LinkedList<T> {
+        /// Checks the elements, read from the front and from the back.
+        fn check(&self, expected: &[T]) {
+            let mut forward = vec![];
+            let mut backward = vec![];
+            unsafe {
+                let mut link = self.head;
+                while let Some(node) = link {
+                    forward.push((*node.as_ptr()).element.clone());
+                    link = (*node.as_ptr()).next;
+                }
+                let mut link = self.tail;
+                while let Some(node) = link {
+                    backward.push((*node.as_ptr()).element.clone());
+                    link = (*node.as_ptr()).prev;
+                }
+            }
+            backward.reverse();
+            assert_eq!(forward, expected);
+            assert_eq!(backward, expected);
+            assert_eq!(self.len, expected.len());
+        }
+    }
+
+    #[test]
+    fn insert_between() {
+        let mut list = LinkedList::new();
+        list.push_back('A');
+        list.push_back('B');
+        list.insert(1, 'C');
+        list.check(&['A', 'C', 'B']);
+    }
+
+    #[test]
+    fn matches_vec() {
+        // Few operations, so that the test is quick under Miri.
+        let mut list = LinkedList::new();
+        let mut vec = vec![];
+        for i in 0..40 {
+            match i % 4 {
+                0 => {
+                    list.push_front(i);
+                    vec.insert(0, i);
+                }
+                1 => {
+                    list.push_back(i);
+                    vec.push(i);
+                }
+                _ => {
+                    let index = (i * 7) % (vec.len() + 1);
+                    list.insert(index, i);
+                    vec.insert(index, i);
+                }
+            }
+            list.check(&vec);
+        }
+    }
+
+    #[test]
+    fn insert_at_the_ends() {
+        let mut list = LinkedList::new();
+        list.insert(0, 2);
+        list.insert(1, 3);
+        list.insert(0, 1);
+        list.insert(3, 4);
+        list.check(&[1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut list = LinkedList::new();
+        list.push_back(1);
+        list.insert(2, 2);
+    }
+
+    #[test]
+    fn drops_every_node() {
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for i in 0..5 {
+            list.insert(i / 2, Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 6);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
 }
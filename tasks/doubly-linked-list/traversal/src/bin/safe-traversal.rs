@@ -0,0 +1,134 @@
+//! Traverses the reference counted list of the element definition both ways.
+//!
+//! An element cannot be borrowed past the `RefCell` of its node, so the list is not traversed
+//! with an iterator of references, but by calling a closure on each element in turn.
+
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+
+struct Node<T> {
+    next: Link<T>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+    element: T,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn push_back(&mut self, elt: T) {
+        let node = Rc::new(RefCell::new(Node {
+            next: None,
+            prev: self.tail.as_ref().map(Rc::downgrade),
+            element: elt,
+        }));
+        match self.tail.take() {
+            None => self.head = Some(Rc::clone(&node)),
+            Some(tail) => tail.borrow_mut().next = Some(Rc::clone(&node)),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    /// Calls `f` on each element, from the first to the last.
+    pub fn traverse<F: FnMut(&T)>(&self, mut f: F) {
+        let mut link = self.head.clone();
+        while let Some(node) = link {
+            let node = node.borrow();
+            f(&node.element);
+            link = node.next.clone();
+        }
+    }
+
+    /// Calls `f` on each element, from the last to the first.
+    pub fn traverse_back<F: FnMut(&T)>(&self, mut f: F) {
+        let mut link = self.tail.clone();
+        while let Some(node) = link {
+            let node = node.borrow();
+            f(&node.element);
+            link = node.prev.as_ref().and_then(Weak::upgrade);
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    /// Unlinks the nodes one by one, so that dropping a long list does not recurse through it.
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut link = self.head.take();
+        while let Some(node) = link {
+            link = node.borrow_mut().next.take();
+        }
+    }
+}
+
+fn main() {
+    let mut list = LinkedList::new();
+    for word in "the quick brown fox".split_whitespace() {
+        list.push_back(word);
+    }
+
+    list.traverse(|word| print!("{} ", word));
+    println!();
+    list.traverse_back(|word| print!("{} ", word));
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_ways() {
+        let mut list = LinkedList::new();
+        for i in 0..10 {
+            list.push_back(i);
+        }
+        let mut forward = vec![];
+        list.traverse(|&i| forward.push(i));
+        assert_eq!(forward, (0..10).collect::<Vec<_>>());
+        let mut backward = vec![];
+        list.traverse_back(|&i| backward.push(i));
+        assert_eq!(backward, (0..10).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn empty() {
+        let list: LinkedList<i32> = LinkedList::new();
+        list.traverse(|_| panic!("no elements"));
+        list.traverse_back(|_| panic!("no elements"));
+    }
+
+    #[test]
+    fn drops_every_node() {
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..5 {
+            list.push_back(Rc::clone(&counter));
+        }
+        assert_eq!(Rc::strong_count(&counter), 6);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
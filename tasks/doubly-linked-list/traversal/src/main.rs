@@ -0,0 +1,206 @@
+//! Traverses the list of the [element definition] from the beginning to the end, and from the end
+//! to the beginning.
+//!
+//! Like the iterator of the standard library, `Iter` holds the two ends of the part of the list
+//! it has not visited yet, so the same iterator can go either way, and `.rev()` traverses the
+//! list backwards.
+//!
+//! `bin/safe-traversal.rs` does the same with the reference counted list.
+//!
+//! [element definition]: http://rosettacode.org/wiki/Doubly-linked_list/Element_definition
+
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    next: Link<T>,
+    prev: Link<T>,
+    element: T,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn push_back(&mut self, elt: T) {
+        let node = Box::new(Node {
+            next: None,
+            prev: self.tail,
+            element: elt,
+        });
+        // The list owns the node from now on, and frees it when it is dropped.
+        let node = Some(NonNull::from(Box::leak(node)));
+        match self.tail {
+            None => self.head = node,
+            Some(tail) => unsafe { (*tail.as_ptr()).next = node },
+        }
+        self.tail = node;
+        self.len += 1;
+    }
+
+    pub fn iter<'a>(&'a self) -> Iter<'a, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let mut link = self.head;
+        while let Some(node) = link {
+            // Every node was allocated as a `Box`, and only the list points to it.
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            link = node.next;
+        }
+    }
+}
+
+pub struct Iter<'a, T: 'a> {
+    head: Link<T>,
+    tail: Link<T>,
+    /// How many elements are left between `head` and `tail`, so that the iterator stops when the
+    /// two ends meet.
+    len: usize,
+    /// The elements are borrowed from the list for as long as the iterator lives.
+    marker: PhantomData<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| {
+            // The list cannot change while it is borrowed by the iterator.
+            let node = unsafe { &*node.as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            &node.element
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| {
+            let node = unsafe { &*node.as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            &node.element
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+fn main() {
+    let mut list = LinkedList::new();
+    for word in "the quick brown fox".split_whitespace() {
+        list.push_back(word);
+    }
+
+    for word in list.iter() {
+        print!("{} ", word);
+    }
+    println!();
+    for word in list.iter().rev() {
+        print!("{} ", word);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    #[test]
+    fn both_ways() {
+        let mut list = LinkedList::new();
+        for i in 0..10 {
+            list.push_back(i);
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            (0..10).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            (0..10).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(list.iter().len(), 10);
+    }
+
+    #[test]
+    fn ends_meet() {
+        let mut list = LinkedList::new();
+        for i in 0..5 {
+            list.push_back(i);
+        }
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn empty() {
+        let list: LinkedList<i32> = LinkedList::new();
+        assert_eq!(list.iter().next(), None);
+        assert_eq!(list.iter().next_back(), None);
+    }
+
+    #[test]
+    fn drops_every_node() {
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..5 {
+            list.push_back(Rc::clone(&counter));
+        }
+        assert_eq!(list.iter().count(), 5);
+        assert_eq!(Rc::strong_count(&counter), 6);
+        drop(list);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
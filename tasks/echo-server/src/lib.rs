@@ -0,0 +1,156 @@
+//! Two echo servers: one that spawns a thread for every connection, and one that handles all
+//! connections from a single thread, waiting for socket readiness with `mio`.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use mio::{Events, Interest, Poll, Token};
+
+/// Accepts connections on `listener`, handling each of them in its own thread.
+pub fn serve_threaded(listener: TcpListener) -> io::Result<()> {
+    println!("Starting echo server on {:?}", listener.local_addr());
+
+    // Process each new connection to the server
+    for stream in listener.incoming() {
+        match stream {
+            Err(e) => println!("Connection failed: {}", e),
+            Ok(stream) => {
+                let addr = stream.peer_addr()?;
+                println!("New connection: {}", addr);
+                // Launch a new thread to deal with the connection.
+                thread::spawn(move || {
+                    if let Err(e) = echo_session(stream) {
+                        println!("I/O error: {} -- {}", addr, e);
+                    }
+                    println!("Closing connection: {}", addr);
+                });
+            }
+        }
+    }
+    Ok(())
+    // Server closes automatically at end of block
+}
+
+// Each connection gets its own session.
+fn echo_session(stream: TcpStream) -> io::Result<()> {
+    let addr = stream.peer_addr()?;
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+    for line in reader.lines() {
+        let line = line?;
+        println!("Received line from {}: {}", addr, line);
+        writer.write_all(line.as_bytes())?;
+        writer.write_all(b"\n")?;
+        println!("Wrote line to {}: {}", addr, line);
+    }
+    Ok(())
+}
+
+const LISTENER: Token = Token(0);
+
+/// A client of the single-threaded server, with the data that is waiting to be echoed.
+struct Connection {
+    stream: mio::net::TcpStream,
+    pending: Vec<u8>,
+}
+
+impl Connection {
+    /// Reads everything that is available and echoes as much of it as the socket accepts.
+    ///
+    /// Returns `true` once the client has closed the connection and everything was echoed.
+    fn ready(&mut self) -> io::Result<bool> {
+        let mut closed = false;
+        let mut buffer = [0; 4096];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => {
+                    closed = true;
+                    break;
+                }
+                Ok(n) => self.pending.extend_from_slice(&buffer[..n]),
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        while !self.pending.is_empty() {
+            match self.stream.write(&self.pending) {
+                Ok(n) => {
+                    self.pending.drain(..n);
+                }
+                // The rest is sent when the socket becomes writable again.
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(closed && self.pending.is_empty())
+    }
+}
+
+/// Accepts connections on `listener` and handles all of them from the calling thread.
+pub fn serve_polled(listener: TcpListener) -> io::Result<()> {
+    println!("Starting echo server on {:?}", listener.local_addr());
+    listener.set_nonblocking(true)?;
+    let mut listener = mio::net::TcpListener::from_std(listener);
+
+    let mut poll = Poll::new()?;
+    poll.registry()
+        .register(&mut listener, LISTENER, Interest::READABLE)?;
+
+    let mut events = Events::with_capacity(128);
+    let mut connections = HashMap::new();
+    let mut next_token = 1;
+
+    loop {
+        if let Err(e) = poll.poll(&mut events, None) {
+            if e.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(e);
+        }
+
+        for event in events.iter() {
+            if event.token() == LISTENER {
+                // Readiness is edge-triggered, so accept every pending connection.
+                loop {
+                    match listener.accept() {
+                        Ok((mut stream, addr)) => {
+                            println!("New connection: {}", addr);
+                            let token = Token(next_token);
+                            next_token += 1;
+                            poll.registry().register(
+                                &mut stream,
+                                token,
+                                Interest::READABLE | Interest::WRITABLE,
+                            )?;
+                            let pending = vec![];
+                            connections.insert(token, Connection { stream, pending });
+                        }
+                        Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                        Err(e) => println!("Connection failed: {}", e),
+                    }
+                }
+                continue;
+            }
+
+            let done = match connections.get_mut(&event.token()) {
+                Some(connection) => connection.ready().unwrap_or_else(|e| {
+                    println!("I/O error: {}", e);
+                    true
+                }),
+                None => false,
+            };
+            if done {
+                if let Some(mut connection) = connections.remove(&event.token()) {
+                    poll.registry().deregister(&mut connection.stream)?;
+                    println!("Closing connection: {:?}", connection.stream.peer_addr());
+                }
+            }
+        }
+    }
+}
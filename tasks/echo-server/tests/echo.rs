@@ -0,0 +1,62 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+use echo_server::{serve_polled, serve_threaded};
+
+/// Starts a server on an ephemeral port and returns its address.
+fn spawn(serve: fn(TcpListener) -> io::Result<()>) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || serve(listener).unwrap());
+    addr
+}
+
+fn check_echo(addr: SocketAddr) {
+    // Several clients at once, to check that one does not block the others.
+    let clients: Vec<_> = (0..4)
+        .map(|i| {
+            let stream = TcpStream::connect(addr).unwrap();
+            (i, BufReader::new(stream.try_clone().unwrap()), stream)
+        })
+        .collect();
+
+    for (i, mut reader, mut writer) in clients {
+        for line in &["hello", "", "rosetta code"] {
+            let sent = format!("{} from client {}\n", line, i);
+            writer.write_all(sent.as_bytes()).unwrap();
+
+            let mut received = String::new();
+            reader.read_line(&mut received).unwrap();
+            assert_eq!(received, sent);
+        }
+    }
+}
+
+#[test]
+fn threaded() {
+    check_echo(spawn(serve_threaded));
+}
+
+#[test]
+fn polled() {
+    check_echo(spawn(serve_polled));
+}
+
+#[test]
+fn polled_large_message() {
+    // Larger than the socket buffers, so the server has to wait until it can write again.
+    let addr = spawn(serve_polled);
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let line = format!("{}\n", "echo ".repeat(1_000_000));
+    let mut writer = stream;
+    let sent = line.clone();
+    let sender = thread::spawn(move || writer.write_all(sent.as_bytes()).unwrap());
+
+    let mut received = String::new();
+    reader.read_line(&mut received).unwrap();
+    sender.join().unwrap();
+    assert_eq!(received, line);
+}
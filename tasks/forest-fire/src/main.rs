@@ -1,21 +1,39 @@
-extern crate ansi_term;
-extern crate rand;
+//! The Drossel and Schwabl forest-fire model, a cellular automaton where every cell is empty,
+//! a tree, or burning, and at each step:
+//!
+//! - a burning cell becomes empty,
+//! - a tree catches fire if one of its eight neighbours burns,
+//! - any other tree is struck by lightning with probability `f`,
+//! - and a tree grows on an empty cell with probability `p`.
+//!
+//! All the cells change at once, so each step reads one buffer and writes the other.
+//!
+//! cargo run -p forest-fire -- [seed]
 
-#[derive(Copy, Clone, PartialEq)]
+use std::fmt;
+use std::io::{stdout, BufWriter, Write};
+use std::mem;
+use std::time::Duration;
+
+use ansi_term::Colour::*;
+use rand::prelude::*;
+use term_grid::{cursor_to, CLEAR};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 enum Tile {
     Empty,
     Tree,
     Burning,
-    Heating,
 }
 
+use Tile::{Burning, Empty, Tree};
+
 impl fmt::Display for Tile {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let output = match *self {
             Empty => Black.paint(" "),
             Tree => Green.bold().paint("T"),
             Burning => Red.bold().paint("B"),
-            Heating => Yellow.bold().paint("T"),
         };
         write!(f, "{}", output)
     }
@@ -30,123 +48,226 @@ const FOREST_HEIGHT: usize = 30;
 
 const SLEEP_MILLIS: u64 = 25;
 
-use std::fmt;
-use std::io::prelude::*;
-use std::io::{stdout, BufWriter, StdoutLock};
-use std::process::Command;
-use std::time::Duration;
-
-use ansi_term::Colour::*;
-use rand::{thread_rng, Rng};
-
-use Tile::{Burning, Empty, Heating, Tree};
+struct Forest {
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+    /// The buffer the next step is written to.
+    next: Vec<Tile>,
+    /// The probability that a tree grows on an empty tile.
+    p: f64,
+    /// The probability that lightning strikes a tree.
+    f: f64,
+}
 
-fn main() {
-    let sleep_duration = Duration::from_millis(SLEEP_MILLIS);
-    let mut forest = [[Tile::Empty; FOREST_WIDTH]; FOREST_HEIGHT];
+impl Forest {
+    /// A forest where each tile has a tree with probability `density`.
+    fn new<R: Rng>(width: usize, height: usize, density: f64, p: f64, f: f64, rng: &mut R) -> Self {
+        let tiles: Vec<Tile> = (0..width * height)
+            .map(|_| if rng.gen_bool(density) { Tree } else { Empty })
+            .collect();
+        Forest {
+            width,
+            height,
+            next: tiles.clone(),
+            tiles,
+            p,
+            f,
+        }
+    }
 
-    prepopulate_forest(&mut forest);
-    print_forest(forest, 0);
+    fn tile(&self, x: usize, y: usize) -> Tile {
+        self.tiles[y * self.width + x]
+    }
 
-    std::thread::sleep(sleep_duration);
+    /// Whether one of the eight neighbours of a tile burns. The forest ends at its edges.
+    fn near_fire(&self, x: usize, y: usize) -> bool {
+        for ny in y.saturating_sub(1)..=(y + 1).min(self.height - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(self.width - 1) {
+                if self.tile(nx, ny) == Burning && (nx, ny) != (x, y) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
 
-    for generation in 1.. {
-        for row in &mut forest {
-            for tile in row.iter_mut() {
-                update_tile(tile);
+    fn step<R: Rng>(&mut self, rng: &mut R) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.next[y * self.width + x] = match self.tile(x, y) {
+                    Burning => Empty,
+                    Tree if self.near_fire(x, y) || rng.gen_bool(self.f) => Burning,
+                    Tree => Tree,
+                    Empty if rng.gen_bool(self.p) => Tree,
+                    Empty => Empty,
+                };
             }
         }
+        mem::swap(&mut self.tiles, &mut self.next);
+    }
 
-        for y in 0..FOREST_HEIGHT {
-            for x in 0..FOREST_WIDTH {
-                if forest[y][x] == Burning {
-                    heat_neighbors(&mut forest, y, x);
-                }
+    /// The share of the tiles that have a tree.
+    fn density(&self) -> f64 {
+        let trees = self.tiles.iter().filter(|&&tile| tile == Tree).count();
+        trees as f64 / self.tiles.len() as f64
+    }
+}
+
+impl fmt::Display for Forest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.tiles.chunks(self.width) {
+            for tile in row {
+                write!(f, "{}", tile)?;
             }
+            writeln!(f)?;
         }
+        Ok(())
+    }
+}
+
+fn main() {
+    let seed = std::env::args()
+        .nth(1)
+        .map_or_else(random, |seed| seed.parse().expect("a seed"));
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut forest = Forest::new(
+        FOREST_WIDTH,
+        FOREST_HEIGHT,
+        INITIAL_TREE_PROB,
+        NEW_TREE_PROB,
+        FIRE_PROB,
+        &mut rng,
+    );
 
-        print_forest(forest, generation);
+    let sleep_duration = Duration::from_millis(SLEEP_MILLIS);
+    print!("{}", CLEAR);
+    for generation in 1.. {
+        // Draw each frame over the last one.
+        let stdout = stdout();
+        let mut writer = BufWriter::new(stdout.lock());
+        write!(writer, "{}", cursor_to(1, 1)).unwrap();
+        writeln!(
+            writer,
+            "Generation: {}, seed: {}, trees: {:.0}%",
+            generation,
+            seed,
+            100.0 * forest.density()
+        )
+        .unwrap();
+        write!(writer, "{}", forest).unwrap();
+        writer.flush().unwrap();
 
+        forest.step(&mut rng);
         std::thread::sleep(sleep_duration);
     }
 }
 
-fn prepopulate_forest(forest: &mut [[Tile; FOREST_WIDTH]; FOREST_HEIGHT]) {
-    let mut rng = thread_rng();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for row in forest.iter_mut() {
-        for tile in row.iter_mut() {
-            *tile = if rng.gen_bool(INITIAL_TREE_PROB) {
-                Tree
-            } else {
-                Empty
-            };
+    fn forest(rows: &[&str]) -> Forest {
+        let tiles: Vec<Tile> = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                'T' => Tree,
+                'B' => Burning,
+                _ => Empty,
+            })
+            .collect();
+        Forest {
+            width: rows[0].len(),
+            height: rows.len(),
+            next: tiles.clone(),
+            tiles,
+            p: 0.0,
+            f: 0.0,
         }
     }
-}
 
-fn update_tile(tile: &mut Tile) {
-    let mut rng = thread_rng();
-    *tile = match *tile {
-        Empty => {
-            if rng.gen_bool(NEW_TREE_PROB) {
-                Tree
-            } else {
-                Empty
-            }
-        }
-        Tree => {
-            if rng.gen_bool(FIRE_PROB) {
-                Burning
-            } else {
-                Tree
-            }
-        }
-        Burning => Empty,
-        Heating => Burning,
+    fn rows(forest: &Forest) -> Vec<String> {
+        let rows = forest.tiles.chunks(forest.width);
+        rows.map(|row| {
+            row.iter()
+                .map(|tile| match tile {
+                    Tree => 'T',
+                    Burning => 'B',
+                    Empty => '.',
+                })
+                .collect()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn fire_spreads() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut forest = forest(&["TTT.T", "TB..T", "TTT.T"]);
+        forest.step(&mut rng);
+        // Every step reads the old tiles, so the fire moves by one tile at a time, and over
+        // diagonals too, but not across the gap.
+        assert_eq!(rows(&forest), ["BBB.T", "B...T", "BBB.T"]);
+        forest.step(&mut rng);
+        assert_eq!(rows(&forest), ["....T", "....T", "....T"]);
+    }
+
+    #[test]
+    fn growth_and_lightning() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut forest = Forest::new(50, 50, 0.0, 1.0, 0.0, &mut rng);
+        forest.step(&mut rng);
+        assert_eq!(forest.density(), 1.0);
+        forest.f = 1.0;
+        forest.step(&mut rng);
+        assert!(forest.tiles.iter().all(|&tile| tile == Burning));
     }
-}
 
-fn heat_neighbors(forest: &mut [[Tile; FOREST_WIDTH]; FOREST_HEIGHT], y: usize, x: usize) {
-    let neighbors = [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ];
-
-    for (nx, ny) in neighbors
-        .iter()
-        .map(|&(x_off, y_off)| (x as i8 + x_off, (y as i8 + y_off)))
-        .filter(|&(nx, ny)| nx > 0 && ny > 0)
-        .map(|(nx, ny)| (nx as usize, (ny as usize)))
-    {
-        if let Some(tile) = forest.get_mut(ny).and_then(|r| r.get_mut(nx)) {
-            if *tile == Tree {
-                *tile = Heating;
+    #[test]
+    fn same_seed_same_forest() {
+        let run = |seed| {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let mut forest = Forest::new(30, 20, 0.5, 0.05, 0.01, &mut rng);
+            for _ in 0..50 {
+                forest.step(&mut rng);
             }
-        }
+            rows(&forest)
+        };
+        assert_eq!(run(3), run(3));
+        assert_ne!(run(3), run(4));
     }
-}
 
-fn print_forest(forest: [[Tile; FOREST_WIDTH]; FOREST_HEIGHT], generation: usize) {
-    let stdout = stdout();
-    let mut writer = BufWriter::new(stdout.lock());
-    clear_screen(&mut writer);
-    writeln!(writer, "Generation: {}", generation + 1).unwrap();
-    for row in &forest {
-        for tree in row.iter() {
-            write!(writer, "{}", tree).unwrap();
+    /// The mean density over 1000 steps, after 500 steps to forget the start.
+    fn steady_density(p: f64, f: f64, seed: u64) -> f64 {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let mut forest = Forest::new(80, 80, INITIAL_TREE_PROB, p, f, &mut rng);
+        for _ in 0..500 {
+            forest.step(&mut rng);
         }
-        writeln!(writer).unwrap();
+        let total: f64 = (0..1000)
+            .map(|_| {
+                forest.step(&mut rng);
+                forest.density()
+            })
+            .sum();
+        total / 1000.0
     }
-}
 
-fn clear_screen(writer: &mut BufWriter<StdoutLock>) {
-    let output = Command::new("clear").output().unwrap();
-    write!(writer, "{}", String::from_utf8_lossy(&output.stdout)).unwrap();
+    #[test]
+    fn steady_state_density() {
+        // Fires keep the density below 0.407, where the trees of a large forest would join up
+        // through their eight neighbours; the slower lightning is next to growth, the closer
+        // the density gets to that.
+        let task = steady_density(NEW_TREE_PROB, FIRE_PROB, 0);
+        assert!(0.2 < task && task < 0.26, "{}", task);
+        let rare_lightning = steady_density(0.02, 0.0001, 0);
+        assert!(
+            0.27 < rare_lightning && rare_lightning < 0.36,
+            "{}",
+            rare_lightning
+        );
+        // Another seed lands close by.
+        assert!((steady_density(NEW_TREE_PROB, FIRE_PROB, 1) - task).abs() < 0.01);
+    }
 }
@@ -1,56 +1,316 @@
-extern crate ftp;
+//! Lists a directory and downloads a file with a small FTP client written on top of
+//! `TcpStream`, using passive mode for the data connections.
 
-use ftp::types::{FileType, Result};
-use ftp::FtpStream;
+use std::fmt;
 use std::fs::File;
-use std::io::{Read, Write};
-
-fn write_file(filename: &str) -> impl Fn(&mut dyn Read) -> Result<()> {
-    let filename = filename.to_string();
-    move |stream| {
-        let mut file = File::create(&filename).unwrap();
-        let mut buf = [0; 2048];
-        loop {
-            match stream.read(&mut buf) {
-                Ok(0) => break,
-                Ok(n) => file.write_all(&buf[0..n]).unwrap(),
-                Err(err) => panic!(err),
-            };
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddrV4, TcpStream, ToSocketAddrs};
+
+/// A reply on the control connection.
+#[derive(Debug, PartialEq)]
+struct Reply {
+    code: u16,
+    text: String,
+}
+
+#[derive(Debug)]
+enum Error {
+    Io(io::Error),
+    /// The server did not give the reply that the command called for.
+    Unexpected(Reply),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Unexpected(reply) => {
+                write!(f, "unexpected reply: {} {}", reply.code, reply.text)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Reads a reply, which is either a single line `123 text`, or several lines starting with
+/// `123-text` and ending with a line that starts with `123 `.
+fn read_reply<R: BufRead>(reader: &mut R) -> io::Result<Reply> {
+    let mut read_line = || -> io::Result<String> {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the server closed the connection",
+            ));
+        }
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    };
+
+    let first = read_line()?;
+    let code: u16 = first
+        .get(..3)
+        .and_then(|c| c.parse().ok())
+        .ok_or_else(|| invalid_data("malformed reply"))?;
+    let mut text = first[3..].trim_start_matches([' ', '-']).to_string();
+
+    match first.as_bytes().get(3) {
+        Some(b' ') | None => {}
+        Some(b'-') => {
+            let last = format!("{} ", code);
+            loop {
+                let line = read_line()?;
+                text.push('\n');
+                if let Some(rest) = line.strip_prefix(&last) {
+                    text.push_str(rest);
+                    break;
+                }
+                text.push_str(&line);
+            }
+        }
+        _ => return Err(invalid_data("malformed reply")),
+    }
+
+    Ok(Reply { code, text })
+}
+
+/// Parses the address in the reply to `PASV`: `Entering Passive Mode (h1,h2,h3,h4,p1,p2).`,
+/// where the port is `p1 * 256 + p2`.
+fn parse_pasv(text: &str) -> Option<SocketAddrV4> {
+    let start = text.find('(')? + 1;
+    let end = start + text[start..].find(')')?;
+    let numbers = text[start..end]
+        .split(',')
+        .map(|n| n.trim().parse::<u8>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .ok()?;
+
+    match numbers[..] {
+        [h1, h2, h3, h4, p1, p2] => Some(SocketAddrV4::new(
+            Ipv4Addr::new(h1, h2, h3, h4),
+            u16::from(p1) << 8 | u16::from(p2),
+        )),
+        _ => None,
+    }
+}
+
+/// Parses the directory in the reply to `PWD`: `"/some/dir" is the current directory`, where
+/// quotes inside the name are doubled.
+fn parse_pwd(text: &str) -> Option<String> {
+    let mut chars = text.strip_prefix('"')?.chars().peekable();
+    let mut path = String::new();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            if chars.peek() == Some(&'"') {
+                chars.next();
+            } else {
+                return Some(path);
+            }
+        }
+        path.push(c);
+    }
+    None
+}
+
+struct FtpClient {
+    control: BufReader<TcpStream>,
+}
+
+impl FtpClient {
+    fn connect<A: ToSocketAddrs>(addr: A) -> Result<Self> {
+        let mut client = FtpClient {
+            control: BufReader::new(TcpStream::connect(addr)?),
+        };
+        client.expect(&[220])?;
+        Ok(client)
+    }
+
+    /// Reads a reply, and checks that it has one of the expected codes.
+    fn expect(&mut self, codes: &[u16]) -> Result<Reply> {
+        let reply = read_reply(&mut self.control)?;
+        if codes.contains(&reply.code) {
+            Ok(reply)
+        } else {
+            Err(Error::Unexpected(reply))
+        }
+    }
+
+    fn command(&mut self, command: &str, codes: &[u16]) -> Result<Reply> {
+        write!(self.control.get_mut(), "{}\r\n", command)?;
+        self.expect(codes)
+    }
+
+    fn login(&mut self, user: &str, password: &str) -> Result<()> {
+        // Some servers let the user in without asking for a password.
+        if self.command(&format!("USER {}", user), &[230, 331])?.code == 331 {
+            self.command(&format!("PASS {}", password), &[230])?;
         }
         Ok(())
     }
+
+    fn cwd(&mut self, path: &str) -> Result<()> {
+        self.command(&format!("CWD {}", path), &[250])?;
+        Ok(())
+    }
+
+    fn pwd(&mut self) -> Result<String> {
+        let reply = self.command("PWD", &[257])?;
+        parse_pwd(&reply.text).ok_or(Error::Unexpected(reply))
+    }
+
+    /// Switches to binary ("image") transfers, so that files are not altered on the way.
+    fn binary(&mut self) -> Result<()> {
+        self.command("TYPE I", &[200])?;
+        Ok(())
+    }
+
+    /// Runs a command that sends its result over a new data connection, and copies that
+    /// result to `output`.
+    fn transfer(&mut self, command: &str, output: &mut dyn Write) -> Result<u64> {
+        let reply = self.command("PASV", &[227])?;
+        let addr = parse_pasv(&reply.text).ok_or(Error::Unexpected(reply))?;
+        let mut data = TcpStream::connect(addr)?;
+
+        self.command(command, &[125, 150])?;
+        let copied = io::copy(&mut data, output)?;
+        drop(data);
+        self.expect(&[226, 250])?;
+        Ok(copied)
+    }
+
+    fn list(&mut self, path: Option<&str>) -> Result<String> {
+        let command = match path {
+            Some(path) => format!("LIST {}", path),
+            None => "LIST".to_string(),
+        };
+        let mut listing = vec![];
+        self.transfer(&command, &mut listing)?;
+        Ok(String::from_utf8_lossy(&listing).into_owned())
+    }
+
+    fn retr(&mut self, name: &str, output: &mut dyn Write) -> Result<u64> {
+        self.transfer(&format!("RETR {}", name), output)
+    }
+
+    fn quit(mut self) -> Result<()> {
+        self.command("QUIT", &[221])?;
+        Ok(())
+    }
 }
 
-fn main() {
+fn main() -> Result<()> {
     // connect to the server
-    let mut ftp = FtpStream::connect("kernel.org:21").unwrap();
-    ftp.login("anonymous", "").unwrap();
+    let mut ftp = FtpClient::connect("kernel.org:21")?;
+    ftp.login("anonymous", "")?;
 
     // change working directory
-    ftp.cwd("/pub/linux/kernel").unwrap();
+    ftp.cwd("/pub/linux/kernel")?;
+    println!("Listing {}:", ftp.pwd()?);
 
     // list files in the current directory
-    match ftp.list(None) {
-        Ok(output) => println!("{}", output.join("")),
-        Err(err) => panic!(err),
-    }
+    println!("{}", ftp.list(None)?);
 
     // download a file a write it on the disk
-    ftp.transfer_type(FileType::Binary).unwrap();
-    ftp.retr("README", write_file("README")).unwrap();
+    ftp.binary()?;
+    let mut file = File::create("README")?;
+    ftp.retr("README", &mut file)?;
+
+    ftp.quit()
 }
 
 #[cfg(test)]
-mod test {
-    use super::write_file;
-    use ftp::types::FileType;
-    use ftp::FtpStream;
+mod tests {
+    use super::*;
+
     use std::fs;
 
-    fn connect() -> FtpStream {
-        let mut ftp = FtpStream::connect("kernel.org:21").unwrap();
+    fn reply(raw: &str) -> io::Result<Reply> {
+        read_reply(&mut raw.as_bytes())
+    }
+
+    #[test]
+    fn single_line_reply() {
+        assert_eq!(
+            reply("220 Welcome to the FTP server\r\n").unwrap(),
+            Reply {
+                code: 220,
+                text: "Welcome to the FTP server".to_string()
+            }
+        );
+        assert_eq!(reply("200\r\n").unwrap().text, "");
+    }
+
+    #[test]
+    fn multi_line_reply() {
+        let raw = "230-Welcome!\r\n\
+                   230-Please behave.\r\n\
+                   \x20123 is not the end\r\n\
+                   230 Login successful.\r\n\
+                   150 next reply\r\n";
+        let mut reader = raw.as_bytes();
+        assert_eq!(
+            read_reply(&mut reader).unwrap(),
+            Reply {
+                code: 230,
+                text: "Welcome!\n230-Please behave.\n 123 is not the end\nLogin successful."
+                    .to_string()
+            }
+        );
+        assert_eq!(read_reply(&mut reader).unwrap().code, 150);
+    }
+
+    #[test]
+    fn malformed_replies() {
+        assert!(reply("22 short\r\n").is_err());
+        assert!(reply("abc def\r\n").is_err());
+        assert!(reply("220+oops\r\n").is_err());
+        assert!(reply("220-never ends\r\n").is_err());
+        assert!(reply("").is_err());
+    }
+
+    #[test]
+    fn pasv() {
+        assert_eq!(
+            parse_pasv("Entering Passive Mode (192,168,1,2,195,80)."),
+            Some("192.168.1.2:50000".parse().unwrap())
+        );
+        assert_eq!(
+            parse_pasv("Entering Passive Mode (10, 0, 0, 1, 0, 21)"),
+            Some("10.0.0.1:21".parse().unwrap())
+        );
+        assert_eq!(parse_pasv("Entering Passive Mode (1,2,3,4,5)."), None);
+        assert_eq!(parse_pasv("Entering Passive Mode (1,2,3,4,5,256)."), None);
+        assert_eq!(parse_pasv("Entering Passive Mode."), None);
+    }
+
+    #[test]
+    fn pwd() {
+        assert_eq!(
+            parse_pwd(r#""/pub/linux" is the current directory"#),
+            Some("/pub/linux".to_string())
+        );
+        assert_eq!(
+            parse_pwd(r#""/a ""quoted"" dir" created"#),
+            Some(r#"/a "quoted" dir"#.to_string())
+        );
+        assert_eq!(parse_pwd("/no/quotes"), None);
+        assert_eq!(parse_pwd(r#""/unterminated"#), None);
+    }
+
+    fn connect() -> FtpClient {
+        let mut ftp = FtpClient::connect("kernel.org:21").unwrap();
         ftp.login("anonymous", "").unwrap();
-        return ftp;
+        ftp
     }
 
     #[ignore]
@@ -68,7 +328,7 @@ mod test {
     fn test_list_dir() {
         let mut ftp = connect();
         assert_eq!(
-            ftp.list(Some("/")).unwrap().join(""),
+            ftp.list(Some("/")).unwrap().trim_end(),
             "drwxr-xr-x    9 ftp      ftp          4096 Dec 01  2011 pub"
         );
     }
@@ -79,16 +339,12 @@ mod test {
         let filename = ".test_download_file";
         let mut ftp = connect();
         ftp.cwd("/pub/linux/kernel").unwrap();
-        // make sure the file does not already exist
-        match fs::metadata(filename) {
-            Ok(_) => fs::remove_file(filename).unwrap(),
-            Err(_) => {}
-        }
-        ftp.transfer_type(FileType::Binary).unwrap();
-        ftp.retr("README", write_file(filename)).unwrap();
+        ftp.binary().unwrap();
+        let mut file = File::create(filename).unwrap();
+        ftp.retr("README", &mut file).unwrap();
         match fs::metadata(filename) {
             Ok(metadata) => {
-                assert_eq!(metadata.is_file(), true);
+                assert!(metadata.is_file());
                 assert_eq!(metadata.len(), 12056);
                 fs::remove_file(filename).unwrap();
             }
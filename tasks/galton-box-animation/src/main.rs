@@ -0,0 +1,211 @@
+//! Balls fall through a triangle of pins, bouncing left or right at random off each one, and
+//! pile up in the bins below. The number of times a ball bounced right picks its bin, so the
+//! piles take the shape of the binomial distribution.
+//!
+//! cargo run -p galton-box-animation -- [balls] [rows] [seed]
+
+use rand::prelude::*;
+use std::io::stdout;
+use std::thread;
+use std::time::Duration;
+use term_grid::{TermGrid, CLEAR};
+
+/// The height of the tallest pile, in lines, in the picture.
+const PILE_HEIGHT: usize = 10;
+const SLEEP_MILLIS: u64 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ball {
+    /// How many rows of pins the ball has gone past.
+    level: usize,
+    /// How many times it bounced right.
+    rights: usize,
+}
+
+struct GaltonBox {
+    rows: usize,
+    /// The balls still to drop in.
+    waiting: usize,
+    falling: Vec<Ball>,
+    bins: Vec<usize>,
+}
+
+impl GaltonBox {
+    /// A box with `rows` rows of pins, the last of which has `rows` pins, so that there are
+    /// `rows + 1` bins.
+    fn new(rows: usize, balls: usize) -> Self {
+        GaltonBox {
+            rows,
+            waiting: balls,
+            falling: vec![],
+            bins: vec![0; rows + 1],
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.waiting == 0 && self.falling.is_empty()
+    }
+
+    /// Moves every falling ball past one more row of pins, or into its bin, and drops in the
+    /// next ball at the top.
+    fn step<R: Rng>(&mut self, rng: &mut R) {
+        let (rows, bins) = (self.rows, &mut self.bins);
+        self.falling.retain_mut(|ball| {
+            if ball.level == rows {
+                bins[ball.rights] += 1;
+                return false;
+            }
+            ball.level += 1;
+            if rng.gen() {
+                ball.rights += 1;
+            }
+            true
+        });
+        if self.waiting > 0 {
+            self.waiting -= 1;
+            self.falling.push(Ball {
+                level: 0,
+                rights: 0,
+            });
+        }
+    }
+
+    /// The pins as dots, on every other line, with the falling balls on the lines between
+    /// them, and the piles in the bins below, scaled down if they are too tall.
+    fn grid(&self) -> TermGrid {
+        let width = 2 * self.rows + 1;
+        let top = 2 * self.rows + 1;
+        let mut grid = TermGrid::new(width, top + PILE_HEIGHT);
+        for row in 0..self.rows {
+            for pin in 0..=row {
+                grid.set(self.rows - row + 2 * pin, 2 * row + 1, '.');
+            }
+        }
+        for ball in &self.falling {
+            grid.set(
+                self.rows - ball.level + 2 * ball.rights,
+                2 * ball.level,
+                'o',
+            );
+        }
+
+        let tallest = self
+            .bins
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(PILE_HEIGHT);
+        for (bin, &count) in self.bins.iter().enumerate() {
+            let height = (count * PILE_HEIGHT).div_ceil(tallest);
+            for y in 0..height {
+                grid.set(2 * bin, top + PILE_HEIGHT - 1 - y, '#');
+            }
+        }
+        grid
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let balls = args
+        .next()
+        .map_or(100, |n| n.parse().expect("a number of balls"));
+    let rows = args
+        .next()
+        .map_or(8, |n| n.parse().expect("a number of rows"));
+    let seed = args
+        .next()
+        .map_or_else(random, |seed| seed.parse().expect("a seed"));
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut galton = GaltonBox::new(rows, balls);
+
+    print!("{}", CLEAR);
+    loop {
+        galton.grid().show(stdout().lock()).unwrap();
+        if galton.is_done() {
+            break;
+        }
+        galton.step(&mut rng);
+        thread::sleep(Duration::from_millis(SLEEP_MILLIS));
+    }
+    println!("Bins: {:?}, seed: {}", galton.bins, seed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl GaltonBox {
+        fn run<R: Rng>(&mut self, rng: &mut R) {
+            while !self.is_done() {
+                self.step(rng);
+            }
+        }
+    }
+
+    fn binomial(n: usize, k: usize) -> f64 {
+        (0..k).fold(1.0, |c, i| c * (n - i) as f64 / (i + 1) as f64)
+    }
+
+    #[test]
+    fn balls_fall_one_row_a_step() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut galton = GaltonBox::new(3, 2);
+        galton.step(&mut rng);
+        assert_eq!(
+            galton.falling,
+            [Ball {
+                level: 0,
+                rights: 0
+            }]
+        );
+        for _ in 0..3 {
+            galton.step(&mut rng);
+        }
+        // The first ball has gone past all three rows, and the second past two.
+        assert_eq!(galton.falling.len(), 2);
+        assert_eq!(galton.falling[0].level, 3);
+        assert_eq!(galton.falling[1].level, 2);
+        assert!(galton.falling.iter().all(|ball| ball.rights <= ball.level));
+        galton.run(&mut rng);
+        assert_eq!(galton.bins.iter().sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn picture() {
+        let mut galton = GaltonBox::new(2, 0);
+        galton.falling.push(Ball {
+            level: 1,
+            rights: 1,
+        });
+        galton.bins = vec![0, 3, 1];
+        let picture = galton.grid().to_string();
+        let lines: Vec<&str> = picture.lines().collect();
+        assert_eq!(&lines[..5], ["     ", "  .  ", "   o ", " . . ", "     "]);
+        // Less than PILE_HEIGHT balls are shown one a line.
+        assert_eq!(&lines[12..], ["  #  ", "  #  ", "  # #"]);
+    }
+
+    #[test]
+    fn binomial_distribution() {
+        let (rows, balls) = (8, 10_000);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut galton = GaltonBox::new(rows, balls);
+        galton.run(&mut rng);
+        assert_eq!(galton.bins.iter().sum::<usize>(), balls);
+
+        // Pearson's chi-squared test: with 8 degrees of freedom, a statistic above 26.1 would
+        // only happen one time in 1000.
+        let chi_squared: f64 = (0..=rows)
+            .map(|k| {
+                let expected = balls as f64 * binomial(rows, k) / 2f64.powi(rows as i32);
+                (galton.bins[k] as f64 - expected).powi(2) / expected
+            })
+            .sum();
+        assert!(chi_squared < 26.1, "{} for {:?}", chi_squared, galton.bins);
+
+        let mean = (0..=rows).map(|k| k * galton.bins[k]).sum::<usize>() as f64 / balls as f64;
+        assert!((mean - 4.0).abs() < 0.05, "{}", mean);
+    }
+}
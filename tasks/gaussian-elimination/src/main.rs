@@ -0,0 +1,109 @@
+/// Solves the linear system `a * x = b` by Gaussian elimination with partial pivoting, followed
+/// by back-substitution.
+///
+/// Returns `None` if the matrix is singular.
+fn gauss_eliminate(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    assert!(a.len() == n && a.iter().all(|row| row.len() == n));
+
+    for k in 0..n {
+        // Partial pivoting: swap the row with the largest absolute value in column `k` into
+        // place, which keeps the multipliers below small and the algorithm numerically stable.
+        let pivot = (k..n)
+            .max_by(|&i, &j| a[i][k].abs().partial_cmp(&a[j][k].abs()).unwrap())
+            .unwrap();
+        if a[pivot][k].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(k, pivot);
+        b.swap(k, pivot);
+
+        // Eliminate column `k` from all rows below the pivot.
+        let (pivot_rows, rows_below) = a.split_at_mut(k + 1);
+        let pivot_row = &pivot_rows[k];
+        for (i, row) in (k + 1..).zip(rows_below) {
+            let factor = row[k] / pivot_row[k];
+            for (value, pivot_value) in row[k..].iter_mut().zip(&pivot_row[k..]) {
+                *value -= factor * pivot_value;
+            }
+            b[i] -= factor * b[k];
+        }
+    }
+
+    // The matrix is now upper triangular, so the unknowns can be found from the bottom up.
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|j| a[i][j] * x[j]).sum();
+        x[i] = (b[i] - sum) / a[i][i];
+    }
+    Some(x)
+}
+
+// The matrix happens to contain 3.14, which is not meant to be π.
+#[allow(clippy::approx_constant)]
+fn task_matrix() -> (Vec<Vec<f64>>, Vec<f64>) {
+    let a = vec![
+        vec![1.00, 0.00, 0.00, 0.00, 0.00, 0.00],
+        vec![1.00, 0.63, 0.39, 0.25, 0.16, 0.10],
+        vec![1.00, 1.26, 1.58, 1.98, 2.49, 3.13],
+        vec![1.00, 1.88, 3.55, 6.70, 12.62, 23.80],
+        vec![1.00, 2.51, 6.32, 15.88, 39.90, 100.28],
+        vec![1.00, 3.14, 9.87, 31.01, 97.41, 306.02],
+    ];
+    let b = vec![-0.01, 0.61, 0.91, 0.99, 0.60, 0.02];
+    (a, b)
+}
+
+fn main() {
+    let (a, b) = task_matrix();
+    match gauss_eliminate(a, b) {
+        Some(x) => {
+            for value in x {
+                println!("{:.15}", value);
+            }
+        }
+        None => println!("The matrix is singular"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn task_example() {
+        let (a, b) = task_matrix();
+        let x = gauss_eliminate(a.clone(), b.clone()).unwrap();
+
+        let expected = [
+            -0.01,
+            1.602_790_394_502_114,
+            -1.613_203_059_905_561_3,
+            1.245_494_121_371_436_8,
+            -0.490_989_719_584_657_6,
+            0.065_760_696_175_232,
+        ];
+        for (value, expected) in x.iter().zip(&expected) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        // Substituting the solution back into the system must give (almost exactly) `b`.
+        for (row, b) in a.iter().zip(&b) {
+            let lhs: f64 = row.iter().zip(&x).map(|(a, x)| a * x).sum();
+            assert!((lhs - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn requires_pivoting() {
+        // Without row swaps, the zero in the top-left corner would cause a division by zero.
+        let a = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        assert_eq!(gauss_eliminate(a, vec![2.0, 3.0]), Some(vec![3.0, 2.0]));
+    }
+
+    #[test]
+    fn singular_matrix() {
+        let a = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(gauss_eliminate(a, vec![1.0, 2.0]), None);
+    }
+}
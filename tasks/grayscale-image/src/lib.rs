@@ -0,0 +1,91 @@
+use bitmap::{Color, Image};
+
+/// An image with a single channel, the luminance of each pixel.
+pub struct GrayImage {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+/// The luminance of a color, with the weights of the CIE 1931 linear luminance:
+/// `0.2126 R + 0.7152 G + 0.0722 B`, rounded to the nearest integer.
+pub fn luminance(color: Color) -> u8 {
+    let weighted =
+        2126 * u32::from(color.red) + 7152 * u32::from(color.green) + 722 * u32::from(color.blue);
+    ((weighted + 5000) / 10_000) as u8
+}
+
+pub fn to_grayscale(image: &Image) -> GrayImage {
+    GrayImage {
+        width: image.width,
+        height: image.height,
+        data: image.data.iter().map(|&c| luminance(c)).collect(),
+    }
+}
+
+/// Turns a grayscale image back into a color one, made only of grays.
+pub fn to_rgb(image: &GrayImage) -> Image {
+    let mut rgb = Image::new(image.width, image.height);
+    for (color, &l) in rgb.data.iter_mut().zip(&image.data) {
+        *color = Color {
+            red: l,
+            green: l,
+            blue: l,
+        };
+    }
+    rgb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(red: u8, green: u8, blue: u8) -> Color {
+        Color { red, green, blue }
+    }
+
+    #[test]
+    fn primaries() {
+        assert_eq!(luminance(color(0, 0, 0)), 0);
+        assert_eq!(luminance(color(255, 255, 255)), 255);
+        assert_eq!(luminance(color(255, 0, 0)), 54);
+        assert_eq!(luminance(color(0, 255, 0)), 182);
+        assert_eq!(luminance(color(0, 0, 255)), 18);
+        assert_eq!(luminance(color(255, 255, 0)), 237);
+    }
+
+    #[test]
+    fn gradients() {
+        let mut image = Image::new(256, 2);
+        for x in 0..256 {
+            image[(x, 0)] = color(x as u8, x as u8, x as u8);
+            image[(x, 1)] = color(x as u8, 0, 0);
+        }
+        let gray = to_grayscale(&image);
+        assert_eq!((gray.width, gray.height), (256, 2));
+        for x in 0..256 {
+            // Grays keep their level, and the red ramp rises by 0.2126 at each step.
+            assert_eq!(gray.data[x], x as u8);
+            assert_eq!(
+                gray.data[256 + x],
+                (0.2126 * x as f64).round() as u8,
+                "red {}",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn back_to_rgb() {
+        let mut image = Image::new(3, 1);
+        image[(0, 0)] = color(10, 200, 30);
+        image[(1, 0)] = color(77, 77, 77);
+        image[(2, 0)] = color(255, 128, 0);
+        let rgb = to_rgb(&to_grayscale(&image));
+        assert_eq!(rgb[(0, 0)], color(147, 147, 147));
+        assert_eq!(rgb[(1, 0)], image[(1, 0)]);
+        assert_eq!(rgb[(2, 0)], color(146, 146, 146));
+        // Grays are unchanged by a second round trip.
+        assert_eq!(to_rgb(&to_grayscale(&rgb)).data, rgb.data);
+    }
+}
@@ -0,0 +1,21 @@
+//! Converts a PPM file to grayscale.
+//!
+//! cargo run -p grayscale-image -- input.ppm output.ppm
+
+use grayscale_image::{to_grayscale, to_rgb};
+use read_a_ppm_file::{read_ppm_file, Error};
+use std::env;
+use write_a_ppm_file::{Format, PPMWritable};
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: grayscale-image input.ppm output.ppm");
+        std::process::exit(2);
+    }
+
+    let image = read_ppm_file(&args[0])?;
+    let gray = to_grayscale(&image);
+    to_rgb(&gray).write_ppm(&args[1], Format::Raw)?;
+    Ok(())
+}
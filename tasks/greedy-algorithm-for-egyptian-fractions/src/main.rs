@@ -0,0 +1,187 @@
+use std::fmt;
+
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+/// A rational number written as an integer part plus a sum of distinct unit fractions.
+#[derive(Clone, Debug, PartialEq)]
+struct Egyptian {
+    integer: BigInt,
+    denominators: Vec<BigInt>,
+}
+
+impl Egyptian {
+    /// Decomposes a non-negative fraction with Fibonacci's greedy algorithm: repeatedly subtract
+    /// the largest unit fraction that is not larger than what remains.
+    fn greedy(fraction: &BigRational) -> Self {
+        assert!(!fraction.is_negative());
+
+        let integer = fraction.to_integer();
+        let remainder = fraction.fract();
+        let (mut x, mut y) = (remainder.numer().clone(), remainder.denom().clone());
+        let mut denominators = vec![];
+
+        while !x.is_zero() {
+            // The largest unit fraction that fits is 1/c, where c = ceil(y / x).
+            let c = (&y + &x - BigInt::one()) / &x;
+            // x/y - 1/c = (-y mod x) / (y * c)
+            x = (-&y).mod_floor(&x);
+            y *= &c;
+            denominators.push(c);
+        }
+
+        Egyptian {
+            integer,
+            denominators,
+        }
+    }
+}
+
+impl fmt::Display for Egyptian {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut terms = vec![];
+        if !self.integer.is_zero() {
+            terms.push(format!("[{}]", self.integer));
+        }
+        terms.extend(self.denominators.iter().map(|d| format!("1/{}", d)));
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+/// Searches all proper fractions whose numerator and denominator are below `bound`.
+///
+/// Returns the fractions with the most terms, and the fractions with the largest denominator,
+/// along with their decompositions.
+#[allow(clippy::type_complexity)]
+fn search(bound: u32) -> (Vec<(BigRational, Egyptian)>, Vec<(BigRational, Egyptian)>) {
+    let mut most_terms: Vec<(BigRational, Egyptian)> = vec![];
+    let mut largest_denominator: Vec<(BigRational, Egyptian)> = vec![];
+
+    let largest = |e: &Egyptian| e.denominators.last().cloned().unwrap_or_else(BigInt::zero);
+
+    for denominator in 2..bound {
+        for numerator in 1..denominator {
+            let fraction = BigRational::new(numerator.into(), denominator.into());
+            // Skip non-reduced fractions, since they were already seen in their reduced form.
+            if *fraction.denom() != BigInt::from(denominator) {
+                continue;
+            }
+            let egyptian = Egyptian::greedy(&fraction);
+
+            let terms = egyptian.denominators.len();
+            match most_terms.first().map(|(_, e)| e.denominators.len()) {
+                Some(max) if max > terms => {}
+                Some(max) if max == terms => most_terms.push((fraction.clone(), egyptian.clone())),
+                _ => most_terms = vec![(fraction.clone(), egyptian.clone())],
+            }
+
+            let denominator = largest(&egyptian);
+            match largest_denominator.first().map(|(_, e)| largest(e)) {
+                Some(ref max) if *max > denominator => {}
+                Some(ref max) if *max == denominator => {
+                    largest_denominator.push((fraction, egyptian))
+                }
+                _ => largest_denominator = vec![(fraction, egyptian)],
+            }
+        }
+    }
+
+    (most_terms, largest_denominator)
+}
+
+fn main() {
+    for &(numerator, denominator) in &[(43, 48), (5, 121), (2014, 59)] {
+        let fraction = BigRational::new(BigInt::from(numerator), BigInt::from(denominator));
+        println!(
+            "{}/{} => {}",
+            numerator,
+            denominator,
+            Egyptian::greedy(&fraction)
+        );
+    }
+    println!();
+
+    let (most_terms, largest_denominator) = search(100);
+    for (fraction, egyptian) in most_terms {
+        println!(
+            "{} has the most terms ({}): {}",
+            fraction,
+            egyptian.denominators.len(),
+            egyptian
+        );
+    }
+    for (fraction, egyptian) in largest_denominator {
+        let denominator = egyptian.denominators.last().unwrap().to_string();
+        println!(
+            "{} has the largest denominator ({} digits): {}",
+            fraction,
+            denominator.len(),
+            denominator
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fraction(numerator: i64, denominator: i64) -> BigRational {
+        BigRational::new(numerator.into(), denominator.into())
+    }
+
+    fn decompose(numerator: i64, denominator: i64) -> String {
+        Egyptian::greedy(&fraction(numerator, denominator)).to_string()
+    }
+
+    #[test]
+    fn task_examples() {
+        assert_eq!(decompose(43, 48), "1/2 + 1/3 + 1/16");
+        assert_eq!(
+            decompose(5, 121),
+            "1/25 + 1/757 + 1/763309 + 1/873960180913 + 1/1527612795642093418846225"
+        );
+        assert_eq!(
+            decompose(2014, 59),
+            "[34] + 1/8 + 1/95 + 1/14947 + 1/670223480"
+        );
+    }
+
+    #[test]
+    fn sums_to_original() {
+        for denominator in 1..30 {
+            for numerator in 0..2 * denominator {
+                let original = fraction(numerator, denominator);
+                let egyptian = Egyptian::greedy(&original);
+                let sum = egyptian
+                    .denominators
+                    .iter()
+                    .fold(BigRational::from_integer(egyptian.integer), |acc, d| {
+                        acc + BigRational::new(BigInt::one(), d.clone())
+                    });
+                assert_eq!(sum, original);
+            }
+        }
+    }
+
+    #[test]
+    fn two_digit_search() {
+        let (most_terms, largest_denominator) = search(100);
+
+        let most_terms: Vec<_> = most_terms
+            .iter()
+            .map(|(f, e)| (f.clone(), e.denominators.len()))
+            .collect();
+        assert_eq!(
+            most_terms,
+            vec![(fraction(44, 53), 8), (fraction(8, 97), 8)]
+        );
+
+        assert_eq!(largest_denominator.len(), 1);
+        let (f, e) = &largest_denominator[0];
+        assert_eq!(*f, fraction(8, 97));
+        assert_eq!(
+            e.denominators.last().unwrap().to_string(),
+            "579504587067542801713103191859918608251030291952195423583529357653899418686342360\
+             361798689053273749372615043661810228371898539583862011424993909789665"
+        );
+    }
+}
@@ -5,7 +5,7 @@ extern crate hamming_numbers;
 use num::bigint::ToBigUint;
 use num::BigUint;
 
-use hamming_numbers::Hamming;
+use hamming_numbers::{nth_hamming, Hamming};
 
 fn main() {
     // capacity of the queue currently needs to be a power of 2 because of a bug with VecDeque
@@ -18,4 +18,7 @@ fn main() {
             _ => continue,
         }
     }
+
+    // The band version finds a single number without keeping the whole sequence around.
+    println!("10000000th number: {}", nth_hamming(10_000_000));
 }
@@ -30,8 +30,7 @@ fn main() {
 }
 
 // we store these to calculate the ln of a hamming number
-pub const LN_2: f64 =
-    0.693_147_180_559_945_309_417_232_121_458_176_568_075_500_134_360_255_254_120_680;
+pub use std::f64::consts::LN_2;
 pub const LN_3: f64 =
     1.098_612_288_668_109_691_395_245_236_922_525_704_647_490_557_822_749_451_734_694;
 pub const LN_5: f64 =
@@ -111,9 +110,9 @@ impl ToBigUint for HammingTriple {
 impl HammingTriple {
     fn new(pow_2: usize, pow_3: usize, pow_5: usize) -> HammingTriple {
         HammingTriple {
-            pow_2: pow_2,
-            pow_3: pow_3,
-            pow_5: pow_5,
+            pow_2,
+            pow_3,
+            pow_5,
             ln: (pow_2 as f64) * LN_2 + (pow_3 as f64) * LN_3 + (pow_5 as f64) * LN_5,
         }
     }
@@ -129,36 +128,36 @@ impl Eq for HammingTriple {}
 
 impl PartialOrd for HammingTriple {
     fn partial_cmp(&self, other: &HammingTriple) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HammingTriple {
+    fn cmp(&self, other: &HammingTriple) -> Ordering {
         if self == other {
-            Some(Equal)
+            Equal
         } else if ((self.pow_2 >= other.pow_2)
             && (self.pow_3 >= other.pow_3)
             && (self.pow_5 >= other.pow_5))
             || (self.ln > other.ln)
         {
-            Some(Greater)
+            Greater
         } else if ((self.pow_2 <= other.pow_2)
             && (self.pow_3 <= other.pow_3)
             && (self.pow_5 <= other.pow_5))
             || (self.ln < other.ln)
         {
-            Some(Less)
+            Less
         } else {
-            None
+            // as a last resort we need to calculate the BigUint values and compare them.
+            // This should be rare. The reason is that for very big values floating point
+            // precision could make hamming_1.ln == hamming_2.ln even if the two numbers are
+            // actually different
+            self.to_biguint().unwrap().cmp(&other.to_biguint().unwrap())
         }
     }
 }
 
-impl Ord for HammingTriple {
-    fn cmp(&self, other: &HammingTriple) -> Ordering {
-        // as a last resort we need to calculate the BigUint values and compare them.
-        // This should be rare. The reason is that for very big values floating point precision
-        // could make hamming_1.ln == hamming_2.ln even if the two numbers are actually different
-        self.partial_cmp(other)
-            .unwrap_or_else(|| self.to_biguint().unwrap().cmp(&other.to_biguint().unwrap()))
-    }
-}
-
 #[test]
 fn hamming_iter() {
     let mut hamming = Hamming::<HammingTriple>::new(20);
@@ -6,6 +6,7 @@ use std::ops::Mul;
 
 use num::bigint::{BigUint, ToBigUint};
 use num::one;
+use num::pow;
 use num::traits::One;
 
 /// representing a Hamming number as a `BigUint`
@@ -95,6 +96,67 @@ impl<T: HammingNumber> Iterator for Hamming<T> {
     }
 }
 
+/// Computes the `n`th Hamming number (1-based) without generating the ones before it.
+///
+/// The number of Hamming numbers below `2^x` is roughly the volume of the tetrahedron
+/// `i + j·log2(3) + k·log2(5) <= x`, so we can estimate the logarithm of the `n`th one, count
+/// the exponent triples below a narrow band around the estimate and only keep the few triples
+/// that fall inside the band. Memory use is proportional to the width of the band rather than
+/// to `n`.
+pub fn nth_hamming(n: u64) -> BigUint {
+    assert!(n > 0, "Hamming numbers are counted from 1");
+
+    // Estimated log2 of the `n`th number.
+    let estimate = (6.0 * LB3 * LB5 * n as f64).cbrt() - 30f64.sqrt().log2();
+    let mut width = 1.0 / estimate.max(1.0);
+    loop {
+        // `count` numbers lie below the top of the band; the `n`th is some way down from there.
+        let (count, mut band) = band(estimate - width, estimate + width);
+        if let Some(from_top) = count.checked_sub(n) {
+            let from_top = from_top as usize;
+            if from_top < band.len() {
+                band.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+                let (_, i, j, k) = band[from_top];
+                return pow(BigUint::from(2u8), i)
+                    * pow(BigUint::from(3u8), j)
+                    * pow(BigUint::from(5u8), k);
+            }
+        }
+        // The estimate is less accurate for small `n`; try again with a wider band.
+        width *= 2.0;
+    }
+}
+
+const LB3: f64 = 1.584_962_500_721_156_3;
+const LB5: f64 = 2.321_928_094_887_362;
+
+/// Counts the Hamming numbers below `2^high` and collects the exponents (and logarithm) of the
+/// ones above `2^low`.
+fn band(low: f64, high: f64) -> (u64, Vec<(f64, usize, usize, usize)>) {
+    let mut count = 0;
+    let mut band = vec![];
+    let mut k = 0;
+    while k as f64 * LB5 <= high {
+        let mut j = 0;
+        while k as f64 * LB5 + j as f64 * LB3 <= high {
+            let log35 = k as f64 * LB5 + j as f64 * LB3;
+            // The largest power of two that stays below the top of the band.
+            let top = (high - log35).floor() as usize;
+            count += top as u64 + 1;
+            for i in (0..=top).rev() {
+                let log = log35 + i as f64;
+                if log <= low {
+                    break;
+                }
+                band.push((log, i, j, k));
+            }
+            j += 1;
+        }
+        k += 1;
+    }
+    (count, band)
+}
+
 #[test]
 fn create() {
     let mut h = Hamming::<BigUint>::new(5);
@@ -125,6 +187,12 @@ fn hamming_iter() {
     assert!(hamming.nth(19).unwrap().to_biguint() == 36.to_biguint());
 }
 
+#[test]
+fn hamming_iter_1691() {
+    let mut hamming = Hamming::<BigUint>::new(128);
+    assert_eq!(hamming.nth(1690), 2_125_764_000u64.to_biguint());
+}
+
 #[ignore]
 #[test]
 fn hamming_iter_1million() {
@@ -137,3 +205,22 @@ fn hamming_iter_1million() {
         millionth_hamming_number.parse::<BigUint>().ok()
     );
 }
+
+#[test]
+fn band_matches_iterator() {
+    let hamming = Hamming::<BigUint>::new(128);
+    for (n, h) in (1..).zip(hamming.take(5000)) {
+        assert_eq!(nth_hamming(n), h);
+    }
+}
+
+#[test]
+fn band_task_values() {
+    assert_eq!(nth_hamming(1691), 2_125_764_000u64.to_biguint().unwrap());
+    let millionth_hamming_number = "51931278044838873608958984375000000000000000000000000000000000\
+                                    0000000000000000000000";
+    assert_eq!(
+        nth_hamming(1_000_000),
+        millionth_hamming_number.parse::<BigUint>().unwrap()
+    );
+}
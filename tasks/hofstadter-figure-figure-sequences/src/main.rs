@@ -0,0 +1,141 @@
+//! The Hofstadter Figure-Figure sequences: R starts at 1 and goes up by the terms of S, which
+//! holds the positive integers that are not in R, so that the two together hold every positive
+//! integer once.
+//!
+//! Both are kept as far as they were needed so far, and grown together when a later term is
+//! asked for.
+
+struct FigureFigure {
+    r: Vec<usize>,
+    s: Vec<usize>,
+}
+
+impl FigureFigure {
+    fn new() -> FigureFigure {
+        FigureFigure {
+            r: vec![1],
+            s: vec![2],
+        }
+    }
+
+    /// R(n) = R(n - 1) + S(n - 1).
+    fn extend_r(&mut self) {
+        let n = self.r.len();
+        while self.s.len() < n {
+            self.extend_s();
+        }
+        self.r.push(self.r[n - 1] + self.s[n - 1]);
+    }
+
+    /// The next term of S is the next integer that is not in R.
+    ///
+    /// R has to be known up to that integer, unless the next term of R depends on the term of
+    /// S being found. Then that next term of R is more than the last one by at least the
+    /// candidate, so it is past it, and the candidate is in S.
+    fn extend_s(&mut self) {
+        let mut candidate = self.s.last().unwrap() + 1;
+        loop {
+            while *self.r.last().unwrap() < candidate && self.s.len() >= self.r.len() {
+                self.extend_r();
+            }
+            if self.r.binary_search(&candidate).is_err() {
+                break;
+            }
+            candidate += 1;
+        }
+        self.s.push(candidate);
+    }
+
+    /// The `n`-th term of R, from 1.
+    fn ffr(&mut self, n: usize) -> usize {
+        while self.r.len() < n {
+            self.extend_r();
+        }
+        self.r[n - 1]
+    }
+
+    /// The `n`-th term of S, from 1.
+    fn ffs(&mut self, n: usize) -> usize {
+        while self.s.len() < n {
+            self.extend_s();
+        }
+        self.s[n - 1]
+    }
+}
+
+impl Default for FigureFigure {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether the first 40 terms of R and the first 960 of S are the integers from 1 to 1000,
+/// each once.
+fn complementary(sequences: &mut FigureFigure) -> bool {
+    let mut seen = vec![false; 1001];
+    let mut terms: Vec<usize> = (1..=40).map(|n| sequences.ffr(n)).collect();
+    terms.extend((1..=960).map(|n| sequences.ffs(n)));
+    for term in terms {
+        if term > 1000 || seen[term] {
+            return false;
+        }
+        seen[term] = true;
+    }
+    seen[1..].iter().all(|&s| s)
+}
+
+fn main() {
+    let mut sequences = FigureFigure::new();
+    let first_ten: Vec<usize> = (1..=10).map(|n| sequences.ffr(n)).collect();
+    println!("The first ten terms of R: {:?}", first_ten);
+    println!(
+        "The first 40 terms of R and the first 960 of S are 1 to 1000 once each: {}",
+        complementary(&mut sequences)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_terms() {
+        let mut sequences = FigureFigure::new();
+        let r: Vec<usize> = (1..=10).map(|n| sequences.ffr(n)).collect();
+        assert_eq!(r, [1, 3, 7, 12, 18, 26, 35, 45, 56, 69]);
+        let s: Vec<usize> = (1..=10).map(|n| sequences.ffs(n)).collect();
+        assert_eq!(s, [2, 4, 5, 6, 8, 9, 10, 11, 13, 14]);
+    }
+
+    #[test]
+    fn first_thousand() {
+        assert!(complementary(&mut FigureFigure::new()));
+        // In whichever order the terms are asked for.
+        let mut sequences = FigureFigure::new();
+        sequences.ffs(960);
+        assert!(complementary(&mut sequences));
+    }
+
+    #[test]
+    fn matches_definition() {
+        // Straight from the definition: each integer up to a bound is either the next term of
+        // R, or the next term of S. While the term of S that the next one of R needs is still
+        // to come, that term of R is further off than the integer.
+        let (mut r, mut s): (Vec<usize>, Vec<usize>) = (vec![1], vec![]);
+        for i in 2..=5000 {
+            let next_r = s.get(r.len() - 1).map(|d| r.last().unwrap() + d);
+            if next_r == Some(i) {
+                r.push(i);
+            } else {
+                s.push(i);
+            }
+        }
+        let mut sequences = FigureFigure::new();
+        for (n, &term) in r.iter().enumerate() {
+            assert_eq!(sequences.ffr(n + 1), term);
+        }
+        for (n, &term) in s.iter().enumerate() {
+            assert_eq!(sequences.ffs(n + 1), term);
+        }
+    }
+}
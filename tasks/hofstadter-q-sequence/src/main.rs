@@ -43,20 +43,40 @@ impl Iterator for HofstadterQ {
     }
 }
 
+/// The number of terms, from the second up to the `upto`-th, that are less than the term
+/// before them.
+fn count_flips(upto: usize) -> usize {
+    let terms = [1, 1].iter().copied().chain(HofstadterQ::new()).take(upto);
+    let mut previous = 0;
+    terms
+        .filter(|&term| {
+            let flip = term < previous;
+            previous = term;
+            flip
+        })
+        .count()
+}
+
 fn main() {
-    // Set up the iterable.
-    let hof: HofstadterQ = HofstadterQ::new();
+    // The first two values are hardcoded, so the iterator starts at the third.
+    let first_ten: Vec<usize> = [1, 1]
+        .iter()
+        .copied()
+        .chain(HofstadterQ::new())
+        .take(10)
+        .collect();
+    println!("The first ten terms: {:?}", first_ten);
     // The number of terms we want from the iterator.
     let upto: usize = 1000;
-    // Create the iterator.
-    let mut it = hof.take(upto - 2);
-    // Print the base values.
-    println!("H(1) = 1");
-    println!("H(2) = 1");
-    // Print the rest of the sequence.
-    for i in 3..1 + upto {
-        println!("H({}) = {}", i, it.next().unwrap());
-    }
+    println!(
+        "H({}) = {}",
+        upto,
+        HofstadterQ::new().nth(upto - 3).unwrap()
+    );
+    println!(
+        "Terms less than the one before, up to H(100000): {}",
+        count_flips(100_000)
+    );
 }
 
 #[test]
@@ -83,3 +103,11 @@ fn test_thousandth() {
     }
     assert_eq!(expected, it.next().unwrap());
 }
+
+#[test]
+fn test_flips() {
+    // 1, 1, 2, 3, 3, 4, 5, 5, 6, 6, 6, 8, 8, 8, 10, 9: the first flip is at the 16th term.
+    assert_eq!(count_flips(15), 0);
+    assert_eq!(count_flips(16), 1);
+    assert_eq!(count_flips(100_000), 49798);
+}
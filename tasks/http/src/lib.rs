@@ -0,0 +1,270 @@
+//! An HTTP/1.1 client written by hand on top of `TcpStream`.
+//!
+//! The request formatting and response parsing work on any stream, so they can be reused on
+//! top of other transports, such as TLS.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Looks up a header, ignoring the case of its name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Splits an `http://` or `https://` URL, `scheme://host[:port]/path`, into its scheme, host,
+/// port and path. Without a port, it is the default one of the scheme.
+pub fn parse_url(url: &str) -> Option<(&str, &str, u16, &str)> {
+    let i = url.find("://")?;
+    let (scheme, rest) = (&url[..i], &url[i + 3..]);
+    let default_port = match scheme {
+        "http" => 80,
+        "https" => 443,
+        _ => return None,
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rfind(':') {
+        Some(i) => (&authority[..i], authority[i + 1..].parse().ok()?),
+        None => (authority, default_port),
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some((scheme, host, port, path))
+}
+
+pub fn format_request(host: &str, path: &str) -> String {
+    format!(
+        "GET {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Accept: */*\r\n\
+         Connection: close\r\n\
+         \r\n",
+        path, host
+    )
+}
+
+/// Reads a line terminated by CRLF (or a bare LF), without the terminator.
+fn read_line<R: BufRead>(reader: &mut R) -> io::Result<String> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "connection closed before the end of the headers",
+        ));
+    }
+    let trimmed = line.trim_end_matches(['\r', '\n']);
+    Ok(trimmed.to_string())
+}
+
+/// Parses a status line such as `HTTP/1.1 200 OK`.
+fn parse_status_line(line: &str) -> io::Result<(u16, String)> {
+    let mut parts = line.splitn(3, ' ');
+    let version = parts.next().unwrap_or("");
+    if !version.starts_with("HTTP/") {
+        return Err(invalid_data("malformed status line"));
+    }
+    let status = parts
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid_data("malformed status code"))?;
+    let reason = parts.next().unwrap_or("").to_string();
+    Ok((status, reason))
+}
+
+/// Decodes a body sent with `Transfer-Encoding: chunked`.
+///
+/// Every chunk is preceded by its size in hexadecimal (possibly followed by extensions, which
+/// are ignored); a chunk of size zero ends the body, followed by optional trailers.
+fn decode_chunked<R: BufRead>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut body = vec![];
+    loop {
+        let line = read_line(reader)?;
+        let size = line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid_data("bad chunk size"))?;
+        if size == 0 {
+            break;
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        if !read_line(reader)?.is_empty() {
+            return Err(invalid_data("missing CRLF after a chunk"));
+        }
+    }
+
+    // Skip the trailers.
+    while !read_line(reader)?.is_empty() {}
+    Ok(body)
+}
+
+/// Reads a response from `reader`, decoding the body according to its headers.
+pub fn parse_response<R: BufRead>(reader: &mut R) -> io::Result<Response> {
+    let (status, reason) = parse_status_line(&read_line(reader)?)?;
+
+    let mut headers = vec![];
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        let colon = line
+            .find(':')
+            .ok_or_else(|| invalid_data("malformed header"))?;
+        let (name, value) = line.split_at(colon);
+        headers.push((name.trim().to_string(), value[1..].trim().to_string()));
+    }
+
+    let mut response = Response {
+        status,
+        reason,
+        headers,
+        body: vec![],
+    };
+
+    let chunked = response
+        .header("Transfer-Encoding")
+        .is_some_and(|te| te.eq_ignore_ascii_case("chunked"));
+    response.body = if chunked {
+        decode_chunked(reader)?
+    } else if let Some(length) = response.header("Content-Length") {
+        let length = length
+            .parse()
+            .map_err(|_| invalid_data("bad Content-Length"))?;
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        body
+    } else {
+        // Without a length, the body lasts until the server closes the connection.
+        let mut body = vec![];
+        reader.read_to_end(&mut body)?;
+        body
+    };
+
+    Ok(response)
+}
+
+/// Performs a `GET` request for an `http://` URL.
+pub fn get(url: &str) -> io::Result<Response> {
+    let (host, port, path) = match parse_url(url) {
+        Some(("http", host, port, path)) => (host, port, path),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "expected an http:// URL",
+            ))
+        }
+    };
+
+    let mut stream = TcpStream::connect((host, port))?;
+    stream.write_all(format_request(host, path).as_bytes())?;
+    parse_response(&mut BufReader::new(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &[u8]) -> io::Result<Response> {
+        parse_response(&mut &raw[..])
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            parse_url("http://rosettacode.org"),
+            Some(("http", "rosettacode.org", 80, "/"))
+        );
+        assert_eq!(
+            parse_url("http://localhost:8080/wiki/HTTP?x=1"),
+            Some(("http", "localhost", 8080, "/wiki/HTTP?x=1"))
+        );
+        assert_eq!(
+            parse_url("https://rosettacode.org/"),
+            Some(("https", "rosettacode.org", 443, "/"))
+        );
+        assert_eq!(parse_url("ftp://rosettacode.org/"), None);
+        assert_eq!(parse_url("http://host:port/"), None);
+        assert_eq!(parse_url("http://:80/"), None);
+        assert_eq!(parse_url("rosettacode.org"), None);
+    }
+
+    #[test]
+    fn request() {
+        assert_eq!(
+            format_request("example.com", "/index.html"),
+            "GET /index.html HTTP/1.1\r\nHost: example.com\r\nAccept: */*\r\n\
+             Connection: close\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn content_length() {
+        let response = parse(
+            b"HTTP/1.1 200 OK\r\n\
+              Content-Type: text/plain\r\n\
+              content-length: 5\r\n\
+              \r\n\
+              helloTRAILING GARBAGE",
+        )
+        .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.reason, "OK");
+        assert_eq!(response.header("Content-Type"), Some("text/plain"));
+        assert_eq!(response.body, b"hello");
+    }
+
+    #[test]
+    fn chunked() {
+        let response = parse(
+            b"HTTP/1.1 200 OK\r\n\
+              Transfer-Encoding: chunked\r\n\
+              \r\n\
+              7\r\nRosetta\r\n\
+              5;name=value\r\n Code\r\n\
+              0\r\n\
+              Expires: never\r\n\
+              \r\n",
+        )
+        .unwrap();
+        assert_eq!(response.body, b"Rosetta Code");
+    }
+
+    #[test]
+    fn read_until_close() {
+        let response = parse(b"HTTP/1.0 404 Not Found\r\n\r\nnothing here").unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.reason, "Not Found");
+        assert_eq!(response.body, b"nothing here");
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(parse(b"SMTP 220 hello\r\n\r\n").is_err());
+        assert!(parse(b"HTTP/1.1 OK\r\n\r\n").is_err());
+        assert!(parse(b"HTTP/1.1 200 OK\r\nno colon\r\n\r\n").is_err());
+        assert!(parse(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nshort").is_err());
+        assert!(parse(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n").is_err());
+        assert!(parse(b"HTTP/1.1 200 OK\r\n").is_err());
+    }
+}
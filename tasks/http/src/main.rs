@@ -1,11 +1,18 @@
-extern crate reqwest;
-
-use std::io::prelude::*;
+//! Accepts the URL to fetch as an optional command line argument.
 
 fn main() {
-    let mut response = reqwest::get("http://rosettacode.org").unwrap();
-    let mut response_text = String::new();
-    response.read_to_string(&mut response_text).unwrap();
+    let url = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "http://rosettacode.org/".to_string());
 
-    println!("{}", response_text);
+    match http::get(&url) {
+        Ok(response) => {
+            eprintln!("{} {}", response.status, response.reason);
+            println!("{}", String::from_utf8_lossy(&response.body));
+        }
+        Err(e) => {
+            eprintln!("could not fetch {}: {}", url, e);
+            std::process::exit(1);
+        }
+    }
 }
@@ -103,12 +103,74 @@ fn build_encoding_table(tree: &HNode, table: &mut HashMap<char, String>, start_s
             build_encoding_table(&data.left, table, &format!("{}0", start_str)[..]);
             build_encoding_table(&data.right, table, &format!("{}1", start_str)[..]);
         }
+        // A tree that is a single leaf still needs one bit per character.
+        HItem::Leaf(ch) if start_str.is_empty() => {
+            table.insert(ch, "0".to_string());
+        }
         HItem::Leaf(ch) => {
             table.insert(ch, start_str.to_string());
         }
     };
 }
 
+/// A sequence of bits packed into bytes, most significant bit first. The last byte is padded
+/// with zeros, so the number of bits is kept alongside.
+struct Bits {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Bits {
+    fn new() -> Bits {
+        Bits {
+            bytes: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = bool> + 'a {
+        (0..self.len).map(move |i| self.bytes[i / 8] & (0x80 >> (i % 8)) != 0)
+    }
+}
+
+/// Replaces every character of `input` with its code from `table`.
+fn encode(input: &str, table: &HashMap<char, String>) -> Bits {
+    let mut bits = Bits::new();
+    for ch in input.chars() {
+        for bit in table[&ch].chars() {
+            bits.push(bit == '1');
+        }
+    }
+    bits
+}
+
+/// Walks down the tree for every bit, going left on a 0 and right on a 1, and starts again
+/// from the root after each leaf.
+fn decode(tree: &HNode, bits: &Bits) -> String {
+    let mut output = String::new();
+    let mut node = tree;
+    for bit in bits.iter() {
+        if let HItem::Tree(ref data) = node.item {
+            node = if bit { &data.right } else { &data.left };
+        }
+        if let HItem::Leaf(ch) = node.item {
+            output.push(ch);
+            node = tree;
+        }
+    }
+    output
+}
+
 /// Attempts to construct a tree, and test that the construction is successful
 ///
 /// ```
@@ -189,13 +251,61 @@ fn test_table_construction() {
     assert!((one == "01" && two == "00") || (one == "00" && two == "01"));
 }
 
+#[test]
+fn test_round_trip() {
+    for &input in &[
+        "this is an example for huffman encoding",
+        "4444221",
+        "aaaa",
+        "ab",
+        "épée dégainée",
+    ] {
+        let tree = huffman_tree(input);
+        let mut table = HashMap::<char, String>::new();
+        build_encoding_table(&tree, &mut table, "");
+        let bits = encode(input, &table);
+
+        let expected_len: usize = input.chars().map(|ch| table[&ch].len()).sum();
+        assert_eq!(bits.len, expected_len);
+        assert_eq!(bits.bytes.len(), bits.len.div_ceil(8));
+        assert_eq!(decode(&tree, &bits), input);
+    }
+}
+
+/// The codes of "4444221" are `1` for '4' and two bits for the others, so the seven characters
+/// fit in ten bits: `1111`, then `0x0x0y` where x and y are the codes of '2' and '1'.
+#[test]
+fn test_bit_packing() {
+    let input = "4444221";
+    let tree = huffman_tree(input);
+    let mut table = HashMap::<char, String>::new();
+    build_encoding_table(&tree, &mut table, "");
+    let bits = encode(input, &table);
+    assert_eq!(bits.len, 10);
+    assert_eq!(bits.bytes.len(), 2);
+    assert_eq!(bits.bytes[0] >> 4, 0b1111);
+    // The padding after the last bit is zero.
+    assert_eq!(bits.bytes[1] & 0b0011_1111, 0);
+}
+
 fn main() {
     let to_encode = "this is an example for huffman encoding";
     let tree = huffman_tree(to_encode);
     let mut table = HashMap::<char, String>::new();
     build_encoding_table(&tree, &mut table, "");
 
-    for (ch, encoding) in &table {
-        println!("{}: {}", *ch, encoding);
+    let mut codes: Vec<_> = table.iter().collect();
+    codes.sort();
+    for (ch, encoding) in codes {
+        println!("{:?}: {}", *ch, encoding);
     }
+
+    let bits = encode(to_encode, &table);
+    println!(
+        "{} characters encoded in {} bits ({} bytes)",
+        to_encode.chars().count(),
+        bits.len,
+        bits.bytes.len()
+    );
+    assert_eq!(decode(&tree, &bits), to_encode);
 }
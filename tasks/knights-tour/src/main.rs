@@ -1,6 +1,11 @@
+//! Finds a knight's tour by Warnsdorff's rule: the knight always moves to the square from which
+//! it has the fewest moves left. The rule alone finds a tour from most squares of most boards,
+//! but can lead into a dead end; the search then backtracks and tries the next best move.
+//!
+//! cargo run -p knights-tour -- [size] [x] [y]
+
 use std::fmt;
 
-const SIZE: usize = 8;
 const MOVES: [(i32, i32); 8] = [
     (2, 1),
     (1, 2),
@@ -12,7 +17,7 @@ const MOVES: [(i32, i32); 8] = [
     (2, -1),
 ];
 
-#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 struct Point {
     x: i32,
     y: i32,
@@ -28,102 +33,146 @@ impl Point {
 }
 
 struct Board {
-    field: [[i32; SIZE]; SIZE],
+    size: usize,
+    /// The step at which the knight visits each square, from 1, or 0 if it did not yet.
+    field: Vec<Vec<usize>>,
 }
 
 impl Board {
-    fn new() -> Board {
+    fn new(size: usize) -> Board {
         Board {
-            field: [[0; SIZE]; SIZE],
+            size,
+            field: vec![vec![0; size]; size],
         }
     }
 
+    fn contains(&self, p: Point) -> bool {
+        let size = self.size as i32;
+        0 <= p.x && p.x < size && 0 <= p.y && p.y < size
+    }
+
     fn available(&self, p: Point) -> bool {
-        let valid = 0 <= p.x && p.x < SIZE as i32 && 0 <= p.y && p.y < SIZE as i32;
+        self.contains(p) && self.field[p.x as usize][p.y as usize] == 0
+    }
 
-        valid && self.field[p.x as usize][p.y as usize] == 0
+    fn set(&mut self, p: Point, step: usize) {
+        self.field[p.x as usize][p.y as usize] = step;
     }
 
     /// calculate the number of possible moves
-    fn count_degree(&self, p: Point) -> i32 {
-        let mut count = 0;
-        for dir in &MOVES {
-            let next = p.mov(dir);
-            if self.available(next) {
-                count += 1;
-            }
-        }
-        count
+    fn count_degree(&self, p: Point) -> usize {
+        MOVES
+            .iter()
+            .filter(|dir| self.available(p.mov(dir)))
+            .count()
+    }
+
+    /// The squares the knight can move to from `p`, worst first, so that the best one gets
+    /// popped first. The best has the fewest moves onward, by Warnsdorff's rule.
+    fn candidates(&self, p: Point) -> Vec<Point> {
+        let mut candidates: Vec<(usize, Point)> = MOVES
+            .iter()
+            .map(|dir| p.mov(dir))
+            .filter(|&adj| self.available(adj))
+            .map(|adj| (self.count_degree(adj), adj))
+            .collect();
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+        candidates.into_iter().map(|(_, adj)| adj).collect()
     }
-}
 
-impl Default for Board {
-    fn default() -> Self {
-        Self::new()
+    /// Whether the knight, just arrived at `p`, has left a square next to it that it can no
+    /// longer reach and then leave, so that the tour cannot be finished.
+    fn strands_neighbour(&self, p: Point, squares_left: usize) -> bool {
+        squares_left > 1
+            && MOVES
+                .iter()
+                .map(|dir| p.mov(dir))
+                .any(|adj| self.available(adj) && self.count_degree(adj) == 0)
     }
 }
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = (self.size * self.size).to_string().len();
         for row in &self.field {
             for x in row.iter() {
-                write!(f, "{:3} ", x)?;
+                write!(f, "{:>width$} ", x, width = width)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
-fn knights_tour(x: i32, y: i32) -> Option<Board> {
-    let mut board = Board::new();
-    let mut p = Point { x: x, y: y };
-    let mut step = 1;
-
-    board.field[p.x as usize][p.y as usize] = step;
-    step += 1;
-
-    while step <= (SIZE * SIZE) as i32 {
-        // choose next square by Warnsdorf's rule
-        let mut candidates = vec![];
-
-        for dir in &MOVES {
-            let adj = p.mov(dir);
-            if board.available(adj) {
-                let degree = board.count_degree(adj);
-                candidates.push((degree, adj));
+/// A tour of a `size` by `size` board from `start`, if there is one, and how many times the
+/// search had to undo a move to find it.
+///
+/// The first path that the search tries is the one Warnsdorff's rule gives, and it seldom has
+/// to go back far, but when it does the search can take exponential time.
+fn knights_tour(size: usize, start: Point) -> (Option<Board>, usize) {
+    let mut board = Board::new(size);
+    // A knight changes colour at each move, so on a board with an odd number of squares, which
+    // has one more of the colour of its corners, the tour must start on that colour.
+    if !board.contains(start) || (size % 2 == 1 && (start.x + start.y) % 2 == 1) {
+        return (None, 0);
+    }
+    board.set(start, 1);
+    // The path so far, each square with the moves from it that are left to try. It is kept on
+    // a stack rather than by recursing, since it gets as long as the board has squares.
+    let mut path = vec![(start, board.candidates(start))];
+    let mut backtracks = 0;
+    while path.len() < size * size {
+        let step = path.len() + 1;
+        let next = match path.last_mut() {
+            Some((_, candidates)) => candidates.pop(),
+            None => return (None, backtracks),
+        };
+        match next {
+            Some(next) => {
+                board.set(next, step);
+                if board.strands_neighbour(next, size * size - step) {
+                    board.set(next, 0);
+                    backtracks += 1;
+                } else {
+                    path.push((next, board.candidates(next)));
+                }
+            }
+            None => {
+                let (dead_end, _) = path.pop().unwrap();
+                board.set(dead_end, 0);
+                backtracks += 1;
             }
         }
-
-        match candidates.iter().min() {
-            Some(&(_, adj)) => p = adj,
-            None => return None,
-        };
-
-        board.field[p.x as usize][p.y as usize] = step;
-        step += 1;
     }
-
-    Some(board)
+    (Some(board), backtracks)
 }
 
 fn main() {
-    let (x, y) = (3, 1);
+    let mut args = std::env::args().skip(1);
+    let mut arg = |default: usize| {
+        args.next()
+            .map_or(default, |a| a.parse().expect("a number"))
+    };
+    let size = arg(8);
+    let (x, y) = (arg(3) as i32, arg(1) as i32);
 
-    println!("Board size: {}", SIZE);
+    println!("Board size: {}", size);
     println!("Starting position: ({}, {})", x, y);
 
-    match knights_tour(x, y) {
-        Some(b) => print!("{}", b),
-        None => println!("Fail!"),
+    match knights_tour(size, Point { x, y }) {
+        (Some(b), backtracks) => {
+            print!("{}", b);
+            println!("Backtracked {} times.", backtracks);
+        }
+        (None, _) => println!("Fail!"),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{knights_tour, SIZE};
+    use super::{knights_tour, Board, Point, MOVES};
 
-    const ANSWER: [[i32; SIZE]; SIZE] = [
+    const ANSWER: [[usize; 8]; 8] = [
         [23, 20, 3, 32, 25, 10, 5, 8],
         [2, 33, 24, 21, 4, 7, 26, 11],
         [19, 22, 51, 34, 31, 28, 9, 6],
@@ -134,12 +183,81 @@ mod tests {
         [48, 45, 16, 43, 38, 59, 14, 63],
     ];
 
+    fn tour(size: usize, x: i32, y: i32) -> (Option<Board>, usize) {
+        knights_tour(size, Point { x, y })
+    }
+
+    /// Checks that every square is visited once, each step a knight's move from the last.
+    fn assert_tour(board: &Board, start: Point) {
+        let n = board.size * board.size;
+        let mut squares = vec![None; n + 1];
+        for (x, row) in board.field.iter().enumerate() {
+            for (y, &step) in row.iter().enumerate() {
+                assert!((1..=n).contains(&step), "step {}", step);
+                assert!(squares[step].is_none(), "step {} twice", step);
+                squares[step] = Some(Point {
+                    x: x as i32,
+                    y: y as i32,
+                });
+            }
+        }
+        assert_eq!(squares[1], Some(start));
+        for step in 2..=n {
+            let (from, to) = (squares[step - 1].unwrap(), squares[step].unwrap());
+            assert!(MOVES.iter().any(|dir| from.mov(dir) == to), "step {}", step);
+        }
+    }
+
     #[test]
     fn test() {
         let (x, y) = (3, 1);
-        match knights_tour(x, y) {
-            Some(b) => assert_eq!(b.field, ANSWER),
-            None => panic!(),
+        match tour(8, x, y) {
+            (Some(b), _) => assert_eq!(b.field, ANSWER),
+            (None, _) => panic!(),
+        }
+    }
+
+    #[test]
+    fn small_boards() {
+        let (board, _) = tour(1, 0, 0);
+        assert_eq!(board.unwrap().field, [[1]]);
+        // These have no tour, which the search finds out by trying every path.
+        for size in 2..=4 {
+            assert!(tour(size, 0, 0).0.is_none(), "{}", size);
+        }
+        assert!(tour(8, 8, 0).0.is_none());
+    }
+
+    #[test]
+    fn every_start() {
+        for size in 5..=8 {
+            for x in 0..size as i32 {
+                for y in 0..size as i32 {
+                    let start = Point { x, y };
+                    let possible = size % 2 == 0 || (x + y) % 2 == 0;
+                    match knights_tour(size, start) {
+                        (Some(board), _) => assert_tour(&board, start),
+                        (None, _) => assert!(!possible, "{} from {:?}", size, start),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_boards() {
+        for &(size, x, y) in &[
+            (10, 0, 0),
+            (23, 5, 5),
+            (28, 1, 0),
+            (50, 49, 0),
+            (60, 13, 31),
+        ] {
+            let start = Point { x, y };
+            match knights_tour(size, start) {
+                (Some(board), _) => assert_tour(&board, start),
+                (None, _) => panic!("{} from {:?}", size, start),
+            }
         }
     }
 }
@@ -0,0 +1,95 @@
+/// For each prefix of the pattern, the length of its longest proper prefix that is also a
+/// suffix of it. When a match fails after `k` characters, the search carries on as if
+/// `table[k - 1]` characters had matched, since those are known to be there.
+pub fn failure_table(pattern: &[u8]) -> Vec<usize> {
+    let mut table = vec![0; pattern.len()];
+    let mut k = 0;
+    for i in 1..pattern.len() {
+        while k > 0 && pattern[i] != pattern[k] {
+            k = table[k - 1];
+        }
+        if pattern[i] == pattern[k] {
+            k += 1;
+        }
+        table[i] = k;
+    }
+    table
+}
+
+/// The positions of all the occurrences of `needle` in `haystack`, including overlapping
+/// ones. Every character of the haystack is looked at once, so this takes O(n + m) time.
+pub fn search(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+    if needle.is_empty() {
+        return (0..=haystack.len()).collect();
+    }
+
+    let table = failure_table(needle);
+    let mut positions = vec![];
+    let mut k = 0;
+    for (i, &c) in haystack.iter().enumerate() {
+        while k > 0 && c != needle[k] {
+            k = table[k - 1];
+        }
+        if c == needle[k] {
+            k += 1;
+        }
+        if k == needle.len() {
+            positions.push(i + 1 - k);
+            k = table[k - 1];
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    fn naive_search(haystack: &[u8], needle: &[u8]) -> Vec<usize> {
+        (0..=haystack.len().saturating_sub(needle.len()))
+            .filter(|&i| haystack[i..].starts_with(needle))
+            .collect()
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(failure_table(b"ABCDABD"), [0, 0, 0, 0, 1, 2, 0]);
+        assert_eq!(failure_table(b"AABAAA"), [0, 1, 0, 1, 2, 2]);
+        assert_eq!(failure_table(b"alfalfa"), [0, 0, 0, 1, 2, 3, 4]);
+        assert!(failure_table(b"").is_empty());
+    }
+
+    #[test]
+    fn overlapping_and_edge_cases() {
+        assert_eq!(search(b"aaaaa", b"aa"), [0, 1, 2, 3]);
+        assert_eq!(search(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(search(b"abc", b"abcd"), [] as [usize; 0]);
+        assert_eq!(search(b"", b""), [0]);
+        assert_eq!(search(b"ab", b""), [0, 1, 2]);
+    }
+
+    #[test]
+    fn same_as_naive_search() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..2000 {
+            // A small alphabet, so that there are many partial matches.
+            let alphabet = if rng.gen() { &b"ab"[..] } else { &b"abc"[..] };
+            let random = |rng: &mut StdRng, len: usize| -> Vec<u8> {
+                (0..len).map(|_| *alphabet.choose(rng).unwrap()).collect()
+            };
+            let len = rng.gen_range(0, 60);
+            let haystack = random(&mut rng, len);
+            let len = rng.gen_range(1, 6);
+            let needle = random(&mut rng, len);
+            assert_eq!(
+                search(&haystack, &needle),
+                naive_search(&haystack, &needle),
+                "{:?} in {:?}",
+                needle,
+                haystack
+            );
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use knuth_morris_pratt_string_search::search;
+
+const TEXTS: [&str; 6] = [
+    "GCTAGCTCTACGAGTCTA",
+    "GGCTATAATGCGTA",
+    "there would have been a time for such a word",
+    "needle need noodle needle",
+    "DKnuthusesandprogramsanimaginarycomputertheMIXanditsassociatedmachinecodeandassemblylanguagestoillustratetheconceptsandalgorithmsastheyarepresentedinthebook",
+    "Nearby farms grew an acre of alfalfa on the dairy's behalf, with bales of all that alfalfa exchanged for milk.",
+];
+
+/// Which text each pattern is looked for in.
+const PATTERNS: [(&str, usize); 7] = [
+    ("TCTA", 0),
+    ("TAATAAA", 1),
+    ("word", 2),
+    ("needle", 3),
+    ("put", 4),
+    ("and", 4),
+    ("alfalfa", 5),
+];
+
+fn main() {
+    for &(pattern, text) in &PATTERNS {
+        println!(
+            "Found '{}' in text {} at positions {:?}",
+            pattern,
+            text + 1,
+            search(TEXTS[text].as_bytes(), pattern.as_bytes())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn task_examples() {
+        let expected: [&[usize]; 7] = [
+            &[6, 14],
+            &[],
+            &[40],
+            &[0, 19],
+            &[35],
+            &[10, 46, 73, 116],
+            &[29, 83],
+        ];
+        for (&(pattern, text), expected) in PATTERNS.iter().zip(&expected) {
+            assert_eq!(
+                search(TEXTS[text].as_bytes(), pattern.as_bytes()),
+                *expected,
+                "{}",
+                pattern
+            );
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Draws curves and plants from Lindenmayer systems, which grow a string from an axiom by
+//! rewriting every symbol of it at once by its rule, over and over. The string is then read as
+//! instructions for a turtle:
+//!
+//! - a drawing symbol moves forward, drawing a line,
+//! - `+` and `-` turn left and right by the angle of the system,
+//! - `[` and `]` save the turtle's position and heading, and go back to it,
+//!
+//! and every other symbol only takes part in the rewriting.
+//!
+//! cargo run -p l-systems -- [iterations]
+//!
+//! Without a number of iterations, each system gets one that suits it.
+
+use bitmap::Color;
+use std::collections::HashMap;
+use turtle_graphics::Turtle;
+use write_a_ppm_file::{Format, PPMWritable};
+
+struct LSystem {
+    axiom: &'static str,
+    /// What each symbol becomes; the symbols without a rule stay as they are.
+    rules: HashMap<char, &'static str>,
+    /// The symbols that draw a line.
+    draw: &'static str,
+    angle: f64,
+    /// The heading the turtle starts with.
+    heading: f64,
+}
+
+impl LSystem {
+    fn new(axiom: &'static str, rules: &[(char, &'static str)], angle: f64) -> Self {
+        LSystem {
+            axiom,
+            rules: rules.iter().copied().collect(),
+            draw: "F",
+            angle,
+            heading: 0.0,
+        }
+    }
+
+    /// The Heighway dragon, which folds a strip of paper in two, again and again.
+    fn dragon() -> Self {
+        Self::new("FX", &[('X', "X+YF+"), ('Y', "-FX-Y")], 90.0)
+    }
+
+    /// Sierpinski's arrowhead curve, which fills a triangle.
+    fn arrowhead() -> Self {
+        LSystem {
+            draw: "AB",
+            ..Self::new("A", &[('A', "B-A-B"), ('B', "A+B+A")], 60.0)
+        }
+    }
+
+    /// A plant, whose branches sprout more branches.
+    fn plant() -> Self {
+        LSystem {
+            heading: 65.0,
+            ..Self::new("X", &[('X', "F+[[X]-X]-F[-FX]+X"), ('F', "FF")], 25.0)
+        }
+    }
+
+    fn step(&self, string: &str) -> String {
+        let mut next = String::with_capacity(2 * string.len());
+        for symbol in string.chars() {
+            match self.rules.get(&symbol) {
+                Some(replacement) => next.push_str(replacement),
+                None => next.push(symbol),
+            }
+        }
+        next
+    }
+
+    /// The string after rewriting the axiom `iterations` times.
+    fn rewrite(&self, iterations: usize) -> String {
+        (0..iterations).fold(self.axiom.to_string(), |string, _| self.step(&string))
+    }
+
+    /// Walks a turtle through the instructions of `string`.
+    fn walk(&self, string: &str) -> Turtle {
+        let mut turtle = Turtle::new();
+        turtle.left(self.heading);
+        for symbol in string.chars() {
+            match symbol {
+                '+' => turtle.left(self.angle),
+                '-' => turtle.right(self.angle),
+                '[' => turtle.push(),
+                ']' => turtle.pop(),
+                _ if self.draw.contains(symbol) => turtle.forward(1.0),
+                _ => {}
+            }
+        }
+        turtle
+    }
+}
+
+fn main() {
+    let iterations: Option<usize> = std::env::args()
+        .nth(1)
+        .map(|n| n.parse().expect("a number of iterations"));
+    let green = Color {
+        red: 40,
+        green: 200,
+        blue: 60,
+    };
+    for &(name, ref system, default) in &[
+        ("dragon", LSystem::dragon(), 12),
+        ("arrowhead", LSystem::arrowhead(), 7),
+        ("plant", LSystem::plant(), 6),
+    ] {
+        let string = system.rewrite(iterations.unwrap_or(default));
+        let image = system.walk(&string).render(600, 600, 10, green);
+        let filename = format!("{}.ppm", name);
+        image.write_ppm(&filename, Format::Raw).unwrap();
+        println!("Wrote {}, from {} symbols", filename, string.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    fn count(string: &str, symbols: &str) -> usize {
+        string.chars().filter(|&c| symbols.contains(c)).count()
+    }
+
+    /// The length of the rewritten string, from how many of each symbol there are, without
+    /// building it.
+    fn predicted_length(system: &LSystem, iterations: usize) -> usize {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for symbol in system.axiom.chars() {
+            *counts.entry(symbol).or_default() += 1;
+        }
+        for _ in 0..iterations {
+            let mut next = HashMap::new();
+            for (&symbol, &n) in &counts {
+                match system.rules.get(&symbol) {
+                    Some(replacement) => {
+                        for produced in replacement.chars() {
+                            *next.entry(produced).or_default() += n;
+                        }
+                    }
+                    None => *next.entry(symbol).or_default() += n,
+                }
+            }
+            counts = next;
+        }
+        counts.values().sum()
+    }
+
+    #[test]
+    fn rewriting() {
+        let dragon = LSystem::dragon();
+        assert_eq!(dragon.rewrite(0), "FX");
+        assert_eq!(dragon.rewrite(1), "FX+YF+");
+        assert_eq!(dragon.rewrite(2), "FX+YF++-FX-YF+");
+        let arrowhead = LSystem::arrowhead();
+        assert_eq!(arrowhead.rewrite(2), "A+B+A-B-A-B-A+B+A");
+    }
+
+    #[test]
+    fn lengths() {
+        for n in 0..12 {
+            // The dragon doubles its segments, and the arrowhead triples them.
+            let dragon = LSystem::dragon();
+            let string = dragon.rewrite(n);
+            assert_eq!(count(&string, "F"), 1 << n);
+            assert_eq!(string.len(), predicted_length(&dragon, n));
+
+            let arrowhead = LSystem::arrowhead();
+            let string = arrowhead.rewrite(n);
+            assert_eq!(count(&string, "AB"), 3usize.pow(n as u32));
+            assert_eq!(string.len(), predicted_length(&arrowhead, n));
+        }
+        for n in 0..7 {
+            let plant = LSystem::plant();
+            let string = plant.rewrite(n);
+            assert_eq!(string.len(), predicted_length(&plant, n));
+            assert_eq!(count(&string, "["), count(&string, "]"));
+        }
+    }
+
+    #[test]
+    fn walks() {
+        // Each drawing symbol draws one line.
+        let dragon = LSystem::dragon();
+        let string = dragon.rewrite(8);
+        assert_eq!(dragon.walk(&string).lines().len(), 256);
+        // The arrowhead of an even order goes from one corner of its triangle to the next.
+        let arrowhead = LSystem::arrowhead();
+        let (x, y) = arrowhead.walk(&arrowhead.rewrite(4)).position();
+        assert!((x - 16.0).abs() < 1e-9 && y.abs() < 1e-9);
+    }
+
+    fn golden(name: &str, system: LSystem, iterations: usize) {
+        let white = Color {
+            red: 255,
+            green: 255,
+            blue: 255,
+        };
+        let image = system
+            .walk(&system.rewrite(iterations))
+            .render(64, 64, 2, white);
+        let mut ppm = vec![];
+        image.write_ppm_to(&mut ppm, Format::Raw).unwrap();
+        let expected = fs::read(format!("resources/{}.ppm", name)).unwrap();
+        assert!(ppm == expected, "{} differs from its golden image", name);
+    }
+
+    #[test]
+    fn golden_images() {
+        golden("dragon", LSystem::dragon(), 7);
+        golden("arrowhead", LSystem::arrowhead(), 4);
+        golden("plant", LSystem::plant(), 3);
+    }
+}
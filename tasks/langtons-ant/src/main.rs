@@ -1,12 +1,19 @@
-use std::num::Wrapping;
+//! Langton's ant walks a grid of white cells: on a white cell it turns right, on a black one
+//! it turns left, and either way it flips the colour of the cell and moves on. After some
+//! 10,000 steps of chaos it settles into building a "highway", the same 104 steps over and
+//! over, which takes it off any finite grid.
+//!
+//! The ant generalizes to more colours by a rule string like `LLRR`: on a cell of colour `i` it
+//! turns by the `i`-th letter, and the cell takes the next colour, wrapping around. Langton's
+//! own ant is `RL`.
+//!
+//! cargo run -p langtons-ant -- [rule] [file.pbm]
 
-struct Ant {
-    x: Wrapping<usize>,
-    y: Wrapping<usize>,
-    dir: Direction,
-}
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use term_grid::TermGrid;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Direction {
     North,
     East,
@@ -16,30 +23,6 @@ enum Direction {
 
 use Direction::*;
 
-impl Ant {
-    fn mv(&mut self, vec: &mut Vec<Vec<u8>>) {
-        let pointer = &mut vec[self.y.0][self.x.0];
-        // change direction
-        match *pointer {
-            0 => self.dir = self.dir.right(),
-            1 => self.dir = self.dir.left(),
-            _ => panic!("Unexpected colour in grid"),
-        }
-        // flip colour
-        // if it's 1 it's black
-        // if it's 0 it's white
-        *pointer ^= 1;
-
-        // move direction
-        match self.dir {
-            North => self.y -= Wrapping(1),
-            South => self.y += Wrapping(1),
-            East => self.x += Wrapping(1),
-            West => self.x -= Wrapping(1),
-        }
-    }
-}
-
 impl Direction {
     fn right(self) -> Direction {
         match self {
@@ -56,26 +39,207 @@ impl Direction {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Turn {
+    Left,
+    Right,
+}
+
+/// Parses a rule string of `L`s and `R`s, one for each colour.
+fn parse_rule(rule: &str) -> Result<Vec<Turn>, String> {
+    if rule.len() < 2 {
+        return Err(format!("{:?} needs at least two colours", rule));
+    }
+    rule.chars()
+        .map(|c| match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            _ => Err(format!("{:?} is not a turn in {:?}", c, rule)),
+        })
+        .collect()
+}
+
+struct Ant {
+    x: usize,
+    y: usize,
+    dir: Direction,
+}
+
+struct World {
+    rule: Vec<Turn>,
+    width: usize,
+    height: usize,
+    /// The colour of each cell, row by row.
+    cells: Vec<u8>,
+    ant: Ant,
+    steps: usize,
+}
+
+impl World {
+    /// A grid of the first colour, with the ant in the middle, facing north.
+    fn new(rule: Vec<Turn>, width: usize, height: usize) -> Self {
+        World {
+            rule,
+            width,
+            height,
+            cells: vec![0; width * height],
+            ant: Ant {
+                x: width / 2,
+                y: height / 2,
+                dir: North,
+            },
+            steps: 0,
+        }
+    }
+
+    /// Moves the ant on by one step, and returns the turn it made, or `None` if the step took
+    /// it off the grid and the walk is over.
+    fn step(&mut self) -> Option<Turn> {
+        let Ant { x, y, dir } = self.ant;
+        let cell = &mut self.cells[y * self.width + x];
+        let turn = self.rule[*cell as usize];
+        let dir = match turn {
+            Turn::Left => dir.left(),
+            Turn::Right => dir.right(),
+        };
+        *cell = ((*cell as usize + 1) % self.rule.len()) as u8;
+        self.steps += 1;
+
+        let (x, y) = match dir {
+            North => (Some(x), y.checked_sub(1)),
+            South => (Some(x), Some(y + 1)),
+            East => (Some(x + 1), Some(y)),
+            West => (x.checked_sub(1), Some(y)),
+        };
+        match (x, y) {
+            (Some(x), Some(y)) if x < self.width && y < self.height => {
+                self.ant = Ant { x, y, dir };
+                Some(turn)
+            }
+            _ => None,
+        }
+    }
+
+    /// Walks until the ant leaves the grid, and returns how many steps that took.
+    fn run(&mut self) -> usize {
+        while self.step().is_some() {}
+        self.steps
+    }
+
+    fn colour(&self, x: usize, y: usize) -> u8 {
+        self.cells[y * self.width + x]
+    }
+
+    /// The grid, with a space for the first colour and other characters for the rest.
+    fn grid(&self) -> TermGrid {
+        const SHADES: &[u8] = b" #o+*=%@&$";
+        let mut grid = TermGrid::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let shade = SHADES[self.colour(x, y) as usize % SHADES.len()];
+                grid.set(x, y, shade as char);
+            }
+        }
+        grid
+    }
+
+    /// Writes the grid as a plain PBM image, with the cells of the first colour white and the
+    /// others black.
+    fn write_pbm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "P1")?;
+        writeln!(out, "{} {}", self.width, self.height)?;
+        for row in self.cells.chunks(self.width) {
+            let bits: Vec<&str> = row
+                .iter()
+                .map(|&c| if c == 0 { "0" } else { "1" })
+                .collect();
+            writeln!(out, "{}", bits.join(" "))?;
+        }
+        out.flush()
+    }
+}
+
 fn main() {
-    // create a 100x100 grid using vectors
-    let mut grid: Vec<Vec<u8>> = vec![vec![0; 100]; 100];
-    let mut ant = Ant {
-        x: Wrapping(50),
-        y: Wrapping(50),
-        dir: Direction::North,
-    };
-
-    while ant.x < Wrapping(100) && ant.y < Wrapping(100) {
-        ant.mv(&mut grid);
+    let mut args = std::env::args().skip(1);
+    let rule = args.next().unwrap_or_else(|| "RL".to_string());
+    let filename = args
+        .next()
+        .unwrap_or_else(|| "langtons-ant.pbm".to_string());
+    let rule = parse_rule(&rule).unwrap_or_else(|e| panic!("{}", e));
+
+    let mut world = World::new(rule, 100, 100);
+    let steps = world.run();
+    print!("{}", world.grid());
+    println!("The ant left the grid after {} steps.", steps);
+
+    let file = File::create(&filename).expect("could not create the image");
+    world.write_pbm(BufWriter::new(file)).unwrap();
+    println!("Wrote {}", filename);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn langton() -> Vec<Turn> {
+        parse_rule("RL").unwrap()
+    }
+
+    #[test]
+    fn first_steps() {
+        let mut world = World::new(langton(), 10, 10);
+        assert_eq!(world.step(), Some(Turn::Right));
+        assert_eq!((world.ant.x, world.ant.y, world.ant.dir), (6, 5, East));
+        assert_eq!(world.colour(5, 5), 1);
+        // Around a square of four cells, back to the start, which is black now.
+        for _ in 0..3 {
+            world.step();
+        }
+        assert_eq!((world.ant.x, world.ant.y), (5, 5));
+        assert_eq!(world.step(), Some(Turn::Left));
+        assert_eq!(world.colour(5, 5), 0);
+    }
+
+    #[test]
+    fn leaves_the_task_grid() {
+        let mut world = World::new(langton(), 100, 100);
+        assert_eq!(world.run(), 11669);
     }
-    for each in &grid {
-        // construct string
-        // using iterator methods to quickly convert the vector
-        // to a string
-        let string = each
-            .iter()
-            .map(|&x| if x == 0 { " " } else { "#" })
-            .fold(String::new(), |x, y| x + y);
-        println!("{}", string);
+
+    #[test]
+    fn builds_a_highway() {
+        // The grid is big enough that the highway does not leave it in 20,000 steps. After the
+        // first 9977 steps, the turns repeat with a period of 104, and not any earlier.
+        let mut world = World::new(langton(), 1000, 1000);
+        let turns: Vec<Turn> = (0..20_000).map(|_| world.step().unwrap()).collect();
+        let periodic_after =
+            |steps: usize| (steps..turns.len() - 104).all(|i| turns[i] == turns[i + 104]);
+        assert!(periodic_after(9977));
+        assert!(!periodic_after(9976));
+    }
+
+    #[test]
+    fn more_colours() {
+        assert!(parse_rule("R").is_err());
+        assert!(parse_rule("RX").is_err());
+        // LLRR grows a symmetric pattern, which keeps it on the grid for long.
+        let mut world = World::new(parse_rule("LLRR").unwrap(), 100, 100);
+        for _ in 0..10_000 {
+            assert!(world.step().is_some());
+        }
+        assert!(world.cells.contains(&3));
+        assert_eq!(
+            world.grid().get(50, 50).map(|c| " #o+".contains(c)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn pbm() {
+        let mut world = World::new(langton(), 3, 2);
+        world.step();
+        let mut out = vec![];
+        world.write_pbm(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "P1\n3 2\n0 0 0\n0 1 0\n");
     }
 }
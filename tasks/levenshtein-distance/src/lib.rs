@@ -0,0 +1,35 @@
+/// The number of single-character insertions, deletions and substitutions it takes to turn
+/// `word1` into `word2`.
+pub fn levenshtein_distance(word1: &str, word2: &str) -> usize {
+    let word1: Vec<char> = word1.chars().collect();
+    let word2: Vec<char> = word2.chars().collect();
+
+    // The distances from the prefixes of `word1` to the current prefix of `word2`.
+    let mut row: Vec<usize> = (0..=word1.len()).collect();
+    for (j, &c2) in word2.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = j + 1;
+        for (i, &c1) in word1.iter().enumerate() {
+            let substitution = diagonal + if c1 == c2 { 0 } else { 1 };
+            diagonal = row[i + 1];
+            row[i + 1] = substitution.min(row[i] + 1).min(row[i + 1] + 1);
+        }
+    }
+    row[word1.len()]
+}
+
+#[test]
+fn test_levenshtein_distance() {
+    assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+}
+
+#[test]
+fn test_other_words() {
+    assert_eq!(levenshtein_distance("saturday", "sunday"), 3);
+    assert_eq!(levenshtein_distance("rosettacode", "raisethysword"), 8);
+    assert_eq!(levenshtein_distance("", "abc"), 3);
+    assert_eq!(levenshtein_distance("abc", ""), 3);
+    assert_eq!(levenshtein_distance("", ""), 0);
+    // Characters are compared, not bytes.
+    assert_eq!(levenshtein_distance("café", "cafe"), 1);
+}
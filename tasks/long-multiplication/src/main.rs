@@ -0,0 +1,194 @@
+//! Multiplication of arbitrarily large numbers stored as arrays of decimal digits, using the
+//! schoolbook method and Karatsuba's algorithm.
+
+/// Digits are stored least significant first, without leading zeros.
+type Digits = Vec<u8>;
+
+/// Below this many digits, Karatsuba falls back to the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 16;
+
+fn parse(s: &str) -> Digits {
+    let digits = s
+        .bytes()
+        .rev()
+        .map(|b| {
+            assert!(b.is_ascii_digit(), "not a decimal digit: {:?}", b as char);
+            b - b'0'
+        })
+        .collect();
+    trim(digits)
+}
+
+fn to_string(digits: &[u8]) -> String {
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().map(|&d| (b'0' + d) as char).collect()
+}
+
+fn trim(mut digits: Digits) -> Digits {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+/// Multiplies every digit of `a` by every digit of `b`, accumulating the partial products in
+/// their column before propagating the carries.
+fn long_multiply(a: &[u8], b: &[u8]) -> Digits {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut columns = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            columns[i + j] += u64::from(x) * u64::from(y);
+        }
+    }
+
+    let mut carry = 0;
+    let digits = columns
+        .into_iter()
+        .map(|column| {
+            let sum = column + carry;
+            carry = sum / 10;
+            (sum % 10) as u8
+        })
+        .collect();
+    trim(digits)
+}
+
+fn add(a: &[u8], b: &[u8]) -> Digits {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let s = a.get(i).cloned().unwrap_or(0) + b.get(i).cloned().unwrap_or(0) + carry;
+        sum.push(s % 10);
+        carry = s / 10;
+    }
+    sum.push(carry);
+    trim(sum)
+}
+
+/// Computes `a - b`, which must not be negative.
+fn sub(a: &[u8], b: &[u8]) -> Digits {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let y = b.get(i).cloned().unwrap_or(0) + borrow;
+        borrow = if x < y { 1 } else { 0 };
+        difference.push(x + 10 * borrow - y);
+    }
+    assert_eq!(borrow, 0, "attempt to subtract with overflow");
+    trim(difference)
+}
+
+/// Multiplies by `10^n`.
+fn shift(digits: &[u8], n: usize) -> Digits {
+    if digits.is_empty() {
+        return vec![];
+    }
+    let mut shifted = vec![0; n];
+    shifted.extend_from_slice(digits);
+    shifted
+}
+
+/// Splits both numbers in a low and a high half and gets away with three multiplications
+/// instead of four: `(a1·10^m + a0)(b1·10^m + b0) = z2·10^2m + z1·10^m + z0`, where
+/// `z1 = (a1 + a0)(b1 + b0) - z2 - z0`.
+fn karatsuba(a: &[u8], b: &[u8]) -> Digits {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return long_multiply(a, b);
+    }
+
+    let m = a.len().max(b.len()) / 2;
+    let split = |x: &[u8]| {
+        let (low, high) = x.split_at(m.min(x.len()));
+        (trim(low.to_vec()), high.to_vec())
+    };
+    let (a0, a1) = split(a);
+    let (b0, b1) = split(b);
+
+    let z0 = karatsuba(&a0, &b0);
+    let z2 = karatsuba(&a1, &b1);
+    let z1 = sub(&sub(&karatsuba(&add(&a0, &a1), &add(&b0, &b1)), &z2), &z0);
+
+    add(&add(&shift(&z2, 2 * m), &shift(&z1, m)), &z0)
+}
+
+fn main() {
+    let two_to_the_64 = parse("18446744073709551616");
+    println!(
+        "2^64 * 2^64 = {}",
+        to_string(&long_multiply(&two_to_the_64, &two_to_the_64))
+    );
+    println!(
+        "Karatsuba:    {}",
+        to_string(&karatsuba(&two_to_the_64, &two_to_the_64))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    const TWO_TO_THE_128: &str = "340282366920938463463374607431768211456";
+
+    fn digits_of(n: u128) -> Digits {
+        parse(&n.to_string())
+    }
+
+    #[test]
+    fn task() {
+        let two_to_the_64 = digits_of(1 << 64);
+        assert_eq!(
+            to_string(&long_multiply(&two_to_the_64, &two_to_the_64)),
+            TWO_TO_THE_128
+        );
+        assert_eq!(
+            to_string(&karatsuba(&two_to_the_64, &two_to_the_64)),
+            TWO_TO_THE_128
+        );
+    }
+
+    #[test]
+    fn zero() {
+        let n = digits_of(12345);
+        assert_eq!(to_string(&long_multiply(&n, &parse("0"))), "0");
+        assert_eq!(to_string(&karatsuba(&parse("000"), &n)), "0");
+    }
+
+    #[test]
+    fn random_against_u128() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            // Vary the sizes so that both the recursive and the base case of Karatsuba run.
+            let a = rng.gen::<u64>() >> rng.gen_range(0, 64);
+            let b = rng.gen::<u64>() >> rng.gen_range(0, 64);
+            let expected = (u128::from(a) * u128::from(b)).to_string();
+
+            let (a, b) = (digits_of(a.into()), digits_of(b.into()));
+            assert_eq!(to_string(&long_multiply(&a, &b)), expected);
+            assert_eq!(to_string(&karatsuba(&a, &b)), expected);
+        }
+    }
+
+    #[test]
+    fn karatsuba_matches_long_multiplication() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let mut random_number = || {
+                let len = rng.gen_range(1, 300);
+                let digits: String = (0..len)
+                    .map(|_| (b'0' + rng.gen_range(0, 10)) as char)
+                    .collect();
+                parse(&digits)
+            };
+            let (a, b) = (random_number(), random_number());
+            assert_eq!(karatsuba(&a, &b), long_multiply(&a, &b));
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 /// Returns the longest common subsequence of a and b.
 fn longest_common_subsequence(a: &str, b: &str) -> String {
     let a_chars: Vec<_> = a.chars().collect();
@@ -34,6 +36,50 @@ fn longest_common_subsequence(a: &str, b: &str) -> String {
     lcs.into_iter().rev().collect()
 }
 
+/// Returns a longest common subsequence of a and b with the Hunt–Szymanski algorithm, which
+/// only looks at the pairs of positions where the characters match. That is much faster than
+/// filling the whole table when there are few of them, as with lines of text.
+///
+/// `thresholds[k]` is the smallest position in b at which a common subsequence of length
+/// `k + 1` can end, given the characters of a seen so far, and `links[k]` is the last node of
+/// such a subsequence in a list of nodes that point to their predecessor.
+fn hunt_szymanski(a: &str, b: &str) -> String {
+    let b_chars: Vec<_> = b.chars().collect();
+    let mut matches: HashMap<char, Vec<usize>> = HashMap::new();
+    // The positions are listed from the last, so that a character of a is never matched twice
+    // in the same subsequence.
+    for (j, &c) in b_chars.iter().enumerate().rev() {
+        matches.entry(c).or_default().push(j);
+    }
+
+    let mut thresholds: Vec<usize> = Vec::new();
+    let mut links: Vec<usize> = Vec::new();
+    let mut nodes: Vec<(usize, Option<usize>)> = Vec::new();
+    for c in a.chars() {
+        for &j in matches.get(&c).into_iter().flatten() {
+            let k = thresholds.partition_point(|&t| t < j);
+            let node = nodes.len();
+            nodes.push((j, k.checked_sub(1).map(|k| links[k])));
+            if k == thresholds.len() {
+                thresholds.push(j);
+                links.push(node);
+            } else {
+                thresholds[k] = j;
+                links[k] = node;
+            }
+        }
+    }
+
+    let mut lcs = Vec::new();
+    let mut node = links.last().copied();
+    while let Some(n) = node {
+        let (j, previous) = nodes[n];
+        lcs.push(b_chars[j]);
+        node = previous;
+    }
+    lcs.into_iter().rev().collect()
+}
+
 fn main() {
     println!("{}", longest_common_subsequence("abc", "abcd"));
     println!("{}", longest_common_subsequence("ABCDGH", "AEDFHR"));
@@ -42,6 +88,7 @@ fn main() {
     println!("{}", longest_common_subsequence("abc", ""));
     println!("{}", longest_common_subsequence("abcdefg", "abc"));
     println!("{}", longest_common_subsequence("aaaa", "aaaaaa"));
+    println!("{}", hunt_szymanski("thisisatest", "testing123testing"));
 }
 
 #[test]
@@ -62,3 +109,52 @@ fn test_unicode() {
         "отмечена в"
     );
 }
+
+#[test]
+fn test_hunt_szymanski() {
+    assert_eq!(hunt_szymanski("abc", "abcdefg"), "abc");
+    assert_eq!(hunt_szymanski("ABCDGH", "AEDFHR"), "ADH");
+    assert_eq!(hunt_szymanski("AGGTAB", "GXTXAYB"), "GTAB");
+    assert_eq!(hunt_szymanski("", "abcdefg"), "");
+    assert_eq!(hunt_szymanski("abc", ""), "");
+    assert_eq!(hunt_szymanski("abc", "def"), "");
+    assert_eq!(hunt_szymanski("aaaa", "aaaaaa"), "aaaa");
+    assert_eq!(
+        hunt_szymanski("thisisatest", "testing123testing"),
+        "tsitest"
+    );
+    assert_eq!(
+        hunt_szymanski("самолетов была отмечена в Японском", "отмечена в"),
+        "отмечена в"
+    );
+}
+
+#[cfg(test)]
+fn is_subsequence(sub: &str, of: &str) -> bool {
+    let mut chars = of.chars();
+    sub.chars().all(|c| chars.any(|d| d == c))
+}
+
+#[test]
+fn test_random_strings() {
+    use rand::prelude::*;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let random_string = |rng: &mut StdRng| -> String {
+        let len = rng.gen_range(0, 30);
+        (0..len)
+            .map(|_| *b"abcd".choose(rng).unwrap() as char)
+            .collect()
+    };
+    for _ in 0..1000 {
+        let a = random_string(&mut rng);
+        let b = random_string(&mut rng);
+        let table = longest_common_subsequence(&a, &b);
+        let hunt = hunt_szymanski(&a, &b);
+        for lcs in &[&table, &hunt] {
+            assert!(is_subsequence(lcs, &a), "{} in {}", lcs, a);
+            assert!(is_subsequence(lcs, &b), "{} in {}", lcs, b);
+        }
+        assert_eq!(table.len(), hunt.len(), "{} and {}", a, b);
+    }
+}
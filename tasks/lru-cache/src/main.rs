@@ -0,0 +1,243 @@
+//! A cache of a bounded number of entries, which evicts the least recently used one to make room
+//! for a new one.
+//!
+//! The entries are kept in a vector, linked into a list from the most recently used to the
+//! least, and a hash map finds the slot of each key. Using an entry moves it to the front of the
+//! list, and the entry to evict is the one at the back, so that every operation takes O(1).
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    /// The entry used just after this one.
+    prev: Option<usize>,
+    /// The entry used just before this one.
+    next: Option<usize>,
+}
+
+pub struct LruCache<K, V> {
+    capacity: usize,
+    slots: HashMap<K, usize>,
+    entries: Vec<Entry<K, V>>,
+    /// The most recently used entry.
+    head: Option<usize>,
+    /// The least recently used entry.
+    tail: Option<usize>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// # Panics
+    ///
+    /// If `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the cache needs room for an entry");
+        LruCache {
+            capacity,
+            slots: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = (self.entries[slot].prev, self.entries[slot].next);
+        match prev {
+            None => self.head = next,
+            Some(prev) => self.entries[prev].next = next,
+        }
+        match next {
+            None => self.tail = prev,
+            Some(next) => self.entries[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, slot: usize) {
+        self.entries[slot].prev = None;
+        self.entries[slot].next = self.head;
+        match self.head {
+            None => self.tail = Some(slot),
+            Some(head) => self.entries[head].prev = Some(slot),
+        }
+        self.head = Some(slot);
+    }
+
+    /// The value of `key`, which becomes the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let slot = *self.slots.get(key)?;
+        self.unlink(slot);
+        self.push_front(slot);
+        Some(&self.entries[slot].value)
+    }
+
+    /// The value of `key`, without counting as a use.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.slots.get(key).map(|&slot| &self.entries[slot].value)
+    }
+
+    /// Sets the value of `key`, which becomes the most recently used. If the cache was full and
+    /// did not have the key, the least recently used entry is evicted and returned.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&slot) = self.slots.get(&key) {
+            self.entries[slot].value = value;
+            self.unlink(slot);
+            self.push_front(slot);
+            return None;
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+            prev: None,
+            next: None,
+        };
+        let (slot, evicted) = if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+            (self.entries.len() - 1, None)
+        } else {
+            // The new entry takes the slot of the one it evicts.
+            let slot = self.tail.unwrap();
+            self.unlink(slot);
+            let evicted = std::mem::replace(&mut self.entries[slot], entry);
+            self.slots.remove(&evicted.key);
+            (slot, Some((evicted.key, evicted.value)))
+        };
+        self.slots.insert(key, slot);
+        self.push_front(slot);
+        evicted
+    }
+
+    /// The keys, from the most recently used to the least.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        std::iter::successors(self.head, move |&slot| self.entries[slot].next)
+            .map(move |slot| &self.entries[slot].key)
+    }
+}
+
+fn main() {
+    let mut cache = LruCache::new(3);
+    for (key, value) in &[("a", 1), ("b", 2), ("c", 3)] {
+        cache.put(*key, *value);
+    }
+    println!("get a: {:?}", cache.get(&"a"));
+    println!("put d evicts {:?}", cache.put("d", 4));
+    println!("get b: {:?}", cache.get(&"b"));
+    println!(
+        "most to least recent: {:?}",
+        cache.keys().collect::<Vec<_>>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+
+    use rand::prelude::*;
+
+    fn keys<K: Hash + Eq + Copy, V>(cache: &LruCache<K, V>) -> Vec<K> {
+        cache.keys().copied().collect()
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.put(1, "one"), None);
+        assert_eq!(cache.put(2, "two"), None);
+        assert_eq!(cache.put(3, "three"), Some((1, "one")));
+        assert_eq!(cache.put(4, "four"), Some((2, "two")));
+        assert_eq!(keys(&cache), [4, 3]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&1), None);
+    }
+
+    #[test]
+    fn access_updates_order() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, 1);
+        cache.put(2, 2);
+        cache.put(3, 3);
+        assert_eq!(cache.get(&1), Some(&1));
+        assert_eq!(keys(&cache), [1, 3, 2]);
+        assert_eq!(cache.put(4, 4), Some((2, 2)));
+
+        // Putting a key that is there updates it, and counts as a use.
+        assert_eq!(cache.put(3, 30), None);
+        assert_eq!(keys(&cache), [3, 4, 1]);
+        assert_eq!(cache.put(5, 5), Some((1, 1)));
+        assert_eq!(cache.get(&3), Some(&30));
+
+        // Peeking does not.
+        assert_eq!(cache.peek(&4), Some(&4));
+        assert_eq!(cache.put(6, 6), Some((4, 4)));
+    }
+
+    #[test]
+    fn capacity_one() {
+        let mut cache = LruCache::new(1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.put('a', 1), None);
+        assert_eq!(cache.get(&'a'), Some(&1));
+        assert_eq!(cache.put('a', 2), None);
+        assert_eq!(cache.put('b', 3), Some(('a', 2)));
+        assert_eq!(cache.get(&'a'), None);
+        assert_eq!(cache.get(&'b'), Some(&3));
+        assert_eq!(keys(&cache), ['b']);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn capacity_zero() {
+        LruCache::<i32, i32>::new(0);
+    }
+
+    #[test]
+    fn matches_a_queue() {
+        // A slow model: the entries in a queue, from the most recently used.
+        let mut rng = StdRng::seed_from_u64(0);
+        for capacity in 1..8 {
+            let mut cache = LruCache::new(capacity);
+            let mut model: VecDeque<(u8, u32)> = VecDeque::new();
+            for _ in 0..2000 {
+                let key = rng.gen_range(0, 12);
+                let position = model.iter().position(|&(k, _)| k == key);
+                if rng.gen() {
+                    let entry = position.map(|i| model.remove(i).unwrap());
+                    if let Some(entry) = entry {
+                        model.push_front(entry);
+                    }
+                    assert_eq!(cache.get(&key), entry.map(|(_, v)| v).as_ref());
+                } else {
+                    let value = rng.gen();
+                    let evicted = match position {
+                        Some(i) => {
+                            model.remove(i);
+                            None
+                        }
+                        None if model.len() == capacity => model.pop_back(),
+                        None => None,
+                    };
+                    model.push_front((key, value));
+                    assert_eq!(cache.put(key, value), evicted);
+                }
+                assert!(cache.keys().eq(model.iter().map(|(k, _)| k)));
+            }
+        }
+    }
+}
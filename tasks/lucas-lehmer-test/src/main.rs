@@ -0,0 +1,99 @@
+use std::env;
+
+use num::{BigUint, One, Zero};
+
+/// Reduces `x` modulo the Mersenne number `m = 2^p - 1` without a division.
+///
+/// Since `2^p ≡ 1 (mod m)`, the bits above position `p` can simply be added to the low `p` bits.
+fn mod_mersenne(mut x: BigUint, p: usize, m: &BigUint) -> BigUint {
+    while x.bits() > p {
+        x = (&x >> p) + (&x & m);
+    }
+    if x == *m {
+        BigUint::zero()
+    } else {
+        x
+    }
+}
+
+/// Returns whether `2^p - 1` is prime, for a prime `p`.
+fn is_mersenne_prime(p: usize) -> bool {
+    if p == 2 {
+        // The test below only works for odd primes.
+        return true;
+    }
+
+    let m = (BigUint::one() << p) - BigUint::one();
+    let two = BigUint::from(2u32);
+    let mut s = BigUint::from(4u32);
+
+    for _ in 0..p - 2 {
+        // Adding m - 2 rather than subtracting 2 keeps the value non-negative.
+        s = mod_mersenne(&s * &s + &m - &two, p, &m);
+    }
+
+    s.is_zero()
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+/// Returns the exponents `p < bound` for which `2^p - 1` is prime.
+fn mersenne_exponents(bound: usize) -> Vec<usize> {
+    (2..bound)
+        .filter(|&p| is_prime(p) && is_mersenne_prime(p))
+        .collect()
+}
+
+fn main() {
+    let bound = env::args()
+        .nth(1)
+        .map(|arg| arg.parse().expect("the bound must be a positive integer"))
+        .unwrap_or(4500);
+
+    println!("Mersenne primes with exponents below {}:", bound);
+    for p in mersenne_exponents(bound) {
+        println!("M{}", p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN_EXPONENTS: [usize; 20] = [
+        2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607, 1279, 2203, 2281, 3217, 4253, 4423,
+    ];
+
+    #[test]
+    fn reduction() {
+        let p = 7;
+        let m = BigUint::from(127u32);
+        for x in 0..20_000u32 {
+            assert_eq!(
+                mod_mersenne(BigUint::from(x), p, &m),
+                BigUint::from(x % 127)
+            );
+        }
+    }
+
+    #[test]
+    fn small_exponents() {
+        let expected: Vec<_> = KNOWN_EXPONENTS
+            .iter()
+            .cloned()
+            .take_while(|&p| p < 1300)
+            .collect();
+        assert_eq!(mersenne_exponents(1300), expected);
+    }
+
+    #[test]
+    #[ignore = "takes minutes unoptimized; CI runs it with --release -- --ignored"]
+    fn exponents_up_to_4423() {
+        assert_eq!(mersenne_exponents(4424), KNOWN_EXPONENTS.to_vec());
+    }
+}
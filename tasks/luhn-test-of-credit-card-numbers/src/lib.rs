@@ -33,12 +33,63 @@ pub fn luhn_test(n: u64) -> bool {
     let sum = numbers.fold(0u64, |s, n| {
         s + match n {
             (n, LuhnState::Odd) => n,
-            (n, LuhnState::Even) => digits(n * 2).fold(0, |s, n| s + n),
+            (n, LuhnState::Even) => digits(n * 2).sum::<u64>(),
         }
     });
     sum % 10 == 0
 }
 
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// Something other than a digit or a space.
+    InvalidCharacter(char),
+    /// No digits at all.
+    Empty,
+}
+
+/// The digits of a number written out in a string, most significant first. Spaces are allowed
+/// between them, as they are often printed in groups of four.
+fn parse_digits(number: &str) -> Result<Vec<u32>, Error> {
+    let digits = number
+        .chars()
+        .filter(|&c| c != ' ')
+        .map(|c| c.to_digit(10).ok_or(Error::InvalidCharacter(c)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.is_empty() {
+        return Err(Error::Empty);
+    }
+    Ok(digits)
+}
+
+/// The Luhn sum of the digits, doubling every second one starting from the last if
+/// `double_last` is set, or from the one before it otherwise.
+fn luhn_sum(digits: &[u32], double_last: bool) -> u32 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i.is_multiple_of(2) == double_last {
+                let doubled = d * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                d
+            }
+        })
+        .sum()
+}
+
+/// Checks a number written as a string, such as `"4111 1111 1111 1111"`.
+pub fn luhn_test_str(number: &str) -> Result<bool, Error> {
+    Ok(luhn_sum(&parse_digits(number)?, false).is_multiple_of(10))
+}
+
+/// The digit to append to `number` so that it passes the test.
+pub fn check_digit(number: &str) -> Result<u32, Error> {
+    let sum = luhn_sum(&parse_digits(number)?, true);
+    Ok((10 - sum % 10) % 10)
+}
+
 #[test]
 fn test_inputs() {
     assert!(luhn_test(49927398716));
@@ -46,3 +97,56 @@ fn test_inputs() {
     assert!(!luhn_test(1234567812345678));
     assert!(luhn_test(1234567812345670));
 }
+
+#[test]
+fn test_strings() {
+    assert_eq!(luhn_test_str("49927398716"), Ok(true));
+    assert_eq!(luhn_test_str("49927398717"), Ok(false));
+    assert_eq!(luhn_test_str("1234 5678 1234 5678"), Ok(false));
+    assert_eq!(luhn_test_str("1234 5678 1234 5670"), Ok(true));
+    // Leading zeros count as digits, which a u64 cannot keep, but do not change the sum.
+    assert_eq!(luhn_test_str("0049927398716"), Ok(true));
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(
+        luhn_test_str("4992-7398-716"),
+        Err(Error::InvalidCharacter('-'))
+    );
+    assert_eq!(
+        luhn_test_str("49927398７16"),
+        Err(Error::InvalidCharacter('７'))
+    );
+    assert_eq!(luhn_test_str("   "), Err(Error::Empty));
+    assert_eq!(check_digit(""), Err(Error::Empty));
+}
+
+#[test]
+fn test_check_digit() {
+    assert_eq!(check_digit("4992739871"), Ok(6));
+    assert_eq!(check_digit("123456781234567"), Ok(0));
+    assert_eq!(check_digit("7992739871"), Ok(3));
+}
+
+#[test]
+fn test_generated_check_digits_validate() {
+    use rand::prelude::*;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let len = rng.gen_range(1, 20);
+        let number: String = (0..len)
+            .map(|_| std::char::from_digit(rng.gen_range(0, 10), 10).unwrap())
+            .collect();
+        let digit = check_digit(&number).unwrap();
+        let complete = format!("{}{}", number, digit);
+        assert_eq!(luhn_test_str(&complete), Ok(true));
+        // Any other last digit fails.
+        let wrong = format!("{}{}", number, (digit + 1) % 10);
+        assert_eq!(luhn_test_str(&wrong), Ok(false));
+        if let Ok(n) = complete.parse::<u64>() {
+            assert!(luhn_test(n));
+        }
+    }
+}
@@ -35,7 +35,11 @@ fn compress(original_str: &str) -> Vec<i32> {
 }
 
 /// Decompress using LZW
-fn decompress(compressed: &[i32]) -> String {
+fn decompress(compressed: &[i32]) -> Result<String, String> {
+    if compressed.is_empty() {
+        return Ok(String::new());
+    }
+
     let mut dict_size = 256;
     let mut dictionary = HashMap::new();
 
@@ -43,18 +47,23 @@ fn decompress(compressed: &[i32]) -> String {
         dictionary.insert(i, vec![i as u8]);
     }
 
-    let mut w = vec![compressed[0] as u8];
+    let mut w = match dictionary.get(&compressed[0]) {
+        Some(v) => v.clone(),
+        None => return Err(format!("invalid first code {}", compressed[0])),
+    };
     let compressed = &compressed[1..];
     let mut result = w.clone();
     for &k in compressed {
         let entry = match dictionary.get(&k) {
             Some(v) => v.clone(),
+            // The code that is about to be added, which happens when the encoder used it right
+            // after creating it: it then starts with the previous string.
             None if k == dict_size => {
                 let mut new = w.clone();
                 new.push(w[0]);
                 new
             }
-            None => panic!("Invalid compressed string"),
+            None => return Err(format!("invalid code {}", k)),
         };
 
         result.extend(entry.iter().cloned());
@@ -64,7 +73,7 @@ fn decompress(compressed: &[i32]) -> String {
         w = entry;
     }
 
-    String::from_utf8(result).unwrap()
+    String::from_utf8(result).map_err(|e| e.to_string())
 }
 
 fn main() {
@@ -77,7 +86,7 @@ fn main() {
     println!("Compressed: {:?}", compressed);
 
     // Show decompressed
-    let decompressed = decompress(&compressed[..]);
+    let decompressed = decompress(&compressed[..]).unwrap();
     println!("Decompressed: {}", &decompressed[..]);
 }
 
@@ -85,7 +94,7 @@ fn main() {
 fn test_coherence() {
     for s in (50000i32..50100).map(|n| n.to_string()) {
         let s = &s[..];
-        assert_eq!(&*decompress(&*compress(s)), s);
+        assert_eq!(&*decompress(&compress(s)).unwrap(), s);
     }
 }
 
@@ -97,3 +106,54 @@ fn test_example() {
         [84i32, 79, 66, 69, 79, 82, 78, 79, 84, 256, 258, 260, 265, 259, 261, 263,]
     );
 }
+
+#[test]
+fn test_example_decompress() {
+    let compressed = [
+        84i32, 79, 66, 69, 79, 82, 78, 79, 84, 256, 258, 260, 265, 259, 261, 263,
+    ];
+    assert_eq!(decompress(&compressed).unwrap(), "TOBEORNOTTOBEORTOBEORNOT");
+}
+
+#[test]
+fn test_code_not_yet_in_dictionary() {
+    // 258 ("aaa") is read before the decoder has added it.
+    assert_eq!(compress("aaaaaa"), [97, 256, 257]);
+    assert_eq!(decompress(&[97, 256, 257]).unwrap(), "aaaaaa");
+    assert_eq!(decompress(&compress("ABABABA")).unwrap(), "ABABABA");
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(decompress(&[]).unwrap(), "");
+    assert_eq!(
+        decompress(&[300]),
+        Err("invalid first code 300".to_string())
+    );
+    assert_eq!(decompress(&[97, 258]), Err("invalid code 258".to_string()));
+}
+
+#[test]
+fn test_random_ascii() {
+    use rand::prelude::*;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..500 {
+        let len = rng.gen_range(0, 500);
+        // A small alphabet makes for long repeated strings, and so for long codes.
+        let alphabet = if rng.gen() {
+            &b"AB"[..]
+        } else {
+            &b"ABCDEFGHIJ"[..]
+        };
+        let s: String = (0..len)
+            .map(|_| *alphabet.choose(&mut rng).unwrap() as char)
+            .collect();
+        assert_eq!(decompress(&compress(&s)).unwrap(), s);
+    }
+    for _ in 0..100 {
+        let len = rng.gen_range(0, 500);
+        let s: String = (0..len).map(|_| rng.gen_range(0u8, 128) as char).collect();
+        assert_eq!(decompress(&compress(&s)).unwrap(), s);
+    }
+}
@@ -0,0 +1,141 @@
+//! Generates text from a Markov chain whose states are the sequences of `order` consecutive
+//! words of a corpus.
+//!
+//! cargo run -p markov-chain-text-generator -- [corpus] [order] [words] [seed]
+
+use std::collections::HashMap;
+use std::fs;
+
+use rand::prelude::*;
+
+struct Chain<'a> {
+    order: usize,
+    /// The words that follow each key in the corpus, as often as they do.
+    table: HashMap<Vec<&'a str>, Vec<&'a str>>,
+    /// The keys, in the order they first appear, so that a seeded generator always starts
+    /// from the same one.
+    keys: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Chain<'a> {
+    /// Builds the table of a corpus split into `words`.
+    ///
+    /// The corpus is read as if it started again after its end, so that every key has a
+    /// successor and the generator never gets stuck.
+    fn new(words: &[&'a str], order: usize) -> Self {
+        assert!(order > 0, "the keys need at least one word");
+        assert!(
+            words.len() > order,
+            "the corpus needs more words than a key has"
+        );
+
+        let mut table: HashMap<Vec<&str>, Vec<&str>> = HashMap::new();
+        let mut keys = vec![];
+        let n = words.len();
+        for i in 0..n {
+            let key: Vec<&str> = (i..i + order).map(|j| words[j % n]).collect();
+            let next = words[(i + order) % n];
+            table
+                .entry(key)
+                .or_insert_with_key(|key| {
+                    keys.push(key.clone());
+                    vec![]
+                })
+                .push(next);
+        }
+        Chain { order, table, keys }
+    }
+
+    /// Generates `count` words, starting from a random key.
+    fn generate<R: Rng>(&self, count: usize, rng: &mut R) -> Vec<&'a str> {
+        let mut text = self.keys.choose(rng).unwrap().clone();
+        while text.len() < count {
+            let key = &text[text.len() - self.order..];
+            let next = self.table[key].choose(rng).unwrap();
+            text.push(next);
+        }
+        text.truncate(count);
+        text
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .unwrap_or_else(|| "resources/alice.txt".to_string());
+    let mut number = |default: u64| {
+        args.next()
+            .map_or(default, |n| n.parse().expect("expected a number"))
+    };
+    let order = number(3) as usize;
+    let count = number(200) as usize;
+    let seed = number(rand::random());
+
+    let corpus = fs::read_to_string(&path).expect("could not read the corpus");
+    let words: Vec<&str> = corpus.split_whitespace().collect();
+    let chain = Chain::new(&words, order);
+    let text = chain.generate(count, &mut StdRng::seed_from_u64(seed));
+    println!("{}", text.join(" "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: &str = include_str!("../resources/alice.txt");
+
+    fn words() -> Vec<&'static str> {
+        CORPUS.split_whitespace().collect()
+    }
+
+    #[test]
+    fn table() {
+        let words = "a b a c a b".split_whitespace().collect::<Vec<_>>();
+        let chain = Chain::new(&words, 1);
+        assert_eq!(chain.keys, [["a"], ["b"], ["c"]]);
+        assert_eq!(chain.table[&["a"][..]], ["b", "c", "b"]);
+        // The last "b" is followed by the first "a".
+        assert_eq!(chain.table[&["b"][..]], ["a", "a"]);
+        assert_eq!(chain.table[&["c"][..]], ["a"]);
+
+        let chain = Chain::new(&words, 2);
+        assert_eq!(chain.table[&["a", "b"][..]], ["a", "a"]);
+        assert_eq!(chain.table[&["b", "a"][..]], ["c", "b"]);
+        assert_eq!(chain.keys.len(), 4);
+    }
+
+    #[test]
+    fn deterministic() {
+        let words = words();
+        let chain = Chain::new(&words, 3);
+        let text = chain.generate(200, &mut StdRng::seed_from_u64(42));
+        assert_eq!(text.len(), 200);
+        assert_eq!(text, chain.generate(200, &mut StdRng::seed_from_u64(42)));
+        assert_ne!(text, chain.generate(200, &mut StdRng::seed_from_u64(43)));
+        assert_eq!(
+            chain.generate(12, &mut StdRng::seed_from_u64(42)).join(" "),
+            "There was nothing so very remarkable in that; nor did Alice think"
+        );
+    }
+
+    #[test]
+    fn n_grams_come_from_the_corpus() {
+        let words = words();
+        // The corpus again after its end, as the chain reads it.
+        let circular: Vec<&str> = words.iter().chain(&words).copied().collect();
+        for order in 1..5 {
+            let chain = Chain::new(&words, order);
+            for seed in 0..20 {
+                let text = chain.generate(100, &mut StdRng::seed_from_u64(seed));
+                for n_gram in text.windows(order + 1) {
+                    assert!(
+                        circular.windows(order + 1).any(|w| w == n_gram),
+                        "{:?}",
+                        n_gram
+                    );
+                }
+            }
+        }
+    }
+}
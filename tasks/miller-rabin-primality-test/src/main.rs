@@ -0,0 +1,126 @@
+/// Testing these witnesses is enough to make the Miller-Rabin test deterministic for every
+/// 64-bit integer (in fact, for every integer below 3.3 * 10^24).
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Computes `a * b mod m`. The intermediate product can take up to 128 bits.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// Computes `base ^ exp mod m` by repeated squaring.
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns whether `a` proves that the odd number `n` is composite, where `n - 1 = 2^s * d`
+/// with `d` odd.
+fn is_witness(a: u64, n: u64, s: u32, d: u64) -> bool {
+    let mut x = pow_mod(a, d, n);
+    if x == 1 || x == n - 1 {
+        return false;
+    }
+
+    // If n is prime, squaring repeatedly must reach n - 1 before reaching 1, because the only
+    // square roots of 1 modulo a prime are 1 and -1.
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    // Small primes (including all of the witnesses) are handled by trial division.
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    // Decompose n - 1 into 2^s * d.
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    !WITNESSES.iter().any(|&a| is_witness(a, n, s, d))
+}
+
+fn main() {
+    let primes: Vec<_> = (0..100).filter(|&n| is_prime(n)).collect();
+    println!("Primes below 100: {:?}", primes);
+
+    for &n in &[
+        3_825_123_056_546_413_051,
+        18_446_744_073_709_551_557,
+        18_446_744_073_709_551_615,
+    ] {
+        println!(
+            "{} is {}",
+            n,
+            if is_prime(n) { "prime" } else { "composite" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sieve(limit: usize) -> Vec<bool> {
+        let mut is_prime = vec![true; limit];
+        is_prime[0] = false;
+        is_prime[1] = false;
+        for i in 2..limit {
+            if is_prime[i] {
+                for multiple in (i * i..limit).step_by(i) {
+                    is_prime[multiple] = false;
+                }
+            }
+        }
+        is_prime
+    }
+
+    #[test]
+    fn matches_sieve() {
+        for (n, &expected) in sieve(100_000).iter().enumerate() {
+            assert_eq!(is_prime(n as u64), expected, "{}", n);
+        }
+    }
+
+    #[test]
+    fn strong_pseudoprimes() {
+        // This is a strong pseudoprime to all prime bases up to 23, so it takes one of the
+        // larger witnesses to prove that it is composite.
+        let n: u64 = 3_825_123_056_546_413_051;
+        let s = (n - 1).trailing_zeros();
+        let d = (n - 1) >> s;
+        assert!(!WITNESSES[..9].iter().any(|&a| is_witness(a, n, s, d)));
+        assert!(!is_prime(n));
+
+        // Carmichael numbers fool the Fermat test, but not Miller-Rabin.
+        for &n in &[561, 1105, 1729, 2465, 2821, 6601, 8911] {
+            assert!(!is_prime(n));
+        }
+    }
+
+    #[test]
+    fn large_primes() {
+        assert!(is_prime(18_446_744_073_709_551_557)); // the largest 64-bit prime
+        assert!(is_prime((1 << 61) - 1)); // a Mersenne prime
+        assert!(!is_prime(u64::MAX));
+        assert!(!is_prime(4_294_967_291 * 4_294_967_279));
+    }
+}
@@ -0,0 +1,57 @@
+/// Computes the modular multiplicative inverse of `a` modulo `m`, if it exists.
+///
+/// The result is always in the range `0..m`.
+pub fn mul_inv(a: i64, m: i64) -> Option<i64> {
+    let (gcd, x, _) = egcd(a.rem_euclid(m), m);
+    if gcd != 1 {
+        // No multiplicative inverse exists
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// The extended Euclidean algorithm.
+///
+/// Returns `(g, x, y)` such that `g` is the greatest common divisor of `a` and `b`, and
+/// `a * x + b * y = g`.
+#[allow(clippy::many_single_char_names)]
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        return (b, 0, 1);
+    }
+
+    let (g, y, x) = egcd(b % a, a);
+    (g, x - (b / a) * y, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{egcd, mul_inv};
+    use rand::prelude::*;
+
+    #[test]
+    fn test() {
+        assert_eq!(mul_inv(42, 2017), Some(1969));
+        assert_eq!(mul_inv(-3, 7), Some(2));
+        assert_eq!(mul_inv(4, 6), None);
+    }
+
+    #[test]
+    fn random_inverses() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let m = rng.gen_range(2, 1_000_000);
+            let a = rng.gen_range(-1_000_000, 1_000_000);
+            let (g, x, y) = egcd(a, m);
+            assert_eq!(a * x + m * y, g);
+
+            match mul_inv(a, m) {
+                Some(inv) => {
+                    assert!(inv >= 0 && inv < m);
+                    assert_eq!((a * inv).rem_euclid(m), 1);
+                }
+                None => assert_ne!(g.abs(), 1),
+            }
+        }
+    }
+}
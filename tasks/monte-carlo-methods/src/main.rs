@@ -1,5 +1,6 @@
 use rand::prelude::*;
 use std::f64::consts::PI;
+use std::thread;
 
 // `(f32, f32)` may be faster for some RNGs (including `rand::XorShiftRng`),
 // but less accurate.
@@ -7,10 +8,33 @@ fn is_inside_circle((x, y): (f64, f64)) -> bool {
     x * x + y * y <= 1.0
 }
 
-fn simulate<R: Rng>(rng: &mut R, samples: usize) -> f64 {
-    let count = (0..samples).filter(|_| is_inside_circle(rng.gen())).count();
+fn count_inside<R: Rng>(rng: &mut R, samples: usize) -> usize {
     // TODO use cargo bench
     // to see if a branchless method might be faster
+    (0..samples).filter(|_| is_inside_circle(rng.gen())).count()
+}
+
+fn simulate<R: Rng>(rng: &mut R, samples: usize) -> f64 {
+    (count_inside(rng, samples) as f64) / (samples as f64)
+}
+
+/// Splits the samples between `threads` threads.
+///
+/// Every thread gets its own RNG, seeded from a master RNG, so the result only depends on
+/// `seed` and the number of threads (not on how the threads are scheduled).
+fn simulate_parallel(seed: u64, samples: usize, threads: usize) -> f64 {
+    let mut master = StdRng::seed_from_u64(seed);
+
+    let handles: Vec<_> = (0..threads)
+        .map(|i| {
+            // Spread the remainder over the first few threads.
+            let share = samples / threads + if i < samples % threads { 1 } else { 0 };
+            let mut rng = StdRng::seed_from_u64(master.gen());
+            thread::spawn(move || count_inside(&mut rng, share))
+        })
+        .collect();
+
+    let count: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
     (count as f64) / (samples as f64)
 }
 
@@ -24,4 +48,63 @@ fn main() {
         let deviation = 100.0 * (1.0 - estimate / PI).abs();
         println!("{:9}: {:<11} dev: {:.5}%", samples, estimate, deviation);
     }
+
+    let threads = thread::available_parallelism().map_or(4, |n| n.get());
+    println!("Using {} threads:", threads);
+    for samples in (3..10).map(|e| 10_usize.pow(e)) {
+        let estimate = 4.0 * simulate_parallel(rng.gen(), samples, threads);
+        let deviation = 100.0 * (1.0 - estimate / PI).abs();
+        println!("{:10}: {:<11} dev: {:.5}%", samples, estimate, deviation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The standard deviation of an estimate of π from `samples` points.
+    ///
+    /// Each point lands inside the circle with probability p = π/4, so the estimate 4·(hits/n)
+    /// has a standard deviation of 4·√(p(1 - p)/n).
+    fn standard_deviation(samples: usize) -> f64 {
+        let p = PI / 4.0;
+        4.0 * (p * (1.0 - p) / samples as f64).sqrt()
+    }
+
+    #[test]
+    fn converges() {
+        for (seed, samples) in (3..7).map(|e| 10_usize.pow(e)).enumerate() {
+            let mut rng = StdRng::seed_from_u64(seed as u64);
+            let estimate = 4.0 * simulate(&mut rng, samples);
+
+            // Five standard deviations: a spurious failure is less likely than one in a million.
+            assert!((estimate - PI).abs() < 5.0 * standard_deviation(samples));
+        }
+    }
+
+    #[test]
+    fn error_shrinks_with_more_samples() {
+        // Averaged over many runs, a hundred times more samples should give roughly a tenth of
+        // the error.
+        let mean_error = |samples| {
+            let mut rng = StdRng::seed_from_u64(samples as u64);
+            (0..100)
+                .map(|_| (4.0 * simulate(&mut rng, samples) - PI).abs())
+                .sum::<f64>()
+                / 100.0
+        };
+
+        let ratio = mean_error(100) / mean_error(10_000);
+        assert!(ratio > 5.0 && ratio < 20.0, "ratio was {}", ratio);
+    }
+
+    #[test]
+    fn parallel() {
+        let samples = 1_000_003;
+        let estimate = 4.0 * simulate_parallel(42, samples, 4);
+        assert!((estimate - PI).abs() < 5.0 * standard_deviation(samples));
+
+        // The same seed gives the same streams, no matter how the threads are scheduled.
+        assert_eq!(estimate, 4.0 * simulate_parallel(42, samples, 4));
+    }
 }
@@ -0,0 +1,311 @@
+//! Translates text to Morse code, and sends it to the console, the terminal bell or a WAV
+//! file.
+
+pub mod wav;
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// The International Morse Code, from the ITU-R M.1677 recommendation.
+pub const CODE: &[(char, &str)] = &[
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    (':', "---..."),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('-', "-....-"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('"', ".-..-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('@', ".--.-."),
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Element {
+    Dot,
+    Dash,
+    /// The silence between two letters of a word.
+    LetterGap,
+    /// The silence between two words.
+    WordGap,
+}
+
+pub fn code(c: char) -> Option<&'static str> {
+    let c = c.to_ascii_uppercase();
+    CODE.iter().find(|&&(k, _)| k == c).map(|&(_, code)| code)
+}
+
+/// Translates text, leaving out the characters that have no code.
+pub fn encode(text: &str) -> Vec<Element> {
+    let mut elements = vec![];
+    for word in text.split_whitespace() {
+        let codes: Vec<_> = word.chars().filter_map(code).collect();
+        if codes.is_empty() {
+            continue;
+        }
+        if !elements.is_empty() {
+            elements.push(Element::WordGap);
+        }
+        for (i, code) in codes.iter().enumerate() {
+            if i > 0 {
+                elements.push(Element::LetterGap);
+            }
+            elements.extend(code.chars().map(|c| match c {
+                '.' => Element::Dot,
+                _ => Element::Dash,
+            }));
+        }
+    }
+    elements
+}
+
+/// Writes the code with a space between letters and ` / ` between words.
+pub fn to_text(elements: &[Element]) -> String {
+    elements
+        .iter()
+        .map(|e| match e {
+            Element::Dot => ".",
+            Element::Dash => "-",
+            Element::LetterGap => " ",
+            Element::WordGap => " / ",
+        })
+        .collect()
+}
+
+/// Reads back what `to_text` writes, or returns `None` for unknown codes.
+pub fn decode(morse: &str) -> Option<String> {
+    let words: Option<Vec<String>> = morse
+        .split(" / ")
+        .filter(|word| !word.trim().is_empty())
+        .map(|word| {
+            word.split_whitespace()
+                .map(|letter| CODE.iter().find(|&&(_, c)| c == letter).map(|&(k, _)| k))
+                .collect()
+        })
+        .collect();
+    Some(words?.join(" "))
+}
+
+/// How long the signal is on or off, in units of the length of a dot.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Span {
+    pub on: bool,
+    pub units: u32,
+}
+
+/// The standard timing: a dash lasts three dots, and the silence is one dot between the
+/// elements of a letter, three between letters and seven between words.
+pub fn timing(elements: &[Element]) -> Vec<Span> {
+    let mut spans: Vec<Span> = vec![];
+    for element in elements {
+        let span = match element {
+            Element::Dot => Span { on: true, units: 1 },
+            Element::Dash => Span { on: true, units: 3 },
+            Element::LetterGap => Span {
+                on: false,
+                units: 3,
+            },
+            Element::WordGap => Span {
+                on: false,
+                units: 7,
+            },
+        };
+        if span.on && spans.last().is_some_and(|s| s.on) {
+            spans.push(Span {
+                on: false,
+                units: 1,
+            });
+        }
+        spans.push(span);
+    }
+    spans
+}
+
+/// Somewhere to send the code.
+pub trait Backend {
+    fn send(&mut self, elements: &[Element]) -> io::Result<()>;
+}
+
+/// Prints the code as dots and dashes.
+pub struct Console<W: Write>(pub W);
+
+impl<W: Write> Backend for Console<W> {
+    fn send(&mut self, elements: &[Element]) -> io::Result<()> {
+        writeln!(self.0, "{}", to_text(elements))
+    }
+}
+
+/// Rings the terminal bell at the start of each dot and dash, and waits for as long as the
+/// signal would last. A bell cannot be held, so the rhythm is all there is to hear.
+pub struct Bell<W: Write> {
+    pub out: W,
+    pub unit: Duration,
+}
+
+impl<W: Write> Backend for Bell<W> {
+    fn send(&mut self, elements: &[Element]) -> io::Result<()> {
+        for span in timing(elements) {
+            if span.on {
+                self.out.write_all(b"\x07")?;
+                self.out.flush()?;
+            }
+            thread::sleep(self.unit * span.units);
+        }
+        Ok(())
+    }
+}
+
+/// Writes the code as a WAV file, with a sine wave while the signal is on.
+pub struct Wav<W: Write> {
+    pub out: W,
+    pub sample_rate: u32,
+    pub frequency: f64,
+    pub unit: Duration,
+}
+
+impl<W: Write> Backend for Wav<W> {
+    fn send(&mut self, elements: &[Element]) -> io::Result<()> {
+        let unit = (self.unit.as_secs_f64() * f64::from(self.sample_rate)).round() as usize;
+        let mut samples = vec![];
+        for span in timing(elements) {
+            let len = unit * span.units as usize;
+            if span.on {
+                samples.extend(wav::tone(self.sample_rate, self.frequency, len));
+            } else {
+                samples.extend(std::iter::repeat_n(0, len));
+            }
+        }
+        wav::write_wav(&mut self.out, self.sample_rate, &samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn table_is_reversible() {
+        let letters: HashSet<_> = CODE.iter().map(|&(k, _)| k).collect();
+        let codes: HashSet<_> = CODE.iter().map(|&(_, c)| c).collect();
+        assert_eq!(letters.len(), CODE.len());
+        assert_eq!(codes.len(), CODE.len());
+
+        for &(k, c) in CODE {
+            assert!(c.chars().all(|c| c == '.' || c == '-'));
+            assert_eq!(code(k), Some(c));
+            assert_eq!(
+                decode(&to_text(&encode(&k.to_string()))),
+                Some(k.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn sos() {
+        assert_eq!(to_text(&encode("SOS")), "... --- ...");
+        assert_eq!(to_text(&encode("sos  sos")), "... --- ... / ... --- ...");
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG 0123456789 (A+B=C?) \"@/.\"";
+        assert_eq!(decode(&to_text(&encode(text))).unwrap(), text);
+        assert_eq!(
+            decode(&to_text(&encode("hello, world"))).unwrap(),
+            "HELLO, WORLD"
+        );
+        // Characters without a code are dropped, along with the words they leave empty.
+        assert_eq!(decode(&to_text(&encode("a # b ëc"))).unwrap(), "A B C");
+        assert_eq!(decode("... ---- ..."), None);
+    }
+
+    #[test]
+    fn spans() {
+        let span = |on, units| Span { on, units };
+        assert_eq!(
+            timing(&encode("AE T")),
+            [
+                span(true, 1),
+                span(false, 1),
+                span(true, 3),
+                span(false, 3),
+                span(true, 1),
+                span(false, 7),
+                span(true, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn backends() {
+        let mut console = Console(vec![]);
+        console.send(&encode("SOS")).unwrap();
+        assert_eq!(console.0, b"... --- ...\n");
+
+        let mut bell = Bell {
+            out: vec![],
+            unit: Duration::from_millis(0),
+        };
+        bell.send(&encode("SOS")).unwrap();
+        assert_eq!(bell.out, [7; 9]);
+
+        // "E" is a single dot, and "E E" adds a word gap and another dot: 9 units.
+        let mut wav = Wav {
+            out: vec![],
+            sample_rate: 1000,
+            frequency: 250.0,
+            unit: Duration::from_millis(10),
+        };
+        wav.send(&encode("E E")).unwrap();
+        assert_eq!(wav.out.len(), wav::HEADER_LEN + 9 * 10 * 2);
+        let samples: Vec<i16> = wav.out[wav::HEADER_LEN..]
+            .chunks(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert!(samples[..10].iter().any(|&s| s != 0));
+        assert!(samples[10..80].iter().all(|&s| s == 0));
+        assert!(samples[80..].iter().any(|&s| s != 0));
+    }
+}
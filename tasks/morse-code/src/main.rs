@@ -0,0 +1,41 @@
+//! cargo run -p morse-code -- [--bell | --wav <file>] [text]
+
+use morse_code::{encode, Backend, Bell, Console, Wav};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::time::Duration;
+
+/// The length of a dot, which is 20 words per minute.
+const UNIT: Duration = Duration::from_millis(60);
+
+fn main() -> io::Result<()> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mut backend: Box<dyn Backend> = match args.first().map(String::as_str) {
+        Some("--bell") => {
+            args.remove(0);
+            Box::new(Bell {
+                out: io::stdout(),
+                unit: UNIT,
+            })
+        }
+        Some("--wav") if args.len() > 1 => {
+            let file = File::create(&args[1])?;
+            args.drain(..2);
+            Box::new(Wav {
+                out: BufWriter::new(file),
+                sample_rate: 44_100,
+                frequency: 700.0,
+                unit: UNIT,
+            })
+        }
+        _ => Box::new(Console(io::stdout())),
+    };
+
+    let text = if args.is_empty() {
+        "Hello, World! SOS".to_string()
+    } else {
+        args.join(" ")
+    };
+    backend.send(&encode(&text))
+}
@@ -0,0 +1,69 @@
+//! Writes sounds as WAV files: a RIFF header followed by 16 bit mono PCM samples.
+
+use std::io::{self, Write};
+
+/// The length of the RIFF, `fmt ` and `data` headers, before the first sample.
+pub const HEADER_LEN: usize = 44;
+
+pub fn write_wav<W: Write>(mut out: W, sample_rate: u32, samples: &[i16]) -> io::Result<()> {
+    const CHANNELS: u16 = 1;
+    const BITS_PER_SAMPLE: u16 = 16;
+    let block_align = CHANNELS * BITS_PER_SAMPLE / 8;
+    let data_len = (samples.len() * usize::from(block_align)) as u32;
+
+    out.write_all(b"RIFF")?;
+    out.write_all(&(HEADER_LEN as u32 - 8 + data_len).to_le_bytes())?;
+    out.write_all(b"WAVE")?;
+
+    out.write_all(b"fmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    // Uncompressed PCM.
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&CHANNELS.to_le_bytes())?;
+    out.write_all(&sample_rate.to_le_bytes())?;
+    out.write_all(&(sample_rate * u32::from(block_align)).to_le_bytes())?;
+    out.write_all(&block_align.to_le_bytes())?;
+    out.write_all(&BITS_PER_SAMPLE.to_le_bytes())?;
+
+    out.write_all(b"data")?;
+    out.write_all(&data_len.to_le_bytes())?;
+    for sample in samples {
+        out.write_all(&sample.to_le_bytes())?;
+    }
+    out.flush()
+}
+
+/// A sine wave at half of the full volume.
+pub fn tone(sample_rate: u32, frequency: f64, samples: usize) -> impl Iterator<Item = i16> {
+    let step = 2.0 * std::f64::consts::PI * frequency / f64::from(sample_rate);
+    (0..samples).map(move |i| ((i as f64 * step).sin() * f64::from(i16::MAX) / 2.0) as i16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header() {
+        let mut out = vec![];
+        write_wav(&mut out, 8000, &[1, -1, 256]).unwrap();
+        assert_eq!(out.len(), HEADER_LEN + 6);
+        assert_eq!(&out[..4], b"RIFF");
+        assert_eq!(out[4..8], 42u32.to_le_bytes());
+        assert_eq!(&out[8..16], b"WAVEfmt ");
+        assert_eq!(out[24..28], 8000u32.to_le_bytes());
+        assert_eq!(out[28..32], 16000u32.to_le_bytes());
+        assert_eq!(&out[36..40], b"data");
+        assert_eq!(out[40..44], 6u32.to_le_bytes());
+        assert_eq!(out[44..], [1, 0, 0xff, 0xff, 0, 1]);
+    }
+
+    #[test]
+    fn sine() {
+        let samples: Vec<i16> = tone(8000, 2000.0, 5).collect();
+        assert_eq!(samples[0], 0);
+        assert_eq!(samples[1], i16::MAX / 2);
+        assert!(samples[2].abs() < 2);
+        assert_eq!(samples[3], -(i16::MAX / 2));
+    }
+}
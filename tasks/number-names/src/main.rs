@@ -0,0 +1,349 @@
+//! Spells out integers in English, like "one hundred twenty-three", and reads them back.
+//!
+//! Large numbers use the short scale, where a billion is a thousand millions. The names go up
+//! to undecillion (10^36), which is enough for every `i128`.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+const SMALL: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// The multiples of ten, from twenty.
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The names of the powers of a thousand, from a thousand itself.
+const SCALES: [&str; 12] = [
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+];
+
+/// Spells out a number below a thousand, which is not 0.
+fn spell_group(n: u128, words: &mut Vec<String>) {
+    let (hundreds, rest) = (n / 100, n % 100);
+    if hundreds > 0 {
+        words.push(SMALL[hundreds as usize].to_string());
+        words.push("hundred".to_string());
+    }
+    match rest {
+        0 => {}
+        1..=19 => words.push(SMALL[rest as usize].to_string()),
+        _ => {
+            let tens = TENS[rest as usize / 10 - 2];
+            match rest % 10 {
+                0 => words.push(tens.to_string()),
+                units => words.push(format!("{}-{}", tens, SMALL[units as usize])),
+            }
+        }
+    }
+}
+
+/// The English name of a number.
+fn spell(n: i128) -> String {
+    if n == 0 {
+        return SMALL[0].to_string();
+    }
+
+    let mut words = vec![];
+    if n < 0 {
+        words.push("negative".to_string());
+    }
+
+    // The groups of three digits, the most significant first.
+    let mut groups = vec![];
+    let mut magnitude = n.unsigned_abs();
+    while magnitude > 0 {
+        groups.push(magnitude % 1000);
+        magnitude /= 1000;
+    }
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group > 0 {
+            spell_group(group, &mut words);
+            if scale > 0 {
+                words.push(SCALES[scale - 1].to_string());
+            }
+        }
+    }
+    words.join(" ")
+}
+
+#[derive(Debug, PartialEq)]
+enum ParseError {
+    UnknownWord(String),
+    /// The words are all numbers, but not in an order that makes a number.
+    Malformed,
+    Overflow,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownWord(word) => write!(f, "unknown word: {}", word),
+            ParseError::Malformed => write!(f, "not a well-formed number"),
+            ParseError::Overflow => write!(f, "too large for an i128"),
+        }
+    }
+}
+
+/// Reads back the name of a number, as written by `spell`. Case and extra spaces do not
+/// matter, and "minus" is understood as well as "negative".
+fn parse(text: &str) -> Result<i128, ParseError> {
+    let text = text.to_lowercase();
+    let mut words = text.split_whitespace().peekable();
+    let negative = match words.peek() {
+        Some(&"negative") | Some(&"minus") => {
+            words.next();
+            true
+        }
+        _ => false,
+    };
+
+    let mut total: u128 = 0;
+    // The group of three digits being read, and the scale of the last group, so that the
+    // scales can only decrease.
+    let mut group: Option<u128> = None;
+    let mut last_scale = SCALES.len() + 1;
+    let mut seen_zero = false;
+    let mut any = false;
+
+    for word in words {
+        any = true;
+        if seen_zero {
+            return Err(ParseError::Malformed);
+        }
+        let (tens, units) = match word.split_once('-') {
+            Some((tens, units)) => (Some(tens), units),
+            None => (None, word),
+        };
+
+        if let Some(tens) = tens {
+            let tens = TENS.iter().position(|&t| t == tens);
+            let units = SMALL[1..10].iter().position(|&u| u == units);
+            match (tens, units) {
+                (Some(t), Some(u)) => {
+                    add_below_hundred(&mut group, (t as u128 + 2) * 10 + u as u128 + 1)?
+                }
+                _ => return Err(ParseError::UnknownWord(word.to_string())),
+            }
+        } else if word == "zero" {
+            if group.is_some() || total > 0 {
+                return Err(ParseError::Malformed);
+            }
+            seen_zero = true;
+        } else if let Some(n) = SMALL.iter().position(|&s| s == word) {
+            add_below_hundred(&mut group, n as u128)?;
+        } else if let Some(t) = TENS.iter().position(|&t| t == word) {
+            add_below_hundred(&mut group, (t as u128 + 2) * 10)?;
+        } else if word == "hundred" {
+            match group {
+                Some(g) if (1..10).contains(&g) => group = Some(g * 100),
+                _ => return Err(ParseError::Malformed),
+            }
+        } else if let Some(scale) = SCALES.iter().position(|&s| s == word) {
+            let scale = scale + 1;
+            match group.take() {
+                Some(g) if scale < last_scale => {
+                    total = 1000u128
+                        .checked_pow(scale as u32)
+                        .and_then(|power| power.checked_mul(g))
+                        .and_then(|value| value.checked_add(total))
+                        .ok_or(ParseError::Overflow)?;
+                    last_scale = scale;
+                }
+                _ => return Err(ParseError::Malformed),
+            }
+        } else {
+            return Err(ParseError::UnknownWord(word.to_string()));
+        }
+    }
+
+    if !any {
+        return Err(ParseError::Malformed);
+    }
+    if let Some(g) = group {
+        total = total.checked_add(g).ok_or(ParseError::Overflow)?;
+    }
+
+    if negative {
+        if total == 0 {
+            return Err(ParseError::Malformed);
+        }
+        // The magnitude of `i128::MIN` is one more than `i128::MAX`.
+        0i128
+            .checked_sub_unsigned(total)
+            .ok_or(ParseError::Overflow)
+    } else {
+        i128::try_from(total).map_err(|_| ParseError::Overflow)
+    }
+}
+
+/// Adds a number below a hundred to the group being read, which may only have hundreds so
+/// far.
+fn add_below_hundred(group: &mut Option<u128>, n: u128) -> Result<(), ParseError> {
+    match *group {
+        None => *group = Some(n),
+        Some(g) if g % 100 == 0 && g > 0 => *group = Some(g + n),
+        Some(_) => return Err(ParseError::Malformed),
+    }
+    Ok(())
+}
+
+fn main() {
+    // cargo run -p number-names -- "forty-two thousand"
+    let words: Vec<String> = std::env::args().skip(1).collect();
+    if !words.is_empty() {
+        match parse(&words.join(" ")) {
+            Ok(n) => println!("{}", n),
+            Err(e) => eprintln!("{}", e),
+        }
+        return;
+    }
+
+    let numbers = [
+        0,
+        7,
+        13,
+        42,
+        100,
+        999,
+        1_001,
+        12_345,
+        1_000_000,
+        -987_654_321,
+        1_234_567_890_123,
+        i128::MAX,
+        i128::MIN,
+    ];
+    for &n in &numbers {
+        let name = spell(n);
+        assert_eq!(parse(&name), Ok(n));
+        println!("{}: {}", n, name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    #[test]
+    fn small_numbers() {
+        assert_eq!(spell(0), "zero");
+        assert_eq!(spell(5), "five");
+        assert_eq!(spell(15), "fifteen");
+        assert_eq!(spell(20), "twenty");
+        assert_eq!(spell(21), "twenty-one");
+        assert_eq!(spell(99), "ninety-nine");
+        assert_eq!(spell(100), "one hundred");
+        assert_eq!(spell(101), "one hundred one");
+        assert_eq!(spell(342), "three hundred forty-two");
+    }
+
+    #[test]
+    fn large_numbers() {
+        assert_eq!(spell(1_000), "one thousand");
+        assert_eq!(spell(1_001), "one thousand one");
+        assert_eq!(spell(1_000_000), "one million");
+        assert_eq!(spell(2_000_030), "two million thirty");
+        assert_eq!(
+            spell(-987_654_321),
+            "negative nine hundred eighty-seven million six hundred fifty-four thousand three \
+             hundred twenty-one"
+        );
+        assert_eq!(spell(10i128.pow(36)), "one undecillion");
+        assert_eq!(
+            spell(i128::MAX),
+            "one hundred seventy undecillion one hundred forty-one decillion one hundred \
+             eighty-three nonillion four hundred sixty octillion four hundred sixty-nine \
+             septillion two hundred thirty-one sextillion seven hundred thirty-one \
+             quintillion six hundred eighty-seven quadrillion three hundred three trillion \
+             seven hundred fifteen billion eight hundred eighty-four million one hundred \
+             five thousand seven hundred twenty-seven"
+        );
+        assert!(spell(i128::MIN).ends_with("seven hundred twenty-eight"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let unknown = |w: &str| Err(ParseError::UnknownWord(w.to_string()));
+        assert_eq!(parse("one zillion"), unknown("zillion"));
+        assert_eq!(parse("twenty-ten"), unknown("twenty-ten"));
+        assert_eq!(parse(""), Err(ParseError::Malformed));
+        assert_eq!(parse("negative"), Err(ParseError::Malformed));
+        assert_eq!(parse("negative zero"), Err(ParseError::Malformed));
+        assert_eq!(parse("zero one"), Err(ParseError::Malformed));
+        assert_eq!(parse("twenty five"), Err(ParseError::Malformed));
+        assert_eq!(parse("hundred"), Err(ParseError::Malformed));
+        assert_eq!(
+            parse("one thousand one million"),
+            Err(ParseError::Malformed)
+        );
+        assert_eq!(parse("one thousand thousand"), Err(ParseError::Malformed));
+        assert_eq!(parse("two hundred undecillion"), Err(ParseError::Overflow));
+    }
+
+    #[test]
+    fn parse_variants() {
+        assert_eq!(parse("Minus  Forty-Two"), Ok(-42));
+        assert_eq!(parse("one hundred twenty"), Ok(120));
+        assert_eq!(parse("seven million"), Ok(7_000_000));
+        assert_eq!(parse(&spell(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(parse(&spell(i128::MAX)), Ok(i128::MAX));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for n in -2000..2000 {
+            assert_eq!(parse(&spell(n)), Ok(n), "{}", spell(n));
+        }
+        for _ in 0..10_000 {
+            // Numbers of every size, with many zero groups among them.
+            let digits = rng.gen_range(1, 39);
+            let mut n: i128 = 0;
+            for _ in 0..digits {
+                let digit = if rng.gen_bool(0.3) {
+                    0
+                } else {
+                    rng.gen_range(0, 10)
+                };
+                n = n.saturating_mul(10).saturating_add(digit);
+            }
+            let n = if rng.gen() { n } else { -n };
+            assert_eq!(parse(&spell(n)), Ok(n), "{}", spell(n));
+        }
+    }
+}
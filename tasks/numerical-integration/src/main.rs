@@ -1,23 +1,200 @@
-//! This is a partial solution and only implements trapezium integration.
+use std::ops::Range;
 
-fn trapezium_integral<F>(f: F, range: &std::ops::Range<f64>, n_steps: u32) -> f64
-where
-    F: Fn(f64) -> f64,
-{
-    let step_size = (range.end - range.start) / f64::from(n_steps);
+/// A method of approximating a definite integral by sampling the integrand over evenly sized
+/// subintervals.
+trait Integrator {
+    /// The name of the method, as given in the task description.
+    fn name(&self) -> &'static str;
 
-    let mut integral = (f(range.start) + f(range.end)) / 2.;
-    let mut pos = range.start + step_size;
-    while pos < range.end {
-        integral += f(pos);
-        pos += step_size;
+    /// The approximate area under `f` on a single subinterval `[a, a + h]`.
+    fn area(&self, f: &dyn Fn(f64) -> f64, a: f64, h: f64) -> f64;
+
+    /// Approximates the integral of `f` over `range` by dividing it into `n_steps` subintervals.
+    fn integrate(&self, f: &dyn Fn(f64) -> f64, range: &Range<f64>, n_steps: u32) -> f64 {
+        let h = (range.end - range.start) / f64::from(n_steps);
+
+        // Computing each subinterval from its index (rather than repeatedly adding `h`) avoids
+        // accumulating rounding errors over millions of steps.
+        (0..n_steps)
+            .map(|i| self.area(f, range.start + f64::from(i) * h, h))
+            .sum()
+    }
+}
+
+struct LeftRectangle;
+struct RightRectangle;
+struct MidRectangle;
+struct Trapezium;
+struct Simpson;
+
+impl Integrator for LeftRectangle {
+    fn name(&self) -> &'static str {
+        "left rectangle"
+    }
+
+    fn area(&self, f: &dyn Fn(f64) -> f64, a: f64, h: f64) -> f64 {
+        h * f(a)
+    }
+}
+
+impl Integrator for RightRectangle {
+    fn name(&self) -> &'static str {
+        "right rectangle"
+    }
+
+    fn area(&self, f: &dyn Fn(f64) -> f64, a: f64, h: f64) -> f64 {
+        h * f(a + h)
+    }
+}
+
+impl Integrator for MidRectangle {
+    fn name(&self) -> &'static str {
+        "mid rectangle"
+    }
+
+    fn area(&self, f: &dyn Fn(f64) -> f64, a: f64, h: f64) -> f64 {
+        h * f(a + h / 2.0)
+    }
+}
+
+impl Integrator for Trapezium {
+    fn name(&self) -> &'static str {
+        "trapezium"
+    }
+
+    fn area(&self, f: &dyn Fn(f64) -> f64, a: f64, h: f64) -> f64 {
+        h * (f(a) + f(a + h)) / 2.0
+    }
+}
+
+impl Integrator for Simpson {
+    fn name(&self) -> &'static str {
+        "simpson"
+    }
+
+    fn area(&self, f: &dyn Fn(f64) -> f64, a: f64, h: f64) -> f64 {
+        h * (f(a) + 4.0 * f(a + h / 2.0) + f(a + h)) / 6.0
     }
-    integral * step_size
+}
+
+const INTEGRATORS: [&dyn Integrator; 5] = [
+    &LeftRectangle,
+    &RightRectangle,
+    &MidRectangle,
+    &Trapezium,
+    &Simpson,
+];
+
+/// A test case from the task description.
+struct Case {
+    description: &'static str,
+    f: fn(f64) -> f64,
+    range: Range<f64>,
+    n_steps: u32,
+    exact: f64,
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            description: "x^3 from 0 to 1",
+            f: |x| x.powi(3),
+            range: 0.0..1.0,
+            n_steps: 100,
+            exact: 0.25,
+        },
+        Case {
+            description: "1/x from 1 to 100",
+            f: |x| 1.0 / x,
+            range: 1.0..100.0,
+            n_steps: 1000,
+            exact: 100f64.ln(),
+        },
+        Case {
+            description: "x from 0 to 5000",
+            f: |x| x,
+            range: 0.0..5000.0,
+            n_steps: 5_000_000,
+            exact: 12_500_000.0,
+        },
+        Case {
+            description: "x from 0 to 6000",
+            f: |x| x,
+            range: 0.0..6000.0,
+            n_steps: 6_000_000,
+            exact: 18_000_000.0,
+        },
+    ]
 }
 
 fn main() {
-    println!("{}", trapezium_integral(|x| x.powi(3), &(0.0..1.0), 100));
-    println!("{}", trapezium_integral(|x| 1.0 / x, &(1.0..100.0), 1000));
-    println!("{}", trapezium_integral(|x| x, &(0.0..5000.0), 5_000_000));
-    println!("{}", trapezium_integral(|x| x, &(0.0..6000.0), 6_000_000));
+    for case in cases() {
+        println!(
+            "{} ({} steps, exact value {}):",
+            case.description, case.n_steps, case.exact
+        );
+        for integrator in &INTEGRATORS {
+            let result = integrator.integrate(&case.f, &case.range, case.n_steps);
+            println!("    {:>15}: {}", integrator.name(), result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        let relative_error = ((actual - expected) / expected).abs();
+        assert!(
+            relative_error < tolerance,
+            "expected {}, got {} (relative error {})",
+            expected,
+            actual,
+            relative_error
+        );
+    }
+
+    #[test]
+    fn matches_task_output() {
+        // The values listed on the task page, one row per case and one column per integrator.
+        let expected = [
+            [0.245025, 0.255025, 0.2499875, 0.250025, 0.25],
+            [4.654991, 4.556981, 4.604763, 4.605986, 4.605170],
+            [
+                12_499_997.5,
+                12_500_002.5,
+                12_500_000.0,
+                12_500_000.0,
+                12_500_000.0,
+            ],
+            [
+                17_999_997.0,
+                18_000_003.0,
+                18_000_000.0,
+                18_000_000.0,
+                18_000_000.0,
+            ],
+        ];
+
+        for (case, row) in cases().iter().zip(&expected) {
+            for (integrator, &value) in INTEGRATORS.iter().zip(row) {
+                let result = integrator.integrate(&case.f, &case.range, case.n_steps);
+                assert_close(result, value, 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn approximates_exact_values() {
+        // Simpson's rule is exact for polynomials of degree three or less.
+        let tolerances = [3e-2, 3e-2, 1e-3, 1e-3, 1e-6];
+
+        for case in cases() {
+            for (integrator, &tolerance) in INTEGRATORS.iter().zip(&tolerances) {
+                let result = integrator.integrate(&case.f, &case.range, case.n_steps);
+                assert_close(result, case.exact, tolerance);
+            }
+        }
+    }
 }
@@ -1,17 +1,35 @@
-/// Returns true if the string is a palindrome
-fn palindrome(string: &str) -> bool {
-    // The first part of the string
-    let forward = string.chars().take(string.len() / 2);
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
-    // The second part of the string in reverse order
-    let reverse = string.chars().rev().take(string.len() / 2);
+/// Returns true if the sequence reads the same in both directions
+fn symmetric<T: PartialEq>(items: &[T]) -> bool {
+    // We pair the first half with the second half in reverse order
+    let half = items.len() / 2;
+    items.iter().take(half).eq(items.iter().rev().take(half))
+}
 
-    // We group the two parts of the string in tuples
-    let mut both_directions = forward.zip(reverse);
+/// Returns true if the string is a palindrome
+///
+/// The string is compared by grapheme clusters rather than by `char`s, so that an accent
+/// written as a combining character stays with its letter.
+fn palindrome(string: &str) -> bool {
+    let graphemes: Vec<&str> = string.graphemes(true).collect();
+    symmetric(&graphemes)
+}
 
-    // The word is a palindrome if each tuple contains two times
-    // the same character
-    both_directions.all(|(a, b)| a == b)
+/// Returns true if the string is a palindrome once case, spaces and punctuation are ignored,
+/// like "A man, a plan, a canal: Panama!"
+///
+/// The string is normalized first, so that "é" is the same whether it was written as one
+/// character or as "e" followed by a combining accent.
+fn inexact_palindrome(string: &str) -> bool {
+    let normalized: String = string.nfc().collect();
+    let graphemes: Vec<String> = normalized
+        .graphemes(true)
+        .filter(|g| g.chars().next().is_some_and(char::is_alphanumeric))
+        .map(str::to_lowercase)
+        .collect();
+    symmetric(&graphemes)
 }
 
 fn main() {
@@ -19,6 +37,16 @@ fn main() {
     for &string in &test_strings {
         println!("{}: {}", string, palindrome(string));
     }
+
+    let sentences = [
+        "A man, a plan, a canal: Panama!",
+        "In girum imus nocte et consumimur igni",
+        "Was it a car or a cat I saw?",
+        "Not a palindrome.",
+    ];
+    for &sentence in &sentences {
+        println!("{}: {}", sentence, inexact_palindrome(sentence));
+    }
 }
 
 #[test]
@@ -29,3 +57,43 @@ fn test_palindromes() {
     assert!(palindromes.iter().all(|&s| palindrome(s)));
     assert!(non_palindromes.iter().all(|&s| !palindrome(s)));
 }
+
+#[test]
+fn test_combining_characters() {
+    // "e" + COMBINING ACUTE ACCENT; reversing the chars would move the accent.
+    assert!(palindrome("e\u{301}te\u{301}"));
+    assert!(palindrome("a\u{301}ba\u{301}"));
+    assert!(!palindrome("a\u{301}ba"));
+    // The accent is on the other side of the "b" in the reversed chars.
+    assert!(!palindrome("a\u{301}b\u{301}a"));
+    // Exact palindromes tell composed and decomposed letters apart.
+    assert!(!palindrome("\u{e9}te\u{301}"));
+}
+
+#[test]
+fn test_cjk() {
+    assert!(palindrome("上海自来水来自海上"));
+    assert!(palindrome("山东落花生花落东山"));
+    assert!(!palindrome("上海自来水"));
+    assert!(inexact_palindrome("上海自来水，来自海上。"));
+}
+
+#[test]
+fn test_inexact_palindromes() {
+    let palindromes = [
+        "A man, a plan, a canal: Panama!",
+        "In girum imus nocte et consumimur igni",
+        "Été",
+        "E\u{301}t\u{e9}!",
+        "Was it a car or a cat I saw?",
+        "",
+        "...",
+    ];
+    // Accents are not ignored, so the "é" at the start of this one is not the "e" at its end.
+    let non_palindromes = ["Not a palindrome.", "ab, c", "Ésope reste ici et se repose"];
+
+    assert!(palindromes.iter().all(|&s| inexact_palindrome(s)));
+    assert!(non_palindromes.iter().all(|&s| !inexact_palindrome(s)));
+    // The exact test does not ignore anything.
+    assert!(!palindrome("Was it a car or a cat I saw?"));
+}
@@ -9,7 +9,7 @@ extern crate prime_decomposition;
 use rayon::prelude::*;
 
 /// Returns the largest minimal factor of the numbers in a slice
-pub fn largest_min_factor(numbers: &[usize]) -> usize {
+pub fn largest_min_factor(numbers: &[u64]) -> u64 {
     numbers
         .par_iter()
         .map(|n| {
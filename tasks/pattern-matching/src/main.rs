@@ -0,0 +1,223 @@
+//! Insertion in a red-black tree, balanced with the pattern matching of Okasaki's functional
+//! implementation: a black node with a red child and a red grandchild, in any of the four
+//! ways this can happen, is rewritten into a red node with two black children.
+//!
+//! Stable Rust cannot match through a `Box` in a pattern, so the children are moved out of
+//! their boxes and matched one level at a time instead.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Color {
+    Red,
+    Black,
+}
+
+use Color::{Black, Red};
+
+#[derive(Debug)]
+enum Tree<T> {
+    Leaf,
+    Node(Color, Box<Tree<T>>, T, Box<Tree<T>>),
+}
+
+use Tree::{Leaf, Node};
+
+fn node<T>(color: Color, left: Tree<T>, value: T, right: Tree<T>) -> Tree<T> {
+    Node(color, Box::new(left), value, Box::new(right))
+}
+
+/// Rebuilds a node, rotating a red-red violation below a black node away.
+fn balance<T>(color: Color, left: Tree<T>, value: T, right: Tree<T>) -> Tree<T> {
+    if color == Red {
+        return node(color, left, value, right);
+    }
+
+    let left = match left {
+        Node(Red, ll, lv, lr) => match (*ll, *lr) {
+            (Node(Red, a, x, b), c) => {
+                return node(
+                    Red,
+                    node(Black, *a, x, *b),
+                    lv,
+                    node(Black, c, value, right),
+                )
+            }
+            (a, Node(Red, b, y, c)) => {
+                return node(
+                    Red,
+                    node(Black, a, lv, *b),
+                    y,
+                    node(Black, *c, value, right),
+                )
+            }
+            (a, b) => node(Red, a, lv, b),
+        },
+        left => left,
+    };
+
+    match right {
+        Node(Red, rl, rv, rr) => match (*rl, *rr) {
+            (Node(Red, b, y, c), d) => {
+                node(Red, node(Black, left, value, *b), y, node(Black, *c, rv, d))
+            }
+            (b, Node(Red, c, z, d)) => {
+                node(Red, node(Black, left, value, b), rv, node(Black, *c, z, *d))
+            }
+            (b, c) => node(Black, left, value, node(Red, b, rv, c)),
+        },
+        right => node(Black, left, value, right),
+    }
+}
+
+impl<T: Ord> Tree<T> {
+    fn new() -> Self {
+        Leaf
+    }
+
+    /// The tree with `x` added, unless it was there already.
+    fn insert(self, x: T) -> Self {
+        // The root is always black, which may add one to the black height of the tree.
+        match self.ins(x) {
+            Node(_, left, value, right) => Node(Black, left, value, right),
+            Leaf => unreachable!("inserting makes a node"),
+        }
+    }
+
+    fn ins(self, x: T) -> Self {
+        match self {
+            Leaf => node(Red, Leaf, x, Leaf),
+            Node(color, left, value, right) => match x.cmp(&value) {
+                Ordering::Less => balance(color, left.ins(x), value, *right),
+                Ordering::Greater => balance(color, *left, value, right.ins(x)),
+                Ordering::Equal => Node(color, left, value, right),
+            },
+        }
+    }
+
+    fn contains(&self, x: &T) -> bool {
+        let mut tree = self;
+        while let Node(_, left, value, right) = tree {
+            tree = match x.cmp(value) {
+                Ordering::Less => left,
+                Ordering::Greater => right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+}
+
+/// Prints the tree sideways, with the right subtree at the top.
+impl<T: fmt::Display> fmt::Display for Tree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write<T: fmt::Display>(
+            tree: &Tree<T>,
+            depth: usize,
+            f: &mut fmt::Formatter,
+        ) -> fmt::Result {
+            if let Node(color, left, value, right) = tree {
+                write(right, depth + 1, f)?;
+                let color = if *color == Red { 'R' } else { 'B' };
+                writeln!(f, "{:indent$}{}{}", "", color, value, indent = 4 * depth)?;
+                write(left, depth + 1, f)?;
+            }
+            Ok(())
+        }
+        write(self, 0, f)
+    }
+}
+
+fn main() {
+    let tree = (1..=10).fold(Tree::new(), Tree::insert);
+    print!("{}", tree);
+    println!("contains 7: {}", tree.contains(&7));
+    println!("contains 11: {}", tree.contains(&11));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    /// Checks the invariants below `tree`, and returns its black height and its values in
+    /// order.
+    fn check<T: Ord + Copy + fmt::Debug>(
+        tree: &Tree<T>,
+        parent: Color,
+        values: &mut Vec<T>,
+    ) -> usize {
+        match tree {
+            Leaf => 1,
+            Node(color, left, value, right) => {
+                assert!(
+                    !(parent == Red && *color == Red),
+                    "red-red edge at {:?}",
+                    value
+                );
+                let left_height = check(left, *color, values);
+                values.push(*value);
+                let right_height = check(right, *color, values);
+                assert_eq!(left_height, right_height, "black heights at {:?}", value);
+                left_height + if *color == Black { 1 } else { 0 }
+            }
+        }
+    }
+
+    fn check_invariants(tree: &Tree<i32>) -> (usize, Vec<i32>) {
+        if let Node(color, ..) = tree {
+            assert_eq!(*color, Black, "the root is black");
+        }
+        let mut values = vec![];
+        let height = check(tree, Black, &mut values);
+        assert!(values.windows(2).all(|w| w[0] < w[1]), "out of order");
+        (height, values)
+    }
+
+    #[test]
+    fn ascending_inserts() {
+        let tree = (0..1000).fold(Tree::new(), Tree::insert);
+        let (height, values) = check_invariants(&tree);
+        assert_eq!(values, (0..1000).collect::<Vec<_>>());
+        // A red-black tree with n nodes has a black height of at most log2(n + 1) + 1.
+        assert!(height <= 11);
+        assert!((0..1000).all(|x| tree.contains(&x)));
+        assert!(!tree.contains(&-1) && !tree.contains(&1000));
+    }
+
+    #[test]
+    fn random_inserts() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let len = rng.gen_range(0, 300);
+            let inserted: Vec<i32> = (0..len).map(|_| rng.gen_range(-100, 100)).collect();
+            let tree = inserted.iter().fold(Tree::new(), |tree, &x| tree.insert(x));
+
+            let (_, values) = check_invariants(&tree);
+            let mut expected = inserted.clone();
+            expected.sort_unstable();
+            expected.dedup();
+            assert_eq!(values, expected);
+            for x in -100..100 {
+                assert_eq!(tree.contains(&x), inserted.contains(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let tree: Tree<i32> = Tree::new();
+        assert!(!tree.contains(&0));
+        assert_eq!(check_invariants(&tree), (1, vec![]));
+        assert_eq!(tree.to_string(), "");
+    }
+
+    #[test]
+    fn display() {
+        // The third insertion makes a red-red edge, which is balanced away.
+        let tree = (1..=3).fold(Tree::new(), Tree::insert);
+        assert_eq!(tree.to_string(), "    B3\nB2\n    B1\n");
+    }
+}
@@ -0,0 +1,114 @@
+use num::{BigInt, One, Zero};
+
+/// An iterator over the terms of the continued fraction expansion of `√n`, excluding the
+/// integer part.
+///
+/// The expansion is computed exactly with integers: at every step, the remaining fraction has
+/// the form `(√n + m) / d`.
+struct SqrtContinuedFraction {
+    n: u64,
+    a0: u64,
+    m: u64,
+    d: u64,
+}
+
+impl SqrtContinuedFraction {
+    fn new(n: u64, a0: u64) -> Self {
+        SqrtContinuedFraction { n, a0, m: a0, d: 1 }
+    }
+}
+
+impl Iterator for SqrtContinuedFraction {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.d = (self.n - self.m * self.m) / self.d;
+        let a = (self.a0 + self.m) / self.d;
+        self.m = a * self.d - self.m;
+        Some(a)
+    }
+}
+
+fn isqrt(n: u64) -> u64 {
+    let mut x = (n as f64).sqrt() as u64;
+    while x * x > n {
+        x -= 1;
+    }
+    while (x + 1) * (x + 1) <= n {
+        x += 1;
+    }
+    x
+}
+
+/// Finds the fundamental solution of `x² - n·y² = 1`, or `None` if `n` is a perfect square.
+///
+/// The solution is one of the convergents `x / y` of the continued fraction of `√n`.
+fn pell(n: u64) -> Option<(BigInt, BigInt)> {
+    let a0 = isqrt(n);
+    if a0 * a0 == n {
+        return None;
+    }
+
+    let n_big = BigInt::from(n);
+    // The previous and current convergents.
+    let (mut x0, mut x1) = (BigInt::one(), BigInt::from(a0));
+    let (mut y0, mut y1) = (BigInt::zero(), BigInt::one());
+
+    for a in SqrtContinuedFraction::new(n, a0) {
+        if &x1 * &x1 - &n_big * &y1 * &y1 == BigInt::one() {
+            return Some((x1, y1));
+        }
+
+        let a = BigInt::from(a);
+        let x2 = &a * &x1 + &x0;
+        let y2 = &a * &y1 + &y0;
+        x0 = std::mem::replace(&mut x1, x2);
+        y0 = std::mem::replace(&mut y1, y2);
+    }
+
+    unreachable!("the continued fraction of √n is infinite")
+}
+
+fn main() {
+    for &n in &[61, 109, 181, 277] {
+        let (x, y) = pell(n).unwrap();
+        println!("x² - {}y² = 1 for x = {} and y = {}", n, x, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(x: &str, y: &str) -> Option<(BigInt, BigInt)> {
+        Some((x.parse().unwrap(), y.parse().unwrap()))
+    }
+
+    #[test]
+    fn continued_fraction() {
+        let terms: Vec<_> = SqrtContinuedFraction::new(14, 3).take(8).collect();
+        assert_eq!(terms, vec![1, 2, 1, 6, 1, 2, 1, 6]);
+    }
+
+    #[test]
+    fn small_solutions() {
+        assert_eq!(pell(2), solution("3", "2"));
+        assert_eq!(pell(3), solution("2", "1"));
+        assert_eq!(pell(7), solution("8", "3"));
+        assert_eq!(pell(4), None);
+    }
+
+    #[test]
+    fn task_solutions() {
+        assert_eq!(pell(61), solution("1766319049", "226153980"));
+        assert_eq!(pell(109), solution("158070671986249", "15140424455100"));
+        assert_eq!(
+            pell(181),
+            solution("2469645423824185801", "183567298683461940")
+        );
+        assert_eq!(
+            pell(277),
+            solution("159150073798980475849", "9562401173878027020")
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use bitmap::Image;
+
+/// The mean difference between the channels of two images of the same size, as a percentage
+/// of the largest possible difference. Returns `None` if the sizes differ.
+pub fn percentage_difference(a: &Image, b: &Image) -> Option<f64> {
+    if (a.width, a.height) != (b.width, b.height) {
+        return None;
+    }
+    if a.data.is_empty() {
+        return Some(0.0);
+    }
+
+    let difference = |x: u8, y: u8| u64::from(x.max(y) - x.min(y));
+    let total: u64 = a
+        .data
+        .iter()
+        .zip(&b.data)
+        .map(|(x, y)| {
+            difference(x.red, y.red) + difference(x.green, y.green) + difference(x.blue, y.blue)
+        })
+        .sum();
+    Some(100.0 * total as f64 / (255.0 * 3.0 * a.data.len() as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bitmap::Color;
+
+    fn filled(width: usize, height: usize, color: Color) -> Image {
+        let mut image = Image::new(width, height);
+        image.fill(color);
+        image
+    }
+
+    const WHITE: Color = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+
+    #[test]
+    fn extremes() {
+        let black = Image::new(4, 3);
+        let white = filled(4, 3, WHITE);
+        assert_eq!(percentage_difference(&black, &white), Some(100.0));
+        assert_eq!(percentage_difference(&white, &black), Some(100.0));
+        assert_eq!(percentage_difference(&white, &white), Some(0.0));
+    }
+
+    #[test]
+    fn per_channel() {
+        let a = filled(
+            2,
+            2,
+            Color {
+                red: 255,
+                green: 0,
+                blue: 0,
+            },
+        );
+        let b = filled(
+            2,
+            2,
+            Color {
+                red: 0,
+                green: 51,
+                blue: 0,
+            },
+        );
+        // One channel entirely different and another by a fifth, out of three.
+        let difference = percentage_difference(&a, &b).unwrap();
+        assert!((difference - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn some_pixels() {
+        let a = Image::new(10, 10);
+        let mut b = Image::new(10, 10);
+        for x in 0..10 {
+            b[(x, 0)] = WHITE;
+        }
+        b[(0, 1)].blue = 255;
+        // 10 white pixels and a third of one.
+        let difference = percentage_difference(&a, &b).unwrap();
+        assert!((difference - 31.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sizes_must_match() {
+        assert_eq!(
+            percentage_difference(&Image::new(2, 3), &Image::new(3, 2)),
+            None
+        );
+        assert_eq!(
+            percentage_difference(&Image::new(0, 0), &Image::new(0, 0)),
+            Some(0.0)
+        );
+    }
+}
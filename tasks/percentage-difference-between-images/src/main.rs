@@ -0,0 +1,26 @@
+//! Compares two PPM images of the same size, such as the two versions of Lenna on the task's
+//! page (converted to PPM), which differ by about 1.62%.
+//!
+//! cargo run -p percentage-difference-between-images -- first.ppm second.ppm
+
+use percentage_difference_between_images::percentage_difference;
+use read_a_ppm_file::{read_ppm_file, Error};
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: percentage-difference-between-images first.ppm second.ppm");
+        std::process::exit(2);
+    }
+
+    let first = read_ppm_file(&args[0])?;
+    let second = read_ppm_file(&args[1])?;
+    match percentage_difference(&first, &second) {
+        Some(difference) => println!("Difference: {:.5}%", difference),
+        None => {
+            eprintln!("the images do not have the same size");
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,293 @@
+//! A persistent singly linked list: no operation changes a list, they make new lists instead,
+//! and the new lists share every node they can with the old ones, so that making one is cheap
+//! and all the versions stay usable.
+//!
+//! The nodes are shared through `Rc`s, and never change once they are made, so no `RefCell` is
+//! needed.
+
+use std::fmt;
+use std::rc::Rc;
+
+struct Node<T> {
+    head: T,
+    tail: List<T>,
+}
+
+pub struct List<T>(Option<Rc<Node<T>>>);
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List(None)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// The list with `head` in front of this one, which becomes its tail without being copied.
+    pub fn cons(&self, head: T) -> Self {
+        List(Some(Rc::new(Node {
+            head,
+            tail: self.clone(),
+        })))
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.0.as_ref().map(|node| &node.head)
+    }
+
+    /// The list without its first element, which is shared with this one.
+    pub fn tail(&self) -> Option<Self> {
+        self.0.as_ref().map(|node| node.tail.clone())
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Whether both lists are the same nodes, rather than nodes with the same elements.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// The lists that start at each node, from this one to the empty list.
+    fn suffixes(&self) -> Vec<&List<T>> {
+        let mut suffixes = vec![self];
+        let mut list = self;
+        while let Some(node) = &list.0 {
+            list = &node.tail;
+            suffixes.push(list);
+        }
+        suffixes
+    }
+
+    /// Rebuilds the list from `elements`, in order, in front of `suffix`.
+    fn rebuild(elements: Vec<T>, suffix: &List<T>) -> Self {
+        elements
+            .into_iter()
+            .rev()
+            .fold(suffix.clone(), |list, x| list.cons(x))
+    }
+
+    /// The elements that satisfy `keep`. The longest suffix whose elements are all kept is
+    /// shared with this list.
+    pub fn filter<F: Fn(&T) -> bool>(&self, keep: F) -> Self
+    where
+        T: Clone,
+    {
+        let suffixes = self.suffixes();
+        // The start of the suffix to share: the node after the last one that goes.
+        let shared = suffixes
+            .iter()
+            .rposition(|list| list.head().is_some_and(|x| !keep(x)))
+            .map_or(0, |i| i + 1);
+        let kept = suffixes[..shared]
+            .iter()
+            .filter_map(|list| list.head())
+            .filter(|x| keep(x))
+            .cloned()
+            .collect();
+        Self::rebuild(kept, suffixes[shared])
+    }
+
+    /// The list of `f` of each element. The longest suffix that `f` does not change is shared
+    /// with this list.
+    pub fn map<F: Fn(&T) -> T>(&self, f: F) -> Self
+    where
+        T: PartialEq,
+    {
+        let suffixes = self.suffixes();
+        let elements: Vec<&T> = self.iter().collect();
+        let mut mapped: Vec<T> = elements.iter().map(|x| f(x)).collect();
+        let unchanged = elements
+            .iter()
+            .zip(&mapped)
+            .rev()
+            .take_while(|(x, y)| **x == *y)
+            .count();
+        let shared = mapped.len() - unchanged;
+        mapped.truncate(shared);
+        Self::rebuild(mapped, suffixes[shared])
+    }
+}
+
+impl<T> Clone for List<T> {
+    /// Another handle on the same nodes.
+    fn clone(&self) -> Self {
+        List(self.0.clone())
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for List<T> {
+    /// Frees the nodes that only this list holds one at a time, rather than by recursing down a
+    /// long list.
+    fn drop(&mut self) {
+        let mut next = self.0.take();
+        while let Some(node) = next {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => next = node.tail.0.take(),
+                // The rest of the list is still in use elsewhere.
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T>(&'a List<T>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.0 .0.as_ref().map(|node| {
+            self.0 = &node.tail;
+            &node.head
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, x) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", x)?;
+        }
+        write!(f, ")")
+    }
+}
+
+fn main() {
+    let empty = List::new();
+    let three = empty.cons(3);
+    let two_three = three.cons(2);
+    let one_two_three = two_three.cons(1);
+    let ten_two_three = two_three.cons(10);
+    let odd = one_two_three.filter(|x| x % 2 == 1);
+    let doubled_head = one_two_three.map(|&x| if x == 1 { 2 } else { x });
+
+    // Every version is still there.
+    for (name, list) in &[
+        ("empty", &empty),
+        ("three", &three),
+        ("two_three", &two_three),
+        ("one_two_three", &one_two_three),
+        ("ten_two_three", &ten_two_three),
+        ("odd", &odd),
+        ("doubled_head", &doubled_head),
+    ] {
+        println!("{:>13}: {}", name, list);
+    }
+    println!(
+        "(2 3) is shared by {} lists",
+        Rc::strong_count(two_three.0.as_ref().unwrap())
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(elements: &[i32]) -> List<i32> {
+        elements
+            .iter()
+            .rev()
+            .fold(List::new(), |list, &x| list.cons(x))
+    }
+
+    fn to_vec(list: &List<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    fn count(list: &List<i32>) -> usize {
+        Rc::strong_count(list.0.as_ref().unwrap())
+    }
+
+    #[test]
+    fn cons_head_tail() {
+        let empty = List::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.head(), None);
+        assert!(empty.tail().is_none());
+
+        let a = empty.cons(1);
+        let b = a.cons(2);
+        assert_eq!(b.head(), Some(&2));
+        assert!(b.tail().unwrap().ptr_eq(&a));
+        assert_eq!(to_vec(&b), [2, 1]);
+        assert_eq!(b.len(), 2);
+        assert_eq!(b.to_string(), "(2 1)");
+    }
+
+    #[test]
+    fn versions_share_suffixes() {
+        let base = list(&[2, 3]);
+        assert_eq!(count(&base), 1);
+        let one = base.cons(1);
+        let ten = base.cons(10);
+        // `base`, and the tails of `one` and `ten`.
+        assert_eq!(count(&base), 3);
+        assert_eq!(to_vec(&one), [1, 2, 3]);
+        assert_eq!(to_vec(&ten), [10, 2, 3]);
+        assert_eq!(to_vec(&base), [2, 3]);
+
+        drop(one);
+        assert_eq!(count(&base), 2);
+        drop(ten);
+        assert_eq!(count(&base), 1);
+    }
+
+    #[test]
+    fn filter_shares_kept_suffix() {
+        let tail = list(&[4, 6, 8]);
+        let whole = tail.cons(5).cons(2).cons(1);
+        let even = whole.filter(|x| x % 2 == 0);
+        assert_eq!(to_vec(&even), [2, 4, 6, 8]);
+        assert!(even.tail().unwrap().ptr_eq(&tail));
+
+        // Nothing is copied when everything is kept.
+        let all = whole.filter(|_| true);
+        assert!(all.ptr_eq(&whole));
+        assert!(whole.filter(|_| false).is_empty());
+        assert_eq!(to_vec(&whole), [1, 2, 5, 4, 6, 8]);
+    }
+
+    #[test]
+    fn map_shares_unchanged_suffix() {
+        let tail = list(&[10, 20]);
+        let whole = tail.cons(3).cons(2);
+        let small_doubled = whole.map(|&x| if x < 10 { 2 * x } else { x });
+        assert_eq!(to_vec(&small_doubled), [4, 6, 10, 20]);
+        assert!(small_doubled.tail().unwrap().tail().unwrap().ptr_eq(&tail));
+
+        let negated = whole.map(|&x| -x);
+        assert_eq!(to_vec(&negated), [-2, -3, -10, -20]);
+        // Held by `tail`, `whole` and `small_doubled`, but not by `negated`.
+        assert_eq!(count(&tail), 3);
+        assert!(whole.map(|&x| x).ptr_eq(&whole));
+    }
+
+    #[test]
+    fn long_lists_drop() {
+        let long = (0..200_000).fold(List::new(), |list, x| list.cons(x));
+        let shared = long.tail().unwrap();
+        drop(long);
+        assert_eq!(shared.len(), 199_999);
+    }
+}
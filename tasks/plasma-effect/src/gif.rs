@@ -0,0 +1,233 @@
+//! Writes animated GIFs: frames of palette indices, compressed with LZW, with one palette of 256
+//! colours for all of them.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use bitmap::Color;
+
+/// The palette indices are 8 bits, so the codes start at 9 bits.
+const MIN_CODE_SIZE: u8 = 8;
+/// Codes are at most 12 bits.
+const MAX_CODES: u16 = 1 << 12;
+
+/// Packs codes of varying widths into bytes, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses palette indices with the variant of LZW that GIF uses: the codes grow from 9 to
+/// 12 bits as the table of strings fills up, and a clear code starts the table afresh when it
+/// is full.
+pub fn lzw_encode(indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << MIN_CODE_SIZE;
+    let end = clear + 1;
+    let mut out = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        bits: 0,
+    };
+    // The codes of the strings after the single indices, by the code of the string without
+    // its last index, and that index.
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = MIN_CODE_SIZE + 1;
+    out.write(clear, width);
+
+    let mut indices = indices.iter().copied();
+    if let Some(first) = indices.next() {
+        let mut string = u16::from(first);
+        for index in indices {
+            if let Some(&code) = table.get(&(string, index)) {
+                string = code;
+                continue;
+            }
+            out.write(string, width);
+            if next == MAX_CODES {
+                out.write(clear, width);
+                table.clear();
+                next = end + 1;
+                width = MIN_CODE_SIZE + 1;
+            } else {
+                table.insert((string, index), next);
+                // The decoder adds each string a code later than this, so it only needs the
+                // wider codes once there is a string with the code that does not fit.
+                if next == 1 << width && width < 12 {
+                    width += 1;
+                }
+                next += 1;
+            }
+            string = u16::from(index);
+        }
+        out.write(string, width);
+    }
+    out.write(end, width);
+    out.finish()
+}
+
+/// Writes `frames` of `width` by `height` palette indices, row by row, as a GIF that loops
+/// forever and shows each frame for `delay` hundredths of a second.
+pub fn write_gif<W: Write>(
+    mut out: W,
+    width: u16,
+    height: u16,
+    palette: &[Color; 256],
+    frames: &[Vec<u8>],
+    delay: u16,
+) -> io::Result<()> {
+    out.write_all(b"GIF89a")?;
+    out.write_all(&width.to_le_bytes())?;
+    out.write_all(&height.to_le_bytes())?;
+    // A global palette of 2^(7 + 1) colours of 8 bits each, and no background or aspect ratio.
+    out.write_all(&[0xf7, 0, 0])?;
+    for color in palette.iter() {
+        out.write_all(&[color.red, color.green, color.blue])?;
+    }
+    // The NETSCAPE2.0 extension, with a loop count of 0 for forever.
+    out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    for frame in frames {
+        assert_eq!(frame.len(), usize::from(width) * usize::from(height));
+        // A graphic control extension, for the delay.
+        out.write_all(&[0x21, 0xf9, 4, 0])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0, 0])?;
+        // An image descriptor for the whole screen, without a palette of its own.
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        out.write_all(&[0, MIN_CODE_SIZE])?;
+        // The compressed data, in blocks of at most 255 bytes, each after its length.
+        for block in lzw_encode(frame).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])?;
+    }
+    out.write_all(b"\x3b")?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    /// Decompresses what `lzw_encode` wrote, the way a GIF decoder would.
+    fn lzw_decode(bytes: &[u8]) -> Vec<u8> {
+        let clear = 1u16 << MIN_CODE_SIZE;
+        let end = clear + 1;
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0, bytes.iter());
+        let mut read = |width: u8| {
+            while bits < width {
+                buffer |= u32::from(*bytes.next().expect("no end code")) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << width) - 1)) as u16;
+            buffer >>= width;
+            bits -= width;
+            code
+        };
+
+        let mut out = vec![];
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut width = MIN_CODE_SIZE + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(width);
+            if code == clear {
+                table = (0..=255).map(|i| vec![i]).collect();
+                // The clear and end codes have no strings.
+                table.push(vec![]);
+                table.push(vec![]);
+                width = MIN_CODE_SIZE + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let string = match (table.get(usize::from(code)), &previous) {
+                (Some(string), _) => string.clone(),
+                // The string that is being added with this code.
+                (None, Some(previous)) => {
+                    let mut string = previous.clone();
+                    string.push(previous[0]);
+                    string
+                }
+                (None, None) => panic!("unknown code {}", code),
+            };
+            out.extend(&string);
+            if let Some(mut previous) = previous {
+                if table.len() < usize::from(MAX_CODES) {
+                    previous.push(string[0]);
+                    table.push(previous);
+                }
+            }
+            if table.len() == 1 << width && width < 12 {
+                width += 1;
+            }
+            previous = Some(string);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let random: Vec<u8> = (0..20_000).map(|_| rng.gen()).collect();
+        // Few colours in long runs, like a plasma, fill the table with long strings.
+        let runs: Vec<u8> = (0..100_000).map(|i| (i / 37 % 5) as u8).collect();
+        for indices in &[vec![], vec![7], vec![1, 1, 1, 1, 1], random, runs] {
+            assert_eq!(&lzw_decode(&lzw_encode(indices)), indices);
+        }
+    }
+
+    #[test]
+    fn codes() {
+        // Clear, 0, 1, the code for "0 1", and the end code, in 9 bits each.
+        let codes = [256u16, 0, 1, 258, 257];
+        let mut expected = BitWriter {
+            bytes: vec![],
+            buffer: 0,
+            bits: 0,
+        };
+        for &code in &codes {
+            expected.write(code, 9);
+        }
+        assert_eq!(lzw_encode(&[0, 1, 0, 1]), expected.finish());
+    }
+
+    #[test]
+    fn file_layout() {
+        let palette = [Color::default(); 256];
+        let mut out = vec![];
+        write_gif(&mut out, 2, 1, &palette, &[vec![0, 0], vec![1, 0]], 5).unwrap();
+        assert_eq!(&out[..13], b"GIF89a\x02\x00\x01\x00\xf7\x00\x00");
+        let frames = &out[13 + 3 * 256 + 19..];
+        assert_eq!(&frames[..8], [0x21, 0xf9, 4, 0, 5, 0, 0, 0]);
+        assert_eq!(frames[8], 0x2c);
+        assert_eq!(out.last(), Some(&0x3b));
+    }
+}
@@ -0,0 +1,215 @@
+//! The plasma effect: a sum of sine waves over the picture picks a colour from a palette for
+//! each pixel, and the palette cycles from one frame to the next, so that the colours seem to
+//! flow.
+//!
+//! cargo run -p plasma-effect -- [terminal | ppm FRAMES | gif FRAMES]
+//!
+//! In the terminal, the colours are characters from dark to light. The PPM frames are written
+//! to `plasma-000.ppm` and so on, and the GIF to `plasma.gif`.
+
+mod gif;
+
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::{stdout, BufWriter};
+use std::thread;
+use std::time::Duration;
+
+use bitmap::{Color, Image};
+use term_grid::{TermGrid, CLEAR};
+use write_a_ppm_file::{Format, PPMWritable};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 240;
+/// How far the palette moves from one frame to the next.
+const SPEED: u8 = 4;
+/// The characters of the terminal picture, from dark to light.
+const SHADES: &[u8] = b" .:-=+*#%@";
+
+/// The plasma, as the palette index of each pixel in the first frame, row by row.
+struct Plasma {
+    width: usize,
+    height: usize,
+    indices: Vec<u8>,
+}
+
+impl Plasma {
+    /// The plasma over `width` by `height` pixels, `x_scale` and `y_scale` times larger than
+    /// the pixels are: characters in a terminal are about twice as tall as they are wide.
+    fn new(width: usize, height: usize, x_scale: f64, y_scale: f64) -> Self {
+        let mut indices = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (x, y) = (x as f64 * x_scale, y as f64 * y_scale);
+                let value = (x / 16.0).sin()
+                    + (y / 8.0).sin()
+                    + ((x + y) / 16.0).sin()
+                    + ((x * x + y * y).sqrt() / 8.0).sin();
+                // From -4..=4 to the indices of the palette.
+                indices.push(((value + 4.0) / 8.0 * 255.0).round() as u8);
+            }
+        }
+        Plasma {
+            width,
+            height,
+            indices,
+        }
+    }
+
+    /// The palette indices of a frame, with the palette moved along.
+    fn frame(&self, frame: usize) -> Vec<u8> {
+        let shift = (frame as u8).wrapping_mul(SPEED);
+        self.indices.iter().map(|i| i.wrapping_add(shift)).collect()
+    }
+
+    fn image(&self, frame: usize, palette: &[Color; 256]) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        for (pixel, index) in image.data.iter_mut().zip(self.frame(frame)) {
+            *pixel = palette[usize::from(index)];
+        }
+        image
+    }
+
+    fn grid(&self, frame: usize) -> TermGrid {
+        let mut grid = TermGrid::new(self.width, self.height);
+        for (i, index) in self.frame(frame).into_iter().enumerate() {
+            let shade = SHADES[usize::from(index) * SHADES.len() / 256];
+            grid.set(i % self.width, i / self.width, shade as char);
+        }
+        grid
+    }
+}
+
+/// A palette that goes around the colour wheel, each of red, green and blue rising and falling
+/// once, a third of the way apart.
+fn palette() -> [Color; 256] {
+    let mut palette = [Color::default(); 256];
+    for (i, color) in palette.iter_mut().enumerate() {
+        let angle = i as f64 / 256.0 * 2.0 * PI;
+        let channel = |offset: f64| (127.5 + 127.5 * (angle + offset).cos()).round() as u8;
+        *color = Color {
+            red: channel(0.0),
+            green: channel(2.0 * PI / 3.0),
+            blue: channel(4.0 * PI / 3.0),
+        };
+    }
+    palette
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mode = args.next().unwrap_or_else(|| "terminal".to_owned());
+    let frames = args.next().map_or(256 / usize::from(SPEED), |n| {
+        n.parse().expect("a number of frames")
+    });
+
+    match &mode[..] {
+        "terminal" => {
+            let plasma = Plasma::new(79, 24, 2.0, 4.0);
+            print!("{}", CLEAR);
+            for frame in 0.. {
+                plasma.grid(frame).show(stdout().lock()).unwrap();
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+        "ppm" => {
+            let (plasma, palette) = (Plasma::new(WIDTH, HEIGHT, 1.0, 1.0), palette());
+            for frame in 0..frames {
+                let filename = format!("plasma-{:03}.ppm", frame);
+                plasma
+                    .image(frame, &palette)
+                    .write_ppm(&filename, Format::Raw)
+                    .unwrap();
+            }
+            println!("Wrote {} frames", frames);
+        }
+        "gif" => {
+            let plasma = Plasma::new(WIDTH, HEIGHT, 1.0, 1.0);
+            let indices: Vec<Vec<u8>> = (0..frames).map(|frame| plasma.frame(frame)).collect();
+            let file = File::create("plasma.gif").expect("could not create plasma.gif");
+            gif::write_gif(
+                BufWriter::new(file),
+                WIDTH as u16,
+                HEIGHT as u16,
+                &palette(),
+                &indices,
+                5,
+            )
+            .unwrap();
+            println!("Wrote plasma.gif");
+        }
+        _ => panic!("usage: [terminal | ppm FRAMES | gif FRAMES]"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use read_a_ppm_file::read_ppm_file;
+
+    #[test]
+    fn first_frame() {
+        let image = Plasma::new(48, 32, 4.0, 4.0).image(0, &palette());
+        let expected = read_ppm_file("resources/first-frame.ppm").unwrap();
+        assert_eq!(
+            (image.width, image.height),
+            (expected.width, expected.height)
+        );
+        // The sines may round differently on other platforms. That can move a pixel to a
+        // neighbouring colour of the palette, at most 4 away in each channel, and change the
+        // palette itself by 1.
+        let close = |a: u8, b: u8| (i16::from(a) - i16::from(b)).abs() <= 5;
+        let different: Vec<_> = image
+            .data
+            .iter()
+            .zip(&expected.data)
+            .enumerate()
+            .filter(|(_, (a, b))| {
+                !(close(a.red, b.red) && close(a.green, b.green) && close(a.blue, b.blue))
+            })
+            .collect();
+        assert_eq!(different, []);
+    }
+
+    #[test]
+    fn palette_cycles() {
+        let plasma = Plasma::new(16, 8, 1.0, 1.0);
+        // After going all the way around, the palette is back where it started.
+        assert_eq!(plasma.frame(256 / usize::from(SPEED)), plasma.frame(0));
+        let (first, second) = (plasma.frame(0), plasma.frame(1));
+        assert!(first
+            .iter()
+            .zip(&second)
+            .all(|(a, b)| b.wrapping_sub(*a) == SPEED));
+    }
+
+    #[test]
+    fn palette_wraps_around() {
+        let palette = palette();
+        assert_eq!(
+            palette[0],
+            Color {
+                red: 255,
+                green: 64,
+                blue: 64
+            }
+        );
+        // Neighbouring colours are close, including the last and the first.
+        for i in 0..256 {
+            let (a, b) = (palette[i], palette[(i + 1) % 256]);
+            assert!((i16::from(a.red) - i16::from(b.red)).abs() <= 4);
+            assert!((i16::from(a.green) - i16::from(b.green)).abs() <= 4);
+        }
+    }
+
+    #[test]
+    fn terminal_shades() {
+        let grid = Plasma::new(79, 24, 2.0, 4.0).grid(0);
+        assert_eq!((grid.width(), grid.height()), (79, 24));
+        // The waves go from dark to light and back several times across the terminal.
+        let text = grid.to_string();
+        let shades = SHADES.iter().filter(|&&c| text.contains(c as char)).count();
+        assert!(shades >= 8, "{}", text);
+    }
+}
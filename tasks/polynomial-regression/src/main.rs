@@ -0,0 +1,68 @@
+use nalgebra::{DMatrix, DVector};
+
+/// Fits a polynomial of the given degree to the data points with the method of least squares.
+///
+/// Returns the coefficients, starting with the constant term, or `None` if the fit is not
+/// uniquely determined (for example, if there are fewer distinct points than coefficients).
+fn polyfit(x: &[f64], y: &[f64], degree: usize) -> Option<Vec<f64>> {
+    assert_eq!(x.len(), y.len());
+
+    // Each row of the Vandermonde matrix holds the powers 1, x, x², ... of one data point.
+    let vandermonde = DMatrix::from_fn(x.len(), degree + 1, |i, j| x[i].powi(j as i32));
+    let y = DVector::from_column_slice(y.len(), y);
+
+    // The least squares solution satisfies the normal equations (VᵀV)·c = Vᵀy.
+    let transposed = vandermonde.transpose();
+    let lhs = &transposed * &vandermonde;
+    let rhs = &transposed * y;
+
+    lhs.lu().solve(&rhs).map(|c| c.iter().cloned().collect())
+}
+
+fn main() {
+    let x: Vec<f64> = (0..=10).map(f64::from).collect();
+    let y = [1., 6., 17., 34., 57., 86., 121., 162., 209., 262., 321.];
+
+    let coefficients = polyfit(&x, &y, 2).unwrap();
+    let terms: Vec<_> = coefficients
+        .iter()
+        .enumerate()
+        .map(|(power, c)| match power {
+            0 => format!("{:.4}", c),
+            1 => format!("{:.4}x", c),
+            _ => format!("{:.4}x^{}", c, power),
+        })
+        .collect();
+    println!("y = {}", terms.join(" + "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::polyfit;
+
+    #[test]
+    fn task_data() {
+        let x: Vec<f64> = (0..=10).map(f64::from).collect();
+        let y = [1., 6., 17., 34., 57., 86., 121., 162., 209., 262., 321.];
+
+        let coefficients = polyfit(&x, &y, 2).unwrap();
+        for (c, expected) in coefficients.iter().zip(&[1., 2., 3.]) {
+            assert!((c - expected).abs() < 1e-9, "{:?}", coefficients);
+        }
+    }
+
+    #[test]
+    fn noisy_line() {
+        // The best line through these points is y = 0.3 + 0.8x.
+        let x = [0., 1., 2., 3.];
+        let y = [0.5, 0.5, 2.5, 2.5];
+        let coefficients = polyfit(&x, &y, 1).unwrap();
+        assert!((coefficients[0] - 0.3).abs() < 1e-12);
+        assert!((coefficients[1] - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn underdetermined() {
+        assert_eq!(polyfit(&[1., 1., 1.], &[1., 2., 3.], 2), None);
+    }
+}
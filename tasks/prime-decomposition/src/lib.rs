@@ -1,35 +1,204 @@
-pub fn factor(mut nb: usize) -> Vec<usize> {
+//! Factorization of 64-bit integers.
+//!
+//! Small factors are removed by trial division. Whatever is left over is split by Pollard's rho
+//! algorithm, using a deterministic Miller-Rabin test to recognize when a factor is prime.
+
+/// Trial division is used for all factors below this bound.
+const TRIAL_DIVISION_BOUND: u64 = 1000;
+
+/// Returns the prime factors of `nb` in ascending order, with repetition.
+///
+/// The factorization of 0 and 1 is empty.
+pub fn factor(mut nb: u64) -> Vec<u64> {
     let mut result = vec![];
 
+    if nb < 2 {
+        return result;
+    }
+
     // First we take out all even factors.
-    while nb % 2 == 0 {
+    while nb.is_multiple_of(2) {
         result.push(2);
         nb /= 2;
     }
 
-    // Then (if any left) we take out the odd ones.
+    // Then (if any left) we take out the small odd ones.
     let mut cand = 3;
-    let mut max_bound = (nb as f32).sqrt() as usize + 1;
-
-    while cand <= max_bound {
-        while nb % cand == 0 {
+    while cand < TRIAL_DIVISION_BOUND && cand * cand <= nb {
+        while nb.is_multiple_of(cand) {
             result.push(cand);
             nb /= cand;
         }
-        max_bound = (nb as f32).sqrt() as usize + 1;
         cand += 2;
     }
 
+    // Anything left over either is prime or only has large factors.
     if nb > 1 {
-        result.push(nb);
+        let mut stack = vec![nb];
+        while let Some(n) = stack.pop() {
+            if is_prime(n) {
+                result.push(n);
+            } else {
+                let d = pollard_rho(n);
+                stack.push(d);
+                stack.push(n / d);
+            }
+        }
     }
 
+    result.sort();
     result
 }
-#[test]
-fn test_basic() {
-    assert!(factor(5) == vec![5]);
-    assert!(factor(15) == vec![3, 5]);
-    assert!(factor(16) == vec![2, 2, 2, 2]);
-    assert!(factor(10287) == vec![3, 3, 3, 3, 127]);
+
+/// Computes `a * b mod m` without overflowing.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// Computes `a + b mod m` without overflowing.
+fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((u128::from(a) + u128::from(b)) % u128::from(m)) as u64
+}
+
+/// Computes `base ^ exp mod m` by repeated squaring.
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// A Miller-Rabin test that is deterministic for all 64-bit integers.
+pub fn is_prime(n: u64) -> bool {
+    // Testing these bases is sufficient for all n < 3.3 * 10^24.
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Finds a non-trivial divisor of the odd composite number `n` with Pollard's rho algorithm,
+/// using Floyd's cycle detection.
+fn pollard_rho(n: u64) -> u64 {
+    // The polynomial x^2 + c occasionally fails to find a factor; try the next constant if so.
+    for c in 1.. {
+        let f = |x| add_mod(mul_mod(x, x, n), c, n);
+        let (mut x, mut y, mut d) = (2, 2, 1);
+
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.abs_diff(y), n);
+        }
+
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_basic() {
+        assert_eq!(factor(5), vec![5]);
+        assert_eq!(factor(15), vec![3, 5]);
+        assert_eq!(factor(16), vec![2, 2, 2, 2]);
+        assert_eq!(factor(10287), vec![3, 3, 3, 3, 127]);
+    }
+
+    #[test]
+    fn test_trivial() {
+        assert_eq!(factor(0), vec![]);
+        assert_eq!(factor(1), vec![]);
+        assert_eq!(factor(2), vec![2]);
+    }
+
+    #[test]
+    fn test_mersenne() {
+        // 2^59 - 1, from the task description
+        assert_eq!(factor((1 << 59) - 1), vec![179_951, 3_203_431_780_337]);
+    }
+
+    #[test]
+    fn test_large() {
+        assert_eq!(
+            factor(u64::MAX),
+            vec![3, 5, 17, 257, 641, 65_537, 6_700_417]
+        );
+        // The largest prime below 2^64 and a square of a large prime.
+        assert_eq!(
+            factor(18_446_744_073_709_551_557),
+            vec![18_446_744_073_709_551_557]
+        );
+        assert_eq!(
+            factor(4_294_967_291 * 4_294_967_291),
+            vec![4_294_967_291; 2]
+        );
+    }
+
+    #[test]
+    fn test_near_overflow() {
+        assert_eq!(add_mod(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+        // A semiprime 38 below 2^64, whose factors are both beyond trial division.
+        assert_eq!(
+            factor(18_446_744_073_709_551_577),
+            vec![139_646_831, 132_095_686_967]
+        );
+    }
+
+    #[test]
+    fn test_product_of_factors() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let n = rng.gen_range(1, u64::MAX);
+            let factors = factor(n);
+
+            assert_eq!(factors.iter().product::<u64>(), n);
+            assert!(factors.iter().all(|&f| is_prime(f)));
+        }
+    }
 }
@@ -7,4 +7,5 @@ fn main() {
     println!("Factors of 15: {:?}", factor(15));
     println!("Factors of 16: {:?}", factor(16));
     println!("Factors of 10287: {:?}", factor(10_287));
+    println!("Factors of 2^59 - 1: {:?}", factor((1 << 59) - 1));
 }
@@ -0,0 +1,295 @@
+//! A priority queue built on a binary min-heap, where the lowest priority comes out first.
+//!
+//! The heap is a complete binary tree stored level by level in a vector: the children of the
+//! entry at `i` are at `2i + 1` and `2i + 2`, and no entry has a lower priority than its parent.
+//! A new entry is added at the end and sifted up past the parents with higher priorities; the
+//! first entry is taken out by moving the last one in its place and sifting it down.
+//!
+//! Each entry has a handle that follows it around the heap, so that its priority can be
+//! lowered later, as Dijkstra's and Prim's algorithms need.
+
+/// Names an entry of the queue, from its insertion until it is popped. After that, the handle
+/// may be given to a new entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle(usize);
+
+struct Entry<P, T> {
+    priority: P,
+    item: T,
+    handle: Handle,
+}
+
+pub struct PriorityQueue<P, T> {
+    heap: Vec<Entry<P, T>>,
+    /// Where the entry of each handle is in the heap, or `None` once it was popped.
+    positions: Vec<Option<usize>>,
+    /// The handles of popped entries, which new entries can reuse.
+    free: Vec<usize>,
+}
+
+impl<P: Ord, T> PriorityQueue<P, T> {
+    pub fn new() -> Self {
+        PriorityQueue {
+            heap: vec![],
+            positions: vec![],
+            free: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Swaps two entries of the heap, and keeps track of where they went.
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i].handle.0] = Some(i);
+        self.positions[self.heap[j].handle.0] = Some(j);
+    }
+
+    /// Moves the entry at `i` up while its parent has a higher priority.
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[parent].priority <= self.heap[i].priority {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Moves the entry at `i` down while one of its children has a lower priority, swapping it
+    /// with the lower of the two, so that it becomes the parent of the other.
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+            let mut lowest = i;
+            if left < self.heap.len() && self.heap[left].priority < self.heap[lowest].priority {
+                lowest = left;
+            }
+            if right < self.heap.len() && self.heap[right].priority < self.heap[lowest].priority {
+                lowest = right;
+            }
+            if lowest == i {
+                break;
+            }
+            self.swap(i, lowest);
+            i = lowest;
+        }
+    }
+
+    pub fn push(&mut self, priority: P, item: T) -> Handle {
+        let handle = match self.free.pop() {
+            Some(index) => {
+                self.positions[index] = Some(self.heap.len());
+                Handle(index)
+            }
+            None => {
+                self.positions.push(Some(self.heap.len()));
+                Handle(self.positions.len() - 1)
+            }
+        };
+        self.heap.push(Entry {
+            priority,
+            item,
+            handle,
+        });
+        self.sift_up(self.heap.len() - 1);
+        handle
+    }
+
+    /// The entry with the lowest priority.
+    pub fn peek_min(&self) -> Option<(&P, &T)> {
+        self.heap
+            .first()
+            .map(|entry| (&entry.priority, &entry.item))
+    }
+
+    /// Takes out the entry with the lowest priority.
+    pub fn pop_min(&mut self) -> Option<(P, T)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let entry = self.heap.pop().unwrap();
+        self.positions[entry.handle.0] = None;
+        self.free.push(entry.handle.0);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((entry.priority, entry.item))
+    }
+
+    /// The priority of the entry of `handle`, if it is still in the queue.
+    pub fn priority(&self, handle: Handle) -> Option<&P> {
+        self.positions[handle.0].map(|i| &self.heap[i].priority)
+    }
+
+    /// Lowers the priority of the entry of `handle`, which only ever moves it up the heap.
+    ///
+    /// # Panics
+    ///
+    /// If the entry was popped already, or the new priority is higher than the old one.
+    pub fn decrease_key(&mut self, handle: Handle, priority: P) {
+        let i = self.positions[handle.0].expect("the entry was popped");
+        assert!(
+            priority <= self.heap[i].priority,
+            "the priority can only decrease"
+        );
+        self.heap[i].priority = priority;
+        self.sift_up(i);
+    }
+}
+
+impl<P: Ord, T> Default for PriorityQueue<P, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn main() {
+    let mut queue = PriorityQueue::new();
+    queue.push(3, "Clear drains");
+    queue.push(4, "Feed cat");
+    let tea = queue.push(5, "Make tea");
+    queue.push(1, "Solve RC tasks");
+    queue.push(2, "Tax return");
+    // Tea cannot wait any longer.
+    queue.decrease_key(tea, 0);
+
+    while let Some((priority, task)) = queue.pop_min() {
+        println!("{} {}", priority, task);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    impl<P: Ord, T> PriorityQueue<P, T> {
+        fn check_invariants(&self) {
+            for i in 1..self.heap.len() {
+                assert!(self.heap[(i - 1) / 2].priority <= self.heap[i].priority);
+            }
+            for (i, entry) in self.heap.iter().enumerate() {
+                assert_eq!(self.positions[entry.handle.0], Some(i));
+            }
+            let in_heap = self.positions.iter().filter(|p| p.is_some()).count();
+            assert_eq!(in_heap, self.heap.len());
+            assert_eq!(in_heap + self.free.len(), self.positions.len());
+        }
+    }
+
+    #[test]
+    fn task_example() {
+        let mut queue = PriorityQueue::new();
+        queue.push(3, "Clear drains");
+        queue.push(4, "Feed cat");
+        queue.push(5, "Make tea");
+        queue.push(1, "Solve RC tasks");
+        queue.push(2, "Tax return");
+        assert_eq!(queue.peek_min(), Some((&1, &"Solve RC tasks")));
+        let tasks: Vec<_> = std::iter::from_fn(|| queue.pop_min()).collect();
+        assert_eq!(
+            tasks,
+            [
+                (1, "Solve RC tasks"),
+                (2, "Tax return"),
+                (3, "Clear drains"),
+                (4, "Feed cat"),
+                (5, "Make tea"),
+            ]
+        );
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop_min(), None);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut queue = PriorityQueue::new();
+        let handles: Vec<Handle> = (0..10).map(|i| queue.push(10 * i, i)).collect();
+        queue.decrease_key(handles[7], 5);
+        queue.decrease_key(handles[9], 9);
+        assert_eq!(queue.priority(handles[7]), Some(&5));
+        queue.check_invariants();
+        let items: Vec<_> = std::iter::from_fn(|| queue.pop_min().map(|(_, i)| i)).collect();
+        assert_eq!(items, [0, 7, 9, 1, 2, 3, 4, 5, 6, 8]);
+        assert_eq!(queue.priority(handles[7]), None);
+    }
+
+    #[test]
+    fn handles_are_reused() {
+        let mut queue = PriorityQueue::new();
+        for i in 0..10 {
+            queue.push(i, ());
+        }
+        for i in 10..1000 {
+            queue.pop_min();
+            queue.push(i, ());
+        }
+        queue.check_invariants();
+        assert_eq!(queue.positions.len(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn increase_key() {
+        let mut queue = PriorityQueue::new();
+        let handle = queue.push(1, ());
+        queue.decrease_key(handle, 2);
+    }
+
+    #[test]
+    fn matches_sorted_reference() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let mut queue = PriorityQueue::new();
+            // The entries in the queue, as (priority, item, handle), where the items are the
+            // numbers of the insertions.
+            let mut reference: Vec<(u32, usize, Handle)> = vec![];
+            for step in 0..500 {
+                match rng.gen_range(0, 10) {
+                    0..=3 => {
+                        let priority = rng.gen_range(0, 100);
+                        let handle = queue.push(priority, step);
+                        reference.push((priority, step, handle));
+                    }
+                    4..=6 => {
+                        reference.sort_unstable();
+                        match queue.pop_min() {
+                            None => assert!(reference.is_empty()),
+                            Some((priority, item)) => {
+                                // Equal priorities may come out in any order.
+                                assert_eq!(priority, reference[0].0);
+                                let i = reference.iter().position(|e| e.1 == item).unwrap();
+                                assert_eq!(reference.remove(i).0, priority);
+                            }
+                        }
+                    }
+                    _ => {
+                        if let Some(entry) = reference.choose_mut(&mut rng) {
+                            entry.0 = rng.gen_range(0, entry.0 + 1);
+                            queue.decrease_key(entry.2, entry.0);
+                        }
+                    }
+                }
+                queue.check_invariants();
+                assert_eq!(queue.len(), reference.len());
+            }
+
+            reference.sort_unstable();
+            let priorities: Vec<u32> =
+                std::iter::from_fn(|| queue.pop_min().map(|(p, _)| p)).collect();
+            let expected: Vec<u32> = reference.iter().map(|e| e.0).collect();
+            assert_eq!(priorities, expected);
+        }
+    }
+}
@@ -1,7 +1,4 @@
 fn main() {
-    let x = "fn main() {\n    let x = ";
-    let y = "print!(\"{}{:?};\n    let y = {:?};\n    {}\", x, x, y, y)\n}\n";
-    print!("{}{:?};
-    let y = {:?};
-    {}", x, x, y, y)
+    let source = "fn main() {\n    let source = @;\n    print!(\"{}\", source.replacen('@', &format!(\"{:?}\", source), 1));\n}\n";
+    print!("{}", source.replacen('@', &format!("{:?}", source), 1));
 }
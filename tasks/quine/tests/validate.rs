@@ -1,24 +1,23 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::PathBuf;
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 
 #[test]
 fn validate_quine() {
-    let contents = {
-        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/main.rs");
-        let mut code = File::open(path).unwrap();
-        let mut contents = String::new();
-        code.read_to_string(&mut contents).unwrap();
-        contents
-    };
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/main.rs");
+    let source = fs::read(path).unwrap();
 
-    let output = Command::new("cargo")
-        .args(&["run", "--release"])
+    // The binary that cargo built for this test, rather than another build through `cargo run`,
+    // whose own messages could get mixed in.
+    let output = Command::new(env!("CARGO_BIN_EXE_quine"))
         .output()
         .expect("failed to execute binary");
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
 
-    let output = String::from_utf8_lossy(&output.stdout);
-
-    assert_eq!(contents, output);
+    // Byte for byte, down to the newline at the end.
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().as_bytes(),
+        &source[..]
+    );
 }
@@ -1,106 +1,57 @@
-use reqwest::Url;
-use serde::Deserialize;
-use serde_json::Value;
-
-#[derive(Deserialize)]
-pub struct Task {
-    #[serde(rename = "pageid")]
-    page_id: u64,
-    pub title: String,
-}
-
-#[derive(Debug)]
-enum ParseError {
-    /// Something went wrong with the HTTP request to the API.
-    Http(reqwest::Error),
-
-    /// There was a problem parsing the API response into JSON.
-    Json(serde_json::Error),
-
-    /// Unexpected JSON format from response
-    UnexpectedFormat,
-}
-
-impl From<serde_json::Error> for ParseError {
-    fn from(error: serde_json::Error) -> Self {
-        ParseError::Json(error)
+//! Counts the examples of each programming task on the wiki: the sections of its page, one per
+//! language, which start with a `=={{header|...}}==` heading.
+
+use wiki_client::{Result, WikiClient};
+
+/// The category of all the tasks that are not drafts.
+pub const TASKS: &str = "Programming Tasks";
+
+/// The number of examples in the wiki markup of a task.
+pub fn count_examples(content: &str) -> usize {
+    content.matches("=={{header|").count()
+}
+
+/// The title of each task and its number of examples, in alphabetical order.
+pub fn examples_per_task(client: &WikiClient) -> Result<Vec<(String, usize)>> {
+    let titles: Vec<String> = client
+        .category_members(TASKS)?
+        .into_iter()
+        .map(|task| task.title)
+        .collect();
+    let contents = client.page_contents(&titles)?;
+    Ok(contents
+        .into_iter()
+        .map(|(title, content)| {
+            let count = count_examples(&content);
+            (title, count)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_headers() {
+        let content = "{{task}}\nDo it.\n=={{header|Ada}}==\ncode\n=={{header|Rust}}==\n\
+                       ===Shorter===\n{{works with|Rust|1.40}}\ncode\n";
+        assert_eq!(count_examples(content), 2);
+        assert_eq!(count_examples("{{task}}\nNobody did it yet."), 0);
     }
-}
 
-impl From<reqwest::Error> for ParseError {
-    fn from(error: reqwest::Error) -> Self {
-        ParseError::Http(error)
+    #[test]
+    fn recorded_responses() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/responses.json");
+        let client = WikiClient::replay(path).unwrap();
+        let examples = examples_per_task(&client).unwrap();
+        assert_eq!(
+            examples,
+            [
+                ("100 doors".to_owned(), 3),
+                ("99 Bottles of Beer".to_owned(), 2),
+                ("A+B".to_owned(), 1),
+            ]
+        );
     }
 }
-
-fn construct_query_category(category: &str) -> Url {
-    let mut base_url = Url::parse("http://rosettacode.org/mw/api.php").unwrap();
-    let cat = format!("Category:{}", category);
-    let query_pairs = vec![
-        ("action", "query"),
-        ("format", "json"),
-        ("list", "categorymembers"),
-        ("cmlimit", "500"),
-        ("cmtitle", &cat),
-        ("continue", ""),
-    ];
-    base_url
-        .query_pairs_mut()
-        .extend_pairs(query_pairs.into_iter());
-    base_url
-}
-
-fn construct_query_task_content(task_id: &str) -> Url {
-    let mut base_url = Url::parse("http://rosettacode.org/mw/api.php").unwrap();
-    let mut query_pairs = vec![
-        ("action", "query"),
-        ("format", "json"),
-        ("prop", "revisions"),
-        ("rvprop", "content"),
-    ];
-    query_pairs.push(("pageids", task_id));
-    base_url
-        .query_pairs_mut()
-        .extend_pairs(query_pairs.into_iter());
-    base_url
-}
-
-fn query_api(url: Url) -> Result<Value, ParseError> {
-    Ok(reqwest::get(url)?.json()?)
-}
-
-fn parse_all_tasks(reply: &Value) -> Result<Vec<Task>, ParseError> {
-    let tasks_json = reply
-        .pointer("/query/categorymembers")
-        .and_then(|tasks| tasks.as_array())
-        .ok_or(ParseError::UnexpectedFormat)?;
-
-    tasks_json
-        .iter()
-        .map(|json| Task::deserialize(json).map_err(From::from))
-        .collect()
-}
-
-fn count_number_examples(task: &Value, task_id: u64) -> Result<u32, ParseError> {
-    let revisions = task
-        .pointer(&format!("/query/pages/{}/revisions", task_id.to_string()))
-        .and_then(|content| content.as_array())
-        .ok_or(ParseError::UnexpectedFormat)?;
-    let content = revisions[0]
-        .get("*")
-        .and_then(Value::as_str)
-        .ok_or(ParseError::UnexpectedFormat)?;
-    Ok(content.split("=={{header").count() as u32)
-}
-
-pub fn query_all_tasks() -> Vec<Task> {
-    let query = construct_query_category("Programming_Tasks");
-    let json: Value = query_api(query).unwrap();
-    parse_all_tasks(&json).unwrap()
-}
-
-pub fn query_a_task(task: &Task) -> u32 {
-    let query = construct_query_task_content(&task.page_id.to_string());
-    let json: Value = query_api(query).unwrap();
-    count_number_examples(&json, task.page_id).unwrap()
-}
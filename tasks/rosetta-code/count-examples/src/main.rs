@@ -1,8 +1,16 @@
-extern crate count_examples;
+//! cargo run -p count-examples -- [--record FILE | --replay FILE]
+//!
+//! With `--replay resources/responses.json`, counts the examples of a few tasks whose pages
+//! were recorded, without going online.
+
+use wiki_client::WikiClient;
+
 fn main() {
-    let all_tasks = count_examples::query_all_tasks();
-    for task in &all_tasks {
-        let count = count_examples::query_a_task(task);
-        println!("Task: {} has {} examples", task.title, count);
+    let client = WikiClient::from_args(std::env::args().skip(1)).unwrap();
+    let examples = count_examples::examples_per_task(&client).unwrap();
+    for (title, count) in &examples {
+        println!("{}: {} examples.", title, count);
     }
+    let total: usize = examples.iter().map(|(_, count)| count).sum();
+    println!("\nTotal: {} examples.", total);
 }
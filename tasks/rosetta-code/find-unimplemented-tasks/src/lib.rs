@@ -1,143 +1,82 @@
-use std::collections::{BTreeMap, HashSet};
+//! Finds the tasks that have no implementation in a language, from the wiki's categories: every
+//! task is in "Programming Tasks", and the tasks implemented in a language are in the category
+//! named after it.
 
-use reqwest::Url;
-use serde::Deserialize;
-use serde_json::Value;
+use std::collections::HashSet;
 
-/// A Rosetta Code task.
-#[derive(Clone, PartialEq, Eq, Hash, Debug, Deserialize)]
-pub struct Task {
-    /// The ID of the page containing the task in the MediaWiki API.
-    #[serde(rename = "pageid")]
-    pub id: u64,
+use wiki_client::{CategoryMember, Result, WikiClient};
 
-    /// The human-readable title of the task.
-    pub title: String,
-}
-
-/// Encapsulates errors that might occur during JSON parsing.
-#[derive(Debug)]
-enum TaskParseError {
-    /// Something went wrong with the HTTP request to the API.
-    Http(reqwest::Error),
-
-    /// Could not parse a URL
-    Url(reqwest::UrlError),
-
-    /// There was a problem parsing the API response into JSON.
-    Json(serde_json::Error),
+/// A Rosetta Code task: the ID of its page, and its title.
+pub type Task = CategoryMember;
 
-    /// The response JSON contained unexpected keys or values.
-    UnexpectedFormat,
+/// All the tasks that are not drafts, in the order the wiki lists them.
+pub fn all_tasks(client: &WikiClient) -> Result<Vec<Task>> {
+    client.category_members("Programming Tasks")
 }
 
-impl From<serde_json::Error> for TaskParseError {
-    fn from(err: serde_json::Error) -> Self {
-        TaskParseError::Json(err)
-    }
+/// The tasks that have no implementation in `lang`, in alphabetical order.
+pub fn unimplemented_tasks(client: &WikiClient, lang: &str) -> Result<Vec<Task>> {
+    // The category of a language holds more than tasks, like its draft tasks and its
+    // subcategories, but those are not in the list of tasks to begin with.
+    let implemented: HashSet<u64> = client
+        .category_members(lang)?
+        .into_iter()
+        .map(|page| page.id)
+        .collect();
+    let mut unimplemented: Vec<Task> = all_tasks(client)?
+        .into_iter()
+        .filter(|task| !implemented.contains(&task.id))
+        .collect();
+    unimplemented.sort_by(|a, b| a.title.cmp(&b.title));
+    Ok(unimplemented)
 }
 
-impl From<reqwest::UrlError> for TaskParseError {
-    fn from(err: reqwest::UrlError) -> Self {
-        TaskParseError::Url(err)
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl From<reqwest::Error> for TaskParseError {
-    fn from(err: reqwest::Error) -> Self {
-        TaskParseError::Http(err)
+    fn client() -> WikiClient {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/responses.json");
+        WikiClient::replay(path).unwrap()
     }
-}
-
-/// Represents a category of pages on Rosetta Code, such as "Rust".
-struct Category {
-    name: String,
-    continue_params: Option<BTreeMap<String, String>>,
-}
 
-impl Category {
-    fn new(name: &str) -> Category {
-        let mut continue_params = BTreeMap::new();
-        continue_params.insert("continue".to_owned(), "".to_owned());
-
-        Category {
-            name: name.to_owned(),
-            continue_params: Some(continue_params),
-        }
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| &task.title[..]).collect()
     }
-}
-
-/// Sends a request to Rosetta Code through the MediaWiki API. If successful, returns the response
-/// as a JSON object.
-fn query_api(
-    category_name: &str,
-    continue_params: &BTreeMap<String, String>,
-) -> Result<Value, TaskParseError> {
-    let mut url = Url::parse("http://rosettacode.org/mw/api.php")?;
-    url.query_pairs_mut()
-        .append_pair("action", "query")
-        .append_pair("list", "categorymembers")
-        .append_pair("cmtitle", &format!("Category:{}", category_name))
-        .append_pair("cmlimit", "500")
-        .append_pair("format", "json")
-        .extend_pairs(continue_params);
 
-    Ok(reqwest::get(url)?.json()?)
-}
-
-/// Given a JSON object, parses the task information from the MediaWiki API response.
-fn parse_tasks(json: &Value) -> Result<Vec<Task>, TaskParseError> {
-    let tasks_json = json
-        .pointer("/query/categorymembers")
-        .and_then(Value::as_array)
-        .ok_or(TaskParseError::UnexpectedFormat)?;
-
-    tasks_json
-        .iter()
-        .map(|json| Task::deserialize(json).map_err(From::from))
-        .collect()
-}
-
-impl Iterator for Category {
-    type Item = Vec<Task>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.continue_params.as_ref()?;
-
-        query_api(&self.name, self.continue_params.as_ref()?)
-            .and_then(|result| {
-                // If there are more pages of results to request, save them for the next iteration.
-                self.continue_params =
-                    result
-                        .get("continue")
-                        .and_then(Value::as_object)
-                        .map(|continue_params| {
-                            continue_params
-                                .iter()
-                                .map(|(key, value)| {
-                                    (key.to_owned(), value.as_str().unwrap().to_owned())
-                                })
-                                .collect()
-                        });
+    #[test]
+    fn all_pages_of_tasks() {
+        // The tasks come in two responses.
+        let tasks = all_tasks(&client()).unwrap();
+        assert_eq!(
+            titles(&tasks),
+            [
+                "100 doors",
+                "24 game",
+                "Zebra puzzle",
+                "Abundant odd numbers",
+                "Yin and yang"
+            ]
+        );
+    }
 
-                parse_tasks(&result)
-            })
-            .map_err(|err| println!("Error parsing response: {:?}", err))
-            .ok()
+    #[test]
+    fn unimplemented_in_rust() {
+        let tasks = unimplemented_tasks(&client(), "Rust").unwrap();
+        assert_eq!(titles(&tasks), ["Abundant odd numbers", "Yin and yang"]);
+        assert_eq!(tasks[0].id, 21316);
     }
-}
 
-pub fn all_tasks() -> Vec<Task> {
-    Category::new("Programming Tasks").flatten().collect()
-}
+    #[test]
+    fn new_language() {
+        // Nothing is implemented in a language without pages yet.
+        let tasks = unimplemented_tasks(&client(), "Zig").unwrap();
+        assert_eq!(tasks.len(), 5);
+        assert_eq!(tasks[0].title, "100 doors");
+    }
 
-pub fn unimplemented_tasks(lang: &str) -> Vec<Task> {
-    let all_tasks = all_tasks().iter().cloned().collect::<HashSet<_>>();
-    let implemented_tasks = Category::new(lang).flatten().collect::<HashSet<_>>();
-    let mut unimplemented_tasks = all_tasks
-        .difference(&implemented_tasks)
-        .cloned()
-        .collect::<Vec<Task>>();
-    unimplemented_tasks.sort_by(|a, b| a.title.cmp(&b.title));
-    unimplemented_tasks
+    #[test]
+    fn unrecorded_language() {
+        assert!(unimplemented_tasks(&client(), "Ada").is_err());
+    }
 }
@@ -1,7 +1,22 @@
-extern crate find_unimplemented_tasks;
+//! cargo run -p find-unimplemented-tasks -- [language] [--record FILE | --replay FILE]
+//!
+//! With `--replay resources/responses.json`, uses a few recorded pages of the categories of
+//! tasks and of Rust, without going online.
+
+use std::env;
+
+use wiki_client::WikiClient;
 
 fn main() {
-    for task in find_unimplemented_tasks::unimplemented_tasks("Rust") {
+    let mut args = env::args().skip(1).peekable();
+    let lang = match args.peek() {
+        Some(arg) if !arg.starts_with("--") => args.next().unwrap(),
+        _ => "Rust".to_owned(),
+    };
+    let client = WikiClient::from_args(args).unwrap();
+    let tasks = find_unimplemented_tasks::unimplemented_tasks(&client, &lang).unwrap();
+    for task in &tasks {
         println!("{:6} {}", task.id, task.title);
     }
+    println!("\n{} tasks are not implemented in {}.", tasks.len(), lang);
 }
@@ -0,0 +1,124 @@
+//! Ranks the programming languages by how many pages are in their categories on the wiki,
+//! which are the subcategories of "Programming Languages". Most of the pages in the category of
+//! a language are its implementations of tasks.
+
+use wiki_client::{Result, WikiClient};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Language {
+    pub name: String,
+    /// How many pages are in its category.
+    pub pages: u64,
+}
+
+/// Every language with a category, in alphabetical order.
+pub fn languages(client: &WikiClient) -> Result<Vec<Language>> {
+    let sizes = client.subcategory_sizes("Programming Languages")?;
+    Ok(sizes
+        .into_iter()
+        .map(|(title, pages)| Language {
+            name: title.trim_start_matches("Category:").to_owned(),
+            pages,
+        })
+        .collect())
+}
+
+/// The languages from the most pages to the fewest, each with its rank. Languages with as many
+/// pages share the rank, and are in alphabetical order; the ones after them skip the ranks that
+/// they took up, so that three languages tied for first are followed by the fourth.
+pub fn ranking(mut languages: Vec<Language>) -> Vec<(usize, Language)> {
+    languages.sort_by(|a, b| b.pages.cmp(&a.pages).then_with(|| a.name.cmp(&b.name)));
+    let mut ranked: Vec<(usize, Language)> = Vec::with_capacity(languages.len());
+    for (i, language) in languages.into_iter().enumerate() {
+        let rank = match ranked.last() {
+            Some((rank, last)) if last.pages == language.pages => *rank,
+            _ => i + 1,
+        };
+        ranked.push((rank, language));
+    }
+    ranked
+}
+
+/// The languages ranked among the first `n`, which are more than `n` when several tie at the
+/// last rank.
+pub fn top(ranking: &[(usize, Language)], n: usize) -> &[(usize, Language)] {
+    let end = ranking
+        .iter()
+        .position(|&(rank, _)| rank > n)
+        .unwrap_or(ranking.len());
+    &ranking[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> WikiClient {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/resources/responses.json");
+        WikiClient::replay(path).unwrap()
+    }
+
+    fn language(name: &str, pages: u64) -> Language {
+        Language {
+            name: name.to_owned(),
+            pages,
+        }
+    }
+
+    fn ranks(ranking: &[(usize, Language)]) -> Vec<(usize, &str)> {
+        ranking
+            .iter()
+            .map(|(rank, language)| (*rank, &language.name[..]))
+            .collect()
+    }
+
+    #[test]
+    fn recorded_languages() {
+        // The languages come in two responses, and an empty category has no size.
+        let languages = languages(&client()).unwrap();
+        assert_eq!(languages.len(), 10);
+        assert_eq!(languages[0], language("Cherry", 0));
+        assert_eq!(languages[1], language("Go", 1468));
+        assert!(languages.contains(&language("Raku", 1389)));
+    }
+
+    #[test]
+    fn recorded_ranking() {
+        let ranking = ranking(languages(&client()).unwrap());
+        assert_eq!(
+            ranks(&ranking),
+            [
+                (1, "Go"),
+                (1, "Phix"),
+                (3, "Julia"),
+                (3, "Raku"),
+                (5, "Python"),
+                (6, "Kotlin"),
+                (6, "Perl"),
+                (6, "Rust"),
+                (9, "Wren"),
+                (10, "Cherry"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_at_the_cutoff() {
+        let ranking = ranking(languages(&client()).unwrap());
+        assert_eq!(top(&ranking, 1).len(), 2);
+        assert_eq!(top(&ranking, 2).len(), 2);
+        assert_eq!(top(&ranking, 5).len(), 5);
+        // Kotlin, Perl and Rust come all together.
+        assert_eq!(top(&ranking, 6).len(), 8);
+        assert_eq!(top(&ranking, 7).len(), 8);
+        assert_eq!(top(&ranking, 100).len(), 10);
+        assert!(top(&ranking, 0).is_empty());
+    }
+
+    #[test]
+    fn all_tied() {
+        let tied = ranking(vec![language("B", 7), language("A", 7), language("C", 7)]);
+        assert_eq!(ranks(&tied), [(1, "A"), (1, "B"), (1, "C")]);
+        assert!(ranking(vec![]).is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+//! cargo run -p rank-languages-by-popularity -- [top] [--record FILE | --replay FILE]
+//!
+//! With `--replay resources/responses.json`, ranks a few languages whose categories were
+//! recorded, without going online.
+
+use std::env;
+
+use rank_languages_by_popularity::{languages, ranking, top};
+use wiki_client::WikiClient;
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    let n = match args.peek() {
+        Some(arg) if !arg.starts_with("--") => args.next().unwrap().parse().expect("a number"),
+        _ => 10,
+    };
+    let client = WikiClient::from_args(args).unwrap();
+    let ranking = ranking(languages(&client).unwrap());
+    let shown = top(&ranking, n);
+    for (i, (rank, language)) in shown.iter().enumerate() {
+        // Tied ranks are marked with an equals sign.
+        let tied = ranking
+            .get(i + 1)
+            .into_iter()
+            .chain(i.checked_sub(1).and_then(|j| ranking.get(j)))
+            .any(|(other, _)| other == rank);
+        let mark = if tied { "=" } else { " " };
+        println!("{:4}{} {:5} {}", rank, mark, language.pages, language.name);
+    }
+    println!("\n{} of {} languages.", shown.len(), ranking.len());
+}
@@ -1,7 +1,10 @@
-use std::iter::repeat;
+use std::iter::repeat_n;
 
 pub const INPUT: &str = r"WWWWWWWWWWWWBWWWWWWWWWWWWBBBWWWWWWWWWWWWWWWWWWWWWWWWBWWWWWWWWWWWWWW";
 
+/// Encodes runs as a count followed by the repeated character, e.g. `12W1B`.
+///
+/// Digits cannot be told apart from the counts, so the input must not contain any.
 pub fn encode(value: &str) -> String {
     let mut ret = String::new();
     let mut chars = value.chars();
@@ -35,7 +38,7 @@ pub fn decode(value: &str) -> Result<String, String> {
     let mut start = 0;
 
     for (i, c) in value.char_indices() {
-        if c.is_numeric() {
+        if c.is_ascii_digit() {
             continue;
         }
         if i == start {
@@ -43,18 +46,132 @@ pub fn decode(value: &str) -> Result<String, String> {
         }
 
         let ret_s = &value[start..i];
-        let ret = ret_s.parse::<usize>().unwrap();
+        let ret = ret_s
+            .parse::<usize>()
+            .map_err(|_| format!("count too large: {}", ret_s))?;
 
-        let repeated: String = repeat(c).take(ret).collect();
-        start = i + 1;
+        let repeated: String = repeat_n(c, ret).collect();
+        start = i + c.len_utf8();
 
         result.push_str(&repeated[..]);
     }
+    if start != value.len() {
+        return Err(format!("missing character after {}", &value[start..]));
+    }
     Ok(result)
 }
 
-#[test]
-fn test_encode_decode() {
-    assert_eq!(decode(&encode(INPUT)[..]).unwrap(), INPUT);
-    assert_eq!(decode("a"), Err("expected digit, found a".to_string()));
+/// Starts a run in the binary format: `ESCAPE count byte` stands for `count` copies of `byte`.
+pub const ESCAPE: u8 = 0xFF;
+
+/// Shorter runs take less room as they are, unless they are made of the escape byte.
+const MIN_RUN: usize = 4;
+
+/// Encodes arbitrary bytes. Bytes other than `ESCAPE` stand for themselves, unless they repeat
+/// at least four times in a row.
+pub fn encode_bytes(data: &[u8]) -> Vec<u8> {
+    let mut ret = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        let byte = data[i];
+        let run = data[i..]
+            .iter()
+            .take(usize::from(u8::MAX))
+            .take_while(|&&b| b == byte)
+            .count();
+
+        if run >= MIN_RUN || byte == ESCAPE {
+            ret.extend_from_slice(&[ESCAPE, run as u8, byte]);
+        } else {
+            ret.extend(repeat_n(byte, run));
+        }
+        i += run;
+    }
+    ret
+}
+
+pub fn decode_bytes(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut ret = Vec::with_capacity(data.len());
+    let mut bytes = data.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte != ESCAPE {
+            ret.push(byte);
+            continue;
+        }
+        match (bytes.next(), bytes.next()) {
+            (Some(0), Some(_)) => return Err("run of length 0".to_string()),
+            (Some(&count), Some(&byte)) => ret.extend(repeat_n(byte, usize::from(count))),
+            _ => return Err("truncated run".to_string()),
+        }
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(decode(&encode(INPUT)[..]).unwrap(), INPUT);
+        assert_eq!(decode("a"), Err("expected digit, found a".to_string()));
+    }
+
+    #[test]
+    fn task_example() {
+        assert_eq!(encode(INPUT), "12W1B12W3B24W1B14W");
+        assert_eq!(decode("12W1B12W3B24W1B14W").unwrap(), INPUT);
+        assert_eq!(encode(""), "");
+    }
+
+    #[test]
+    fn malformed_text() {
+        assert_eq!(decode("12W3"), Err("missing character after 3".to_string()));
+        assert!(decode("99999999999999999999999W").is_err());
+        assert_eq!(decode("2é1ü").unwrap(), "ééü");
+    }
+
+    #[test]
+    fn random_text_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let len = rng.gen_range(0, 100);
+            // Few letters, so that there are long runs.
+            let text: String = (0..len)
+                .map(|_| ['a', 'b', 'ß'][rng.gen_range(0, 3)])
+                .collect();
+            assert_eq!(decode(&encode(&text)).unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        assert_eq!(encode_bytes(b"abbbcccc"), b"abbb\xFF\x04c");
+        assert_eq!(encode_bytes(&[ESCAPE, 1]), [ESCAPE, 1, ESCAPE, 1]);
+        assert_eq!(encode_bytes(&[0; 300]), [ESCAPE, 255, 0, ESCAPE, 45, 0]);
+        assert_eq!(decode_bytes(b"ab\xFF\x03c").unwrap(), b"abccc");
+        assert_eq!(decode_bytes(b"a\xFF\x03"), Err("truncated run".to_string()));
+        assert_eq!(
+            decode_bytes(b"\xFF\x00a"),
+            Err("run of length 0".to_string())
+        );
+    }
+
+    #[test]
+    fn random_bytes_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let len = rng.gen_range(0, 1000);
+            // Runs of random lengths, to exercise both the literal and the escaped forms.
+            let mut data = vec![];
+            while data.len() < len {
+                let byte = *[0, 7, ESCAPE].choose(&mut rng).unwrap();
+                let run = rng.gen_range(1, 300);
+                data.extend(repeat_n(byte, run));
+            }
+            assert_eq!(decode_bytes(&encode_bytes(&data)).unwrap(), data);
+        }
+    }
 }
@@ -1,6 +1,4 @@
-extern crate run_length_encoding;
-
-use run_length_encoding::{decode, encode, INPUT};
+use run_length_encoding::{decode, decode_bytes, encode, encode_bytes, INPUT};
 
 fn main() {
     let enc = encode(INPUT);
@@ -8,4 +6,8 @@ fn main() {
 
     let dec = decode(&enc[..]);
     println!("decoded {}", dec.unwrap());
+
+    let bytes = encode_bytes(INPUT.as_bytes());
+    println!("encoded bytes {:?}", bytes);
+    assert_eq!(decode_bytes(&bytes).unwrap(), INPUT.as_bytes());
 }
@@ -0,0 +1,67 @@
+//! A generic fourth-order Runge-Kutta integrator for ordinary differential equations of the form
+//! `y' = f(t, y)`.
+//!
+//! The state `Y` can be anything that can be added together and scaled by a step size, so the
+//! same integrator works for scalar equations as well as for systems of equations (such as the
+//! motion of a pendulum).
+
+use std::ops::{Add, Mul};
+
+/// Advances the solution of `y' = f(t, y)` by a single step of size `dt`, starting at `(t, y)`.
+pub fn rk4_step<Y, F>(f: &F, t: f64, y: Y, dt: f64) -> Y
+where
+    Y: Copy + Add<Output = Y> + Mul<f64, Output = Y>,
+    F: Fn(f64, Y) -> Y,
+{
+    let k1 = f(t, y) * dt;
+    let k2 = f(t + dt / 2.0, y + k1 * 0.5) * dt;
+    let k3 = f(t + dt / 2.0, y + k2 * 0.5) * dt;
+    let k4 = f(t + dt, y + k3) * dt;
+
+    y + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (1.0 / 6.0)
+}
+
+/// Solves `y' = f(t, y)` with the initial condition `y(t0) = y0`, taking `steps` steps of size
+/// `dt`.
+///
+/// Returns every computed point `(t, y)`, starting with the initial condition, so the result has
+/// `steps + 1` elements.
+pub fn rk4<Y, F>(f: F, y0: Y, t0: f64, dt: f64, steps: usize) -> Vec<(f64, Y)>
+where
+    Y: Copy + Add<Output = Y> + Mul<f64, Output = Y>,
+    F: Fn(f64, Y) -> Y,
+{
+    let mut points = Vec::with_capacity(steps + 1);
+    let mut y = y0;
+    points.push((t0, y));
+
+    for i in 0..steps {
+        // Computing `t` from the step index avoids accumulating rounding errors.
+        let t = t0 + i as f64 * dt;
+        y = rk4_step(&f, t, y, dt);
+        points.push((t0 + (i + 1) as f64 * dt, y));
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rk4;
+
+    #[test]
+    fn exponential_growth() {
+        // y' = y, y(0) = 1 has the solution y = e^t.
+        let points = rk4(|_, y| y, 1.0, 0.0, 0.01, 100);
+        let &(t, y) = points.last().unwrap();
+
+        assert!((t - 1.0).abs() < 1e-12);
+        assert!((y - std::f64::consts::E).abs() < 1e-9);
+    }
+
+    #[test]
+    fn returns_initial_condition() {
+        let points = rk4(|t, _| t, 5.0, 2.0, 0.5, 0);
+        assert_eq!(points, vec![(2.0, 5.0)]);
+    }
+}
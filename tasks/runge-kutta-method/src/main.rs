@@ -0,0 +1,44 @@
+use runge_kutta_method::rk4;
+
+/// The differential equation from the task: `y' = t * sqrt(y)`.
+fn derivative(t: f64, y: f64) -> f64 {
+    t * y.sqrt()
+}
+
+/// The analytic solution of the equation with `y(0) = 1`.
+fn exact(t: f64) -> f64 {
+    (t * t + 4.0).powi(2) / 16.0
+}
+
+fn main() {
+    let points = rk4(derivative, 1.0, 0.0, 0.1, 100);
+
+    // Only print the values at whole-number times.
+    for &(t, y) in points.iter().step_by(10) {
+        println!(
+            "y({:>4.1}) = {:>12.6}\t error: {:e}",
+            t,
+            y,
+            (exact(t) - y).abs()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_within_task_bounds() {
+        let points = rk4(derivative, 1.0, 0.0, 0.1, 100);
+
+        for &(t, y) in points.iter().step_by(10) {
+            // The task output shows relative errors on the order of 1e-7 or smaller.
+            assert!((exact(t) - y).abs() / exact(t) < 1e-6);
+        }
+
+        let &(t, y) = points.last().unwrap();
+        assert!((t - 10.0).abs() < 1e-9);
+        assert!((y - 675.999949).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,409 @@
+//! Sends an email with a minimal SMTP client, upgrading the connection with `STARTTLS` when
+//! the server offers it.
+//!
+//! cargo run -p send-email -- server[:port] from to subject body
+
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use rustls::{ClientConfig, ClientSession, StreamOwned};
+use webpki::DNSNameRef;
+
+struct Email {
+    from: String,
+    to: Vec<String>,
+    subject: String,
+    body: String,
+}
+
+/// A (possibly multi-line) reply from the server.
+#[derive(Debug, PartialEq)]
+struct Reply {
+    code: u16,
+    lines: Vec<String>,
+}
+
+#[derive(Debug)]
+enum Error {
+    Io(io::Error),
+    /// The server replied with something other than what the dialogue expected.
+    Unexpected {
+        command: String,
+        reply: Reply,
+    },
+    /// The name of the server is not a valid DNS name, so its certificate cannot be checked.
+    InvalidServerName(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Unexpected { command, reply } => write!(
+                f,
+                "{} was answered with {} {}",
+                command,
+                reply.code,
+                reply.lines.join(" ")
+            ),
+            Error::InvalidServerName(name) => write!(f, "invalid server name: {}", name),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Reads a reply. Every line starts with the same three-digit code, followed by a `-` if more
+/// lines follow, or a space on the last line.
+fn read_reply<R: BufRead>(reader: &mut R) -> io::Result<Reply> {
+    let mut lines = vec![];
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the server closed the connection",
+            ));
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed reply");
+
+        let code = line
+            .get(..3)
+            .and_then(|c| c.parse().ok())
+            .ok_or_else(invalid)?;
+        let text = line.get(4..).unwrap_or("").to_string();
+        lines.push(text);
+        match line.as_bytes().get(3) {
+            Some(b'-') => continue,
+            Some(b' ') | None => return Ok(Reply { code, lines }),
+            _ => return Err(invalid()),
+        }
+    }
+}
+
+/// Doubles the dots at the start of lines, so that none of them end the message early, and
+/// normalizes line endings to CRLF.
+fn dot_stuff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.starts_with('.') {
+                format!(".{}\r\n", line)
+            } else {
+                format!("{}\r\n", line)
+            }
+        })
+        .collect()
+}
+
+/// The client side of an SMTP session on top of any stream.
+struct Client<S: Read + Write> {
+    stream: BufReader<S>,
+}
+
+impl<S: Read + Write> Client<S> {
+    /// Waits for the greeting of the server.
+    fn connect(stream: S) -> Result<Self, Error> {
+        let mut client = Client {
+            stream: BufReader::new(stream),
+        };
+        client.expect("connection", 220)?;
+        Ok(client)
+    }
+
+    fn expect(&mut self, command: &str, code: u16) -> Result<Reply, Error> {
+        let reply = read_reply(&mut self.stream)?;
+        if reply.code != code {
+            return Err(Error::Unexpected {
+                command: command.to_string(),
+                reply,
+            });
+        }
+        Ok(reply)
+    }
+
+    fn command(&mut self, command: &str, code: u16) -> Result<Reply, Error> {
+        let stream = self.stream.get_mut();
+        write!(stream, "{}\r\n", command)?;
+        stream.flush()?;
+        self.expect(command, code)
+    }
+
+    /// Introduces the client, and returns the extensions supported by the server.
+    fn ehlo(&mut self, domain: &str) -> Result<Vec<String>, Error> {
+        let reply = self.command(&format!("EHLO {}", domain), 250)?;
+        // The first line only greets us back.
+        Ok(reply
+            .lines
+            .into_iter()
+            .skip(1)
+            .map(|line| line.to_ascii_uppercase())
+            .collect())
+    }
+
+    fn send(&mut self, email: &Email) -> Result<(), Error> {
+        self.command(&format!("MAIL FROM:<{}>", email.from), 250)?;
+        for to in &email.to {
+            self.command(&format!("RCPT TO:<{}>", to), 250)?;
+        }
+        self.command("DATA", 354)?;
+
+        let message = format!(
+            "From: <{}>\r\nTo: {}\r\nSubject: {}\r\n\r\n{}",
+            email.from,
+            email
+                .to
+                .iter()
+                .map(|to| format!("<{}>", to))
+                .collect::<Vec<_>>()
+                .join(", "),
+            email.subject,
+            dot_stuff(&email.body)
+        );
+        self.stream.get_mut().write_all(message.as_bytes())?;
+        self.command(".", 250)?;
+        Ok(())
+    }
+
+    fn quit(mut self) -> Result<(), Error> {
+        self.command("QUIT", 221)?;
+        Ok(())
+    }
+
+    /// Upgrades the connection with TLS, after which the client has to introduce itself again.
+    fn starttls(
+        mut self,
+        server: &str,
+        config: &Arc<ClientConfig>,
+    ) -> Result<Client<StreamOwned<ClientSession, S>>, Error> {
+        self.command("STARTTLS", 220)?;
+        let name = DNSNameRef::try_from_ascii_str(server)
+            .map_err(|_| Error::InvalidServerName(server.to_string()))?;
+        // Nothing may follow the reply to `STARTTLS` before the handshake, so the buffer is
+        // empty and nothing is lost here.
+        let stream = self.stream.into_inner();
+        let session = ClientSession::new(config, name);
+        Ok(Client {
+            stream: BufReader::new(StreamOwned::new(session, stream)),
+        })
+    }
+}
+
+/// Runs a whole session: introduction, optional `STARTTLS`, the message itself, and `QUIT`.
+fn send_email<S: Read + Write>(
+    stream: S,
+    server: &str,
+    email: &Email,
+    tls: Option<&Arc<ClientConfig>>,
+) -> Result<(), Error> {
+    const DOMAIN: &str = "localhost";
+
+    let mut client = Client::connect(stream)?;
+    let extensions = client.ehlo(DOMAIN)?;
+    match tls {
+        Some(config) if extensions.iter().any(|e| e == "STARTTLS") => {
+            let mut client = client.starttls(server, config)?;
+            client.ehlo(DOMAIN)?;
+            client.send(email)?;
+            client.quit()
+        }
+        _ => {
+            client.send(email)?;
+            client.quit()
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() != 5 {
+        eprintln!("usage: send-email server[:port] from to subject body");
+        std::process::exit(2);
+    }
+
+    let address = if args[0].contains(':') {
+        args[0].clone()
+    } else {
+        format!("{}:25", args[0])
+    };
+    let server = address.rsplit_once(':').unwrap().0;
+    let email = Email {
+        from: args[1].clone(),
+        to: args[2].split(',').map(str::to_string).collect(),
+        subject: args[3].clone(),
+        body: args[4].clone(),
+    };
+
+    let mut config = ClientConfig::new();
+    config
+        .root_store
+        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+    let config = Arc::new(config);
+
+    let result = TcpStream::connect(&address)
+        .map_err(Error::from)
+        .and_then(|stream| send_email(stream, server, &email, Some(&config)));
+    match result {
+        Ok(()) => println!("Sent."),
+        Err(e) => {
+            eprintln!("could not send the email: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    /// A server that plays back a script of replies, and records what the client sends.
+    struct FakeServer {
+        replies: Cursor<Vec<u8>>,
+        received: Vec<u8>,
+    }
+
+    impl FakeServer {
+        fn new(replies: &str) -> Self {
+            FakeServer {
+                replies: Cursor::new(replies.as_bytes().to_vec()),
+                received: vec![],
+            }
+        }
+    }
+
+    impl Read for FakeServer {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.replies.read(buf)
+        }
+    }
+
+    impl Write for FakeServer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.received.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn email() -> Email {
+        Email {
+            from: "alice@example.com".to_string(),
+            to: vec![
+                "bob@example.com".to_string(),
+                "carol@example.com".to_string(),
+            ],
+            subject: "Rosetta Code".to_string(),
+            body: "Hello,\n.hidden dot\nBye".to_string(),
+        }
+    }
+
+    /// Runs a session against the fake server, returning what the client sent.
+    fn run(replies: &str) -> (Result<(), Error>, String) {
+        let mut server = FakeServer::new(replies);
+        let result = send_email(&mut server, "mail.example.com", &email(), None);
+        (result, String::from_utf8(server.received).unwrap())
+    }
+
+    #[test]
+    fn replies() {
+        let mut reader = &b"250-mail.example.com\r\n250-SIZE 1000\r\n250 STARTTLS\r\n"[..];
+        assert_eq!(
+            read_reply(&mut reader).unwrap(),
+            Reply {
+                code: 250,
+                lines: vec![
+                    "mail.example.com".to_string(),
+                    "SIZE 1000".to_string(),
+                    "STARTTLS".to_string(),
+                ],
+            }
+        );
+
+        assert!(read_reply(&mut &b"25 short\r\n"[..]).is_err());
+        assert!(read_reply(&mut &b"250+what\r\n"[..]).is_err());
+        assert!(read_reply(&mut &b"250-unfinished\r\n"[..]).is_err());
+    }
+
+    #[test]
+    fn dialogue() {
+        let (result, sent) = run("220 mail.example.com ESMTP\r\n\
+                                  250-mail.example.com\r\n\
+                                  250 8BITMIME\r\n\
+                                  250 OK\r\n\
+                                  250 OK\r\n\
+                                  250 OK\r\n\
+                                  354 go ahead\r\n\
+                                  250 queued\r\n\
+                                  221 bye\r\n");
+        result.unwrap();
+        assert_eq!(
+            sent,
+            "EHLO localhost\r\n\
+             MAIL FROM:<alice@example.com>\r\n\
+             RCPT TO:<bob@example.com>\r\n\
+             RCPT TO:<carol@example.com>\r\n\
+             DATA\r\n\
+             From: <alice@example.com>\r\n\
+             To: <bob@example.com>, <carol@example.com>\r\n\
+             Subject: Rosetta Code\r\n\
+             \r\n\
+             Hello,\r\n\
+             ..hidden dot\r\n\
+             Bye\r\n\
+             .\r\n\
+             QUIT\r\n"
+        );
+    }
+
+    #[test]
+    fn rejected_recipient() {
+        let (result, sent) = run("220 hi\r\n\
+                                  250 hi\r\n\
+                                  250 OK\r\n\
+                                  550 no such user\r\n");
+        match result {
+            Err(Error::Unexpected { command, reply }) => {
+                assert_eq!(command, "RCPT TO:<bob@example.com>");
+                assert_eq!(reply.code, 550);
+            }
+            r => panic!("unexpected result: {:?}", r),
+        }
+        // The session stops at the first error.
+        assert!(sent.ends_with("RCPT TO:<bob@example.com>\r\n"));
+    }
+
+    #[test]
+    fn server_hangs_up() {
+        let (result, _) = run("220 hi\r\n");
+        match result {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn starttls_is_requested_when_offered() {
+        let mut server = FakeServer::new(
+            "220 hi\r\n\
+             250-mail.example.com\r\n\
+             250 starttls\r\n\
+             220 ready\r\n",
+        );
+        let config = Arc::new(ClientConfig::new());
+        // The fake server cannot do a TLS handshake, so the session fails right after.
+        let result = send_email(&mut server, "mail.example.com", &email(), Some(&config));
+        assert!(result.is_err());
+        let sent = String::from_utf8_lossy(&server.received);
+        assert!(sent.starts_with("EHLO localhost\r\nSTARTTLS\r\n"));
+    }
+}
@@ -0,0 +1,374 @@
+//! A two-phase tableau implementation of the simplex algorithm over exact rational numbers.
+//!
+//! Solves linear programs of the form: maximize `c·x` subject to `A·x ≤ b` and `x ≥ 0`.
+//! Rows with a negative right-hand side are handled by the first phase, which searches for a
+//! feasible starting point using artificial variables. Bland's rule is used to pick the pivots in
+//! both phases, which guarantees termination even for degenerate problems.
+
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+#[derive(Debug, PartialEq)]
+enum Solution {
+    /// The optimal value of the objective function and a point at which it is attained.
+    Optimal {
+        value: BigRational,
+        point: Vec<BigRational>,
+    },
+    /// The objective function can be made arbitrarily large.
+    Unbounded,
+    /// No point satisfies all the constraints.
+    Infeasible,
+}
+
+struct Tableau {
+    /// The constraint rows. Each row holds one coefficient per column, followed by the
+    /// right-hand side.
+    rows: Vec<Vec<BigRational>>,
+    /// The index of the basic variable associated with each row.
+    basis: Vec<usize>,
+    /// The number of columns, not counting the right-hand side.
+    columns: usize,
+}
+
+impl Tableau {
+    /// Builds the initial tableau, adding a slack variable to every constraint and an artificial
+    /// variable to every constraint with a negative right-hand side.
+    ///
+    /// Returns the tableau and the index of the first artificial column.
+    fn new(a: &[Vec<BigRational>], b: &[BigRational]) -> (Tableau, usize) {
+        let m = a.len();
+        let n = a.first().map_or(0, Vec::len);
+        let artificial_count = b.iter().filter(|b| b.is_negative()).count();
+        let first_artificial = n + m;
+        let columns = first_artificial + artificial_count;
+
+        let mut rows = Vec::with_capacity(m);
+        let mut basis = Vec::with_capacity(m);
+        let mut next_artificial = first_artificial;
+
+        for (i, (coefficients, rhs)) in a.iter().zip(b).enumerate() {
+            let mut row = vec![BigRational::zero(); columns + 1];
+            row[..n].clone_from_slice(coefficients);
+            row[n + i] = BigRational::one();
+            row[columns] = rhs.clone();
+
+            if rhs.is_negative() {
+                // Negate the row so that the right-hand side is non-negative, then let an
+                // artificial variable absorb the (now negative) slack.
+                for value in &mut row {
+                    *value = -value.clone();
+                }
+                row[next_artificial] = BigRational::one();
+                basis.push(next_artificial);
+                next_artificial += 1;
+            } else {
+                basis.push(n + i);
+            }
+
+            rows.push(row);
+        }
+
+        let tableau = Tableau {
+            rows,
+            basis,
+            columns,
+        };
+        (tableau, first_artificial)
+    }
+
+    /// Computes the row of reduced costs for the given objective, followed by the current
+    /// objective value.
+    fn objective_row(&self, costs: &[BigRational]) -> Vec<BigRational> {
+        let mut objective: Vec<_> = (0..=self.columns)
+            .map(|j| {
+                if j < self.columns {
+                    -costs[j].clone()
+                } else {
+                    BigRational::zero()
+                }
+            })
+            .collect();
+
+        for (row, &basic) in self.rows.iter().zip(&self.basis) {
+            for (value, entry) in objective.iter_mut().zip(row) {
+                *value += &costs[basic] * entry;
+            }
+        }
+
+        objective
+    }
+
+    /// Performs a pivot on the given row and column, updating the objective row as well.
+    fn pivot(&mut self, row: usize, column: usize, objective: &mut [BigRational]) {
+        let pivot = self.rows[row][column].clone();
+        for value in &mut self.rows[row] {
+            *value /= &pivot;
+        }
+
+        let pivot_row = self.rows[row].clone();
+        let eliminate = |target: &mut [BigRational]| {
+            let factor = target[column].clone();
+            if !factor.is_zero() {
+                for (value, p) in target.iter_mut().zip(&pivot_row) {
+                    *value -= &factor * p;
+                }
+            }
+        };
+
+        for (i, other) in self.rows.iter_mut().enumerate() {
+            if i != row {
+                eliminate(other);
+            }
+        }
+        eliminate(objective);
+
+        self.basis[row] = column;
+    }
+
+    /// Maximizes the objective given by `costs` (one per column), only allowing the columns
+    /// before `eligible` to enter the basis.
+    ///
+    /// Returns the objective row at the optimum, or `None` if the objective is unbounded.
+    fn maximize(&mut self, costs: &[BigRational], eligible: usize) -> Option<Vec<BigRational>> {
+        let mut objective = self.objective_row(costs);
+
+        loop {
+            // Bland's rule: the entering variable is the lowest-numbered column with a negative
+            // reduced cost...
+            let entering = match (0..eligible).find(|&j| objective[j].is_negative()) {
+                Some(j) => j,
+                None => return Some(objective),
+            };
+
+            // ...and ties in the ratio test are broken by the lowest-numbered basic variable.
+            let leaving = (0..self.rows.len())
+                .filter(|&i| self.rows[i][entering].is_positive())
+                .min_by(|&i, &k| {
+                    let ratio_i = &self.rows[i][self.columns] / &self.rows[i][entering];
+                    let ratio_k = &self.rows[k][self.columns] / &self.rows[k][entering];
+                    ratio_i
+                        .cmp(&ratio_k)
+                        .then(self.basis[i].cmp(&self.basis[k]))
+                })?;
+
+            self.pivot(leaving, entering, &mut objective);
+        }
+    }
+}
+
+/// Maximizes `c·x` subject to `a·x ≤ b` and `x ≥ 0`.
+fn simplex(c: &[BigRational], a: &[Vec<BigRational>], b: &[BigRational]) -> Solution {
+    let n = c.len();
+    let (mut tableau, first_artificial) = Tableau::new(a, b);
+
+    // Phase one: minimize the sum of the artificial variables to find a feasible basis.
+    if first_artificial < tableau.columns {
+        let costs: Vec<_> = (0..tableau.columns)
+            .map(|j| {
+                if j >= first_artificial {
+                    -BigRational::one()
+                } else {
+                    BigRational::zero()
+                }
+            })
+            .collect();
+
+        let mut objective = tableau
+            .maximize(&costs, tableau.columns)
+            .expect("phase one is always bounded");
+        if objective[tableau.columns].is_negative() {
+            return Solution::Infeasible;
+        }
+
+        // Artificial variables may remain in the basis at zero. Pivot them out, or drop their
+        // row entirely if it is redundant.
+        let mut i = 0;
+        while i < tableau.rows.len() {
+            if tableau.basis[i] >= first_artificial {
+                match (0..first_artificial).find(|&j| !tableau.rows[i][j].is_zero()) {
+                    Some(j) => tableau.pivot(i, j, &mut objective),
+                    None => {
+                        tableau.rows.remove(i);
+                        tableau.basis.remove(i);
+                        continue;
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    // Phase two: optimize the real objective, never letting the artificial variables re-enter.
+    let costs: Vec<_> = (0..tableau.columns)
+        .map(|j| c.get(j).cloned().unwrap_or_else(BigRational::zero))
+        .collect();
+
+    match tableau.maximize(&costs, first_artificial) {
+        Some(objective) => {
+            let mut point = vec![BigRational::zero(); n];
+            for (row, &basic) in tableau.rows.iter().zip(&tableau.basis) {
+                if basic < n {
+                    point[basic] = row[tableau.columns].clone();
+                }
+            }
+
+            Solution::Optimal {
+                value: objective[tableau.columns].clone(),
+                point,
+            }
+        }
+        None => Solution::Unbounded,
+    }
+}
+
+/// Convenience function to create a rational number from a numerator and denominator.
+fn q(numerator: i64, denominator: i64) -> BigRational {
+    BigRational::new(BigInt::from(numerator), BigInt::from(denominator))
+}
+
+/// Convenience function to create a vector of integral rational numbers.
+fn ints(values: &[i64]) -> Vec<BigRational> {
+    values.iter().map(|&v| q(v, 1)).collect()
+}
+
+fn print_solution(solution: &Solution) {
+    match solution {
+        Solution::Optimal { value, point } => {
+            let point: Vec<_> = point.iter().map(ToString::to_string).collect();
+            println!("optimal value {} at ({})", value, point.join(", "));
+        }
+        Solution::Unbounded => println!("unbounded"),
+        Solution::Infeasible => println!("infeasible"),
+    }
+}
+
+fn main() {
+    // maximize 3x + 5y
+    // subject to x ≤ 4, 2y ≤ 12, 3x + 2y ≤ 18
+    println!("maximize 3x + 5y:");
+    print_solution(&simplex(
+        &ints(&[3, 5]),
+        &[ints(&[1, 0]), ints(&[0, 2]), ints(&[3, 2])],
+        &ints(&[4, 12, 18]),
+    ));
+
+    // minimize 2x + 3y (by maximizing -2x - 3y)
+    // subject to x + y ≥ 4, x + 3y ≥ 6
+    println!("minimize 2x + 3y:");
+    print_solution(&simplex(
+        &ints(&[-2, -3]),
+        &[ints(&[-1, -1]), ints(&[-1, -3])],
+        &ints(&[-4, -6]),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn textbook_example() {
+        let solution = simplex(
+            &ints(&[3, 5]),
+            &[ints(&[1, 0]), ints(&[0, 2]), ints(&[3, 2])],
+            &ints(&[4, 12, 18]),
+        );
+
+        assert_eq!(
+            solution,
+            Solution::Optimal {
+                value: q(36, 1),
+                point: ints(&[2, 6]),
+            }
+        );
+    }
+
+    #[test]
+    fn fractional_optimum() {
+        let solution = simplex(
+            &ints(&[1, 1]),
+            &[ints(&[3, 2]), ints(&[1, 4])],
+            &ints(&[7, 5]),
+        );
+
+        assert_eq!(
+            solution,
+            Solution::Optimal {
+                value: q(13, 5),
+                point: vec![q(9, 5), q(4, 5)],
+            }
+        );
+    }
+
+    #[test]
+    fn minimization_needs_phase_one() {
+        let solution = simplex(
+            &ints(&[-2, -3]),
+            &[ints(&[-1, -1]), ints(&[-1, -3])],
+            &ints(&[-4, -6]),
+        );
+
+        assert_eq!(
+            solution,
+            Solution::Optimal {
+                value: q(-9, 1),
+                point: ints(&[3, 1]),
+            }
+        );
+    }
+
+    #[test]
+    fn beales_cycling_example() {
+        // Without an anti-cycling rule, the textbook pivoting rule cycles forever on this
+        // degenerate problem.
+        let solution = simplex(
+            &[q(3, 4), q(-20, 1), q(1, 2), q(-6, 1)],
+            &[
+                vec![q(1, 4), q(-8, 1), q(-1, 1), q(9, 1)],
+                vec![q(1, 2), q(-12, 1), q(-1, 2), q(3, 1)],
+                ints(&[0, 0, 1, 0]),
+            ],
+            &ints(&[0, 0, 1]),
+        );
+
+        assert_eq!(
+            solution,
+            Solution::Optimal {
+                value: q(5, 4),
+                point: ints(&[1, 0, 1, 0]),
+            }
+        );
+    }
+
+    #[test]
+    fn detects_unboundedness() {
+        let solution = simplex(&ints(&[1, 0]), &[ints(&[-1, 1])], &ints(&[1]));
+        assert_eq!(solution, Solution::Unbounded);
+    }
+
+    #[test]
+    fn detects_infeasibility() {
+        // x ≤ 1 and x ≥ 2
+        let solution = simplex(&ints(&[1]), &[ints(&[1]), ints(&[-1])], &ints(&[1, -2]));
+        assert_eq!(solution, Solution::Infeasible);
+    }
+
+    #[test]
+    fn redundant_equality() {
+        // x + y ≤ 2 and x + y ≥ 2 describe the same line. The artificial variable of the
+        // second row stays basic at zero after phase one and must be pivoted out.
+        let solution = simplex(
+            &ints(&[1, 2]),
+            &[ints(&[1, 1]), ints(&[-1, -1])],
+            &ints(&[2, -2]),
+        );
+
+        assert_eq!(
+            solution,
+            Solution::Optimal {
+                value: q(4, 1),
+                point: ints(&[0, 2]),
+            }
+        );
+    }
+}
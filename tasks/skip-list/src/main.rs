@@ -0,0 +1,303 @@
+//! A skip list: a sorted linked list with extra levels of links that skip over more and more
+//! nodes, so that a search takes O(log n) steps on average.
+//!
+//! Each node gets a random number of levels, one more with probability 1/2 each time, so that
+//! half the nodes are on level 1, a quarter on level 2, and so on. The random generator can be
+//! seeded, so that the shape of the list is reproducible.
+//!
+//! The nodes are kept in a vector, and the links are indices into it; removed nodes leave a
+//! hole that the next insertion fills.
+
+use std::fmt::Debug;
+
+use rand::prelude::*;
+
+/// No node has more levels than this, which is plenty for any list that fits in memory.
+const MAX_LEVEL: usize = 32;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// The next node on each level the node is on.
+    next: Vec<Option<usize>>,
+}
+
+pub struct SkipList<K, V> {
+    /// The first node on each level.
+    head: Vec<Option<usize>>,
+    nodes: Vec<Option<Node<K, V>>>,
+    /// The holes in `nodes`.
+    free: Vec<usize>,
+    len: usize,
+    rng: StdRng,
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// A list whose levels come from a generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
+        SkipList {
+            head: vec![None; MAX_LEVEL],
+            nodes: vec![],
+            free: vec![],
+            len: 0,
+            rng,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node(&self, index: usize) -> &Node<K, V> {
+        self.nodes[index].as_ref().unwrap()
+    }
+
+    /// The node after `previous` on `level`, where `None` is the head.
+    fn next(&self, previous: Option<usize>, level: usize) -> Option<usize> {
+        match previous {
+            None => self.head[level],
+            Some(index) => self.node(index).next[level],
+        }
+    }
+
+    fn set_next(&mut self, previous: Option<usize>, level: usize, to: Option<usize>) {
+        match previous {
+            None => self.head[level] = to,
+            Some(index) => self.nodes[index].as_mut().unwrap().next[level] = to,
+        }
+    }
+
+    /// The last node before `key` on each level, where `None` is the head.
+    fn predecessors(&self, key: &K) -> [Option<usize>; MAX_LEVEL] {
+        let mut predecessors = [None; MAX_LEVEL];
+        let mut current = None;
+        for level in (0..MAX_LEVEL).rev() {
+            while let Some(next) = self.next(current, level) {
+                if self.node(next).key >= *key {
+                    break;
+                }
+                current = Some(next);
+            }
+            predecessors[level] = current;
+        }
+        predecessors
+    }
+
+    /// The first node from the key on, after its predecessor on the lowest level. It has the
+    /// key if the list does.
+    fn find(&self, predecessors: &[Option<usize>]) -> Option<usize> {
+        self.next(predecessors[0], 0)
+    }
+
+    fn random_level(&mut self) -> usize {
+        let mut level = 1;
+        while level < MAX_LEVEL && self.rng.gen() {
+            level += 1;
+        }
+        level
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let predecessors = self.predecessors(key);
+        self.find(&predecessors)
+            .map(|index| self.node(index))
+            .filter(|node| node.key == *key)
+            .map(|node| &node.value)
+    }
+
+    /// Inserts `value` at `key`, and returns the value that was there before, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let predecessors = self.predecessors(&key);
+        if let Some(index) = self.find(&predecessors) {
+            let node = self.nodes[index].as_mut().unwrap();
+            if node.key == key {
+                return Some(std::mem::replace(&mut node.value, value));
+            }
+        }
+
+        let level = self.random_level();
+        let next = (0..level).map(|l| self.next(predecessors[l], l)).collect();
+        let node = Some(Node { key, value, next });
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (l, &previous) in predecessors.iter().enumerate().take(level) {
+            self.set_next(previous, l, Some(index));
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Removes `key`, and returns its value if it was there.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let predecessors = self.predecessors(key);
+        let index = self.find(&predecessors)?;
+        if self.node(index).key != *key {
+            return None;
+        }
+
+        let node = self.nodes[index].take().unwrap();
+        for (l, &next) in node.next.iter().enumerate() {
+            self.set_next(predecessors[l], l, next);
+        }
+        self.free.push(index);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// The entries in order of their keys.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        std::iter::successors(self.head[0], move |&index| self.node(index).next[0]).map(
+            move |index| {
+                let node = self.node(index);
+                (&node.key, &node.value)
+            },
+        )
+    }
+
+    /// How many nodes are on each level, from the lowest, up to the highest level in use.
+    pub fn level_sizes(&self) -> Vec<usize> {
+        (0..MAX_LEVEL)
+            .map(|level| {
+                std::iter::successors(self.head[level], |&i| self.node(i).next[level]).count()
+            })
+            .take_while(|&count| count > 0)
+            .collect()
+    }
+}
+
+impl<K: Ord, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Debug, V: Debug> Debug for SkipList<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+fn main() {
+    let mut list = SkipList::with_seed(0);
+    for (i, word) in "the quick brown fox jumps over the lazy dog"
+        .split_whitespace()
+        .enumerate()
+    {
+        list.insert(word, i);
+    }
+    println!("{:?}", list);
+    println!("fox: {:?}", list.get(&"fox"));
+    println!("removed the: {:?}", list.remove(&"the"));
+    println!("the: {:?}", list.get(&"the"));
+
+    let mut numbers = SkipList::with_seed(0);
+    for i in 0..100_000 {
+        numbers.insert(i, ());
+    }
+    println!("nodes on each level of 100000: {:?}", numbers.level_sizes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn basic_operations() {
+        let mut list = SkipList::with_seed(0);
+        assert!(list.is_empty());
+        assert_eq!(list.get(&1), None);
+        assert_eq!(list.remove(&1), None);
+
+        assert_eq!(list.insert(2, "two"), None);
+        assert_eq!(list.insert(1, "one"), None);
+        assert_eq!(list.insert(3, "three"), None);
+        assert_eq!(list.insert(2, "deux"), Some("two"));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.get(&2), Some(&"deux"));
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            [(&1, &"one"), (&2, &"deux"), (&3, &"three")]
+        );
+
+        assert_eq!(list.remove(&2), Some("deux"));
+        assert_eq!(list.remove(&2), None);
+        assert_eq!(list.get(&2), None);
+        assert_eq!(list.len(), 2);
+        assert_eq!(format!("{:?}", list), r#"{1: "one", 3: "three"}"#);
+    }
+
+    #[test]
+    fn same_seed_same_shape() {
+        let build = |seed| {
+            let mut list = SkipList::with_seed(seed);
+            for i in 0..1000 {
+                list.insert(i, i);
+            }
+            list.level_sizes()
+        };
+        assert_eq!(build(7), build(7));
+        assert_ne!(build(7), build(8));
+    }
+
+    #[test]
+    fn levels_halve() {
+        let mut list = SkipList::with_seed(0);
+        for i in 0..100_000 {
+            list.insert(i, ());
+        }
+        let sizes = list.level_sizes();
+        assert_eq!(sizes[0], 100_000);
+        // Each level has about half the nodes of the one below, while there are enough of
+        // them for that to hold.
+        for pair in sizes.windows(2).take_while(|pair| pair[0] > 1000) {
+            let ratio = pair[1] as f64 / pair[0] as f64;
+            assert!((0.45..0.55).contains(&ratio), "{:?}", sizes);
+        }
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for seed in 0..50 {
+            let mut list = SkipList::with_seed(seed);
+            let mut map = BTreeMap::new();
+            for _ in 0..1000 {
+                let key = rng.gen_range(0, 100);
+                match rng.gen_range(0, 3) {
+                    0 => assert_eq!(list.remove(&key), map.remove(&key)),
+                    1 => assert_eq!(list.get(&key), map.get(&key)),
+                    _ => {
+                        let value = rng.gen::<u32>();
+                        assert_eq!(list.insert(key, value), map.insert(key, value));
+                    }
+                }
+                assert_eq!(list.len(), map.len());
+            }
+            assert!(list.iter().eq(map.iter()));
+            // The holes left by removals are reused.
+            assert!(list.nodes.len() <= 100);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#![feature(test)]
+
+extern crate test;
+
+#[cfg(test)]
+#[macro_use]
+extern crate meta;
+
+/// Sorts the slice with the fewest possible writes: every element is moved straight to its
+/// final position, found by counting the elements smaller than it, and the element it displaces
+/// is placed in turn until the cycle closes.
+///
+/// Returns the number of writes, which is the number of elements that were out of place. The
+/// cycles are followed with swaps, and the last swap of a cycle puts two elements in place.
+fn cycle_sort<T: Ord>(v: &mut [T]) -> usize {
+    let mut writes = 0;
+    for start in 0..v.len().saturating_sub(1) {
+        // The element at `start` is carried around the cycle; `v[start]` holds it.
+        let mut pos = position(v, start);
+        if pos == start {
+            continue;
+        }
+        loop {
+            // Equal elements go after the ones already in place.
+            while v[pos] == v[start] {
+                pos += 1;
+            }
+            v.swap(start, pos);
+            writes += 1;
+
+            pos = position(v, start);
+            if pos == start {
+                writes += 1;
+                break;
+            }
+        }
+    }
+    writes
+}
+
+/// Where the element at `start` belongs: after `start`, plus every later element that is
+/// smaller than it.
+fn position<T: Ord>(v: &[T], start: usize) -> usize {
+    start + v[start + 1..].iter().filter(|&x| *x < v[start]).count()
+}
+
+fn main() {
+    let mut numbers = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    println!("Before: {:?}", numbers);
+    let writes = cycle_sort(&mut numbers);
+    println!("After: {:?} ({} writes)", numbers, writes);
+
+    let mut strings = ["beach", "hotel", "airplane", "car", "house", "art"];
+    println!("Before: {:?}", strings);
+    let writes = cycle_sort(&mut strings);
+    println!("After: {:?} ({} writes)", strings, writes);
+}
+
+#[cfg(test)]
+mod tests {
+    use meta::test_utils::Sorter;
+
+    struct CycleSort;
+
+    impl Sorter for CycleSort {
+        fn sort<T: Ord + Clone>(elements: &mut [T]) {
+            super::cycle_sort(elements);
+        }
+    }
+
+    test_sorter!(CycleSort);
+
+    #[test]
+    fn writes() {
+        // Every element of a rotation is out of place.
+        let mut v = [2, 3, 4, 5, 1];
+        assert_eq!(super::cycle_sort(&mut v), 5);
+        let mut v = [1, 2, 3, 4, 5];
+        assert_eq!(super::cycle_sort(&mut v), 0);
+        // Only one of the 3s, and the last 1, need to move.
+        let mut v = [1, 3, 2, 3, 1];
+        assert_eq!(super::cycle_sort(&mut v), 2);
+        assert_eq!(v, [1, 1, 2, 3, 3]);
+    }
+}
@@ -1,3 +1,7 @@
+#![feature(test)]
+
+extern crate test;
+
 #[cfg(test)]
 #[macro_use]
 extern crate meta;
@@ -47,5 +51,15 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    test_sort!(super::pancake_sort);
+    use meta::test_utils::Sorter;
+
+    struct PancakeSort;
+
+    impl Sorter for PancakeSort {
+        fn sort<T: Ord + Clone>(elements: &mut [T]) {
+            super::pancake_sort(elements);
+        }
+    }
+
+    test_sorter!(PancakeSort);
 }
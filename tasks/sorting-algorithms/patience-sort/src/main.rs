@@ -0,0 +1,73 @@
+#![feature(test)]
+
+extern crate test;
+
+#[cfg(test)]
+#[macro_use]
+extern crate meta;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Deals the elements into piles like in the card game, each pile having smaller elements
+/// towards its top, then merges the piles by repeatedly taking the smallest top.
+fn patience_sort<T: Ord + Clone>(v: &mut [T]) {
+    let mut piles: Vec<Vec<T>> = Vec::new();
+    for elem in v.iter() {
+        // The tops of the piles are in increasing order, so the leftmost pile that the
+        // element can go on is found with a binary search.
+        let pile = piles
+            .binary_search_by(|pile| {
+                pile.last()
+                    .unwrap()
+                    .cmp(elem)
+                    .then(std::cmp::Ordering::Less)
+            })
+            .unwrap_err();
+        match piles.get_mut(pile) {
+            Some(pile) => pile.push(elem.clone()),
+            None => piles.push(vec![elem.clone()]),
+        }
+    }
+
+    // The heap holds the top of every pile, with the index of its pile.
+    let mut heap: BinaryHeap<_> = piles
+        .iter_mut()
+        .enumerate()
+        .map(|(i, pile)| Reverse((pile.pop().unwrap(), i)))
+        .collect();
+    for slot in v.iter_mut() {
+        let Reverse((elem, i)) = heap.pop().expect("as many elements as slots");
+        if let Some(next) = piles[i].pop() {
+            heap.push(Reverse((next, i)));
+        }
+        *slot = elem;
+    }
+}
+
+fn main() {
+    let mut numbers = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    println!("Before: {:?}", numbers);
+    patience_sort(&mut numbers);
+    println!("After: {:?}", numbers);
+
+    let mut strings = ["beach", "hotel", "airplane", "car", "house", "art"];
+    println!("Before: {:?}", strings);
+    patience_sort(&mut strings);
+    println!("After: {:?}", strings);
+}
+
+#[cfg(test)]
+mod tests {
+    use meta::test_utils::Sorter;
+
+    struct PatienceSort;
+
+    impl Sorter for PatienceSort {
+        fn sort<T: Ord + Clone>(elements: &mut [T]) {
+            super::patience_sort(elements);
+        }
+    }
+
+    test_sorter!(PatienceSort);
+}
@@ -1,24 +1,68 @@
+use std::convert::TryFrom;
+use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
-fn sleepsort<I>(nums: I)
+/// Sorts the numbers by sleeping as many units of time as each of them, in its own thread, and
+/// collecting them as they wake up. A unit has to be long enough for the threads to start, or
+/// close numbers may come out of order.
+///
+/// Returns `None`, without sleeping at all, if some number is too large for its time to be a
+/// `Duration`.
+fn sleepsort<I>(nums: I, unit: Duration) -> Option<Vec<u64>>
 where
     I: Iterator<Item = u64>,
 {
-    let threads: Vec<_> = nums
+    let sleeps: Vec<(u64, Duration)> = nums
         .map(|n| {
+            let times = u32::try_from(n).ok()?;
+            Some((n, unit.checked_mul(times)?))
+        })
+        .collect::<Option<_>>()?;
+
+    let (sender, receiver) = mpsc::channel();
+    let threads: Vec<_> = sleeps
+        .into_iter()
+        .map(|(n, sleep)| {
+            let sender = sender.clone();
             thread::spawn(move || {
-                thread::sleep(Duration::from_millis(n));
-                println!("{}", n);
+                thread::sleep(sleep);
+                sender.send(n).unwrap();
             })
         })
         .collect();
+    // Once the threads are done with their senders, the channel closes.
+    drop(sender);
 
     for t in threads {
         t.join().unwrap();
     }
+    Some(receiver.iter().collect())
 }
 
 fn main() {
-    sleepsort(std::env::args().skip(1).map(|s| s.parse().unwrap()));
+    let nums = std::env::args().skip(1).map(|s| s.parse().unwrap());
+    let sorted = sleepsort(nums, Duration::from_millis(1)).expect("a number too large to sleep");
+    for n in sorted {
+        println!("{}", n);
+    }
+}
+
+#[test]
+fn test_sleepsort() {
+    // Units far longer than it takes to start a thread, even on a busy machine.
+    let unit = Duration::from_millis(200);
+    let sorted = sleepsort(vec![3, 2, 0, 4, 1, 2].into_iter(), unit);
+    assert_eq!(sorted.unwrap(), [0, 1, 2, 2, 3, 4]);
+    assert!(sleepsort(Vec::new().into_iter(), unit).unwrap().is_empty());
+}
+
+#[test]
+fn too_large() {
+    // Nothing sleeps, though the first number would not take long: the second is more units
+    // than fit in a `u32`, and then longer than a `Duration` can be.
+    let unit = Duration::from_secs(1);
+    assert_eq!(sleepsort(vec![1, 1 << 32].into_iter(), unit), None);
+    let era = Duration::from_secs(1 << 40);
+    assert_eq!(sleepsort(vec![1, 1 << 30].into_iter(), era), None);
 }
@@ -0,0 +1,86 @@
+#![feature(test)]
+
+extern crate test;
+
+#[cfg(test)]
+#[macro_use]
+extern crate meta;
+
+use std::collections::VecDeque;
+
+/// Repeatedly pulls an increasing strand out of the unsorted elements, taking every element
+/// that is not smaller than the last one taken, and merges the strand into the result.
+fn strand_sort<T: Ord + Clone>(v: &mut [T]) {
+    let mut unsorted: VecDeque<T> = v.iter().cloned().collect();
+    let mut sorted: Vec<T> = Vec::with_capacity(v.len());
+
+    while let Some(first) = unsorted.pop_front() {
+        let mut strand = vec![first];
+        let mut rest = VecDeque::with_capacity(unsorted.len());
+        for elem in unsorted.drain(..) {
+            if elem >= *strand.last().unwrap() {
+                strand.push(elem);
+            } else {
+                rest.push_back(elem);
+            }
+        }
+        unsorted = rest;
+        sorted = merge(sorted, strand);
+    }
+
+    for (slot, elem) in v.iter_mut().zip(sorted) {
+        *slot = elem;
+    }
+}
+
+/// Merges two sorted vectors, keeping the elements of `a` first when they are equal.
+fn merge<T: Ord>(a: Vec<T>, b: Vec<T>) -> Vec<T> {
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    loop {
+        let take_a = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) => x <= y,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => return merged,
+        };
+        merged.push(if take_a { a.next() } else { b.next() }.unwrap());
+    }
+}
+
+fn main() {
+    let mut numbers = [4, 65, 2, -31, 0, 99, 2, 83, 782, 1];
+    println!("Before: {:?}", numbers);
+    strand_sort(&mut numbers);
+    println!("After: {:?}", numbers);
+
+    let mut strings = ["beach", "hotel", "airplane", "car", "house", "art"];
+    println!("Before: {:?}", strings);
+    strand_sort(&mut strings);
+    println!("After: {:?}", strings);
+}
+
+#[cfg(test)]
+mod tests {
+    use meta::test_utils::Sorter;
+
+    struct StrandSort;
+
+    impl Sorter for StrandSort {
+        fn sort<T: Ord + Clone>(elements: &mut [T]) {
+            super::strand_sort(elements);
+        }
+    }
+
+    test_sorter!(StrandSort);
+
+    #[test]
+    fn merge() {
+        assert_eq!(
+            super::merge(vec![1, 4, 9], vec![2, 4, 10, 11]),
+            [1, 2, 4, 4, 9, 10, 11]
+        );
+        assert_eq!(super::merge(vec![], vec![3]), [3]);
+    }
+}
@@ -1,71 +1,55 @@
-use std::collections::HashMap;
+/// The digit of a consonant, or `None` for the letters that have no code.
+fn code(ch: char) -> Option<char> {
+    let digit = match ch {
+        'B' | 'F' | 'P' | 'V' => '1',
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => '2',
+        'D' | 'T' => '3',
+        'L' => '4',
+        'M' | 'N' => '5',
+        'R' => '6',
+        _ => return None,
+    };
+    Some(digit)
+}
 
+/// The American Soundex code of a name: its first letter followed by three digits.
+///
+/// Letters with the same digit count once when they are next to each other, or only separated
+/// by an H or a W; this also holds for the first letter. A vowel (A, E, I, O, U or Y) in
+/// between makes the same digit count again. Anything other than ASCII letters is ignored, so
+/// a name without any letters gives an empty string.
 pub fn soundex(word: &str) -> String {
-    let dropped_vowels = ['a', 'e', 'i', 'o', 'u', 'y'];
-    let mut consonants_value = HashMap::new();
-
-    let letter_groups = vec![
-        vec!['b', 'f', 'p', 'v'],
-        vec!['c', 'g', 'j', 'k', 'q', 's', 'x', 'z'],
-        vec!['d', 't'],
-        vec!['l'],
-        vec!['m', 'n'],
-        vec!['r'],
-    ];
-    for (idx, letter_group) in letter_groups.iter().enumerate() {
-        for ch in letter_group {
-            // so we have '1', '2', etc chars instead of numbers
-            consonants_value.insert(ch, (b'1' + idx as u8) as char);
-        }
-    }
-
-    let soundex = String::from(word);
-    let mut soundex_numbers = {
-        soundex
-            .chars()
-            .enumerate()
-            .map(|(_, ch)| {
-                if consonants_value.contains_key(&ch) {
-                    consonants_value[&ch]
-                } else {
-                    ch
-                }
-            })
-            .collect::<Vec<char>>()
-    };
-    soundex_numbers.dedup();
-    let mut soundex_numbers = {
-        soundex_numbers
-            .iter()
-            .enumerate()
-            .filter(|(idx, ch)| {
-                if *idx == 0 {
-                    return true;
-                } else if dropped_vowels.contains(ch) {
-                    return false;
-                }
-                true
-            })
-            .map(|(_, ch)| *ch)
-            .collect::<Vec<char>>()
+    let mut letters = word
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|ch| ch.to_ascii_uppercase());
+    let first = match letters.next() {
+        Some(first) => first,
+        None => return String::new(),
     };
 
-    // deals with h and w
-    while let Some(index) = soundex_numbers.iter().position(|&x| x == 'h' || x == 'w') {
-        soundex_numbers.remove(index);
-        if let Some(before) = soundex_numbers.get(index - 1) {
-            if let Some(after) = soundex_numbers.get(index) {
-                if before == after {
-                    soundex_numbers.remove(index);
+    let mut soundex = first.to_string();
+    let mut last = code(first);
+    for ch in letters {
+        match ch {
+            'H' | 'W' => {}
+            _ => {
+                let digit = code(ch);
+                if digit.is_some() && digit != last {
+                    soundex.extend(digit);
+                    if soundex.len() == 4 {
+                        break;
+                    }
                 }
+                last = digit;
             }
         }
     }
 
-    while soundex_numbers.len() < 4 {
-        soundex_numbers.push('0');
+    while soundex.len() < 4 {
+        soundex.push('0');
     }
-    soundex_numbers[..4].iter().collect::<String>()
+    soundex
 }
 
 #[cfg(test)]
@@ -80,4 +64,56 @@ mod tests {
         assert_eq!("G362", soundex("Gutierrez"));
         assert_eq!("M220", soundex("Moses"));
     }
+
+    #[test]
+    fn task_examples() {
+        let examples = [
+            ("Ashcraft", "A261"),
+            ("Ashcroft", "A261"),
+            ("Burroughs", "B620"),
+            ("Burrows", "B620"),
+            ("Ekzampul", "E251"),
+            ("Example", "E251"),
+            ("Ellery", "E460"),
+            ("Euler", "E460"),
+            ("Gauss", "G200"),
+            ("Ghosh", "G200"),
+            ("Gutierrez", "G362"),
+            ("Heilbronn", "H416"),
+            ("Hilbert", "H416"),
+            ("Honeyman", "H555"),
+            ("Jackson", "J250"),
+            ("Kant", "K530"),
+            ("Knuth", "K530"),
+            ("Lee", "L000"),
+            ("Lissajous", "L222"),
+            ("Lloyd", "L300"),
+            ("Lukasiewicz", "L222"),
+            ("Moses", "M220"),
+            ("O'Hara", "O600"),
+            ("Pfister", "P236"),
+            ("Robert", "R163"),
+            ("Rubin", "R150"),
+            ("Rupert", "R163"),
+            ("Soundex", "S532"),
+            ("Sownteks", "S532"),
+            ("Tymczak", "T522"),
+            ("VanDeusen", "V532"),
+            ("Washington", "W252"),
+            ("Wheaton", "W350"),
+        ];
+        for &(name, expected) in &examples {
+            assert_eq!(soundex(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn case_and_other_characters() {
+        assert_eq!(soundex("ashcraft"), "A261");
+        assert_eq!(soundex("  van deusen "), "V532");
+        assert_eq!(soundex("hhh"), "H000");
+        assert_eq!(soundex("A"), "A000");
+        assert_eq!(soundex(""), "");
+        assert_eq!(soundex("12 -"), "");
+    }
 }
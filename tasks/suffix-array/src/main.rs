@@ -0,0 +1,151 @@
+/// The starting positions of the suffixes of `text`, in lexicographic order of the suffixes.
+///
+/// This uses prefix doubling: once the suffixes are sorted by their first `k` bytes, sorting
+/// them by their first `2k` bytes only takes sorting the pairs of ranks of the two halves.
+/// With a counting sort for each round, the whole construction takes O(n log n).
+fn suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by_key(|&i| text[i]);
+    let mut rank: Vec<usize> = text.iter().map(|&c| c as usize).collect();
+
+    let mut k = 1;
+    while k < n {
+        // The rank of the second half of a suffix. A suffix without one sorts first, as it
+        // is a prefix of the others with the same first half.
+        let second = |i: usize| rank.get(i + k).map(|&r| r + 1).unwrap_or(0);
+
+        // By the second half: the suffixes that have none, then in the previous order.
+        let by_second: Vec<usize> = (n - k..n)
+            .chain(sa.iter().filter(|&&i| i >= k).map(|&i| i - k))
+            .collect();
+
+        // Then stably by the first half.
+        let classes = rank.iter().max().map_or(0, |&r| r + 1);
+        let mut counts = vec![0; classes + 1];
+        for &i in &by_second {
+            counts[rank[i] + 1] += 1;
+        }
+        for c in 1..counts.len() {
+            counts[c] += counts[c - 1];
+        }
+        for &i in &by_second {
+            sa[counts[rank[i]]] = i;
+            counts[rank[i]] += 1;
+        }
+
+        let mut next = vec![0; n];
+        for w in 1..n {
+            let (a, b) = (sa[w - 1], sa[w]);
+            let same = rank[a] == rank[b] && second(a) == second(b);
+            next[b] = next[a] + if same { 0 } else { 1 };
+        }
+        rank = next;
+        if rank[sa[n - 1]] == n - 1 {
+            // All the ranks are different, so the order is final.
+            break;
+        }
+        k *= 2;
+    }
+    sa
+}
+
+/// The length of the longest common prefix of each suffix in `sa` with the one before it,
+/// with Kasai's algorithm. The first entry is always 0.
+///
+/// The suffixes are visited from the longest one; going from the suffix at `i` to the one at
+/// `i + 1` drops only the first byte, so the common prefix shrinks by at most one and the
+/// comparisons take O(n) time overall.
+fn lcp_array(text: &[u8], sa: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    let mut rank = vec![0; n];
+    for (r, &i) in sa.iter().enumerate() {
+        rank[i] = r;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+    for i in 0..n {
+        if rank[i] == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + h < n && j + h < n && text[i + h] == text[j + h] {
+            h += 1;
+        }
+        lcp[rank[i]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+fn main() {
+    let text = "banana";
+    let sa = suffix_array(text.as_bytes());
+    let lcp = lcp_array(text.as_bytes(), &sa);
+    println!("{:>5}  {:>3}  suffix", "index", "lcp");
+    for (&i, &l) in sa.iter().zip(&lcp) {
+        println!("{:>5}  {:>3}  {}", i, l, &text[i..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    fn naive_suffix_array(text: &[u8]) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..text.len()).collect();
+        sa.sort_by_key(|&i| &text[i..]);
+        sa
+    }
+
+    fn naive_lcp_array(text: &[u8], sa: &[usize]) -> Vec<usize> {
+        let common = |a: &[u8], b: &[u8]| a.iter().zip(b).take_while(|(x, y)| x == y).count();
+        (0..sa.len())
+            .map(|r| match r {
+                0 => 0,
+                _ => common(&text[sa[r - 1]..], &text[sa[r]..]),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn banana() {
+        let sa = suffix_array(b"banana");
+        assert_eq!(sa, [5, 3, 1, 0, 4, 2]);
+        assert_eq!(lcp_array(b"banana", &sa), [0, 1, 3, 0, 0, 2]);
+    }
+
+    #[test]
+    fn small_texts() {
+        assert!(suffix_array(b"").is_empty());
+        assert!(lcp_array(b"", &[]).is_empty());
+        assert_eq!(suffix_array(b"a"), [0]);
+        assert_eq!(suffix_array(b"aaaa"), [3, 2, 1, 0]);
+        assert_eq!(lcp_array(b"aaaa", &[3, 2, 1, 0]), [0, 1, 2, 3]);
+        assert_eq!(
+            suffix_array(b"mississippi"),
+            naive_suffix_array(b"mississippi")
+        );
+    }
+
+    #[test]
+    fn same_as_naive_construction() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..500 {
+            // Small alphabets give long repeats and many rounds of doubling.
+            let alphabet = rng.gen_range(1, 5);
+            let len = rng.gen_range(0, 200);
+            let text: Vec<u8> = (0..len)
+                .map(|_| b'a' + rng.gen_range(0, alphabet))
+                .collect();
+
+            let sa = suffix_array(&text);
+            assert_eq!(sa, naive_suffix_array(&text), "{:?}", text);
+            assert_eq!(lcp_array(&text, &sa), naive_lcp_array(&text, &sa));
+        }
+    }
+}
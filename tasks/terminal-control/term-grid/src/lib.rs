@@ -0,0 +1,118 @@
+//! A grid of characters to draw a picture on, and show in a terminal.
+//!
+//! An animation shows one grid after another in the same place: `show` moves the cursor to the
+//! top left corner of the terminal with a VT100 escape sequence before it prints, so each frame
+//! overwrites the one before.
+
+use std::fmt;
+use std::io::{self, Write};
+
+/// Clears the terminal and moves the cursor to its top left corner.
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// The escape sequence that moves the cursor to `row` and `column`, from 1 at the top left.
+pub fn cursor_to(row: usize, column: usize) -> String {
+    format!("\x1b[{};{}H", row, column)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TermGrid {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+}
+
+impl TermGrid {
+    /// A grid of spaces.
+    pub fn new(width: usize, height: usize) -> Self {
+        TermGrid {
+            width,
+            height,
+            cells: vec![' '; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        if x < self.width && y < self.height {
+            Some(self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Puts `c` at column `x` and row `y`, from 0 at the top left. Anything off the grid is
+    /// left out, so pictures can be drawn without clipping them first.
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = c;
+        }
+    }
+
+    /// Writes `text` from column `x` of row `y` to the right.
+    pub fn put_str(&mut self, x: usize, y: usize, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            self.set(x + i, y, c);
+        }
+    }
+
+    pub fn fill(&mut self, c: char) {
+        for cell in &mut self.cells {
+            *cell = c;
+        }
+    }
+
+    /// Prints the grid over whatever is in the top left corner of the terminal.
+    pub fn show<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "{}{}", cursor_to(1, 1), self)?;
+        out.flush()
+    }
+}
+
+impl fmt::Display for TermGrid {
+    /// The rows of the grid, each on its own line.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            let line: String = row.iter().collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drawing() {
+        let mut grid = TermGrid::new(5, 3);
+        grid.put_str(1, 1, "Hello");
+        grid.set(0, 2, '#');
+        grid.set(9, 9, '#');
+        assert_eq!(grid.get(4, 1), Some('l'));
+        assert_eq!(grid.get(5, 1), None);
+        assert_eq!(grid.to_string(), "     \n Hell\n#    \n");
+        grid.fill('.');
+        assert_eq!(grid.to_string(), ".....\n.....\n.....\n");
+    }
+
+    #[test]
+    fn show_redraws_in_place() {
+        let mut grid = TermGrid::new(2, 2);
+        grid.set(1, 0, '*');
+        let mut out = vec![];
+        grid.show(&mut out).unwrap();
+        grid.show(&mut out).unwrap();
+        let frame = "\x1b[1;1H *\n  \n";
+        assert_eq!(String::from_utf8(out).unwrap(), frame.repeat(2));
+        assert_eq!(cursor_to(6, 3), "\x1b[6;3H");
+    }
+}
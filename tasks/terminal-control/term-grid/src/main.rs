@@ -0,0 +1,13 @@
+use std::io;
+use term_grid::{cursor_to, TermGrid, CLEAR};
+
+fn main() {
+    // The task: "Hello" at the sixth row and third column of the terminal.
+    print!("{}{}Hello", CLEAR, cursor_to(6, 3));
+    println!();
+
+    // The same, on a grid that is then drawn over the top of the terminal.
+    let mut grid = TermGrid::new(10, 6);
+    grid.put_str(2, 5, "Hello");
+    grid.show(io::stdout()).unwrap();
+}
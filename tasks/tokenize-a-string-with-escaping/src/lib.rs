@@ -0,0 +1,122 @@
+/// Where the tokenizer is within a token.
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    /// Reading characters as they are.
+    Normal,
+    /// Right after an escape character, so the next character is part of the token whatever
+    /// it is.
+    Escaped,
+}
+
+/// Splits `input` at every `separator` that is not preceded by an `escape`. An escape makes
+/// the character after it part of the token, and is itself dropped; an escape at the very end
+/// of the input has nothing to escape, and is dropped too.
+///
+/// A separator at the start or the end of the input, or two separators in a row, give empty
+/// tokens.
+///
+/// # Panics
+///
+/// If the separator and the escape are the same character.
+pub fn tokenize(input: &str, separator: char, escape: char) -> Vec<String> {
+    assert_ne!(separator, escape, "the separator cannot be the escape");
+
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut state = State::Normal;
+    for c in input.chars() {
+        state = match state {
+            State::Escaped => {
+                token.push(c);
+                State::Normal
+            }
+            State::Normal if c == escape => State::Escaped,
+            State::Normal if c == separator => {
+                tokens.push(std::mem::take(&mut token));
+                State::Normal
+            }
+            State::Normal => {
+                token.push(c);
+                State::Normal
+            }
+        };
+    }
+    tokens.push(token);
+    tokens
+}
+
+/// Joins `tokens` with `separator`, escaping the separators and escapes they contain, so that
+/// `tokenize` gives them back.
+pub fn join<S: AsRef<str>>(tokens: &[S], separator: char, escape: char) -> String {
+    let mut joined = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 {
+            joined.push(separator);
+        }
+        for c in token.as_ref().chars() {
+            if c == separator || c == escape {
+                joined.push(escape);
+            }
+            joined.push(c);
+        }
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    #[test]
+    fn task_example() {
+        assert_eq!(
+            tokenize("one^|uno||three^^^^|four^^^|^cuatro|", '|', '^'),
+            ["one|uno", "", "three^^", "four^|cuatro", ""]
+        );
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(tokenize("", '|', '^'), [""]);
+        assert_eq!(tokenize("|", '|', '^'), ["", ""]);
+        assert_eq!(tokenize("a^", '|', '^'), ["a"]);
+        assert_eq!(tokenize("^a^b", '|', '^'), ["ab"]);
+        assert_eq!(tokenize("é,ü\\,ö", ',', '\\'), ["é", "ü,ö"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn same_separator_and_escape() {
+        tokenize("a|b", '|', '|');
+    }
+
+    #[test]
+    fn join_escapes() {
+        assert_eq!(
+            join(&["one|uno", "", "three^^", "four^|cuatro", ""], '|', '^'),
+            "one^|uno||three^^^^|four^^^|cuatro|"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Mostly separators and escapes, so that they follow each other in every way.
+        let alphabet = ['|', '^', 'a', 'é'];
+        for _ in 0..2000 {
+            let count = rng.gen_range(1, 6);
+            let tokens: Vec<String> = (0..count)
+                .map(|_| {
+                    let len = rng.gen_range(0, 6);
+                    (0..len)
+                        .map(|_| *alphabet.choose(&mut rng).unwrap())
+                        .collect()
+                })
+                .collect();
+            let joined = join(&tokens, '|', '^');
+            assert_eq!(tokenize(&joined, '|', '^'), tokens, "{}", joined);
+        }
+    }
+}
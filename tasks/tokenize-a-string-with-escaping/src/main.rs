@@ -0,0 +1,9 @@
+use tokenize_a_string_with_escaping::tokenize;
+
+fn main() {
+    let input = "one^|uno||three^^^^|four^^^|^cuatro|";
+    println!("{}", input);
+    for token in tokenize(input, '|', '^') {
+        println!("{:?}", token);
+    }
+}
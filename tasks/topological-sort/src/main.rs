@@ -0,0 +1,198 @@
+//! Orders the VHDL libraries of the task so that each comes after the libraries it depends on,
+//! by Kahn's algorithm: a library is ready once all of its dependencies are placed, and the
+//! ready libraries are placed one by one. If some library never gets ready, its dependencies go
+//! round in a cycle, and there is no order.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+const LIBRARIES: &str = "\
+des_system_lib   std synopsys std_cell_lib des_system_lib dw02 dw01 ramlib ieee
+dw01             ieee dw01 dware gtech
+dw02             ieee dw02 dware
+dw03             std synopsys dware dw03 dw02 dw01 ieee gtech
+dw04             dw04 ieee dw01 dware gtech
+dw05             dw05 ieee dware
+dw06             dw06 ieee dware
+dw07             ieee dware
+dware            ieee dware
+gtech            ieee gtech
+ramlib           std ieee
+std_cell_lib     ieee std_cell_lib
+synopsys
+";
+
+/// The dependencies of each library, from lines with a library followed by its dependencies.
+/// Libraries that only appear as dependencies have none, and a library that depends on itself
+/// does not count.
+fn parse(text: &str) -> BTreeMap<&str, BTreeSet<&str>> {
+    let mut dependencies: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        if let Some(library) = words.next() {
+            let words: Vec<&str> = words.filter(|&w| w != library).collect();
+            for &dependency in &words {
+                dependencies.entry(dependency).or_default();
+            }
+            dependencies.entry(library).or_default().extend(words);
+        }
+    }
+    dependencies
+}
+
+/// The libraries in an order where each comes after its dependencies, taking the first ready
+/// library by name at each step. Or, if there is no such order, a cycle of libraries where each
+/// depends on the next, and the last on the first.
+fn topological_sort<'a>(
+    dependencies: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+) -> Result<Vec<&'a str>, Vec<&'a str>> {
+    // How many dependencies of each library are not placed yet, and which libraries depend on
+    // each.
+    let mut waiting: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut dependents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (&library, deps) in dependencies {
+        waiting.insert(library, deps.len());
+        for &dependency in deps {
+            dependents.entry(dependency).or_default().push(library);
+        }
+    }
+
+    let mut ready: BTreeSet<&str> = waiting
+        .iter()
+        .filter(|&(_, &count)| count == 0)
+        .map(|(&library, _)| library)
+        .collect();
+    let mut order = vec![];
+    while let Some(&library) = ready.iter().next() {
+        ready.remove(library);
+        order.push(library);
+        for &dependent in dependents.get(library).into_iter().flatten() {
+            let count = waiting.get_mut(dependent).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.insert(dependent);
+            }
+        }
+    }
+
+    if order.len() == dependencies.len() {
+        return Ok(order);
+    }
+    // Every library left waits for another one left, so following those leads round a cycle.
+    let left = |library: &&&str| waiting[**library] > 0;
+    let mut path = vec![*waiting.keys().find(left).unwrap()];
+    loop {
+        let last = *path.last().unwrap();
+        let next = *dependencies[last].iter().find(left).unwrap();
+        if let Some(start) = path.iter().position(|&library| library == next) {
+            return Err(path.split_off(start));
+        }
+        path.push(next);
+    }
+}
+
+fn main() {
+    let good = parse(LIBRARIES);
+    match topological_sort(&good) {
+        Ok(order) => println!("Compile order: {}", order.join(" ")),
+        Err(cycle) => println!("Cycle: {}", cycle.join(" -> ")),
+    }
+
+    // The task's unorderable variant: dw01 also depends on dw04, which depends on dw01.
+    let bad_text = LIBRARIES.replace("dware gtech\n", "dware gtech dw04\n");
+    let bad = parse(&bad_text);
+    match topological_sort(&bad) {
+        Ok(order) => println!("Compile order: {}", order.join(" ")),
+        Err(cycle) => println!("Unorderable, because of the cycle: {}", cycle.join(" -> ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that `order` has each library once, after all of its dependencies.
+    fn assert_respects(dependencies: &BTreeMap<&str, BTreeSet<&str>>, order: &[&str]) {
+        assert_eq!(order.len(), dependencies.len());
+        let position: BTreeMap<&str, usize> =
+            order.iter().enumerate().map(|(i, &l)| (l, i)).collect();
+        assert_eq!(position.len(), order.len());
+        for (library, deps) in dependencies {
+            for dependency in deps {
+                assert!(
+                    position[dependency] < position[library],
+                    "{} before {}",
+                    library,
+                    dependency
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn task_libraries() {
+        let dependencies = parse(LIBRARIES);
+        assert_eq!(dependencies.len(), 15);
+        assert!(dependencies["ieee"].is_empty());
+        assert!(!dependencies["dw01"].contains("dw01"));
+        let order = topological_sort(&dependencies).unwrap();
+        assert_respects(&dependencies, &order);
+        assert_eq!(
+            order,
+            [
+                "ieee",
+                "dware",
+                "dw02",
+                "dw05",
+                "dw06",
+                "dw07",
+                "gtech",
+                "dw01",
+                "dw04",
+                "std",
+                "ramlib",
+                "std_cell_lib",
+                "synopsys",
+                "des_system_lib",
+                "dw03",
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_libraries() {
+        let text = LIBRARIES.replace("dware gtech\n", "dware gtech dw04\n");
+        let dependencies = parse(&text);
+        assert_eq!(topological_sort(&dependencies), Err(vec!["dw01", "dw04"]));
+    }
+
+    #[test]
+    fn longer_cycle() {
+        let dependencies = parse("a b\nb c\nc d\nd b\ne a\n");
+        let cycle = topological_sort(&dependencies).unwrap_err();
+        assert_eq!(cycle, ["b", "c", "d"]);
+        // Each library of the cycle depends on the next.
+        for (i, library) in cycle.iter().enumerate() {
+            assert!(dependencies[library].contains(cycle[(i + 1) % cycle.len()]));
+        }
+    }
+
+    #[test]
+    fn every_order_respects_edges() {
+        // Chains and diamonds of every shape up to a size, from the numbers' divisors: each
+        // number depends on its proper divisors other than one.
+        for n in 1..60 {
+            let text: String = (2..=n)
+                .map(|i| {
+                    let divisors: Vec<String> = (2..i)
+                        .filter(|d| i % d == 0)
+                        .map(|d| d.to_string())
+                        .collect();
+                    format!("{} {}\n", i, divisors.join(" "))
+                })
+                .collect();
+            let dependencies = parse(&text);
+            let order = topological_sort(&dependencies).unwrap();
+            assert_respects(&dependencies, &order);
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! A set of words stored as a trie: a tree with a character on each edge, where the words are
+//! the paths from the root to the nodes marked as their ends. The words that share a prefix
+//! share the path that spells it, so all the words with a prefix are in the subtree at its end,
+//! which makes autocompletion quick.
+//!
+//! cargo run -p trie -- [prefix...]
+
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+struct Node {
+    /// The children in order of their characters, so that the words come out sorted.
+    children: BTreeMap<char, Node>,
+    /// Whether a word ends here.
+    end: bool,
+}
+
+#[derive(Default)]
+pub struct Trie {
+    root: Node,
+    len: usize,
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds `word`, and returns whether it was new.
+    pub fn insert(&mut self, word: &str) -> bool {
+        let mut node = &mut self.root;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        let new = !node.end;
+        node.end = true;
+        if new {
+            self.len += 1;
+        }
+        new
+    }
+
+    /// The node at the end of the path that spells `prefix`, if there is one.
+    fn find(&self, prefix: &str) -> Option<&Node> {
+        prefix
+            .chars()
+            .try_fold(&self.root, |node, c| node.children.get(&c))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| node.end)
+    }
+
+    /// Takes `word` out, and returns whether it was there. The nodes that no longer lead to
+    /// any word are removed with it.
+    pub fn remove(&mut self, word: &str) -> bool {
+        /// Removes the rest of the word below `node`, and returns whether it was there, and
+        /// whether `node` is now useless.
+        fn remove(node: &mut Node, mut chars: std::str::Chars) -> (bool, bool) {
+            let removed = match chars.next() {
+                None => std::mem::replace(&mut node.end, false),
+                Some(c) => match node.children.get_mut(&c) {
+                    None => false,
+                    Some(child) => {
+                        let (removed, prune) = remove(child, chars);
+                        if prune {
+                            node.children.remove(&c);
+                        }
+                        removed
+                    }
+                },
+            };
+            (removed, !node.end && node.children.is_empty())
+        }
+
+        let (removed, _) = remove(&mut self.root, word.chars());
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// The words that start with `prefix`, in order.
+    pub fn starting_with(&self, prefix: &str) -> Vec<String> {
+        fn collect(node: &Node, word: &mut String, words: &mut Vec<String>) {
+            if node.end {
+                words.push(word.clone());
+            }
+            for (&c, child) in &node.children {
+                word.push(c);
+                collect(child, word, words);
+                word.pop();
+            }
+        }
+
+        let mut words = vec![];
+        if let Some(node) = self.find(prefix) {
+            collect(node, &mut prefix.to_string(), &mut words);
+        }
+        words
+    }
+
+    /// The number of nodes, counting the root.
+    fn node_count(&self) -> usize {
+        fn count(node: &Node) -> usize {
+            1 + node.children.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+}
+
+/// The word list of the anagrams task, which other word tasks share.
+const DICTIONARY: &str = include_str!("../../anagrams/resources/unixdict.txt");
+
+fn main() {
+    let mut trie = Trie::new();
+    for word in DICTIONARY.lines() {
+        trie.insert(word);
+    }
+    println!("{} words in {} nodes", trie.len(), trie.node_count());
+
+    let mut prefixes: Vec<String> = std::env::args().skip(1).collect();
+    if prefixes.is_empty() {
+        prefixes = vec!["auto".to_string(), "zy".to_string(), "qu".to_string()];
+    }
+    for prefix in &prefixes {
+        let words = trie.starting_with(prefix);
+        let shown: Vec<&str> = words.iter().take(10).map(String::as_str).collect();
+        let more = if words.len() > shown.len() {
+            ", ..."
+        } else {
+            ""
+        };
+        println!("{} ({}): {}{}", prefix, words.len(), shown.join(", "), more);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use rand::prelude::*;
+
+    #[test]
+    fn basic_operations() {
+        let mut trie = Trie::new();
+        assert!(trie.is_empty());
+        assert!(trie.insert("tea"));
+        assert!(trie.insert("ten"));
+        assert!(trie.insert("te"));
+        assert!(trie.insert("inn"));
+        assert!(!trie.insert("tea"));
+        assert_eq!(trie.len(), 4);
+
+        assert!(trie.contains("te"));
+        assert!(!trie.contains("t"));
+        assert!(!trie.contains("teas"));
+        assert_eq!(trie.starting_with("te"), ["te", "tea", "ten"]);
+        assert_eq!(trie.starting_with("x"), Vec::<String>::new());
+        assert_eq!(trie.starting_with(""), ["inn", "te", "tea", "ten"]);
+
+        assert!(trie.remove("te"));
+        assert!(!trie.remove("te"));
+        assert!(!trie.remove("t"));
+        assert_eq!(trie.starting_with("te"), ["tea", "ten"]);
+        assert_eq!(trie.len(), 3);
+    }
+
+    #[test]
+    fn the_empty_word() {
+        let mut trie = Trie::new();
+        assert!(!trie.contains(""));
+        assert!(trie.insert(""));
+        assert!(trie.contains(""));
+        assert!(trie.remove(""));
+        assert!(trie.is_empty());
+    }
+
+    #[test]
+    fn removal_prunes_nodes() {
+        let mut trie = Trie::new();
+        trie.insert("abc");
+        trie.insert("abd");
+        assert_eq!(trie.node_count(), 5);
+        trie.remove("abc");
+        assert_eq!(trie.node_count(), 4);
+        // Nothing is pruned above a word.
+        trie.insert("a");
+        trie.remove("abd");
+        assert_eq!(trie.node_count(), 2);
+        trie.remove("a");
+        assert_eq!(trie.node_count(), 1);
+    }
+
+    #[test]
+    fn unixdict() {
+        let words: Vec<&str> = DICTIONARY.lines().collect();
+        let mut trie = Trie::new();
+        for word in &words {
+            trie.insert(word);
+        }
+        assert_eq!(trie.len(), words.len());
+        let mut expected: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|word| word.starts_with("pre"))
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(trie.starting_with("pre"), expected);
+        assert_eq!(expected.len(), 129);
+        assert_eq!(trie.starting_with("zy"), ["zygote"]);
+    }
+
+    #[test]
+    fn matches_hash_set() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Short words over a small alphabet, so that they share many prefixes.
+        let word = |rng: &mut StdRng| -> String {
+            let len = rng.gen_range(0, 5);
+            (0..len)
+                .map(|_| *b"abc".choose(rng).unwrap() as char)
+                .collect()
+        };
+        for _ in 0..20 {
+            let mut trie = Trie::new();
+            let mut set = HashSet::new();
+            for _ in 0..300 {
+                let w = word(&mut rng);
+                match rng.gen_range(0, 3) {
+                    0 => assert_eq!(trie.remove(&w), set.remove(&w)),
+                    1 => assert_eq!(trie.contains(&w), set.contains(&w)),
+                    _ => assert_eq!(trie.insert(&w), set.insert(w.clone())),
+                }
+                assert_eq!(trie.len(), set.len());
+
+                let prefix = word(&mut rng);
+                let mut expected: Vec<String> = set
+                    .iter()
+                    .filter(|w| w.starts_with(&prefix))
+                    .cloned()
+                    .collect();
+                expected.sort_unstable();
+                assert_eq!(trie.starting_with(&prefix), expected);
+            }
+
+            // Removing every word prunes the trie down to its root.
+            for w in &set {
+                assert!(trie.remove(w));
+            }
+            assert!(trie.is_empty());
+            assert_eq!(trie.node_count(), 1);
+        }
+    }
+}
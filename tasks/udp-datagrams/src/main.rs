@@ -0,0 +1,133 @@
+//! Sending and receiving UDP datagrams, directly, by broadcast and by multicast.
+//!
+//! cargo run -p udp-datagrams -- send <address> <message>
+//! cargo run -p udp-datagrams -- receive <port>
+//! cargo run -p udp-datagrams -- broadcast <port> <message>
+//! cargo run -p udp-datagrams -- multicast-send <group:port> <message>
+//! cargo run -p udp-datagrams -- multicast-receive <group:port>
+//!
+//! Without arguments, a message is sent to ourselves over the loopback interface.
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+/// The largest payload of a UDP datagram over IPv4.
+const MAX_DATAGRAM: usize = 65_507;
+
+fn send<A: ToSocketAddrs>(socket: &UdpSocket, to: A, message: &str) -> io::Result<()> {
+    let sent = socket.send_to(message.as_bytes(), to)?;
+    if sent != message.len() {
+        return Err(io::Error::other("the message was truncated"));
+    }
+    Ok(())
+}
+
+fn receive(socket: &UdpSocket) -> io::Result<(String, SocketAddr)> {
+    let mut buffer = vec![0; MAX_DATAGRAM];
+    let (len, from) = socket.recv_from(&mut buffer)?;
+    Ok((String::from_utf8_lossy(&buffer[..len]).into_owned(), from))
+}
+
+/// A socket that may send to the broadcast address.
+fn broadcast_socket() -> io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.set_broadcast(true)?;
+    Ok(socket)
+}
+
+/// A socket that receives the datagrams sent to a multicast group.
+fn join_multicast(group: SocketAddrV4) -> io::Result<UdpSocket> {
+    assert!(group.ip().is_multicast(), "not a multicast address");
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, group.port()))?;
+    socket.join_multicast_v4(group.ip(), &Ipv4Addr::UNSPECIFIED)?;
+    Ok(socket)
+}
+
+/// A socket that sends to multicast groups, and also delivers its datagrams to this host.
+fn multicast_sender() -> io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.set_multicast_loop_v4(true)?;
+    socket.set_multicast_ttl_v4(1)?;
+    Ok(socket)
+}
+
+/// Sends a message to a socket on an ephemeral port of the loopback interface, and returns
+/// what that socket received.
+fn loopback(message: &str) -> io::Result<String> {
+    let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+    receiver.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let sender = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0))?;
+
+    send(&sender, receiver.local_addr()?, message)?;
+    let (received, from) = receive(&receiver)?;
+    assert_eq!(from, sender.local_addr()?);
+    Ok(received)
+}
+
+fn print_received(socket: &UdpSocket) -> io::Result<()> {
+    loop {
+        let (message, from) = receive(socket)?;
+        println!("{}: {}", from, message);
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let group = |s: &str| s.parse::<SocketAddrV4>().expect("expected group:port");
+
+    match args[..] {
+        [] => println!("Received: {}", loopback("hello, datagram world")?),
+        ["send", to, message] => send(&UdpSocket::bind("0.0.0.0:0")?, to, message)?,
+        ["receive", port] => {
+            let port: u16 = port.parse().expect("expected a port");
+            print_received(&UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?)?;
+        }
+        ["broadcast", port, message] => {
+            let port: u16 = port.parse().expect("expected a port");
+            send(&broadcast_socket()?, (Ipv4Addr::BROADCAST, port), message)?;
+        }
+        ["multicast-send", to, message] => send(&multicast_sender()?, group(to), message)?,
+        ["multicast-receive", to] => print_received(&join_multicast(group(to))?)?,
+        _ => {
+            eprintln!("unexpected arguments; see the documentation at the top of main.rs");
+            std::process::exit(2);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback_round_trip() {
+        assert_eq!(loopback("hello").unwrap(), "hello");
+        assert_eq!(loopback("").unwrap(), "");
+        let large = "datagram ".repeat(7000);
+        assert_eq!(loopback(&large).unwrap(), large);
+    }
+
+    #[test]
+    fn too_large() {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let message = "x".repeat(MAX_DATAGRAM + 1);
+        assert!(send(&socket, socket.local_addr().unwrap(), &message).is_err());
+    }
+
+    /// Needs a network interface with multicast enabled, which sandboxes often lack.
+    #[test]
+    #[ignore]
+    fn multicast_round_trip() {
+        let group = SocketAddrV4::new(Ipv4Addr::new(239, 255, 42, 99), 49_152);
+        let receiver = join_multicast(group).unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+
+        send(&multicast_sender().unwrap(), group, "to the group").unwrap();
+        assert_eq!(receive(&receiver).unwrap().0, "to the group");
+    }
+}
@@ -0,0 +1,219 @@
+//! Decodes UPC-A barcodes scanned as lines of `#` (a bar) and ` ` (a space).
+//!
+//! A barcode is a start guard `# #`, six left digits, a middle guard ` # # `, six right
+//! digits and an end guard `# #`, each digit taking seven modules. Left digits have an odd
+//! number of bars and right digits, the complement of the left ones, an even number. A scan
+//! read backwards would start with reversed right digits, which then have the wrong parity
+//! for left ones, so an upside-down scan is never mistaken for another number.
+
+use std::fmt;
+
+/// The patterns of the left digits, with the most significant bit first and a 1 for a bar.
+pub const LEFT: [u8; 10] = [
+    0b000_1101, 0b001_1001, 0b001_0011, 0b011_1101, 0b010_0011, 0b011_0001, 0b010_1111, 0b011_1011,
+    0b011_0111, 0b000_1011,
+];
+
+const START: &str = "# #";
+const MIDDLE: &str = " # # ";
+const END: &str = "# #";
+const DIGIT_LEN: usize = 7;
+const BARCODE_LEN: usize = START.len() + 12 * DIGIT_LEN + MIDDLE.len() + END.len();
+
+/// A right digit is a left digit with bars and spaces swapped.
+pub fn right(digit: u8) -> u8 {
+    !LEFT[usize::from(digit)] & 0b111_1111
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    InvalidCharacter(char),
+    /// The number of modules between the first and the last bar, if not 95.
+    WrongLength(usize),
+    MissingGuard,
+    /// The (zero-based) position of a digit that matches no pattern.
+    InvalidDigit(usize),
+    WrongChecksum,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidCharacter(c) => write!(f, "invalid character {:?}", c),
+            Error::WrongLength(len) => write!(f, "{} modules instead of {}", len, BARCODE_LEN),
+            Error::MissingGuard => write!(f, "missing guard pattern"),
+            Error::InvalidDigit(i) => write!(f, "digit {} is unreadable", i + 1),
+            Error::WrongChecksum => write!(f, "wrong checksum"),
+        }
+    }
+}
+
+/// Whether three times the sum of the digits in odd positions, plus the sum of the others, is
+/// a multiple of ten.
+pub fn checksum_ok(digits: &[u8; 12]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| u32::from(d) * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn pattern(modules: &str) -> u8 {
+    modules
+        .bytes()
+        .fold(0, |bits, m| bits << 1 | u8::from(m == b'#'))
+}
+
+/// Decodes a scan that is the right way up.
+fn decode_oriented(barcode: &str) -> Result<[u8; 12], Error> {
+    let (left, rest) = barcode[START.len()..].split_at(6 * DIGIT_LEN);
+    let (middle, rest) = rest.split_at(MIDDLE.len());
+    let (right_half, end) = rest.split_at(6 * DIGIT_LEN);
+    if !barcode.starts_with(START) || middle != MIDDLE || end != END {
+        return Err(Error::MissingGuard);
+    }
+
+    let mut digits = [0; 12];
+    for (i, digit) in digits.iter_mut().enumerate() {
+        let (half, offset, code): (&str, usize, fn(u8) -> u8) = if i < 6 {
+            (left, i, |d| LEFT[usize::from(d)])
+        } else {
+            (right_half, i - 6, right)
+        };
+        let bits = pattern(&half[offset * DIGIT_LEN..(offset + 1) * DIGIT_LEN]);
+        *digit = (0..10)
+            .find(|&d| code(d) == bits)
+            .ok_or(Error::InvalidDigit(i))?;
+    }
+    Ok(digits)
+}
+
+/// Decodes a scan, which may be upside down and surrounded by blank space.
+pub fn decode(scan: &str) -> Result<[u8; 12], Error> {
+    if let Some(c) = scan.chars().find(|&c| c != ' ' && c != '#') {
+        return Err(Error::InvalidCharacter(c));
+    }
+    let barcode = scan.trim_matches(' ');
+    if barcode.len() != BARCODE_LEN {
+        return Err(Error::WrongLength(barcode.len()));
+    }
+
+    let digits = decode_oriented(barcode).or_else(|e| {
+        let reversed: String = barcode.chars().rev().collect();
+        decode_oriented(&reversed).map_err(|_| e)
+    })?;
+    if checksum_ok(&digits) {
+        Ok(digits)
+    } else {
+        Err(Error::WrongChecksum)
+    }
+}
+
+/// Draws a barcode, without the blank space around it.
+pub fn encode(digits: &[u8; 12]) -> String {
+    let modules = |bits: u8| -> String {
+        (0..DIGIT_LEN)
+            .rev()
+            .map(|i| if bits >> i & 1 == 1 { '#' } else { ' ' })
+            .collect()
+    };
+    let mut barcode = String::from(START);
+    for &d in &digits[..6] {
+        barcode.push_str(&modules(LEFT[usize::from(d)]));
+    }
+    barcode.push_str(MIDDLE);
+    for &d in &digits[6..] {
+        barcode.push_str(&modules(right(d)));
+    }
+    barcode.push_str(END);
+    barcode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(digits: &[u8; 12]) -> String {
+        digits.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn parity() {
+        for d in 0..10 {
+            assert_eq!(LEFT[d as usize].count_ones() % 2, 1);
+            assert_eq!(right(d).count_ones() % 2, 0);
+            // Left patterns start with a space and end with a bar.
+            assert_eq!(LEFT[d as usize] >> 6, 0);
+            assert_eq!(LEFT[d as usize] & 1, 1);
+        }
+        let mut all: Vec<u8> = (0..10).map(|d| LEFT[d]).chain((0..10).map(right)).collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 20);
+    }
+
+    #[test]
+    fn checksum() {
+        assert!(checksum_ok(&[9, 2, 4, 7, 7, 3, 2, 7, 1, 0, 1, 9]));
+        assert!(!checksum_ok(&[9, 2, 4, 7, 7, 3, 2, 7, 1, 0, 1, 8]));
+    }
+
+    #[test]
+    fn examples() {
+        let expected = [
+            Ok("924773271019"),
+            Ok("403944441050"),
+            Ok("834999676706"),
+            Ok("939825158811"),
+            Err(Error::InvalidDigit(11)),
+            Ok("316313718717"),
+            Ok("214575875608"),
+            Ok("818778841813"),
+            Ok("706466743030"),
+            Ok("653483540435"),
+        ];
+        let scans: Vec<&str> = include_str!("../resources/scans.txt").lines().collect();
+        assert_eq!(scans.len(), expected.len());
+        for (scan, expected) in scans.iter().zip(&expected) {
+            let decoded = decode(scan).map(|d| number(&d));
+            assert_eq!(
+                decoded.as_ref().map(String::as_str),
+                expected.as_ref().copied()
+            );
+        }
+    }
+
+    #[test]
+    fn encode_then_decode() {
+        let digits = [0, 3, 6, 0, 0, 0, 2, 9, 1, 4, 5, 2];
+        let barcode = encode(&digits);
+        assert_eq!(barcode.len(), 95);
+        assert_eq!(decode(&format!("   {}  ", barcode)), Ok(digits));
+        let upside_down: String = barcode.chars().rev().collect();
+        assert_eq!(decode(&upside_down), Ok(digits));
+    }
+
+    #[test]
+    fn invalid_scans() {
+        let barcode = encode(&[0, 3, 6, 0, 0, 0, 2, 9, 1, 4, 5, 2]);
+        assert_eq!(
+            decode(&barcode.replace('#', "|")),
+            Err(Error::InvalidCharacter('|'))
+        );
+        assert_eq!(decode(&barcode[..93]), Err(Error::WrongLength(93)));
+        assert_eq!(decode(""), Err(Error::WrongLength(0)));
+
+        let mut no_middle = barcode.clone();
+        no_middle.replace_range(45..50, "#####");
+        assert_eq!(decode(&no_middle), Err(Error::MissingGuard));
+
+        let wrong_check = encode(&[0, 3, 6, 0, 0, 0, 2, 9, 1, 4, 5, 3]);
+        assert_eq!(decode(&wrong_check), Err(Error::WrongChecksum));
+
+        // The last digit, damaged.
+        let mut damaged = barcode;
+        damaged.replace_range(85..92, "#######");
+        assert_eq!(decode(&damaged), Err(Error::InvalidDigit(11)));
+    }
+}
@@ -0,0 +1,26 @@
+//! Decodes the example scans, or the scans given on standard input, one per line.
+
+use std::io::{self, BufRead};
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let scans: Vec<String> = if std::env::args().any(|a| a == "-") {
+        stdin.lock().lines().collect::<io::Result<_>>()?
+    } else {
+        include_str!("../resources/scans.txt")
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+
+    for (i, scan) in scans.iter().enumerate() {
+        match upc::decode(scan) {
+            Ok(digits) => {
+                let number: String = digits.iter().map(|d| d.to_string()).collect();
+                println!("{:>2}: {}", i + 1, number);
+            }
+            Err(e) => println!("{:>2}: Invalid ({})", i + 1, e),
+        }
+    }
+    Ok(())
+}
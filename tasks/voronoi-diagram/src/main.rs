@@ -0,0 +1,262 @@
+//! Colours each pixel of an image by the site nearest to it, under one of three metrics.
+//!
+//! Rather than measuring the distance to every site from every pixel, the sites are sorted into
+//! square buckets, and the search for the nearest one looks at the buckets in rings around the
+//! pixel's own, from the inside out. Every site in a ring is at least as far as the ring is
+//! from the pixel's bucket along x or y, which no metric here is shorter than, so once a site
+//! is nearer than the next ring can be, the search stops.
+//!
+//! cargo run -p voronoi-diagram -- [sites] [seed]
+
+use bitmap::{Color, Image};
+use rand::prelude::*;
+use write_a_ppm_file::{Format, PPMWritable};
+
+type Point = (i64, i64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Metric {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl Metric {
+    /// A number that orders the distances the way the metric does: for the Euclidean metric,
+    /// the distance squared, so that it stays whole.
+    fn distance(self, a: Point, b: Point) -> i64 {
+        let (dx, dy) = ((a.0 - b.0).abs(), (a.1 - b.1).abs());
+        match self {
+            Metric::Euclidean => dx * dx + dy * dy,
+            Metric::Manhattan => dx + dy,
+            Metric::Chebyshev => dx.max(dy),
+        }
+    }
+
+    /// The least `distance` a point can have when it is `d` away along x or y.
+    fn lower_bound(self, d: i64) -> i64 {
+        match self {
+            Metric::Euclidean => d * d,
+            Metric::Manhattan | Metric::Chebyshev => d,
+        }
+    }
+}
+
+struct Buckets {
+    size: i64,
+    columns: i64,
+    rows: i64,
+    /// The indices of the sites in each bucket, row by row.
+    sites: Vec<Vec<usize>>,
+}
+
+impl Buckets {
+    /// Buckets about as many as the sites, to hold one each on average.
+    fn new(sites: &[Point], width: i64, height: i64) -> Self {
+        let area = (width * height) as f64 / sites.len().max(1) as f64;
+        let size = (area.sqrt().ceil() as i64).max(1);
+        let (columns, rows) = ((width + size - 1) / size, (height + size - 1) / size);
+        let mut buckets = vec![vec![]; (columns * rows) as usize];
+        for (i, &(x, y)) in sites.iter().enumerate() {
+            buckets[(y / size * columns + x / size) as usize].push(i);
+        }
+        Buckets {
+            size,
+            columns,
+            rows,
+            sites: buckets,
+        }
+    }
+
+    /// The sites in the buckets `ring` away from the bucket at `column` and `row`, along x or
+    /// y, whichever is more.
+    fn ring(&self, column: i64, row: i64, ring: i64) -> impl Iterator<Item = usize> + '_ {
+        let rows = (row - ring).max(0)..=(row + ring).min(self.rows - 1);
+        rows.flat_map(move |r| {
+            // All of the top and bottom rows of the ring, and the ends of the others.
+            let step = if (r - row).abs() == ring {
+                1
+            } else {
+                2 * ring.max(1)
+            };
+            (column - ring..=column + ring)
+                .step_by(step as usize)
+                .filter(move |&c| 0 <= c && c < self.columns)
+                .flat_map(move |c| self.sites[(r * self.columns + c) as usize].iter().copied())
+        })
+    }
+}
+
+/// The index of the site nearest to each pixel, row by row. Of sites at the same distance, the
+/// first one wins.
+fn nearest_sites(sites: &[Point], width: i64, height: i64, metric: Metric) -> Vec<usize> {
+    let buckets = Buckets::new(sites, width, height);
+    let mut nearest = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let (column, row) = (x / buckets.size, y / buckets.size);
+            let mut best: Option<(i64, usize)> = None;
+            for ring in 0.. {
+                // The sites in this ring and further are at least this far along x or y.
+                let closest = (ring - 1).max(0) * buckets.size;
+                if best.is_some_and(|(d, _)| d < metric.lower_bound(closest)) {
+                    break;
+                }
+                if ring > buckets.columns.max(buckets.rows) {
+                    break;
+                }
+                for i in buckets.ring(column, row, ring) {
+                    let candidate = (metric.distance((x, y), sites[i]), i);
+                    if best.is_none_or(|b| candidate < b) {
+                        best = Some(candidate);
+                    }
+                }
+            }
+            nearest.push(best.expect("no sites").1);
+        }
+    }
+    nearest
+}
+
+/// A Voronoi diagram of `sites` with their colours, with each site marked by a black dot.
+fn diagram(sites: &[Point], colors: &[Color], width: i64, height: i64, metric: Metric) -> Image {
+    let mut image = Image::new(width as usize, height as usize);
+    for (pixel, &site) in nearest_sites(sites, width, height, metric)
+        .iter()
+        .enumerate()
+    {
+        image.data[pixel] = colors[site];
+    }
+    for &(x, y) in sites {
+        for (dx, dy) in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (dx, dy))) {
+            let (px, py) = (x + dx, y + dy);
+            if 0 <= px && px < width && 0 <= py && py < height {
+                image[(px as usize, py as usize)] = Color::default();
+            }
+        }
+    }
+    image
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let count = args
+        .next()
+        .map_or(50, |n| n.parse().expect("a number of sites"));
+    let seed = args.next().map_or(0, |s| s.parse().expect("a seed"));
+    let (width, height) = (640, 480);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let sites: Vec<Point> = (0..count)
+        .map(|_| (rng.gen_range(0, width), rng.gen_range(0, height)))
+        .collect();
+    let colors: Vec<Color> = (0..count)
+        .map(|_| Color {
+            red: rng.gen_range(64, 256) as u8,
+            green: rng.gen_range(64, 256) as u8,
+            blue: rng.gen_range(64, 256) as u8,
+        })
+        .collect();
+
+    for &(metric, name) in &[
+        (Metric::Euclidean, "euclidean"),
+        (Metric::Manhattan, "manhattan"),
+        (Metric::Chebyshev, "chebyshev"),
+    ] {
+        let filename = format!("voronoi-{}.ppm", name);
+        diagram(&sites, &colors, width, height, metric)
+            .write_ppm(&filename, Format::Raw)
+            .unwrap();
+        println!("Wrote {}", filename);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: [Metric; 3] = [Metric::Euclidean, Metric::Manhattan, Metric::Chebyshev];
+
+    /// The nearest site by measuring the distance to all of them.
+    fn brute_force(sites: &[Point], width: i64, height: i64, metric: Metric) -> Vec<usize> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|pixel| {
+                (0..sites.len())
+                    .min_by_key(|&i| (metric.distance(pixel, sites[i]), i))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small_configuration() {
+        let sites = [(1, 1), (8, 2), (4, 6), (9, 9), (2, 8)];
+        for &metric in &METRICS {
+            let nearest = nearest_sites(&sites, 10, 10, metric);
+            assert_eq!(nearest, brute_force(&sites, 10, 10, metric), "{:?}", metric);
+            // Each site is nearest to itself.
+            for (i, &(x, y)) in sites.iter().enumerate() {
+                assert_eq!(nearest[(y * 10 + x) as usize], i);
+            }
+        }
+        // The metrics disagree where (4, 3) goes: it is 3 from (4, 6) along y, but 3 from (1, 1)
+        // too under Chebyshev, which gives it to the first; and (5, 1) is 4 from (8, 2) by
+        // Manhattan and from (1, 1), but nearer (8, 2) otherwise.
+        let at = |x: usize, y: usize, metric| nearest_sites(&sites, 10, 10, metric)[y * 10 + x];
+        assert_eq!(at(4, 3, Metric::Euclidean), 2);
+        assert_eq!(at(4, 3, Metric::Manhattan), 2);
+        assert_eq!(at(4, 3, Metric::Chebyshev), 0);
+        assert_eq!(at(5, 1, Metric::Euclidean), 1);
+        assert_eq!(at(5, 1, Metric::Manhattan), 0);
+        assert_eq!(at(5, 1, Metric::Chebyshev), 1);
+    }
+
+    #[test]
+    fn random_configurations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..30 {
+            let (width, height) = (rng.gen_range(1, 60), rng.gen_range(1, 60));
+            let count = rng.gen_range(1, 40);
+            // Sometimes bunched up in a corner, which leaves most buckets empty.
+            let spread = if rng.gen() { 1 } else { 5 };
+            let sites: Vec<Point> = (0..count)
+                .map(|_| {
+                    let x = rng.gen_range(0, (width + spread - 1) / spread);
+                    let y = rng.gen_range(0, (height + spread - 1) / spread);
+                    (x, y)
+                })
+                .collect();
+            for &metric in &METRICS {
+                assert_eq!(
+                    nearest_sites(&sites, width, height, metric),
+                    brute_force(&sites, width, height, metric),
+                    "{:?} {:?}",
+                    metric,
+                    sites
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn image() {
+        let sites = [(1, 1), (8, 8)];
+        let colors = [
+            Color {
+                red: 255,
+                green: 0,
+                blue: 0,
+            },
+            Color {
+                red: 0,
+                green: 0,
+                blue: 255,
+            },
+        ];
+        let image = diagram(&sites, &colors, 10, 10, Metric::Euclidean);
+        assert_eq!(image[(1, 1)], Color::default());
+        assert_eq!(image[(0, 9)], colors[0]);
+        assert_eq!(image[(9, 5)], colors[1]);
+    }
+}
@@ -1,20 +1,34 @@
-use regex::Regex;
+//! Create a program that downloads the time from this URL: http://tycho.usno.navy.mil/cgi-bin/timer.pl
+//! and then prints the current UTC time by extracting just the UTC time from the web page's HTML.
+//! For an introduction to the ecosystem of web scraping in Rust,
+//! read https://kadekillary.work/post/webscraping-rust/
+
 use std::error::Error;
-/// Create a program that downloads the time from this URL: http://tycho.usno.navy.mil/cgi-bin/timer.pl
-/// and then prints the current UTC time by extracting just the UTC time from the web page's HTML.
-/// For an introduction to the ecosystem of web scraping in Rust,
-/// read https://kadekillary.work/post/webscraping-rust/
 
 const A_URL: &str = "http://tycho.usno.navy.mil/cgi-bin/timer.pl";
-fn extract_timestamp(raw_html: &str) -> Option<regex::Match> {
-    let pattern = Regex::new(r"<BR>(?P<time>.*) UTC").unwrap();
-    pattern.captures(raw_html).unwrap().name("time")
+
+/// Finds the time on the line that reads `<BR>... UTC`, ignoring the case of the tag.
+fn extract_timestamp(raw_html: &str) -> Option<&str> {
+    const TAG: &str = "<br>";
+    const SUFFIX: &str = " UTC";
+
+    raw_html.lines().find_map(|line| {
+        let start = line.to_ascii_lowercase().find(TAG)? + TAG.len();
+        let rest = &line[start..];
+        let end = rest.find(SUFFIX)?;
+        // The zone has to be a word of its own, not the start of a longer one.
+        match rest[end + SUFFIX.len()..].chars().next() {
+            Some(c) if c.is_alphanumeric() => None,
+            _ => Some(rest[..end].trim()),
+        }
+    })
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let raw_html = reqwest::get(A_URL)?.text()?;
+    let response = http::get(A_URL)?;
+    let raw_html = String::from_utf8_lossy(&response.body);
     if let Some(timestamp) = extract_timestamp(&raw_html) {
-        print!("{}", timestamp.as_str());
+        print!("{}", timestamp);
     } else {
         eprint!("Error: Could not parse URL for getting current time.");
     }
@@ -28,25 +42,23 @@ mod tests {
 
     #[test]
     fn test_extract_timestamp() {
-        let body = r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 3.2 Final"//EN>
-            <html>
-            <body>
-            <TITLE>What time is it?</TITLE>
-            <H2> US Naval Observatory Master Clock Time</H2> <H3><PRE>
-            <BR>Aug. 12, 23:55:08 UTC		Universal Time
-            <BR>Aug. 12, 07:55:08 PM EDT		Eastern Time
-            <BR>Aug. 12, 06:55:08 PM CDT		Central Time
-            <BR>Aug. 12, 05:55:08 PM MDT		Mountain Time
-            <BR>Aug. 12, 04:55:08 PM PDT		Pacific Time
-            <BR>Aug. 12, 03:55:08 PM AKDT	Alaska Time
-            <BR>Aug. 12, 01:55:08 PM HAST	Hawaii-Aleutian Time
-            </PRE></H3><P><A HREF="http://www.usno.navy.mil"> US Naval Observatory</A>
-
-            </body></html>
-            "#;
+        let body = include_str!("../resources/usno.html");
+        assert_eq!(extract_timestamp(body).unwrap(), "Aug. 12, 23:55:08");
+    }
+
+    #[test]
+    fn utc_on_a_later_line() {
+        let body = include_str!("../resources/leap-second.html");
+        assert_eq!(extract_timestamp(body).unwrap(), "Dec. 31, 23:59:60");
+    }
+
+    #[test]
+    fn no_timestamp() {
         assert_eq!(
-            extract_timestamp(&body).unwrap().as_str(),
-            "Aug. 12, 23:55:08"
+            extract_timestamp("<html><body>It is late.</body></html>"),
+            None
         );
+        assert_eq!(extract_timestamp("<BR>Aug. 12, 07:55:08 PM EDT"), None);
+        assert_eq!(extract_timestamp("<BR>Aug. 12, 23:55:08 UTCX"), None);
     }
 }
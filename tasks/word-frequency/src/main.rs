@@ -0,0 +1,114 @@
+//! Lists the most frequent words of a text, by default Les Misérables from Project Gutenberg:
+//!
+//! curl -o resources/135-0.txt https://www.gutenberg.org/files/135/135-0.txt
+//! cargo run -p word-frequency -- [file] [n]
+//!
+//! A word is a run of letters, so numbers and punctuation separate words, and "it's" counts
+//! as "it" and "s". Words that only differ by case are the same word.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+
+/// Counts the words of a text one line at a time, so that the text never needs to be held in
+/// memory all at once.
+fn count_words<R: BufRead>(mut reader: R) -> io::Result<HashMap<String, usize>> {
+    let mut counts = HashMap::new();
+    let mut line = String::new();
+    let mut word = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        // The line always ends with a character that is not a letter, or the text ends.
+        for c in line.chars().chain(Some('\n')) {
+            if c.is_alphabetic() {
+                // Some characters become more than one in lowercase, like 'İ'.
+                word.extend(c.to_lowercase());
+            } else if !word.is_empty() {
+                match counts.get_mut(&word) {
+                    Some(count) => *count += 1,
+                    None => {
+                        counts.insert(word.clone(), 1);
+                    }
+                }
+                word.clear();
+            }
+        }
+        line.clear();
+    }
+    Ok(counts)
+}
+
+/// The `n` most frequent words, the most frequent first; words that are as frequent are in
+/// alphabetical order.
+fn most_frequent(counts: &HashMap<String, usize>, n: usize) -> Vec<(&str, usize)> {
+    let mut words: Vec<(&str, usize)> = counts.iter().map(|(w, &c)| (w.as_str(), c)).collect();
+    words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    words.truncate(n);
+    words
+}
+
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .unwrap_or_else(|| "resources/135-0.txt".to_string());
+    let n = args
+        .next()
+        .map_or(10, |n| n.parse().expect("expected a number of words"));
+
+    let counts = count_words(BufReader::new(File::open(&path)?))?;
+    println!("Rank  Word        Frequency");
+    for (rank, (word, count)) in most_frequent(&counts, n).into_iter().enumerate() {
+        println!("{:>4}  {:<10}  {:>9}", rank + 1, word, count);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt() -> io::Result<()> {
+        let file = File::open("resources/excerpt.txt")?;
+        let counts = count_words(BufReader::new(file))?;
+        assert_eq!(counts.values().sum::<usize>(), 227);
+        assert_eq!(counts.len(), 134);
+        assert_eq!(
+            most_frequent(&counts, 10),
+            [
+                ("of", 16),
+                ("the", 14),
+                ("in", 10),
+                ("to", 6),
+                ("was", 6),
+                ("a", 5),
+                ("he", 5),
+                ("had", 4),
+                ("that", 4),
+                ("which", 4),
+            ]
+        );
+        assert_eq!(counts["françois"], 1);
+        assert_eq!(counts["myriel"], 3);
+        Ok(())
+    }
+
+    #[test]
+    fn separators_and_case() -> io::Result<()> {
+        let counts = count_words("L'ÉCOLE, l'école!\nTwo-by-two 2x2 it's".as_bytes())?;
+        assert_eq!(counts["l"], 2);
+        assert_eq!(counts["école"], 2);
+        assert_eq!(counts["two"], 2);
+        assert_eq!(counts["x"], 1);
+        assert_eq!(counts["s"], 1);
+        assert_eq!(counts.values().sum::<usize>(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> io::Result<()> {
+        assert!(count_words(&b""[..])?.is_empty());
+        assert!(most_frequent(&HashMap::new(), 10).is_empty());
+        Ok(())
+    }
+}
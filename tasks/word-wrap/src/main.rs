@@ -2,7 +2,12 @@
 //!
 //! Implemented as a lazy `String` iterator, returning a wrapped line each time.
 //!
+//! The [minimum raggedness] variant instead finds the breaks that leave the least uneven
+//! right margin, with the dynamic programming approach of Knuth and Plass. Either result can
+//! then be justified to the full width.
+//!
 //! [minimum length greedy algorithm]: http://en.wikipedia.org/wiki/Word_wrap#Minimum_length
+//! [minimum raggedness]: http://en.wikipedia.org/wiki/Word_wrap#Minimum_raggedness
 use std::mem::swap;
 use std::str::SplitWhitespace;
 
@@ -13,10 +18,10 @@ pub struct WordWrap<'a> {
 }
 
 impl<'a> WordWrap<'a> {
-    fn new(text: &'a str, line_length: usize) -> WordWrap {
+    fn new(text: &'a str, line_length: usize) -> WordWrap<'a> {
         WordWrap {
             words: text.split_whitespace(),
-            line_length: line_length,
+            line_length,
             next_line: String::new(),
         }
     }
@@ -25,17 +30,17 @@ impl<'a> WordWrap<'a> {
 impl<'a> Iterator for WordWrap<'a> {
     type Item = String;
 
-    #[cfg_attr(feature = "clippy", allow(while_let_on_iterator))]
     fn next(&mut self) -> Option<String> {
         // Move anything left over from last run to this_line
         let mut this_line = String::new();
         swap(&mut self.next_line, &mut this_line);
 
-        let mut space_left = self.line_length - this_line.chars().count();
+        // A word longer than a line gets a line of its own.
+        let mut space_left = self.line_length.saturating_sub(this_line.chars().count());
         const SPACE_WIDTH: usize = 1;
 
         // Loop, adding words until we run out of words or hit the line length
-        while let Some(word) = self.words.next() {
+        for word in self.words.by_ref() {
             let word_length = word.chars().count();
 
             // If not the first word for this line
@@ -53,7 +58,7 @@ impl<'a> Iterator for WordWrap<'a> {
 
             // Add word to this line
             this_line.push_str(word);
-            space_left -= word_length;
+            space_left = space_left.saturating_sub(word_length);
         }
 
         if this_line.is_empty() {
@@ -64,8 +69,86 @@ impl<'a> Iterator for WordWrap<'a> {
     }
 }
 
-fn main() {
-    let text = "In olden times when wishing still helped one, there lived a king whose daughters \
+/// Wraps the text to lines of at most `line_length` characters, minimizing the sum of the
+/// squares of the spaces left at the end of every line but the last.
+fn minimum_raggedness(text: &str, line_length: usize) -> Vec<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let lengths: Vec<usize> = words.iter().map(|w| w.chars().count()).collect();
+
+    // The cost of a line made of `words[i..j]`, or `None` if they do not fit. A single word
+    // always fits, even if it is too long.
+    let line_cost = |i: usize, j: usize| -> Option<usize> {
+        let width = lengths[i..j].iter().sum::<usize>() + (j - i - 1);
+        if j == words.len() && width <= line_length {
+            Some(0)
+        } else if width <= line_length {
+            Some((line_length - width).pow(2))
+        } else if j == i + 1 {
+            Some(0)
+        } else {
+            None
+        }
+    };
+
+    // `best[i]` is the least cost of wrapping `words[i..]`, and `next[i]` where the first line
+    // of that wrapping ends.
+    let n = words.len();
+    let mut best = vec![0; n + 1];
+    let mut next = vec![n; n + 1];
+    for i in (0..n).rev() {
+        best[i] = usize::MAX;
+        for j in i + 1..=n {
+            match line_cost(i, j) {
+                Some(cost) if cost + best[j] < best[i] => {
+                    best[i] = cost + best[j];
+                    next[i] = j;
+                }
+                Some(_) => {}
+                // Longer lines will not fit either.
+                None => break,
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let mut i = 0;
+    while i < n {
+        lines.push(words[i..next[i]].join(" "));
+        i = next[i];
+    }
+    lines
+}
+
+/// Widens the spaces between the words of every line but the last, so that the lines are
+/// `line_length` characters long. The extra spaces are spread as evenly as possible, the
+/// leftmost gaps getting one more than the others when they do not divide evenly.
+fn justify(lines: &[String], line_length: usize) -> Vec<String> {
+    let last = lines.len().saturating_sub(1);
+    lines
+        .iter()
+        .enumerate()
+        .map(|(n, line)| {
+            let words: Vec<&str> = line.split(' ').collect();
+            let gaps = words.len() - 1;
+            let letters: usize = words.iter().map(|w| w.chars().count()).sum();
+            if n == last || gaps == 0 || letters + gaps > line_length {
+                return line.clone();
+            }
+
+            let spaces = line_length - letters;
+            let mut justified = words[0].to_string();
+            for (gap, word) in words[1..].iter().enumerate() {
+                let width = spaces / gaps + if gap < spaces % gaps { 1 } else { 0 };
+                justified.extend(std::iter::repeat_n(' ', width));
+                justified.push_str(word);
+            }
+            justified
+        })
+        .collect()
+}
+
+const TEXT: &str =
+    "In olden times when wishing still helped one, there lived a king whose daughters \
                 were all beautiful, but the youngest was so beautiful that the sun itself, which \
                 has seen so much, was astonished whenever it shone in her face.  Close by the \
                 king's castle lay a great dark forest, and under an old lime tree in the forest \
@@ -74,9 +157,28 @@ fn main() {
                 took a golden ball, and threw it up on high and caught it, and this ball was her \
                 favorite plaything.";
 
+fn main() {
     for length in 72..81 {
         println!("Text wrapped at {}", length);
-        for line in WordWrap::new(text, length) {
+        for line in WordWrap::new(TEXT, length) {
+            println!("{}", line);
+        }
+        println!();
+    }
+
+    for &length in &[40, 72] {
+        let lines = minimum_raggedness(TEXT, length);
+        println!("Text wrapped at {} with minimum raggedness", length);
+        for line in &lines {
+            println!("{}", line);
+        }
+        println!();
+
+        println!(
+            "Text wrapped at {} with minimum raggedness, and justified",
+            length
+        );
+        for line in justify(&lines, length) {
             println!("{}", line);
         }
         println!();
@@ -132,3 +234,88 @@ fn test_unicode() {
     assert_eq!(w.next().unwrap(), "Proviseur");
     assert_eq!(w.next().unwrap(), "entra");
 }
+
+#[cfg(test)]
+fn golden(contents: &str) -> Vec<String> {
+    contents.lines().map(str::to_string).collect()
+}
+
+#[cfg(test)]
+fn raggedness(lines: &[String], line_length: usize) -> usize {
+    let last = lines.len().saturating_sub(1);
+    lines[..last]
+        .iter()
+        .map(|line| line_length.saturating_sub(line.chars().count()).pow(2))
+        .sum()
+}
+
+#[test]
+fn test_long_word() {
+    let mut w = WordWrap::new("a extraordinarily long word", 6);
+    assert_eq!(w.next().unwrap(), "a");
+    assert_eq!(w.next().unwrap(), "extraordinarily");
+    assert_eq!(w.next().unwrap(), "long");
+    assert_eq!(w.next().unwrap(), "word");
+    assert_eq!(w.next(), None);
+    assert_eq!(
+        minimum_raggedness("a extraordinarily long word", 6),
+        ["a", "extraordinarily", "long", "word"]
+    );
+}
+
+#[test]
+fn test_greedy_golden() {
+    let greedy: Vec<String> = WordWrap::new(TEXT, 72).collect();
+    assert_eq!(greedy, golden(include_str!("../resources/greedy-72.txt")));
+    let greedy: Vec<String> = WordWrap::new(TEXT, 80).collect();
+    assert_eq!(greedy, golden(include_str!("../resources/greedy-80.txt")));
+}
+
+#[test]
+fn test_minimum_raggedness_golden() {
+    assert_eq!(
+        minimum_raggedness(TEXT, 40),
+        golden(include_str!("../resources/raggedness-40.txt"))
+    );
+    assert_eq!(
+        minimum_raggedness(TEXT, 72),
+        golden(include_str!("../resources/raggedness-72.txt"))
+    );
+}
+
+#[test]
+fn test_minimum_raggedness_beats_greedy() {
+    for length in 10..100 {
+        let greedy: Vec<String> = WordWrap::new(TEXT, length).collect();
+        let best = minimum_raggedness(TEXT, length);
+        assert!(best.iter().all(|line| line.chars().count() <= length));
+        assert!(raggedness(&best, length) <= raggedness(&greedy, length));
+        assert_eq!(best.join(" "), greedy.join(" "));
+    }
+    assert!(minimum_raggedness("", 10).is_empty());
+}
+
+#[test]
+fn test_justify_golden() {
+    for &length in &[40, 72] {
+        let justified = justify(&minimum_raggedness(TEXT, length), length);
+        let expected = match length {
+            40 => include_str!("../resources/justified-40.txt"),
+            _ => include_str!("../resources/justified-72.txt"),
+        };
+        assert_eq!(justified, golden(expected));
+        let (last, full) = justified.split_last().unwrap();
+        assert!(full.iter().all(|line| line.chars().count() == length));
+        assert!(last.chars().count() < length);
+    }
+}
+
+#[test]
+fn test_justify_spreads_spaces() {
+    let lines = golden("a b c d\nwide\nthe end");
+    // Two spaces in every gap, a single word that stays as it is, and the last line too.
+    assert_eq!(justify(&lines, 10), ["a  b  c  d", "wide", "the end"]);
+    // The leftmost gap gets the space that is left over.
+    assert_eq!(justify(&golden("ab cd ef\nx"), 11), ["ab   cd  ef", "x"]);
+    assert!(justify(&[], 10).is_empty());
+}
@@ -0,0 +1,392 @@
+//! A small XML parser, which reads a document into a tree of elements and text.
+//!
+//! It handles what documents such as the one of this task are made of: elements, attributes,
+//! text, character and entity references, comments, CDATA sections and processing
+//! instructions. Document type declarations are skipped, so only the five predefined entities
+//! are known.
+
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Element {
+    pub name: String,
+    /// The attributes in the order of the document.
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The child elements, leaving out the text between them.
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(e) => Some(e),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// All the text inside the element, including that of nested elements.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            match node {
+                Node::Element(e) => text.push_str(&e.text()),
+                Node::Text(t) => text.push_str(t),
+            }
+        }
+        text
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /// The byte offset in the document where the error was found.
+    pub position: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+type Result<T> = std::result::Result<T, ParseError>;
+
+/// Replaces the entity and character references of text or of an attribute value.
+pub fn unescape(text: &str) -> std::result::Result<String, &'static str> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        let end = rest[start..].find(';').ok_or("unterminated reference")? + start;
+        let name = &rest[start + 1..end];
+        let c = match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = name.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16)
+                } else if let Some(decimal) = name.strip_prefix('#') {
+                    decimal.parse()
+                } else {
+                    return Err("unknown entity");
+                };
+                code.ok()
+                    .and_then(std::char::from_u32)
+                    .ok_or("invalid character reference")?
+            }
+        };
+        unescaped.push(c);
+        rest = &rest[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            position: self.pos,
+            message,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str, message: &'static str) -> Result<()> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    /// Advances past the next `end`, and returns what was before it.
+    fn until(&mut self, end: &str, message: &'static str) -> Result<&'a str> {
+        let len = self.rest().find(end).ok_or_else(|| self.error(message))?;
+        let skipped = &self.rest()[..len];
+        self.pos += len + end.len();
+        Ok(skipped)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || "_-.:".contains(c)))
+            .unwrap_or(rest.len());
+        if len == 0 || rest.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn unescape(&self, start: usize, text: &str) -> Result<String> {
+        unescape(text).map_err(|message| ParseError {
+            position: start,
+            message,
+        })
+    }
+
+    /// Skips comments, processing instructions and document type declarations, along with
+    /// the whitespace around them.
+    fn skip_misc(&mut self) -> Result<()> {
+        loop {
+            self.skip_whitespace();
+            if self.eat("<!--") {
+                self.until("-->", "unterminated comment")?;
+            } else if self.eat("<?") {
+                self.until("?>", "unterminated processing instruction")?;
+            } else if self.eat("<!DOCTYPE") {
+                self.until(">", "unterminated document type declaration")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn attribute_value(&mut self) -> Result<String> {
+        let quote = match self.rest().chars().next() {
+            Some(q @ '"') | Some(q @ '\'') => q,
+            _ => return Err(self.error("expected a quoted value")),
+        };
+        self.pos += 1;
+        let start = self.pos;
+        let raw = self.until(&quote.to_string(), "unterminated attribute value")?;
+        if raw.contains('<') {
+            return Err(ParseError {
+                position: start,
+                message: "'<' in an attribute value",
+            });
+        }
+        self.unescape(start, raw)
+    }
+
+    /// Parses an element, starting at its `<`.
+    fn element(&mut self) -> Result<Element> {
+        self.expect("<", "expected an element")?;
+        let name = self.name()?.to_string();
+        let mut attributes: Vec<(String, String)> = vec![];
+        loop {
+            let had_whitespace = {
+                let before = self.pos;
+                self.skip_whitespace();
+                self.pos != before
+            };
+            if self.eat("/>") {
+                return Ok(Element {
+                    name,
+                    attributes,
+                    children: vec![],
+                });
+            }
+            if self.eat(">") {
+                break;
+            }
+            if !had_whitespace {
+                return Err(self.error("expected whitespace before an attribute"));
+            }
+            let start = self.pos;
+            let attribute = self.name()?.to_string();
+            if attributes.iter().any(|(n, _)| *n == attribute) {
+                return Err(ParseError {
+                    position: start,
+                    message: "duplicate attribute",
+                });
+            }
+            self.skip_whitespace();
+            self.expect("=", "expected '='")?;
+            self.skip_whitespace();
+            let value = self.attribute_value()?;
+            attributes.push((attribute, value));
+        }
+
+        let mut children = vec![];
+        let mut text = String::new();
+        loop {
+            if self.eat("</") {
+                let start = self.pos;
+                if self.name()? != name {
+                    return Err(ParseError {
+                        position: start,
+                        message: "mismatched end tag",
+                    });
+                }
+                self.skip_whitespace();
+                self.expect(">", "expected '>'")?;
+                break;
+            } else if self.eat("<!--") {
+                self.until("-->", "unterminated comment")?;
+            } else if self.eat("<![CDATA[") {
+                text.push_str(self.until("]]>", "unterminated CDATA section")?);
+            } else if self.eat("<?") {
+                self.until("?>", "unterminated processing instruction")?;
+            } else if self.rest().starts_with('<') {
+                if !text.is_empty() {
+                    children.push(Node::Text(std::mem::take(&mut text)));
+                }
+                children.push(Node::Element(self.element()?));
+            } else if self.rest().is_empty() {
+                return Err(self.error("unclosed element"));
+            } else {
+                let start = self.pos;
+                let len = self.rest().find('<').unwrap_or(self.rest().len());
+                let raw = &self.rest()[..len];
+                self.pos += len;
+                text.push_str(&self.unescape(start, raw)?);
+            }
+        }
+        if !text.is_empty() {
+            children.push(Node::Text(text));
+        }
+        Ok(Element {
+            name,
+            attributes,
+            children,
+        })
+    }
+}
+
+/// Parses a document, and returns its root element.
+pub fn parse(document: &str) -> Result<Element> {
+    let mut parser = Parser {
+        input: document,
+        pos: 0,
+    };
+    parser.skip_misc()?;
+    let root = parser.element()?;
+    parser.skip_misc()?;
+    if !parser.rest().is_empty() {
+        return Err(parser.error("content after the root element"));
+    }
+    Ok(root)
+}
+
+/// The names of the students of the task's document, in order.
+pub fn student_names(document: &str) -> Result<Vec<String>> {
+    let root = parse(document)?;
+    Ok(root
+        .elements()
+        .filter(|e| e.name == "Student")
+        .filter_map(|e| e.attribute("Name"))
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn students() {
+        assert_eq!(
+            student_names(include_str!("../resources/students.xml")).unwrap(),
+            ["April", "Bob", "Chad", "Dave", "Émily"]
+        );
+    }
+
+    #[test]
+    fn tree() {
+        let root = parse(
+            "<?xml version=\"1.0\"?>\n<!-- a comment -->\n\
+             <a x='1' y=\"&quot;2&quot;\">one <b>two</b><![CDATA[ <3> ]]>&amp; four<c/></a>\n",
+        )
+        .unwrap();
+        assert_eq!(root.name, "a");
+        assert_eq!(root.attribute("x"), Some("1"));
+        assert_eq!(root.attribute("y"), Some("\"2\""));
+        assert_eq!(root.attribute("z"), None);
+        assert_eq!(root.text(), "one two <3> & four");
+        assert_eq!(
+            root.children,
+            [
+                Node::Text("one ".to_string()),
+                Node::Element(Element {
+                    name: "b".to_string(),
+                    attributes: vec![],
+                    children: vec![Node::Text("two".to_string())],
+                }),
+                Node::Text(" <3> & four".to_string()),
+                Node::Element(Element {
+                    name: "c".to_string(),
+                    attributes: vec![],
+                    children: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn references() {
+        assert_eq!(
+            unescape("&lt;&gt;&amp;&apos;&quot;&#65;&#x42;&#x1F600;").unwrap(),
+            "<>&'\"AB\u{1F600}"
+        );
+        assert_eq!(unescape("no references").unwrap(), "no references");
+        assert_eq!(unescape("&nbsp;"), Err("unknown entity"));
+        assert_eq!(unescape("&#xD800;"), Err("invalid character reference"));
+        assert_eq!(unescape("AT&T"), Err("unterminated reference"));
+    }
+
+    #[test]
+    fn errors() {
+        let message = |document| parse(document).unwrap_err().message;
+        assert_eq!(message(""), "expected an element");
+        assert_eq!(message("<a>"), "unclosed element");
+        assert_eq!(message("<a></b>"), "mismatched end tag");
+        assert_eq!(message("<a x=1/>"), "expected a quoted value");
+        assert_eq!(
+            message("<a x='1'y='2'/>"),
+            "expected whitespace before an attribute"
+        );
+        assert_eq!(message("<a x='1' x='2'/>"), "duplicate attribute");
+        assert_eq!(message("<a x='<'/>"), "'<' in an attribute value");
+        assert_eq!(message("<a/><b/>"), "content after the root element");
+        assert_eq!(message("<a>&bogus;</a>"), "unknown entity");
+        assert_eq!(message("<1a/>"), "expected a name");
+        assert_eq!(
+            parse("<a>\n  <b>").unwrap_err(),
+            ParseError {
+                position: 9,
+                message: "unclosed element"
+            }
+        );
+    }
+}
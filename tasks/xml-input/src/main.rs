@@ -0,0 +1,22 @@
+//! Prints the names of the students in the task's document, or in the file given as argument.
+
+use std::env;
+use std::fs;
+
+fn main() {
+    let document = match env::args().nth(1) {
+        Some(filename) => fs::read_to_string(filename).expect("could not read the file"),
+        None => include_str!("../resources/students.xml").to_string(),
+    };
+    match xml_input::student_names(&document) {
+        Ok(names) => {
+            for name in names {
+                println!("{}", name);
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,38 +1,60 @@
-extern crate xml;
-
 use std::collections::HashMap;
-use std::str;
 
-use xml::writer::{EmitterConfig, XmlEvent};
+/// Escapes text so that it can be put between tags.
+///
+/// Only `&` and `<` have to be escaped, along with the `>` that would end a `]]>`, which is not
+/// allowed in text.
+pub fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace("]]>", "]]&gt;")
+}
 
-pub fn characters_to_xml(characters: HashMap<String, String>) -> String {
-    let mut output: Vec<u8> = Vec::new();
-    let mut writer = EmitterConfig::new()
-        .perform_indent(true)
-        .create_writer(&mut output);
+/// Escapes the value of an attribute, to be put between double quotes.
+///
+/// Both quotes are escaped, so the value can be quoted either way. Line breaks and tabs are
+/// escaped too, since parsers would otherwise turn them into spaces.
+pub fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
 
-    writer
-        .write(XmlEvent::start_element("CharacterRemarks"))
-        .unwrap();
+/// Writes the remarks of each character, sorted by name so that the output does not depend on
+/// the order of the map.
+pub fn characters_to_xml(characters: HashMap<String, String>) -> String {
+    let mut characters: Vec<_> = characters.into_iter().collect();
+    characters.sort();
 
+    let mut output =
+        String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<CharacterRemarks>\n");
     for (character, line) in &characters {
-        let element = XmlEvent::start_element("Character").attr("name", character);
-        writer.write(element).unwrap();
-        writer.write(XmlEvent::characters(line)).unwrap();
-        writer.write(XmlEvent::end_element()).unwrap();
+        output.push_str(&format!(
+            "  <Character name=\"{}\">{}</Character>\n",
+            escape_attribute(character),
+            escape_text(line)
+        ));
     }
-
-    writer.write(XmlEvent::end_element()).unwrap();
-    str::from_utf8(&output).unwrap().to_string()
+    output.push_str("</CharacterRemarks>\n");
+    output
 }
 
 #[cfg(test)]
 mod tests {
-    use super::characters_to_xml;
-    use std::collections::HashMap;
+    use super::*;
 
-    #[test]
-    fn test_xml_output() {
+    fn input() -> HashMap<String, String> {
         let mut input = HashMap::new();
         input.insert(
             "April".to_string(),
@@ -43,8 +65,12 @@ mod tests {
             "Burns: \"When chapman billies leave the street ...\"".to_string(),
         );
         input.insert("Emily".to_string(), "Short & shrift".to_string());
+        input
+    }
 
-        let output = characters_to_xml(input);
+    #[test]
+    fn test_xml_output() {
+        let output = characters_to_xml(input());
 
         println!("{}", output);
         assert!(output.contains(
@@ -55,4 +81,41 @@ mod tests {
             .contains("<Character name=\"April\">Bubbly: I'm > Tam and &lt;= Emily</Character>"));
         assert!(output.contains("<Character name=\"Emily\">Short &amp; shrift</Character>"));
     }
+
+    #[test]
+    fn whole_document() {
+        assert_eq!(
+            characters_to_xml(input()),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+             <CharacterRemarks>\n\
+             \x20 <Character name=\"April\">Bubbly: I'm > Tam and &lt;= Emily</Character>\n\
+             \x20 <Character name=\"Emily\">Short &amp; shrift</Character>\n\
+             \x20 <Character name=\"Tam O&apos;Shanter\">Burns: \"When chapman billies leave \
+             the street ...\"</Character>\n\
+             </CharacterRemarks>\n"
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape_text("a]]>b > c"), "a]]&gt;b > c");
+        assert_eq!(escape_attribute("a\"b'c\nd"), "a&quot;b&apos;c&#10;d");
+    }
+
+    #[test]
+    fn parses_back() {
+        let mut input = input();
+        input.insert(
+            "Odd\t\"one\"".to_string(),
+            "<![CDATA[ ]]> &amp;\n".to_string(),
+        );
+        let root = xml_input::parse(&characters_to_xml(input.clone())).unwrap();
+
+        assert_eq!(root.name, "CharacterRemarks");
+        let parsed: HashMap<String, String> = root
+            .elements()
+            .map(|e| (e.attribute("name").unwrap().to_string(), e.text()))
+            .collect();
+        assert_eq!(parsed, input);
+    }
 }
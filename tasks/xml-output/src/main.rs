@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+use xml_output::characters_to_xml;
+
+fn main() {
+    let names = ["April", "Tam O'Shanter", "Emily"];
+    let remarks = [
+        "Bubbly: I'm > Tam and <= Emily",
+        "Burns: \"When chapman billies leave the street ...\"",
+        "Short & shrift",
+    ];
+    let characters: HashMap<String, String> = names
+        .iter()
+        .zip(&remarks)
+        .map(|(name, remark)| (name.to_string(), remark.to_string()))
+        .collect();
+    print!("{}", characters_to_xml(characters));
+}
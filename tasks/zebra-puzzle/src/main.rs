@@ -0,0 +1,285 @@
+//! Solves the zebra puzzle by constraint propagation, with backtracking when propagation alone
+//! gets stuck.
+//!
+//! Every value of every attribute, like "green" or "Swede", has a domain: the set of houses it
+//! may still be in, as five bits. The clues, and the rule that the values of an attribute are in
+//! different houses, each remove the houses that they rule out, until nothing changes any more.
+//! Then, if a value may still be in several houses, each of them is tried in turn.
+
+use std::fmt::Write;
+
+const HOUSES: usize = 5;
+const ALL: u8 = (1 << HOUSES) - 1;
+
+const ATTRIBUTES: [[&str; HOUSES]; 5] = [
+    ["red", "green", "white", "yellow", "blue"],
+    ["English", "Swede", "Dane", "Norwegian", "German"],
+    ["tea", "coffee", "milk", "beer", "water"],
+    ["Pall Mall", "Dunhill", "Blend", "Blue Master", "Prince"],
+    ["dog", "birds", "cats", "horse", "zebra"],
+];
+
+/// A value of an attribute, as the indices of both in `ATTRIBUTES`.
+#[derive(Clone, Copy, Debug)]
+struct Var(usize, usize);
+
+impl Var {
+    fn named(name: &str) -> Var {
+        for (attribute, values) in ATTRIBUTES.iter().enumerate() {
+            if let Some(value) = values.iter().position(|&v| v == name) {
+                return Var(attribute, value);
+            }
+        }
+        panic!("no such value: {}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Clue {
+    /// Both are in the same house.
+    Same(Var, Var),
+    /// In the house with this index, from the left.
+    At(Var, usize),
+    /// The first is in the house just right of the second.
+    RightOf(Var, Var),
+    /// They are in neighbouring houses.
+    NextTo(Var, Var),
+}
+
+fn clues() -> Vec<Clue> {
+    use Clue::*;
+    let v = Var::named;
+    vec![
+        Same(v("English"), v("red")),
+        Same(v("Swede"), v("dog")),
+        Same(v("Dane"), v("tea")),
+        RightOf(v("white"), v("green")),
+        Same(v("green"), v("coffee")),
+        Same(v("Pall Mall"), v("birds")),
+        Same(v("yellow"), v("Dunhill")),
+        At(v("milk"), 2),
+        At(v("Norwegian"), 0),
+        NextTo(v("Blend"), v("cats")),
+        NextTo(v("horse"), v("Dunhill")),
+        Same(v("Blue Master"), v("beer")),
+        Same(v("German"), v("Prince")),
+        NextTo(v("Norwegian"), v("blue")),
+        NextTo(v("Blend"), v("water")),
+    ]
+}
+
+/// The houses each value may be in, as bits.
+type Domains = [[u8; HOUSES]; 5];
+
+/// The houses to the right of some house in `houses`.
+fn right_of(houses: u8) -> u8 {
+    (houses << 1) & ALL
+}
+
+fn left_of(houses: u8) -> u8 {
+    houses >> 1
+}
+
+/// Narrows `var` to `houses`, and returns whether that changed it.
+fn restrict(domains: &mut Domains, var: Var, houses: u8) -> bool {
+    let domain = &mut domains[var.0][var.1];
+    let narrowed = *domain & houses;
+    let changed = narrowed != *domain;
+    *domain = narrowed;
+    changed
+}
+
+/// Removes the houses that the clues and the attributes rule out, until none is left to
+/// remove. Returns false if some value has no house left.
+fn propagate(domains: &mut Domains, clues: &[Clue]) -> bool {
+    let domain = |domains: &Domains, var: Var| domains[var.0][var.1];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &clue in clues {
+            changed |= match clue {
+                Clue::Same(a, b) => {
+                    let both = domain(domains, a) & domain(domains, b);
+                    restrict(domains, a, both) | restrict(domains, b, both)
+                }
+                Clue::At(a, house) => restrict(domains, a, 1 << house),
+                Clue::RightOf(a, b) => {
+                    let a_houses = right_of(domain(domains, b));
+                    let b_houses = left_of(domain(domains, a));
+                    restrict(domains, a, a_houses) | restrict(domains, b, b_houses)
+                }
+                Clue::NextTo(a, b) => {
+                    let (da, db) = (domain(domains, a), domain(domains, b));
+                    restrict(domains, a, left_of(db) | right_of(db))
+                        | restrict(domains, b, left_of(da) | right_of(da))
+                }
+            };
+        }
+
+        for attribute in domains.iter_mut() {
+            // A value that is known to be in a house is the only one of its attribute there.
+            for value in 0..HOUSES {
+                let houses = attribute[value];
+                if houses.count_ones() == 1 {
+                    for (other, domain) in attribute.iter_mut().enumerate() {
+                        if other != value && *domain & houses != 0 {
+                            *domain &= !houses;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            // And a house that only one value may be in gets that value.
+            for house in 0..HOUSES {
+                let bit = 1 << house;
+                let mut candidates = (0..HOUSES).filter(|&value| attribute[value] & bit != 0);
+                match (candidates.next(), candidates.next()) {
+                    (None, _) => return false,
+                    (Some(value), None) if attribute[value] != bit => {
+                        attribute[value] = bit;
+                        changed = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if domains.iter().flatten().any(|&domain| domain == 0) {
+            return false;
+        }
+    }
+    true
+}
+
+/// The solutions, and how many times the search had to guess.
+fn solve(clues: &[Clue]) -> (Vec<Domains>, usize) {
+    fn search(mut domains: Domains, clues: &[Clue], solutions: &mut Vec<Domains>) -> usize {
+        if !propagate(&mut domains, clues) {
+            return 0;
+        }
+        // Guess the house of the value with the fewest left, other than the solved ones.
+        let undecided = (0..5)
+            .flat_map(|a| (0..HOUSES).map(move |v| Var(a, v)))
+            .filter(|var| domains[var.0][var.1].count_ones() > 1)
+            .min_by_key(|var| domains[var.0][var.1].count_ones());
+        match undecided {
+            None => {
+                solutions.push(domains);
+                0
+            }
+            Some(var) => {
+                let mut guesses = 0;
+                for house in 0..HOUSES {
+                    if domains[var.0][var.1] & (1 << house) != 0 {
+                        let mut guess = domains;
+                        guess[var.0][var.1] = 1 << house;
+                        guesses += 1 + search(guess, clues, solutions);
+                    }
+                }
+                guesses
+            }
+        }
+    }
+
+    let mut solutions = vec![];
+    let guesses = search([[ALL; HOUSES]; 5], clues, &mut solutions);
+    (solutions, guesses)
+}
+
+/// The values in each house, from the left, in the order of `ATTRIBUTES`.
+fn houses(solution: &Domains) -> Vec<Vec<&'static str>> {
+    (0..HOUSES)
+        .map(|house| {
+            (0..5)
+                .map(|attribute| {
+                    let value = (0..HOUSES)
+                        .find(|&v| solution[attribute][v] == 1 << house)
+                        .unwrap();
+                    ATTRIBUTES[attribute][value]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn table(solution: &Domains) -> String {
+    let mut table = String::new();
+    for (i, house) in houses(solution).iter().enumerate() {
+        write!(table, "{}", i + 1).unwrap();
+        for value in house {
+            write!(table, " | {:<11}", value).unwrap();
+        }
+        table.truncate(table.trim_end().len());
+        table.push('\n');
+    }
+    table
+}
+
+fn main() {
+    let (solutions, guesses) = solve(&clues());
+    for solution in &solutions {
+        print!("{}", table(solution));
+        let zebra = houses(solution)
+            .into_iter()
+            .find(|house| house[4] == "zebra")
+            .unwrap();
+        println!("The {} owns the zebra.", zebra[1]);
+    }
+    println!(
+        "{} solution(s), after {} guesses.",
+        solutions.len(),
+        guesses
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_solution() {
+        let (solutions, _) = solve(&clues());
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(
+            houses(&solutions[0]),
+            [
+                ["yellow", "Norwegian", "water", "Dunhill", "cats"],
+                ["blue", "Dane", "tea", "Blend", "horse"],
+                ["red", "English", "milk", "Pall Mall", "birds"],
+                ["green", "German", "coffee", "Prince", "zebra"],
+                ["white", "Swede", "beer", "Blue Master", "dog"],
+            ]
+        );
+    }
+
+    #[test]
+    fn few_guesses() {
+        // Far from the 5!^5 assignments of a blind search.
+        let (_, guesses) = solve(&clues());
+        assert!(guesses < 20, "{} guesses", guesses);
+    }
+
+    #[test]
+    fn fewer_clues_more_solutions() {
+        // Without knowing that the Norwegian lives in the first house, the puzzle is
+        // ambiguous, and only the search can tell the solutions apart.
+        let mut clues = clues();
+        clues.remove(8);
+        let (solutions, guesses) = solve(&clues);
+        assert_eq!(solutions.len(), 17);
+        assert!(guesses > 0);
+        for solution in &solutions {
+            assert!(propagate(&mut solution.clone(), &clues));
+        }
+        assert!(solutions
+            .iter()
+            .any(|solution| houses(solution)[0][1] != "Norwegian"));
+    }
+
+    #[test]
+    fn contradiction() {
+        let mut clues = clues();
+        clues.push(Clue::At(Var::named("zebra"), 0));
+        assert!(solve(&clues).0.is_empty());
+    }
+}
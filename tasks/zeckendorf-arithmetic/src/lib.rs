@@ -0,0 +1,246 @@
+//! Addition and subtraction performed directly on Zeckendorf representations, without
+//! converting them to binary integers.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// A non-negative integer in Zeckendorf form.
+///
+/// The digits are stored least significant first. The representation is always canonical: no
+/// two consecutive digits are `1`, and there are no leading zeros, so zero has no digits at all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Zeckendorf {
+    digits: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseZeckendorfError {
+    /// The string contained a character other than `0` or `1`.
+    InvalidDigit,
+    /// The string contained two consecutive `1`s.
+    NotCanonical,
+}
+
+impl Zeckendorf {
+    /// Returns `self - other`, or `None` if the result would be negative.
+    pub fn checked_sub(&self, other: &Zeckendorf) -> Option<Zeckendorf> {
+        if self < other {
+            return None;
+        }
+
+        let mut digits: Vec<i64> = self.digits.iter().map(|&d| i64::from(d)).collect();
+        for (d, &o) in digits.iter_mut().zip(&other.digits) {
+            *d -= i64::from(o);
+        }
+
+        // Borrow from the nearest higher non-zero digit, using F(j) = F(j - 1) + F(j - 2) to
+        // move it down one position at a time, until the negative digit is covered.
+        while let Some(k) = digits.iter().position(|&d| d < 0) {
+            let j = (k + 1..digits.len())
+                .find(|&j| digits[j] > 0)
+                .expect("a larger number must have a larger non-zero digit");
+            digits[j] -= 1;
+            if j >= 2 {
+                digits[j - 1] += 1;
+                digits[j - 2] += 1;
+            } else {
+                // The second digit is worth 2, which is twice the first digit.
+                digits[0] += 2;
+            }
+        }
+
+        Some(Zeckendorf::normalize(
+            digits.into_iter().map(|d| d as u32).collect(),
+        ))
+    }
+
+    /// Turns a list of arbitrary non-negative digits into a canonical representation.
+    fn normalize(mut digits: Vec<u32>) -> Zeckendorf {
+        loop {
+            // Make room for any carries out of the most significant digit.
+            digits.resize(digits.len() + 2, 0);
+
+            let mut changed = false;
+            for k in (0..digits.len() - 2).rev() {
+                if digits[k] >= 2 {
+                    // Split a doubled digit: 2 * F(k) = F(k + 1) + F(k - 2).
+                    digits[k] -= 2;
+                    digits[k + 1] += 1;
+                    match k {
+                        0 => {}
+                        1 => digits[0] += 1,
+                        _ => digits[k - 2] += 1,
+                    }
+                    changed = true;
+                } else if digits[k] >= 1 && digits[k + 1] >= 1 {
+                    // Combine adjacent digits: F(k) + F(k + 1) = F(k + 2).
+                    digits[k] -= 1;
+                    digits[k + 1] -= 1;
+                    digits[k + 2] += 1;
+                    changed = true;
+                }
+            }
+
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+
+            if !changed {
+                return Zeckendorf {
+                    digits: digits.into_iter().map(|d| d as u8).collect(),
+                };
+            }
+        }
+    }
+}
+
+impl FromStr for Zeckendorf {
+    type Err = ParseZeckendorfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut digits = s
+            .bytes()
+            .rev()
+            .map(|b| match b {
+                b'0' => Ok(0),
+                b'1' => Ok(1),
+                _ => Err(ParseZeckendorfError::InvalidDigit),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        if digits.windows(2).any(|w| w == [1, 1]) {
+            return Err(ParseZeckendorfError::NotCanonical);
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Ok(Zeckendorf { digits })
+    }
+}
+
+impl fmt::Display for Zeckendorf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+        for d in self.digits.iter().rev() {
+            write!(f, "{}", d)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for Zeckendorf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Canonical representations compare like binary numbers: more digits means a larger
+        // number, and numbers of the same length compare digit by digit.
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for Zeckendorf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &Zeckendorf {
+    type Output = Zeckendorf;
+
+    fn add(self, other: &Zeckendorf) -> Zeckendorf {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |z: &Zeckendorf, i| u32::from(z.digits.get(i).cloned().unwrap_or(0));
+        Zeckendorf::normalize((0..len).map(|i| digit(self, i) + digit(other, i)).collect())
+    }
+}
+
+impl Sub for &Zeckendorf {
+    type Output = Zeckendorf;
+
+    fn sub(self, other: &Zeckendorf) -> Zeckendorf {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+    use zeckendorf_number_representation::{from_zeckendorf, to_zeckendorf};
+
+    fn z(n: u64) -> Zeckendorf {
+        to_zeckendorf(n).parse().unwrap()
+    }
+
+    fn value(z: &Zeckendorf) -> u64 {
+        from_zeckendorf(&z.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!("0".parse::<Zeckendorf>().unwrap().to_string(), "0");
+        assert_eq!("00101".parse::<Zeckendorf>().unwrap().to_string(), "101");
+        assert_eq!(
+            "102".parse::<Zeckendorf>(),
+            Err(ParseZeckendorfError::InvalidDigit)
+        );
+        assert_eq!(
+            "110".parse::<Zeckendorf>(),
+            Err(ParseZeckendorfError::NotCanonical)
+        );
+    }
+
+    #[test]
+    fn small_examples() {
+        let parse = |s: &str| s.parse::<Zeckendorf>().unwrap();
+        assert_eq!((&parse("10") + &parse("10")).to_string(), "101");
+        assert_eq!((&parse("10100") + &parse("1010")).to_string(), "101000");
+        assert_eq!((&parse("1000") - &parse("101")).to_string(), "1");
+        assert_eq!((&parse("10101") - &parse("10101")).to_string(), "0");
+        assert_eq!(parse("1").checked_sub(&parse("10")), None);
+    }
+
+    #[test]
+    fn ordering() {
+        for a in 0..100 {
+            for b in 0..100 {
+                assert_eq!(z(a).cmp(&z(b)), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_small() {
+        for a in 0..200 {
+            for b in 0..200 {
+                assert_eq!(value(&(&z(a) + &z(b))), a + b);
+                if a >= b {
+                    assert_eq!(value(&(&z(a) - &z(b))), a - b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_round_trip() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let a = rng.gen_range(0, 1 << 60);
+            let b = rng.gen_range(0, 1 << 60);
+            let sum = &z(a) + &z(b);
+            assert_eq!(sum, z(a + b));
+
+            let (big, small) = if a > b { (a, b) } else { (b, a) };
+            assert_eq!(&z(big) - &z(small), z(big - small));
+            assert_eq!(&sum - &z(b), z(a));
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use zeckendorf_arithmetic::Zeckendorf;
+
+fn main() {
+    let additions = [
+        ("10", "10"),
+        ("10", "1"),
+        ("10100", "1010"),
+        ("10101", "10101"),
+    ];
+    for &(a, b) in &additions {
+        let sum = &a.parse::<Zeckendorf>().unwrap() + &b.parse().unwrap();
+        println!("{} + {} = {}", a, b, sum);
+    }
+
+    let subtractions = [("1000", "101"), ("10101010", "1010101"), ("100000", "1")];
+    for &(a, b) in &subtractions {
+        let difference = &a.parse::<Zeckendorf>().unwrap() - &b.parse().unwrap();
+        println!("{} - {} = {}", a, b, difference);
+    }
+}
@@ -0,0 +1,101 @@
+//! Conversion between integers and their Zeckendorf representation.
+//!
+//! Every positive integer can be written uniquely as a sum of non-consecutive Fibonacci numbers.
+//! Writing a `1` for every Fibonacci number (1, 2, 3, 5, 8, ...) that is used and a `0` for
+//! every one that is not gives a binary-like representation with no two consecutive `1`s.
+
+use std::convert::TryFrom;
+
+/// Returns the Fibonacci numbers 1, 2, 3, 5, 8, ... that are less than or equal to `n`.
+fn fibonacci_up_to(n: u64) -> Vec<u64> {
+    let mut fibs = vec![];
+    // The next Fibonacci number may not fit in a `u64`.
+    let (mut a, mut b) = (1u128, 2u128);
+    while a <= u128::from(n) {
+        fibs.push(a as u64);
+        let next = a + b;
+        a = b;
+        b = next;
+    }
+    fibs
+}
+
+/// Returns the Zeckendorf representation of `n`, most significant digit first.
+pub fn to_zeckendorf(n: u64) -> String {
+    if n == 0 {
+        return "0".to_string();
+    }
+
+    // Greedily take the largest Fibonacci number that still fits.
+    let mut remainder = n;
+    fibonacci_up_to(n)
+        .iter()
+        .rev()
+        .map(|&fib| {
+            if fib <= remainder {
+                remainder -= fib;
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect()
+}
+
+/// Parses a Zeckendorf representation back into an integer.
+///
+/// Returns `None` if the string contains anything other than `0` and `1`, or if the value does
+/// not fit in a `u64`.
+pub fn from_zeckendorf(s: &str) -> Option<u64> {
+    let (mut a, mut b) = (1u128, 2u128);
+    let mut n = 0u128;
+    for digit in s.chars().rev() {
+        match digit {
+            '0' => {}
+            '1' => n = n.saturating_add(a),
+            _ => return None,
+        }
+        let next = a.saturating_add(b);
+        a = b;
+        b = next;
+    }
+    u64::try_from(n).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_twenty() {
+        let expected = [
+            "0", "1", "10", "100", "101", "1000", "1001", "1010", "10000", "10001", "10010",
+            "10100", "10101", "100000", "100001", "100010", "100100", "100101", "101000", "101001",
+            "101010",
+        ];
+        for (n, &z) in expected.iter().enumerate() {
+            assert_eq!(to_zeckendorf(n as u64), z);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in 0..10_000 {
+            let z = to_zeckendorf(n);
+            assert!(!z.contains("11"));
+            assert_eq!(from_zeckendorf(&z), Some(n));
+        }
+    }
+
+    #[test]
+    fn largest() {
+        let z = to_zeckendorf(u64::MAX);
+        assert_eq!(from_zeckendorf(&z), Some(u64::MAX));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(from_zeckendorf("102"), None);
+        assert_eq!(from_zeckendorf(&"10".repeat(100)), None);
+    }
+}
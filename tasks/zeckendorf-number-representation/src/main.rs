@@ -0,0 +1,7 @@
+use zeckendorf_number_representation::to_zeckendorf;
+
+fn main() {
+    for n in 0..=20 {
+        println!("{:2}: {:>6}", n, to_zeckendorf(n));
+    }
+}
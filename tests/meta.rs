@@ -42,3 +42,14 @@ fn tasks_are_sorted() {
 fn parse_local_tasks() {
     local::parse_tasks(&*MANIFEST_PATH).unwrap();
 }
+
+#[test]
+fn network_tasks_are_flagged() {
+    let tasks = local::parse_tasks(&*MANIFEST_PATH).unwrap();
+    for name in &["ftp", "http", "https", "send-email", "web-scraping"] {
+        let task = tasks.iter().find(|t| t.package_name == *name).unwrap();
+        assert!(task.requires_network, "{} should need network access", name);
+    }
+    let fizzbuzz = tasks.iter().find(|t| t.package_name == "fizzbuzz").unwrap();
+    assert!(!fizzbuzz.requires_network);
+}
@@ -0,0 +1,448 @@
+//! A client for the [MediaWiki API] of the Rosetta Code wiki, shared by the `meta` crate and the
+//! tasks that query the wiki.
+//!
+//! Queries can be answered by the wiki, or replayed from a file of responses that were recorded
+//! earlier, so that the code which makes sense of them can be tested offline.
+//!
+//! [MediaWiki API]: https://www.mediawiki.org/wiki/API:Query
+
+#![warn(missing_docs)]
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The endpoint of the API.
+pub const API_URL: &str = "http://rosettacode.org/mw/api.php";
+
+/// How many titles the API accepts in one query.
+pub const TITLES_PER_QUERY: usize = 50;
+
+/// The ways a query can fail.
+#[derive(Debug)]
+pub enum Error {
+    /// Something went wrong with the HTTP request to the API.
+    Http(reqwest::Error),
+
+    /// The response was not JSON, or not in the expected shape.
+    Json(serde_json::Error),
+
+    /// A file of recorded responses could not be read or written.
+    Io(io::Error),
+
+    /// The API answered with an error, with this description.
+    Api(String),
+
+    /// No response to this query was recorded.
+    NotRecorded(String),
+
+    /// The response JSON contained unexpected keys or values.
+    UnexpectedFormat,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Http(err) => write!(f, "HTTP request failed: {}", err),
+            Error::Json(err) => write!(f, "malformed response: {}", err),
+            Error::Io(err) => write!(f, "recorded responses: {}", err),
+            Error::Api(info) => write!(f, "the API failed: {}", info),
+            Error::NotRecorded(query) => write!(f, "no recorded response to {}", query),
+            Error::UnexpectedFormat => write!(f, "unexpected response format"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Crate-specific result type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A page in a category.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Deserialize)]
+pub struct CategoryMember {
+    /// The ID of the page in the MediaWiki API.
+    #[serde(rename = "pageid")]
+    pub id: u64,
+
+    /// The human-readable title of the page.
+    pub title: String,
+}
+
+enum Backend {
+    Live(Client),
+    Replay(BTreeMap<String, Value>),
+}
+
+/// Responses saved as they come in, by their queries.
+struct Recording {
+    path: PathBuf,
+    responses: RefCell<BTreeMap<String, Value>>,
+}
+
+/// Sends queries to the wiki, or answers them from recorded responses.
+pub struct WikiClient {
+    backend: Backend,
+    recording: Option<Recording>,
+}
+
+impl WikiClient {
+    /// A client that queries the wiki.
+    pub fn new() -> Self {
+        WikiClient {
+            backend: Backend::Live(Client::new()),
+            recording: None,
+        }
+    }
+
+    /// A client that queries the wiki, and saves every response to the file at `path`, for
+    /// `replay` to answer the same queries later.
+    pub fn record<P: AsRef<Path>>(path: P) -> Self {
+        WikiClient {
+            recording: Some(Recording {
+                path: path.as_ref().to_owned(),
+                responses: RefCell::new(BTreeMap::new()),
+            }),
+            ..Self::new()
+        }
+    }
+
+    /// A client that answers queries from the responses recorded in the file at `path`, without
+    /// going online.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let responses = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        Ok(Self::replay_responses(responses))
+    }
+
+    /// A client that answers queries from `responses`, by the keys that `query_key` gives them.
+    pub fn replay_responses(responses: BTreeMap<String, Value>) -> Self {
+        WikiClient {
+            backend: Backend::Replay(responses),
+            recording: None,
+        }
+    }
+
+    /// The client that the command line asks for: `--record FILE` or `--replay FILE`, or one
+    /// that only queries the wiki when there are no arguments.
+    pub fn from_args<I: Iterator<Item = String>>(mut args: I) -> Result<Self> {
+        match (args.next().as_deref(), args.next()) {
+            (None, _) => Ok(Self::new()),
+            (Some("--record"), Some(path)) => Ok(Self::record(path)),
+            (Some("--replay"), Some(path)) => Self::replay(path),
+            _ => Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "usage: [--record FILE | --replay FILE]",
+            ))),
+        }
+    }
+
+    /// Sends a query with the given parameters, along with `action=query` and `format=json`.
+    pub fn query(&self, params: &[(&str, &str)]) -> Result<Value> {
+        let key = query_key(params);
+        let response = match &self.backend {
+            Backend::Live(client) => {
+                let mut url = Url::parse(API_URL).unwrap();
+                url.query_pairs_mut()
+                    .append_pair("action", "query")
+                    .append_pair("format", "json")
+                    .extend_pairs(params);
+                client.get(url).send()?.json()?
+            }
+            Backend::Replay(responses) => responses
+                .get(&key)
+                .cloned()
+                .ok_or(Error::NotRecorded(key.clone()))?,
+        };
+
+        if let Some(recording) = &self.recording {
+            let mut responses = recording.responses.borrow_mut();
+            responses.insert(key, response.clone());
+            fs::write(&recording.path, serde_json::to_string_pretty(&*responses)?)?;
+        }
+
+        if let Some(error) = response.get("error") {
+            let info = error.get("info").and_then(Value::as_str).unwrap_or("");
+            return Err(Error::Api(info.to_owned()));
+        }
+        Ok(response)
+    }
+
+    /// Sends a query, and then the queries that continue it, for as long as the API has more
+    /// results, which it splits into pages of a few hundred. Returns the responses in order.
+    pub fn query_continued(&self, params: &[(&str, &str)]) -> Result<Vec<Value>> {
+        let mut responses = vec![];
+        let mut continue_params = vec![("continue".to_owned(), String::new())];
+        loop {
+            let mut all_params = params.to_vec();
+            all_params.extend(continue_params.iter().map(|(k, v)| (&k[..], &v[..])));
+            let response = self.query(&all_params)?;
+
+            // Where to carry on from, if there is more.
+            let next = response
+                .get("continue")
+                .and_then(Value::as_object)
+                .map(|params| {
+                    params
+                        .iter()
+                        .map(|(key, value)| {
+                            let value =
+                                value.as_str().map_or_else(|| value.to_string(), From::from);
+                            (key.clone(), value)
+                        })
+                        .collect()
+                });
+            responses.push(response);
+            match next {
+                Some(next) => continue_params = next,
+                None => return Ok(responses),
+            }
+        }
+    }
+
+    /// The pages in a category, such as "Programming Tasks", or "Rust" for the tasks that are
+    /// implemented in Rust.
+    pub fn category_members(&self, category: &str) -> Result<Vec<CategoryMember>> {
+        let title = format!("Category:{}", category);
+        let params = [
+            ("list", "categorymembers"),
+            ("cmtitle", &title[..]),
+            ("cmlimit", "500"),
+        ];
+        let mut members = vec![];
+        for response in self.query_continued(&params)? {
+            let page = response
+                .pointer("/query/categorymembers")
+                .ok_or(Error::UnexpectedFormat)?;
+            members.extend(Vec::<CategoryMember>::deserialize(page)?);
+        }
+        Ok(members)
+    }
+
+    /// The subcategories of a category, such as the languages in "Programming Languages", by
+    /// their titles, with how many pages each holds.
+    pub fn subcategory_sizes(&self, category: &str) -> Result<BTreeMap<String, u64>> {
+        let title = format!("Category:{}", category);
+        let params = [
+            ("generator", "categorymembers"),
+            ("gcmtitle", &title[..]),
+            ("gcmtype", "subcat"),
+            ("gcmlimit", "500"),
+            ("prop", "categoryinfo"),
+        ];
+        let mut sizes = BTreeMap::new();
+        for response in self.query_continued(&params)? {
+            let pages = response
+                .pointer("/query/pages")
+                .and_then(Value::as_object)
+                .ok_or(Error::UnexpectedFormat)?;
+            for page in pages.values() {
+                let title = page
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .ok_or(Error::UnexpectedFormat)?;
+                // Empty categories come without their information, and a category can come
+                // again without it in a response that continues the query.
+                let size = sizes.entry(title.to_owned()).or_insert(0);
+                if let Some(pages) = page.pointer("/categoryinfo/pages").and_then(Value::as_u64) {
+                    *size = pages;
+                }
+            }
+        }
+        Ok(sizes)
+    }
+
+    /// The wiki markup of the pages with the given titles, by their titles. Missing pages are
+    /// left out.
+    pub fn page_contents<S: AsRef<str>>(&self, titles: &[S]) -> Result<BTreeMap<String, String>> {
+        let mut contents = BTreeMap::new();
+        for batch in titles.chunks(TITLES_PER_QUERY) {
+            let titles: Vec<&str> = batch.iter().map(AsRef::as_ref).collect();
+            let titles = titles.join("|");
+            let params = [
+                ("prop", "revisions"),
+                ("rvprop", "content"),
+                ("titles", &titles[..]),
+            ];
+            // When the pages are long, the API sends the contents of some of them in the
+            // responses that continue the query.
+            for response in self.query_continued(&params)? {
+                let pages = response
+                    .pointer("/query/pages")
+                    .and_then(Value::as_object)
+                    .ok_or(Error::UnexpectedFormat)?;
+                for page in pages.values() {
+                    let title = page.get("title").and_then(Value::as_str);
+                    let content = page.pointer("/revisions/0/*").and_then(Value::as_str);
+                    if let (Some(title), Some(content)) = (title, content) {
+                        contents.insert(title.to_owned(), content.to_owned());
+                    }
+                }
+            }
+        }
+        Ok(contents)
+    }
+}
+
+impl Default for WikiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The key of the response to a query among the recorded ones: its parameters, other than
+/// `action` and `format`, as `key=value`, joined by `&` without escaping, so that they are easy
+/// to read.
+pub fn query_key(params: &[(&str, &str)]) -> String {
+    let params: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    params.join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn client(responses: Vec<(&str, Value)>) -> WikiClient {
+        let responses = responses
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect();
+        WikiClient::replay_responses(responses)
+    }
+
+    #[test]
+    fn follows_continuations() {
+        let client = client(vec![
+            (
+                "list=categorymembers&cmtitle=Category:Rust&cmlimit=500&continue=",
+                json!({
+                    "continue": {"cmcontinue": "page|4142|1", "continue": "-||"},
+                    "query": {"categorymembers": [{"pageid": 1, "ns": 0, "title": "A"}]},
+                }),
+            ),
+            (
+                "list=categorymembers&cmtitle=Category:Rust&cmlimit=500&cmcontinue=page|4142|1&continue=-||",
+                json!({
+                    "batchcomplete": "",
+                    "query": {"categorymembers": [{"pageid": 2, "ns": 0, "title": "B"}]},
+                }),
+            ),
+        ]);
+        let members = client.category_members("Rust").unwrap();
+        let titles: Vec<&str> = members.iter().map(|m| &m.title[..]).collect();
+        assert_eq!(titles, ["A", "B"]);
+        assert_eq!(members[1].id, 2);
+    }
+
+    #[test]
+    fn contents_across_continuations() {
+        let client = client(vec![
+            (
+                "prop=revisions&rvprop=content&titles=A|B|C&continue=",
+                json!({
+                    "continue": {"rvcontinue": "2|0", "continue": "||"},
+                    "query": {"pages": {
+                        "1": {"pageid": 1, "title": "A", "revisions": [{"*": "a"}]},
+                        "2": {"pageid": 2, "title": "B"},
+                        "-1": {"title": "C", "missing": ""},
+                    }},
+                }),
+            ),
+            (
+                "prop=revisions&rvprop=content&titles=A|B|C&continue=||&rvcontinue=2|0",
+                json!({
+                    "query": {"pages": {
+                        "1": {"pageid": 1, "title": "A"},
+                        "2": {"pageid": 2, "title": "B", "revisions": [{"*": "b"}]},
+                    }},
+                }),
+            ),
+        ]);
+        let contents = client.page_contents(&["A", "B", "C"]).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["A"], "a");
+        assert_eq!(contents["B"], "b");
+    }
+
+    #[test]
+    fn sizes_across_continuations() {
+        let client = client(vec![
+            (
+                "generator=categorymembers&gcmtitle=Category:Languages&gcmtype=subcat&gcmlimit=500&prop=categoryinfo&continue=",
+                json!({
+                    "continue": {"gcmcontinue": "subcat|52|1", "continue": "gcmcontinue||"},
+                    "query": {"pages": {
+                        "1": {"pageid": 1, "title": "Category:A", "categoryinfo": {"pages": 3}},
+                        "2": {"pageid": 2, "title": "Category:B"},
+                    }},
+                }),
+            ),
+            (
+                "generator=categorymembers&gcmtitle=Category:Languages&gcmtype=subcat&gcmlimit=500&prop=categoryinfo&continue=gcmcontinue||&gcmcontinue=subcat|52|1",
+                json!({
+                    "query": {"pages": {
+                        "1": {"pageid": 1, "title": "Category:A"},
+                        "3": {"pageid": 3, "title": "Category:C", "categoryinfo": {"pages": 5}},
+                    }},
+                }),
+            ),
+        ]);
+        let sizes = client.subcategory_sizes("Languages").unwrap();
+        let sizes: Vec<(&str, u64)> = sizes.iter().map(|(t, &n)| (&t[..], n)).collect();
+        assert_eq!(
+            sizes,
+            [("Category:A", 3), ("Category:B", 0), ("Category:C", 5)]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let client = client(vec![(
+            "list=nonsense",
+            json!({"error": {"code": "badvalue", "info": "Unrecognized value"}}),
+        )]);
+        match client.query(&[("list", "nonsense")]) {
+            Err(Error::Api(info)) => assert_eq!(info, "Unrecognized value"),
+            other => panic!("{:?}", other),
+        }
+        match client.query(&[("list", "other")]) {
+            Err(Error::NotRecorded(key)) => assert_eq!(key, "list=other"),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn arguments() {
+        let args = |args: &[&str]| args.iter().map(|&a| a.to_owned()).collect::<Vec<_>>();
+        assert!(WikiClient::from_args(args(&[]).into_iter()).is_ok());
+        assert!(WikiClient::from_args(args(&["--record"]).into_iter()).is_err());
+        assert!(WikiClient::from_args(args(&["--replay", "/nonexistent"]).into_iter()).is_err());
+    }
+}